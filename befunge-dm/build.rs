@@ -0,0 +1,16 @@
+//! Copies the example program shown in the `befunge!` docs into `OUT_DIR`. If it has gone missing,
+//! a placeholder is used instead (with a warning) so that the crate itself still builds.
+
+use std::{env, fs, path::Path};
+
+const EXAMPLE: &str = "examples/example.bfg";
+
+fn main() {
+    println!("cargo::rerun-if-changed={EXAMPLE}");
+    let contents = fs::read_to_string(EXAMPLE).unwrap_or_else(|e| {
+        println!("cargo::warning=Couldn't read '{EXAMPLE}' for the `befunge!` docs: {e}");
+        String::from("Example program missing!\n")
+    });
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("example.bfg");
+    fs::write(out, contents).unwrap();
+}
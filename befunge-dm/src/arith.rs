@@ -6,13 +6,19 @@
 //! `pos`, or `neg`. Empty and `pos` are treated equivalently. The value of `[magnitude]` is
 //! equivalent to the number of `:tt`s in it, though `[]`s were used throughout this crate as the
 //! only "digit" in these base 1 numbers. Additionally, zero should always be positive - that is,
-//! `[[neg] []]` should never be allowed to occur. Where possible, this crate attempts to correct
-//! such occurrences, though it may still cause errors. Here are some examples of valid numbers:
+//! `[[neg] []]` should never be allowed to occur. The macros in this module still accept it, and
+//! every result they give back goes through [`crate::arith_canonicalize`] first, which corrects
+//! it. Here are some examples of valid numbers:
 //!   - `[[pos] []]`: `0`
 //!   - `[[] []]`: `0`
 //!   - `[[pos] [[] [] [] [] []]]`: `5`
 //!   - `[[neg] [[] []]]`: `-2`
 //!
+//! [`crate::arith_add`], [`crate::arith_sub`], [`crate::arith_mul`], [`crate::arith_div`], and
+//! [`crate::arith_mod`] also take either operand as an integer literal, like `a: 5,` or `b: -3,`.
+//! These are turned into base 1 numbers with [`befunge_pm::base1_from_literal!`] before anything
+//! else is done with them, so the result is still a base 1 number.
+//!
 //! Second, the calling convention for these macros is generally consistent, except for
 //! [`crate::arith_div_mod`]. These chould be called as:
 //! ```ignore
@@ -36,7 +42,130 @@
 //! }
 //! ```
 //! The sole exception to this is [`crate::arith_div_mod`]. Please refer to the documentation for
-//! that macro if you wish to call it by itself for some reason.
+//! that macro if you wish to call it by itself for some reason. [`crate::arith_cmp`] is called the
+//! same way, but its callback has `ord: [lt],`, `ord: [eq],`, or `ord: [gt],` in place of `res:`.
+//!
+//! # Division
+//!
+//! By default, [`crate::arith_div`] and [`crate::arith_mod`] truncate towards zero, the same as
+//! Rust's `/` and `%` on integers. [`crate::arith_div_mod`] only ever sees the magnitudes, and the
+//! signs are put back afterwards, so the quotient is negative when exactly one of `a` and `b` is,
+//! and the remainder has the sign of `a`: `-7 / 2 = -3` and `-7 % 2 = -1`. The Befunge-93 spec
+//! doesn't say which way these should round, so both macros can also be given `divmode: floor,`
+//! just after `b:`. That rounds the quotient down instead, and gives the remainder the sign of `b`
+//! so that `(a / b) * b + a % b` is still `a`: `-7 / 2 = -4` and `-7 % 2 = 1`. `divmode: trunc,`
+//! is the same as leaving it out. Nothing is rounded when `a` is a multiple of `b`, and dividing by
+//! 0 is handled the same way in both modes.
+
+/// Turns `[[neg] []]` into `[[pos] []]`, and passes every other number through unchanged.
+///
+/// Every result from [`crate::arith_add`], [`crate::arith_sub`], [`crate::arith_mul`],
+/// [`crate::arith_div`], and [`crate::arith_mod`] goes through this before their callback is made,
+/// so none of them ever give back a negative zero.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! is_pos_zero {
+///     ([[pos] []]) => {
+///         true
+///     };
+///     ($res:tt) => {
+///         false
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         $macro:ident @$op:ident a: $a:tt, $(b: $b:tt,)?
+///     ) => {{
+///         befunge_dm::$macro! {
+///             @$op
+///             a: $a,
+///             $(b: $b,)?
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         is_pos_zero!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // -0 becomes 0
+///     let tmp = wrapper!(arith_canonicalize @canonicalize a: [[neg] []],);
+///     assert!(tmp);
+///     // 0 stays as it is
+///     let tmp = wrapper!(arith_canonicalize @canonicalize a: [[pos] []],);
+///     assert!(tmp);
+///     // -2 stays as it is
+///     let tmp = wrapper!(arith_canonicalize @canonicalize a: [[neg] [[] []]],);
+///     assert!(!tmp);
+///
+///     // Zeros that come out of the other macros are always positive:
+///     // 3 - 3 = 0
+///     let tmp = wrapper!(arith_sub @sub a: [[pos] [[] [] []]], b: [[pos] [[] [] []]],);
+///     assert!(tmp);
+///     // (-2) - (-2) = 0
+///     let tmp = wrapper!(arith_sub @sub a: [[neg] [[] []]], b: [[neg] [[] []]],);
+///     assert!(tmp);
+///     // (-0) - 0 = 0
+///     let tmp = wrapper!(arith_sub @sub a: [[neg] []], b: [[pos] []],);
+///     assert!(tmp);
+///     // 0 * (-3) = 0
+///     let tmp = wrapper!(arith_mul @mul a: [[pos] []], b: [[neg] [[] [] []]],);
+///     assert!(tmp);
+///     // (-4) % 2 = 0
+///     let tmp = wrapper!(arith_mod @mod a: [[neg] [[] [] [] []]], b: [[pos] [[] []]],);
+///     assert!(tmp);
+///     // (-1) / 2 = 0
+///     let tmp = wrapper!(arith_div @div a: [[neg] [[]]], b: [[pos] [[] []]],);
+///     assert!(tmp);
+///     // (-3) + 3 = 0
+///     let tmp = wrapper!(arith_add @add a: [[neg] [[] [] []]], b: [[pos] [[] [] []]],);
+///     assert!(tmp);
+/// };
+/// ```
+#[macro_export]
+macro_rules! arith_canonicalize {
+    (
+        @canonicalize
+        a: [[neg] []],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            $($pst)*
+        }
+    };
+    (
+        @canonicalize
+        a: $a:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: $a,
+            $($pst)*
+        }
+    };
+}
 
 /// Add two signed magnitude base 1 numbers.
 ///
@@ -134,6 +263,36 @@
 ///         b: [[pos] [[] [] []]],
 ///     );
 ///     assert!(tmp == 1);
+///
+///     // Either operand can also be given as an integer literal:
+///     // -7 + 3 = -4
+///     let tmp = {
+///         befunge_dm::arith_add! {
+///             @add
+///             a: -7,
+///             b: [[pos] [[] [] []]],
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == -4);
+///     // 2 + 5 = 7
+///     let tmp = {
+///         befunge_dm::arith_add! {
+///             @add
+///             a: [[pos] [[] []]],
+///             b: 5,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == 7);
 /// };
 /// ```
 ///
@@ -145,6 +304,61 @@
 ///   4. Expand callback with result.
 #[macro_export]
 macro_rules! arith_add {
+    // a given as an integer literal
+    (
+        @add
+        a: $a:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $a,
+            callback: [
+                name: $crate::arith_add,
+                pre: [@literal @a],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @a
+        num: $a:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_add! {
+            @add
+            a: $a,
+            $($rest)*
+        }
+    };
+    // b given as an integer literal
+    (
+        @add
+        a: $a:tt,
+        b: $b:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $b,
+            callback: [
+                name: $crate::arith_add,
+                pre: [@literal @b a: $a,],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @b
+        a: $a:tt,
+        num: $b:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_add! {
+            @add
+            a: $a,
+            b: $b,
+            $($rest)*
+        }
+    };
     // a + 0
     (
         @add
@@ -156,10 +370,14 @@ macro_rules! arith_add {
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [$asgn $a],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [$asgn $a],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
         }
     };
     // 0 + b
@@ -173,10 +391,14 @@ macro_rules! arith_add {
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [$bsgn $b],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [$bsgn $b],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
         }
     };
     // a + b
@@ -190,10 +412,14 @@ macro_rules! arith_add {
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [[pos] [$($a)* $($b)*]],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] [$($a)* $($b)*]],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
         }
     };
     // a + (-b) = a - b
@@ -235,10 +461,14 @@ macro_rules! arith_add {
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [[neg] [$($a)* $($b)*]],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[neg] [$($a)* $($b)*]],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
         }
     };
 }
@@ -345,6 +575,36 @@ macro_rules! arith_add {
 ///         b: [[neg] [[] [] [] [] [] []]],
 ///     );
 ///     assert!(tmp == 1);
+///
+///     // Either operand can also be given as an integer literal:
+///     // 5 - (-3) = 8
+///     let tmp = {
+///         befunge_dm::arith_sub! {
+///             @sub
+///             a: 5,
+///             b: [[neg] [[] [] []]],
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == 8);
+///     // 2 - 6 = -4
+///     let tmp = {
+///         befunge_dm::arith_sub! {
+///             @sub
+///             a: [[pos] [[] []]],
+///             b: 6,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == -4);
 /// };
 /// ```
 ///
@@ -352,17 +612,72 @@ macro_rules! arith_add {
 ///   1. Check for easy cases (subtracting 0, subtracting from 0)
 ///   2. Defer to [`arith_add`] where possible.
 ///   3. Define an ad-hoc appropriate for the remaining cases:
-///      a. For `a - b`, define a macro with two branches. The first branch assumes that `a >= b`,
-///         and as such `a`'s magnitude can be matched as `b` repetitions of `[]`, plus an unknown
-///         (`a - b`) number of additional token trees. This expands to a result of
-///         `[[pos] [a - b]]`. The second branch assumes `a < b`, and as such `b` can be matched as
-///         `a` repetitions of `[]` plus an unknown (`b - a`) number of additional token trees. This
-///         expands to a result of `[[neg] [b - a]]`.
-///      b. For `(-a) - (-b) = (-a) + b = b - a`, define a macro very similar to the one described
-///         just above, but flip the branches.
+///      - For `a - b`, define a macro with two branches. The first branch assumes that `a >= b`,
+///        and as such `a`'s magnitude can be matched as `b` repetitions of `[]`, plus an unknown
+///        (`a - b`) number of additional token trees. This expands to a result of
+///        `[[pos] [a - b]]`. The second branch assumes `a < b`, and as such `b` can be matched as
+///        `a` repetitions of `[]` plus an unknown (`b - a`) number of additional token trees. This
+///        expands to a result of `[[neg] [b - a]]`.
+///      - For `(-a) - (-b) = (-a) + b = b - a`, define a macro very similar to the one described
+///        just above, but flip the branches.
 ///   4. Expand callback with result.
 #[macro_export]
 macro_rules! arith_sub {
+    // a given as an integer literal
+    (
+        @sub
+        a: $a:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $a,
+            callback: [
+                name: $crate::arith_sub,
+                pre: [@literal @a],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @a
+        num: $a:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_sub! {
+            @sub
+            a: $a,
+            $($rest)*
+        }
+    };
+    // b given as an integer literal
+    (
+        @sub
+        a: $a:tt,
+        b: $b:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $b,
+            callback: [
+                name: $crate::arith_sub,
+                pre: [@literal @b a: $a,],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @b
+        a: $a:tt,
+        num: $b:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_sub! {
+            @sub
+            a: $a,
+            b: $b,
+            $($rest)*
+        }
+    };
     // a - 0
     (
         @sub
@@ -374,10 +689,14 @@ macro_rules! arith_sub {
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [$asgn $a],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [$asgn $a],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
         }
     };
     // 0 - b
@@ -391,10 +710,14 @@ macro_rules! arith_sub {
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [[neg] $b],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[neg] $b],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
         }
     };
     // 0 - (-b)
@@ -408,10 +731,14 @@ macro_rules! arith_sub {
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [[pos] $b],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] $b],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
         }
     };
     // a - b
@@ -431,10 +758,14 @@ macro_rules! arith_sub {
                 a: [$($b)* $$($$diff:tt)*],
                 b: $$_:tt,
             ) => {
-                $name! {
-                    $($pre)*
-                    res: [[pos] [$$($$diff)*]],
-                    $($pst)*
+                $crate::arith_canonicalize! {
+                    @canonicalize
+                    a: [[pos] [$$($$diff)*]],
+                    callback: [
+                        name: $name,
+                        pre: [$($pre)*],
+                        pst: [$($pst)*],
+                    ],
                 }
             };
             (
@@ -442,10 +773,14 @@ macro_rules! arith_sub {
                 a: $$_:tt,
                 b: [$($a)* $$($$diff:tt)*],
             ) => {
-                $name! {
-                    $($pre)*
-                    res: [[neg] [$$($$diff)*]],
-                    $($pst)*
+                $crate::arith_canonicalize! {
+                    @canonicalize
+                    a: [[neg] [$$($$diff)*]],
+                    callback: [
+                        name: $name,
+                        pre: [$($pre)*],
+                        pst: [$($pst)*],
+                    ],
                 }
             };
         }
@@ -500,10 +835,14 @@ macro_rules! arith_sub {
                 a: [$($b)* $$($$diff:tt)*],
                 b: $$_:tt,
             ) => {
-                $name! {
-                    $($pre)*
-                    res: [[neg] [$$($$diff)*]],
-                    $($pst)*
+                $crate::arith_canonicalize! {
+                    @canonicalize
+                    a: [[neg] [$$($$diff)*]],
+                    callback: [
+                        name: $name,
+                        pre: [$($pre)*],
+                        pst: [$($pst)*],
+                    ],
                 }
             };
             (
@@ -511,10 +850,14 @@ macro_rules! arith_sub {
                 a: $$_:tt,
                 b: [$($a)* $$($$diff:tt)*],
             ) => {
-                $name! {
-                    $($pre)*
-                    res: [[pos] [$$($$diff)*]],
-                    $($pst)*
+                $crate::arith_canonicalize! {
+                    @canonicalize
+                    a: [[pos] [$$($$diff)*]],
+                    callback: [
+                        name: $name,
+                        pre: [$($pre)*],
+                        pst: [$($pst)*],
+                    ],
                 }
             };
         }
@@ -526,28 +869,23 @@ macro_rules! arith_sub {
     };
 }
 
-/// Multiplies two signed magnitude base 1 numbers
+/// Compares two signed magnitude base 1 numbers (`a` against `b`).
+///
+/// This follows the usual calling convention, except that the callback is made with
+/// `ord: [lt],`, `ord: [eq],`, or `ord: [gt],` in place of `res: [..],`, for `a < b`, `a == b`, and
+/// `a > b` respectively. `[[neg] []]` is compared as 0.
 ///
 /// Examples:
 /// ```
 /// #![feature(macro_metavar_expr)]
 ///
-/// macro_rules! num_to_lit {
-///     ([[$(pos)?] [$($num:tt)*]]) => {
-///         ${count($num)}
-///     };
-///     ([[neg] [$($num:tt)*]]) => {
-///         -${count($num)}
-///     };
-/// }
-///
 /// macro_rules! wrapper {
 ///     (
 ///         a: $a:tt,
 ///         b: $b:tt,
 ///     ) => {{
-///         befunge_dm::arith_mul! {
-///             @mul
+///         befunge_dm::arith_cmp! {
+///             @cmp
 ///             a: $a,
 ///             b: $b,
 ///             callback: [
@@ -558,301 +896,133 @@ macro_rules! arith_sub {
 ///         }
 ///     }};
 ///     (
-///         res: $res:tt,
+///         ord: [lt],
 ///     ) => {
-///         num_to_lit!($res)
+///         -1
+///     };
+///     (
+///         ord: [eq],
+///     ) => {
+///         0
+///     };
+///     (
+///         ord: [gt],
+///     ) => {
+///         1
 ///     };
 /// }
 ///
 /// const _: () = {
-///     // 0 * 0 = 0
+///     // Cases with a zero in them:
+///     // 0 cmp 0 = eq
 ///     let tmp = wrapper!(
 ///         a: [[pos] []],
 ///         b: [[pos] []],
 ///     );
 ///     assert!(tmp == 0);
-///     // 0 * 4 = 0
+///     // (-0) cmp 0 = eq
 ///     let tmp = wrapper!(
-///         a: [[pos] []],
-///         b: [[pos] [[] [] [] []]],
+///         a: [[neg] []],
+///         b: [[pos] []],
 ///     );
 ///     assert!(tmp == 0);
-///     // 3 * 4 = 12
+///     // 2 cmp 0 = gt
 ///     let tmp = wrapper!(
-///         a: [[pos] [[] [] []]],
-///         b: [[pos] [[] [] [] []]],
+///         a: [[pos] [[] []]],
+///         b: [[pos] []],
 ///     );
-///     assert!(tmp == 12);
-///     // (-2) * (-6) = 12
+///     assert!(tmp == 1);
+///     // -2 cmp 0 = lt
 ///     let tmp = wrapper!(
 ///         a: [[neg] [[] []]],
-///         b: [[neg] [[] [] [] [] [] []]],
+///         b: [[neg] []],
 ///     );
-///     assert!(tmp == 12);
-///     // (-3) * 3 = -9
+///     assert!(tmp == -1);
+///     // 0 cmp 3 = lt
 ///     let tmp = wrapper!(
-///         a: [[neg] [[] [] []]],
+///         a: [[] []],
 ///         b: [[pos] [[] [] []]],
 ///     );
-///     assert!(tmp == -9);
-///     // 4 * (-2) = -8
+///     assert!(tmp == -1);
+///     // 0 cmp (-3) = gt
 ///     let tmp = wrapper!(
-///         a: [[pos] [[] [] [] []]],
+///         a: [[pos] []],
+///         b: [[neg] [[] [] []]],
+///     );
+///     assert!(tmp == 1);
+///
+///     // Cases where the signs differ:
+///     // 1 cmp (-2) = gt
+///     let tmp = wrapper!(
+///         a: [[pos] [[]]],
 ///         b: [[neg] [[] []]],
 ///     );
-///     assert!(tmp == -8);
-/// };
-/// ```
+///     assert!(tmp == 1);
+///     // -2 cmp 1 = lt
+///     let tmp = wrapper!(
+///         a: [[neg] [[] []]],
+///         b: [[] [[]]],
+///     );
+///     assert!(tmp == -1);
 ///
-/// Execution strategy:
-///   1. Using features from `macro_metavar_expr`, we can simply repeat the magnitude of one number
-///      a number of times equal to the number of token trees in the other number's magnitude. The
-///      only thing we have to handle manually is the sign of the result. No special casing is
-///      necessary - all cases are equally easy to handle.
-///   2. Expand the callback with the result.
-#[macro_export]
-macro_rules! arith_mul {
-    (
-        @mul
-        a: [[$(pos)?] [$($a:tt)*]],
-        b: [[$(pos)?] $b:tt],
-        callback: $callback:tt,
-    ) => {
-        $crate::arith_mul! {
-            @catch
-            res: [[pos] $(${ignore($a)}$b)*],
-            callback: $callback,
-        }
-    };
-    (
-        @mul
-        a: [[neg] [$($a:tt)*]],
-        b: [[neg] $b:tt],
-        callback: $callback:tt,
-    ) => {
-        $crate::arith_mul! {
-            @catch
-            res: [[pos] $(${ignore($a)}$b)*],
-            callback: $callback,
-        }
-    };
-    (
-        @mul
-        a: [[$($asgn:tt)?] [$($a:tt)*]],
-        b: [[$($bsgn:tt)?] $b:tt],
-        callback: $callback:tt,
-    ) => {
-        $crate::arith_mul! {
-            @catch
-            res: [[neg] $(${ignore($a)}$b)*],
-            callback: $callback,
-        }
-    };
-    (
-        @catch
-        res: [[$sgn:tt] $([$($val:tt)*])*],
-        callback: [
-            name: $name:path,
-            pre: [$($pre:tt)*],
-            pst: [$($pst:tt)*],
-        ],
-    ) => {
-        $name! {
-            $($pre)*
-            res: [[$sgn] [$($($val)*)*]],
-            $($pst)*
-        }
-    };
-}
-
-// macro_rules! num_to_lit {
-//     ([[$(pos)?] [$($num:tt)*]]) => {
-//         ${count($num)}
-//     };
-//     ([[neg] [$($num:tt)*]]) => {
-//         -${count($num)}
-//     };
-// }
-// macro_rules! wrapper {
-//     (
-//         a: $a:tt,
-//         b: $b:tt,
-//     ) => {{
-//         $crate::arith_div! {
-//             @div
-//             a: $a,
-//             b: $b,
-//             callback: [
-//                 name: wrapper,
-//                 pre: [],
-//                 pst: [],
-//             ],
-//         }
-//     }};
-//     (
-//         res: $res:tt,
-//     ) => {
-//         num_to_lit!($res)
-//     };
-// }
-// const _: () = {
-//     // 2 / 5 = 0
-//     let tmp = wrapper!(
-//         a: [[pos] [[] []]],
-//         b: [[pos] [[] [] [] [] []]],
-//     );
-//     assert!(tmp == 0);
-//     // (-2) / 5 = 0
-//     let tmp = wrapper!(
-//         a: [[neg] [[] []]],
-//         b: [[pos] [[] [] [] [] []]],
-//     );
-//     assert!(tmp == 0);
-//     // 2 / (-5) = 0
-//     let tmp = wrapper!(
-//         a: [[pos] [[] []]],
-//         b: [[neg] [[] [] [] [] []]],
-//     );
-//     assert!(tmp == 0);
-//     // (-2) / (-5) = 0
-//     let tmp = wrapper!(
-//         a: [[neg] [[] []]],
-//         b: [[neg] [[] [] [] [] []]],
-//     );
-//     assert!(tmp == 0);
-//     // 5 / 2 = 2
-//     let tmp = wrapper!(
-//         a: [[pos] [[] [] [] [] []]],
-//         b: [[pos] [[] []]],
-//     );
-//     assert!(tmp == 2);
-//     // 5 % (-2) = -2
-//     let tmp = wrapper!(
-//         a: [[pos] [[] [] [] [] []]],
-//         b: [[neg] [[] []]],
-//     );
-//     assert!(tmp == -2);
-//     // (-5) % 2 = -2
-//     let tmp = wrapper!(
-//         a: [[neg] [[] [] [] [] []]],
-//         b: [[pos] [[] []]],
-//     );
-//     assert!(tmp == -2);
-//     // (-5) % (-2) = 2
-//     let tmp = wrapper!(
-//         a: [[neg] [[] [] [] [] []]],
-//         b: [[neg] [[] []]],
-//     );
-//     assert!(tmp == 2);
-// };
-
-/// Divides two signed magnitude base 1 numbers (`a / b` ordering).
-///
-/// If `a / 0` is attempted, it will defer to the [`befunge_pm::div_by_zero!`] proc macro, which
-/// will cause the Befunge interface to prompt for a response. This response will then be used in
-/// the expansion of this macro.
-///
-/// Examples:
-/// ```
-/// #![feature(macro_metavar_expr)]
-///
-/// macro_rules! num_to_lit {
-///     ([[$(pos)?] [$($num:tt)*]]) => {
-///         ${count($num)}
-///     };
-///     ([[neg] [$($num:tt)*]]) => {
-///         -${count($num)}
-///     };
-/// }
-///
-/// macro_rules! wrapper {
-///     (
-///         a: $a:tt,
-///         b: $b:tt,
-///     ) => {{
-///         befunge_dm::arith_div! {
-///             @div
-///             a: $a,
-///             b: $b,
-///             callback: [
-///                 name: wrapper,
-///                 pre: [],
-///                 pst: [],
-///             ],
-///         }
-///     }};
-///     (
-///         res: $res:tt,
-///     ) => {
-///         num_to_lit!($res)
-///     };
-/// }
-///
-/// const _: () = {
-///     // 2 / 5 = 0
+///     // Cases where both are positive:
+///     // 5 cmp 6 = lt
 ///     let tmp = wrapper!(
-///         a: [[pos] [[] []]],
-///         b: [[pos] [[] [] [] [] []]],
+///         a: [[pos] [[] [] [] [] []]],
+///         b: [[pos] [[] [] [] [] [] []]],
 ///     );
-///     assert!(tmp == 0);
-///     // (-2) / 5 = 0
+///     assert!(tmp == -1);
+///     // 6 cmp 5 = gt
 ///     let tmp = wrapper!(
-///         a: [[neg] [[] []]],
+///         a: [[pos] [[] [] [] [] [] []]],
 ///         b: [[pos] [[] [] [] [] []]],
 ///     );
-///     assert!(tmp == 0);
-///     // 2 / (-5) = 0
-///     let tmp = wrapper!(
-///         a: [[pos] [[] []]],
-///         b: [[neg] [[] [] [] [] []]],
-///     );
-///     assert!(tmp == 0);
-///     // (-2) / (-5) = 0
+///     assert!(tmp == 1);
+///     // 4 cmp 4 = eq
 ///     let tmp = wrapper!(
-///         a: [[neg] [[] []]],
-///         b: [[neg] [[] [] [] [] []]],
+///         a: [[pos] [[] [] [] []]],
+///         b: [[] [[] [] [] []]],
 ///     );
 ///     assert!(tmp == 0);
-///     // 5 / 2 = 2
-///     let tmp = wrapper!(
-///         a: [[pos] [[] [] [] [] []]],
-///         b: [[pos] [[] []]],
-///     );
-///     assert!(tmp == 2);
-///     // 5 % (-2) = -2
+///
+///     // Cases where both are negative:
+///     // -5 cmp (-6) = gt
 ///     let tmp = wrapper!(
-///         a: [[pos] [[] [] [] [] []]],
-///         b: [[neg] [[] []]],
+///         a: [[neg] [[] [] [] [] []]],
+///         b: [[neg] [[] [] [] [] [] []]],
 ///     );
-///     assert!(tmp == -2);
-///     // (-5) % 2 = -2
+///     assert!(tmp == 1);
+///     // -6 cmp (-5) = lt
 ///     let tmp = wrapper!(
-///         a: [[neg] [[] [] [] [] []]],
-///         b: [[pos] [[] []]],
+///         a: [[neg] [[] [] [] [] [] []]],
+///         b: [[neg] [[] [] [] [] []]],
 ///     );
-///     assert!(tmp == -2);
-///     // (-5) % (-2) = 2
+///     assert!(tmp == -1);
+///     // -4 cmp (-4) = eq
 ///     let tmp = wrapper!(
-///         a: [[neg] [[] [] [] [] []]],
-///         b: [[neg] [[] []]],
+///         a: [[neg] [[] [] [] []]],
+///         b: [[neg] [[] [] [] []]],
 ///     );
-///     assert!(tmp == 2);
+///     assert!(tmp == 0);
 /// };
 /// ```
 ///
 /// Execution strategy:
-///   1. Handle easy cases (`0 / n`, `n / 1`, `n / (-1)`, `a / b` where `|a| < |b|`).
-///   2. Handle div by zero case with [`befunge_pm::div_by_zero`] proc macro.
-///   3. Call [`crate::arith_div_mod`] macro using signs of numbers as internal rule labels for
-///      callback.
-///   4. Handle callback from [`crate::arith_div_mod`], make callback given to this macro call.
+///   1. Check for easy cases (either side is 0, or the signs differ), where the signs alone give
+///      the answer.
+///   2. Define an ad-hoc macro for the remaining cases, in the same way as [`arith_sub`]:
+///      - For `a` against `b`, define a macro with three branches. The first matches `a`'s
+///        magnitude as `b` repetitions of `[]` plus at least one more token tree, so `a > b`. The
+///        second does the same for `b` against `a`, so `a < b`. The third is left for `a == b`.
+///      - For `-a` against `-b`, define the same macro with the first two results swapped.
+///   3. Expand callback with result.
 #[macro_export]
-macro_rules! arith_div {
-    // 0 / b = 0
+macro_rules! arith_cmp {
+    // 0 cmp 0
     (
-        @div
+        @cmp
         a: [$asgn:tt []],
-        b: $b:tt,
+        b: [$bsgn:tt []],
         callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
@@ -861,27 +1031,15 @@ macro_rules! arith_div {
     ) => {
         $name! {
             $($pre)*
-            res: [[pos] []],
+            ord: [eq],
             $($pst)*
         }
     };
-    // a / 0
+    // a cmp 0 and 0 cmp (-b)
     (
-        @div
-        a: $a:tt,
+        @cmp
+        a: [[$(pos)?] $a:tt],
         b: [$bsgn:tt []],
-        callback: $callback:tt,
-    ) => {
-        $crate::befunge_pm::div_by_zero! {
-            socket: "befunge.input",
-            callback: $callback,
-        }
-    };
-    // a / 1
-    (
-        @div
-        a: $a:tt,
-        b: [[$(pos)?] [[]]],
         callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
@@ -890,15 +1048,14 @@ macro_rules! arith_div {
     ) => {
         $name! {
             $($pre)*
-            res: $a,
+            ord: [gt],
             $($pst)*
         }
     };
-    // a / (-1)
     (
-        @div
-        a: [[$(pos)?] $a:tt],
-        b: [[neg] [[]]],
+        @cmp
+        a: [$asgn:tt []],
+        b: [[neg] $b:tt],
         callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
@@ -907,15 +1064,15 @@ macro_rules! arith_div {
     ) => {
         $name! {
             $($pre)*
-            res: [[neg] $a],
+            ord: [gt],
             $($pst)*
         }
     };
-    // (-a) / (-1)
+    // (-a) cmp 0 and 0 cmp b
     (
-        @div
+        @cmp
         a: [[neg] $a:tt],
-        b: [[neg] [[]]],
+        b: [$bsgn:tt []],
         callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
@@ -924,30 +1081,2864 @@ macro_rules! arith_div {
     ) => {
         $name! {
             $($pre)*
-            res: [[pos] $a],
+            ord: [lt],
             $($pst)*
         }
     };
-    // if |a| < |b|, then a / b = 0
     (
-        @div
-        a: [[$($asgn:tt)?] $a:tt],
-        b: [[$($bsgn:tt)?] [$($b:tt)+]],
-        callback: [
+        @cmp
+        a: [$asgn:tt []],
+        b: [[$(pos)?] $b:tt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            ord: [lt],
+            $($pst)*
+        }
+    };
+    // a cmp (-b)
+    (
+        @cmp
+        a: [[$(pos)?] $a:tt],
+        b: [[neg] $b:tt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            ord: [gt],
+            $($pst)*
+        }
+    };
+    // (-a) cmp b
+    (
+        @cmp
+        a: [[neg] $a:tt],
+        b: [[$(pos)?] $b:tt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            ord: [lt],
+            $($pst)*
+        }
+    };
+    // a cmp b
+    (
+        @cmp
+        a: [[$(pos)?] [$($a:tt)*]],
+        b: [[$(pos)?] [$($b:tt)*]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        macro_rules! exec_cmp {
+            (
+                @cmp
+                a: [$($b)* $$_amore:tt $$($$_arest:tt)*],
+                b: $$_b:tt,
+            ) => {
+                $name! {
+                    $($pre)*
+                    ord: [gt],
+                    $($pst)*
+                }
+            };
+            (
+                @cmp
+                a: $$_a:tt,
+                b: [$($a)* $$_bmore:tt $$($$_brest:tt)*],
+            ) => {
+                $name! {
+                    $($pre)*
+                    ord: [lt],
+                    $($pst)*
+                }
+            };
+            (
+                @cmp
+                a: $$_a:tt,
+                b: $$_b:tt,
+            ) => {
+                $name! {
+                    $($pre)*
+                    ord: [eq],
+                    $($pst)*
+                }
+            };
+        }
+        exec_cmp! {
+            @cmp
+            a: [$($a)*],
+            b: [$($b)*],
+        }
+    };
+    // (-a) cmp (-b), where the one further from 0 is less
+    (
+        @cmp
+        a: [[neg] [$($a:tt)*]],
+        b: [[neg] [$($b:tt)*]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        macro_rules! exec_cmp {
+            (
+                @cmp
+                a: [$($b)* $$_amore:tt $$($$_arest:tt)*],
+                b: $$_b:tt,
+            ) => {
+                $name! {
+                    $($pre)*
+                    ord: [lt],
+                    $($pst)*
+                }
+            };
+            (
+                @cmp
+                a: $$_a:tt,
+                b: [$($a)* $$_bmore:tt $$($$_brest:tt)*],
+            ) => {
+                $name! {
+                    $($pre)*
+                    ord: [gt],
+                    $($pst)*
+                }
+            };
+            (
+                @cmp
+                a: $$_a:tt,
+                b: $$_b:tt,
+            ) => {
+                $name! {
+                    $($pre)*
+                    ord: [eq],
+                    $($pst)*
+                }
+            };
+        }
+        exec_cmp! {
+            @cmp
+            a: [$($a)*],
+            b: [$($b)*],
+        }
+    };
+}
+
+/// Negates a signed magnitude base 1 number.
+///
+/// Zero stays positive, so negating `[[pos] []]` (or `[[neg] []]`) gives `[[pos] []]`.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     // Zero should always come back positive
+///     ([[neg] []]) => {
+///         compile_error!("got `[[neg] []]`")
+///     };
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///     ) => {{
+///         befunge_dm::arith_neg! {
+///             @neg
+///             a: $a,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // -0 = 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///     );
+///     assert!(tmp == 0);
+///     // -(-0) = 0
+///     let tmp = wrapper!(
+///         a: [[neg] []],
+///     );
+///     assert!(tmp == 0);
+///     // -(3) = -3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] []]],
+///     );
+///     assert!(tmp == -3);
+///     // -(3) = -3, with an empty sign
+///     let tmp = wrapper!(
+///         a: [[] [[] [] []]],
+///     );
+///     assert!(tmp == -3);
+///     // -(-2) = 2
+///     let tmp = wrapper!(
+///         a: [[neg] [[] []]],
+///     );
+///     assert!(tmp == 2);
+/// };
+/// ```
+#[macro_export]
+macro_rules! arith_neg {
+    // -0 = 0
+    (
+        @neg
+        a: [$sgn:tt []],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            $($pst)*
+        }
+    };
+    // -a
+    (
+        @neg
+        a: [[$(pos)?] $a:tt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[neg] $a],
+            $($pst)*
+        }
+    };
+    // -(-a) = a
+    (
+        @neg
+        a: [[neg] $a:tt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] $a],
+            $($pst)*
+        }
+    };
+}
+
+/// Takes the absolute value of a signed magnitude base 1 number.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     // Zero should always come back positive
+///     ([[neg] []]) => {
+///         compile_error!("got `[[neg] []]`")
+///     };
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///     ) => {{
+///         befunge_dm::arith_abs! {
+///             @abs
+///             a: $a,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // |0| = 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///     );
+///     assert!(tmp == 0);
+///     // |-0| = 0
+///     let tmp = wrapper!(
+///         a: [[neg] []],
+///     );
+///     assert!(tmp == 0);
+///     // |3| = 3
+///     let tmp = wrapper!(
+///         a: [[] [[] [] []]],
+///     );
+///     assert!(tmp == 3);
+///     // |-2| = 2
+///     let tmp = wrapper!(
+///         a: [[neg] [[] []]],
+///     );
+///     assert!(tmp == 2);
+/// };
+/// ```
+#[macro_export]
+macro_rules! arith_abs {
+    // |a| = |-a| = a
+    (
+        @abs
+        a: [$sgn:tt $a:tt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] $a],
+            $($pst)*
+        }
+    };
+}
+
+/// Gets the sign of a signed magnitude base 1 number as 1, 0, or -1.
+///
+/// The result is `[[pos] [[]]]` for positive numbers, `[[pos] []]` for zero (including
+/// `[[neg] []]`), and `[[neg] [[]]]` for negative numbers.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     // Zero should always come back positive
+///     ([[neg] []]) => {
+///         compile_error!("got `[[neg] []]`")
+///     };
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///     ) => {{
+///         befunge_dm::arith_sign! {
+///             @sign
+///             a: $a,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // sign(0) = 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///     );
+///     assert!(tmp == 0);
+///     // sign(-0) = 0
+///     let tmp = wrapper!(
+///         a: [[neg] []],
+///     );
+///     assert!(tmp == 0);
+///     // sign(3) = 1
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] []]],
+///     );
+///     assert!(tmp == 1);
+///     // sign(3) = 1, with an empty sign
+///     let tmp = wrapper!(
+///         a: [[] [[] [] []]],
+///     );
+///     assert!(tmp == 1);
+///     // sign(-2) = -1
+///     let tmp = wrapper!(
+///         a: [[neg] [[] []]],
+///     );
+///     assert!(tmp == -1);
+/// };
+/// ```
+#[macro_export]
+macro_rules! arith_sign {
+    // sign(0) = sign(-0) = 0
+    (
+        @sign
+        a: [$sgn:tt []],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            $($pst)*
+        }
+    };
+    // sign(a) = 1
+    (
+        @sign
+        a: [[$(pos)?] $a:tt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] [[]]],
+            $($pst)*
+        }
+    };
+    // sign(-a) = -1
+    (
+        @sign
+        a: [[neg] $a:tt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[neg] [[]]],
+            $($pst)*
+        }
+    };
+}
+
+/// Gives the smaller of two signed magnitude base 1 numbers.
+///
+/// This compares them with [`arith_cmp`], and gives `a` if they're the same. Zero always comes
+/// back as `[[pos] []]`, even if it was given as `[[neg] []]`.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     // Zero should always come back positive
+///     ([[neg] []]) => {
+///         compile_error!("got `[[neg] []]`")
+///     };
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///         b: $b:tt,
+///     ) => {{
+///         befunge_dm::arith_min! {
+///             @min
+///             a: $a,
+///             b: $b,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // min(-3, 2) = -3
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] []]],
+///         b: [[pos] [[] []]],
+///     );
+///     assert!(tmp == -3);
+///     // min(2, -3) = -3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] []]],
+///         b: [[neg] [[] [] []]],
+///     );
+///     assert!(tmp == -3);
+///     // min(-5, -2) = -5
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///     );
+///     assert!(tmp == -5);
+///     // min(4, 6) = 4
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] []]],
+///         b: [[] [[] [] [] [] [] []]],
+///     );
+///     assert!(tmp == 4);
+///     // min(0, 1) = 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///         b: [[pos] [[]]],
+///     );
+///     assert!(tmp == 0);
+///     // min(3, 3) = 3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] []]],
+///         b: [[pos] [[] [] []]],
+///     );
+///     assert!(tmp == 3);
+///     // min(-0, 0) = 0
+///     let tmp = wrapper!(
+///         a: [[neg] []],
+///         b: [[pos] []],
+///     );
+///     assert!(tmp == 0);
+///     // min(-0, 5) = 0
+///     let tmp = wrapper!(
+///         a: [[neg] []],
+///         b: [[pos] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == 0);
+/// };
+/// ```
+#[macro_export]
+macro_rules! arith_min {
+    (
+        @min
+        a: $a:tt,
+        b: $b:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_cmp! {
+            @cmp
+            a: $a,
+            b: $b,
+            callback: [
+                name: $crate::arith_min,
+                pre: [
+                    @catch
+                    a: $a,
+                    b: $b,
+                ],
+                pst: [
+                    callback: $callback,
+                ],
+            ],
+        }
+    };
+    // zero always comes back as `[[pos] []]`, in case it was given as `[[neg] []]`
+    (
+        @catch
+        a: $a:tt,
+        b: [$bsgn:tt []],
+        ord: [gt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            $($pst)*
+        }
+    };
+    (
+        @catch
+        a: $a:tt,
+        b: $b:tt,
+        ord: [gt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: $b,
+            $($pst)*
+        }
+    };
+    (
+        @catch
+        a: [$asgn:tt []],
+        b: $b:tt,
+        ord: $ord:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            $($pst)*
+        }
+    };
+    // otherwise a is the smaller, or they're the same
+    (
+        @catch
+        a: $a:tt,
+        b: $b:tt,
+        ord: $ord:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: $a,
+            $($pst)*
+        }
+    };
+}
+
+/// Gives the bigger of two signed magnitude base 1 numbers.
+///
+/// This compares them with [`arith_cmp`], and gives `a` if they're the same. Zero always comes
+/// back as `[[pos] []]`, even if it was given as `[[neg] []]`.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     // Zero should always come back positive
+///     ([[neg] []]) => {
+///         compile_error!("got `[[neg] []]`")
+///     };
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///         b: $b:tt,
+///     ) => {{
+///         befunge_dm::arith_max! {
+///             @max
+///             a: $a,
+///             b: $b,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // max(0, 0) = 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///         b: [[pos] []],
+///     );
+///     assert!(tmp == 0);
+///     // max(0, -0) = 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///         b: [[neg] []],
+///     );
+///     assert!(tmp == 0);
+///     // max(-3, 2) = 2
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] []]],
+///         b: [[pos] [[] []]],
+///     );
+///     assert!(tmp == 2);
+///     // max(-5, -2) = -2
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///     );
+///     assert!(tmp == -2);
+///     // max(6, 4) = 6
+///     let tmp = wrapper!(
+///         a: [[] [[] [] [] [] [] []]],
+///         b: [[pos] [[] [] [] []]],
+///     );
+///     assert!(tmp == 6);
+///     // max(-1, -0) = 0
+///     let tmp = wrapper!(
+///         a: [[neg] [[]]],
+///         b: [[neg] []],
+///     );
+///     assert!(tmp == 0);
+///     // max(-4, -4) = -4
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] []]],
+///         b: [[neg] [[] [] [] []]],
+///     );
+///     assert!(tmp == -4);
+/// };
+/// ```
+#[macro_export]
+macro_rules! arith_max {
+    (
+        @max
+        a: $a:tt,
+        b: $b:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_cmp! {
+            @cmp
+            a: $a,
+            b: $b,
+            callback: [
+                name: $crate::arith_max,
+                pre: [
+                    @catch
+                    a: $a,
+                    b: $b,
+                ],
+                pst: [
+                    callback: $callback,
+                ],
+            ],
+        }
+    };
+    // zero always comes back as `[[pos] []]`, in case it was given as `[[neg] []]`
+    (
+        @catch
+        a: $a:tt,
+        b: [$bsgn:tt []],
+        ord: [lt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            $($pst)*
+        }
+    };
+    (
+        @catch
+        a: $a:tt,
+        b: $b:tt,
+        ord: [lt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: $b,
+            $($pst)*
+        }
+    };
+    (
+        @catch
+        a: [$asgn:tt []],
+        b: $b:tt,
+        ord: $ord:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            $($pst)*
+        }
+    };
+    // otherwise a is the bigger, or they're the same
+    (
+        @catch
+        a: $a:tt,
+        b: $b:tt,
+        ord: $ord:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: $a,
+            $($pst)*
+        }
+    };
+}
+
+/// Doubles a signed magnitude base 1 number by writing its magnitude out twice.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     // Zero should always come back positive
+///     ([[neg] []]) => {
+///         compile_error!("got `[[neg] []]`")
+///     };
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///     ) => {{
+///         befunge_dm::arith_double! {
+///             @double
+///             a: $a,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // 2 * 0 = 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///     );
+///     assert!(tmp == 0);
+///     // 2 * (-0) = 0
+///     let tmp = wrapper!(
+///         a: [[neg] []],
+///     );
+///     assert!(tmp == 0);
+///     // 2 * 3 = 6
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] []]],
+///     );
+///     assert!(tmp == 6);
+///     // 2 * 4 = 8
+///     let tmp = wrapper!(
+///         a: [[] [[] [] [] []]],
+///     );
+///     assert!(tmp == 8);
+///     // 2 * (-5) = -10
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == -10);
+/// };
+/// ```
+#[macro_export]
+macro_rules! arith_double {
+    // 2 * 0 = 0
+    (
+        @double
+        a: [$sgn:tt []],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            $($pst)*
+        }
+    };
+    // 2 * a
+    (
+        @double
+        a: [$sgn:tt [$($a:tt)*]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [$sgn [$($a)* $($a)*]],
+            $($pst)*
+        }
+    };
+}
+
+/// Halves a signed magnitude base 1 number, rounding towards zero, by taking its magnitude two
+/// token trees at a time.
+///
+/// The callback is made with `res: [..],` and then `rem: [[pos] []],` or `rem: [[pos] [[]]],`,
+/// depending on whether the magnitude was even or odd. For `a >= 0` that makes
+/// `a = 2 * res + rem`, and for `a < 0`, `a = 2 * res - rem`. This relies on every token tree in
+/// the magnitude being `[]`.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     // Zero should always come back positive
+///     ([[neg] []]) => {
+///         compile_error!("got `[[neg] []]`")
+///     };
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///     ) => {{
+///         befunge_dm::arith_halve! {
+///             @halve
+///             a: $a,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///         rem: $rem:tt,
+///     ) => {
+///         (num_to_lit!($res), num_to_lit!($rem))
+///     };
+/// }
+///
+/// const _: () = {
+///     // 0 / 2 = 0, remainder 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///     );
+///     assert!(tmp.0 == 0 && tmp.1 == 0);
+///     // (-0) / 2 = 0, remainder 0
+///     let tmp = wrapper!(
+///         a: [[neg] []],
+///     );
+///     assert!(tmp.0 == 0 && tmp.1 == 0);
+///     // 1 / 2 = 0, remainder 1
+///     let tmp = wrapper!(
+///         a: [[pos] [[]]],
+///     );
+///     assert!(tmp.0 == 0 && tmp.1 == 1);
+///     // (-1) / 2 = 0, remainder 1
+///     let tmp = wrapper!(
+///         a: [[neg] [[]]],
+///     );
+///     assert!(tmp.0 == 0 && tmp.1 == 1);
+///     // 6 / 2 = 3, remainder 0
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] []]],
+///     );
+///     assert!(tmp.0 == 3 && tmp.1 == 0);
+///     // 7 / 2 = 3, remainder 1
+///     let tmp = wrapper!(
+///         a: [[] [[] [] [] [] [] [] []]],
+///     );
+///     assert!(tmp.0 == 3 && tmp.1 == 1);
+///     // (-8) / 2 = -4, remainder 0
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] [] []]],
+///     );
+///     assert!(tmp.0 == -4 && tmp.1 == 0);
+///     // (-9) / 2 = -4, remainder 1
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] [] [] []]],
+///     );
+///     assert!(tmp.0 == -4 && tmp.1 == 1);
+/// };
+/// ```
+#[macro_export]
+macro_rules! arith_halve {
+    // 0 / 2 = 0
+    (
+        @halve
+        a: [$sgn:tt []],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            rem: [[pos] []],
+            $($pst)*
+        }
+    };
+    // 1 / 2 = (-1) / 2 = 0, remainder 1
+    (
+        @halve
+        a: [$sgn:tt [[]]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [[pos] []],
+            rem: [[pos] [[]]],
+            $($pst)*
+        }
+    };
+    // a is even, so the magnitude is made of pairs
+    (
+        @halve
+        a: [$sgn:tt [$($half:tt [])*]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [$sgn [$($half)*]],
+            rem: [[pos] []],
+            $($pst)*
+        }
+    };
+    // a is odd, so the magnitude is one more than some pairs
+    (
+        @halve
+        a: [$sgn:tt [[] $($half:tt [])*]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            res: [$sgn [$($half)*]],
+            rem: [[pos] [[]]],
+            $($pst)*
+        }
+    };
+}
+
+/// Multiplies two signed magnitude base 1 numbers
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///         b: $b:tt,
+///     ) => {{
+///         befunge_dm::arith_mul! {
+///             @mul
+///             a: $a,
+///             b: $b,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // 0 * 0 = 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///         b: [[pos] []],
+///     );
+///     assert!(tmp == 0);
+///     // 0 * 4 = 0
+///     let tmp = wrapper!(
+///         a: [[pos] []],
+///         b: [[pos] [[] [] [] []]],
+///     );
+///     assert!(tmp == 0);
+///     // 3 * 4 = 12
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] []]],
+///         b: [[pos] [[] [] [] []]],
+///     );
+///     assert!(tmp == 12);
+///     // (-2) * (-6) = 12
+///     let tmp = wrapper!(
+///         a: [[neg] [[] []]],
+///         b: [[neg] [[] [] [] [] [] []]],
+///     );
+///     assert!(tmp == 12);
+///     // (-3) * 3 = -9
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] []]],
+///         b: [[pos] [[] [] []]],
+///     );
+///     assert!(tmp == -9);
+///     // 4 * (-2) = -8
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] []]],
+///         b: [[neg] [[] []]],
+///     );
+///     assert!(tmp == -8);
+///
+///     // The biggest `a` that's multiplied directly, and then cases big enough to be halved
+///     // first. None of these need a `#![recursion_limit]`.
+///     // 16 * 16 = 256
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+///         b: [[pos] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+///     );
+///     assert!(tmp == 256);
+///     // 17 * 3 = 51
+///     let tmp = wrapper!(
+///         a: [[pos] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             []
+///         ]],
+///         b: [[pos] [[] [] []]],
+///     );
+///     assert!(tmp == 51);
+///     // (-33) * 2 = -66
+///     let tmp = wrapper!(
+///         a: [[neg] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             []
+///         ]],
+///         b: [[pos] [[] []]],
+///     );
+///     assert!(tmp == -66);
+///     // 64 * 64 = 4096
+///     let tmp = wrapper!(
+///         a: [[pos] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///         ]],
+///         b: [[pos] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///         ]],
+///     );
+///     assert!(tmp == 4096);
+///     // (-63) * (-64) = 4032
+///     let tmp = wrapper!(
+///         a: [[neg] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///         ]],
+///         b: [[neg] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///         ]],
+///     );
+///     assert!(tmp == 4032);
+///     // 99 * (-99) = -9801
+///     let tmp = wrapper!(
+///         a: [[pos] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] []
+///         ]],
+///         b: [[neg] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] []
+///         ]],
+///     );
+///     assert!(tmp == -9801);
+///
+///     // Either operand can also be given as an integer literal:
+///     // -3 * 4 = -12
+///     let tmp = {
+///         befunge_dm::arith_mul! {
+///             @mul
+///             a: -3,
+///             b: [[pos] [[] [] [] []]],
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == -12);
+///     // (-2) * (-5) = 10
+///     let tmp = {
+///         befunge_dm::arith_mul! {
+///             @mul
+///             a: [[neg] [[] []]],
+///             b: -5,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == 10);
+/// };
+/// ```
+///
+/// Execution strategy:
+///   1. Work out the sign of the result from the signs of `a` and `b`.
+///   2. If `|a| <= 16`, use features from `macro_metavar_expr` to simply repeat the magnitude of
+///      `b` a number of times equal to the number of token trees in the magnitude of `a`.
+///   3. Otherwise, split `|a|` into pairs of `[]`s (and one left over if it's odd), multiply `b` by
+///      the number of pairs, and then write that out twice (and `b` once more if `|a|` was odd):
+///      `a * b = 2 * ((a / 2) * b) + (a % 2) * b`. This halves `|a|` each time, so only a handful
+///      of macro calls deep, no single repetition has to write out more than `16 * |b|` tokens.
+///   4. Expand the callback with the result.
+#[macro_export]
+macro_rules! arith_mul {
+    // a given as an integer literal
+    (
+        @mul
+        a: $a:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $a,
+            callback: [
+                name: $crate::arith_mul,
+                pre: [@literal @a],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @a
+        num: $a:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_mul! {
+            @mul
+            a: $a,
+            $($rest)*
+        }
+    };
+    // b given as an integer literal
+    (
+        @mul
+        a: $a:tt,
+        b: $b:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $b,
+            callback: [
+                name: $crate::arith_mul,
+                pre: [@literal @b a: $a,],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @b
+        a: $a:tt,
+        num: $b:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_mul! {
+            @mul
+            a: $a,
+            b: $b,
+            $($rest)*
+        }
+    };
+    (
+        @mul
+        a: [[$(pos)?] $a:tt],
+        b: [[$(pos)?] $b:tt],
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_mul! {
+            @mag
+            sgn: pos,
+            a: $a,
+            b: $b,
+            callback: $callback,
+        }
+    };
+    (
+        @mul
+        a: [[neg] $a:tt],
+        b: [[neg] $b:tt],
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_mul! {
+            @mag
+            sgn: pos,
+            a: $a,
+            b: $b,
+            callback: $callback,
+        }
+    };
+    (
+        @mul
+        a: [[$($asgn:tt)?] $a:tt],
+        b: [[$($bsgn:tt)?] $b:tt],
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_mul! {
+            @mag
+            sgn: neg,
+            a: $a,
+            b: $b,
+            callback: $callback,
+        }
+    };
+    // |a| > 16: a * b = 2 * ((a / 2) * b) + (a % 2) * b
+    (
+        @mag
+        sgn: $sgn:ident,
+        a: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] $($a:tt)*],
+        b: $b:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_mul! {
+            @halve
+            sgn: $sgn,
+            a: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] $($a)*],
+            b: $b,
+            callback: $callback,
+        }
+    };
+    // |a| <= 16: repeat b a times
+    (
+        @mag
+        sgn: $sgn:ident,
+        a: [$($a:tt)*],
+        b: $b:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_mul! {
+            @catch
+            res: [[$sgn] $(${ignore($a)}$b)*],
+            callback: $callback,
+        }
+    };
+    // a is even
+    (
+        @halve
+        sgn: $sgn:ident,
+        a: [$($half:tt [])*],
+        b: $b:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_mul! {
+            @mag
+            sgn: pos,
+            a: [$($half)*],
+            b: $b,
+            callback: [
+                name: $crate::arith_mul,
+                pre: [
+                    @double
+                    sgn: $sgn,
+                    odd: [],
+                ],
+                pst: [
+                    callback: $callback,
+                ],
+            ],
+        }
+    };
+    // a is odd
+    (
+        @halve
+        sgn: $sgn:ident,
+        a: [[] $($half:tt [])*],
+        b: $b:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_mul! {
+            @mag
+            sgn: pos,
+            a: [$($half)*],
+            b: $b,
+            callback: [
+                name: $crate::arith_mul,
+                pre: [
+                    @double
+                    sgn: $sgn,
+                    odd: $b,
+                ],
+                pst: [
+                    callback: $callback,
+                ],
+            ],
+        }
+    };
+    (
+        @double
+        sgn: $sgn:ident,
+        odd: [$($odd:tt)*],
+        res: [$rsgn:tt [$($res:tt)*]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[$sgn] [$($res)* $($res)* $($odd)*]],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    (
+        @catch
+        res: [[$sgn:tt] $([$($val:tt)*])*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[$sgn] [$($($val)*)*]],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+}
+
+// macro_rules! num_to_lit {
+//     ([[$(pos)?] [$($num:tt)*]]) => {
+//         ${count($num)}
+//     };
+//     ([[neg] [$($num:tt)*]]) => {
+//         -${count($num)}
+//     };
+// }
+// macro_rules! wrapper {
+//     (
+//         a: $a:tt,
+//         b: $b:tt,
+//     ) => {{
+//         $crate::arith_div! {
+//             @div
+//             a: $a,
+//             b: $b,
+//             callback: [
+//                 name: wrapper,
+//                 pre: [],
+//                 pst: [],
+//             ],
+//         }
+//     }};
+//     (
+//         res: $res:tt,
+//     ) => {
+//         num_to_lit!($res)
+//     };
+// }
+// const _: () = {
+//     // 2 / 5 = 0
+//     let tmp = wrapper!(
+//         a: [[pos] [[] []]],
+//         b: [[pos] [[] [] [] [] []]],
+//     );
+//     assert!(tmp == 0);
+//     // (-2) / 5 = 0
+//     let tmp = wrapper!(
+//         a: [[neg] [[] []]],
+//         b: [[pos] [[] [] [] [] []]],
+//     );
+//     assert!(tmp == 0);
+//     // 2 / (-5) = 0
+//     let tmp = wrapper!(
+//         a: [[pos] [[] []]],
+//         b: [[neg] [[] [] [] [] []]],
+//     );
+//     assert!(tmp == 0);
+//     // (-2) / (-5) = 0
+//     let tmp = wrapper!(
+//         a: [[neg] [[] []]],
+//         b: [[neg] [[] [] [] [] []]],
+//     );
+//     assert!(tmp == 0);
+//     // 5 / 2 = 2
+//     let tmp = wrapper!(
+//         a: [[pos] [[] [] [] [] []]],
+//         b: [[pos] [[] []]],
+//     );
+//     assert!(tmp == 2);
+//     // 5 % (-2) = -2
+//     let tmp = wrapper!(
+//         a: [[pos] [[] [] [] [] []]],
+//         b: [[neg] [[] []]],
+//     );
+//     assert!(tmp == -2);
+//     // (-5) % 2 = -2
+//     let tmp = wrapper!(
+//         a: [[neg] [[] [] [] [] []]],
+//         b: [[pos] [[] []]],
+//     );
+//     assert!(tmp == -2);
+//     // (-5) % (-2) = 2
+//     let tmp = wrapper!(
+//         a: [[neg] [[] [] [] [] []]],
+//         b: [[neg] [[] []]],
+//     );
+//     assert!(tmp == 2);
+// };
+
+/// Divides two signed magnitude base 1 numbers (`a / b` ordering).
+///
+/// If `a / 0` is attempted, it will defer to the [`befunge_pm::div_by_zero!`] proc macro, which
+/// will cause the Befunge interface to prompt for a response. This response will then be used in
+/// the expansion of this macro. The prompt goes to `befunge.input`, or to `prefix.input` if
+/// `sockets: "prefix",` is given just before the callback.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///         b: $b:tt,
+///     ) => {{
+///         befunge_dm::arith_div! {
+///             @div
+///             a: $a,
+///             b: $b,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // 2 / 5 = 0
+///     let tmp = wrapper!(
+///         a: [[pos] [[] []]],
+///         b: [[pos] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == 0);
+///     // (-2) / 5 = 0
+///     let tmp = wrapper!(
+///         a: [[neg] [[] []]],
+///         b: [[pos] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == 0);
+///     // 2 / (-5) = 0
+///     let tmp = wrapper!(
+///         a: [[pos] [[] []]],
+///         b: [[neg] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == 0);
+///     // (-2) / (-5) = 0
+///     let tmp = wrapper!(
+///         a: [[neg] [[] []]],
+///         b: [[neg] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == 0);
+///     // 5 / 2 = 2
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///     );
+///     assert!(tmp == 2);
+///     // 5 / (-2) = -2
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///     );
+///     assert!(tmp == -2);
+///     // (-5) / 2 = -2
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///     );
+///     assert!(tmp == -2);
+///     // (-5) / (-2) = 2
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///     );
+///     assert!(tmp == 2);
+///
+///     // Either operand can also be given as an integer literal:
+///     // -9 / 2 = -4
+///     let tmp = {
+///         befunge_dm::arith_div! {
+///             @div
+///             a: -9,
+///             b: [[pos] [[] []]],
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == -4);
+///     // 7 / (-3) = -2
+///     let tmp = {
+///         befunge_dm::arith_div! {
+///             @div
+///             a: [[pos] [[] [] [] [] [] [] []]],
+///             b: -3,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == -2);
+/// };
+/// ```
+///
+/// Given `divmode: floor,` after `b:`, the quotient is rounded down rather than towards zero (see
+/// [the module documentation](crate::arith)). Every combination of signs, with and without a
+/// remainder, in both modes:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///         b: $b:tt,
+///         divmode: $divmode:ident,
+///     ) => {{
+///         befunge_dm::arith_div! {
+///             @div
+///             a: $a,
+///             b: $b,
+///             divmode: $divmode,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // 7 / 2 = 3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 3);
+///     // 7 / 2 = 3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 3);
+///     // (-7) / 2 = -3
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == -3);
+///     // (-7) / 2 = -4
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == -4);
+///     // 7 / (-2) = -3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == -3);
+///     // 7 / (-2) = -4
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == -4);
+///     // (-7) / (-2) = 3
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 3);
+///     // (-7) / (-2) = 3
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 3);
+///     // 6 / 2 = 3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 3);
+///     // 6 / 2 = 3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 3);
+///     // (-6) / 2 = -3
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == -3);
+///     // (-6) / 2 = -3
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == -3);
+///     // 6 / (-2) = -3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == -3);
+///     // 6 / (-2) = -3
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == -3);
+///     // (-6) / (-2) = 3
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 3);
+///     // (-6) / (-2) = 3
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 3);
+/// };
+/// ```
+///
+/// Execution strategy:
+///   1. Handle easy cases (`0 / n`, `n / 1`, `n / (-1)`, `a / b` where `|a| < |b|`).
+///   2. Handle div by zero case with [`befunge_pm::div_by_zero`] proc macro.
+///   3. Call [`crate::arith_div_mod`] macro using signs of numbers as internal rule labels for
+///      callback.
+///   4. Handle callback from [`crate::arith_div_mod`], make callback given to this macro call.
+///
+/// With `divmode: floor,`, only `0 / n` and div by zero are handled first. Everything else goes
+/// straight to step 3 with `@floor` in front of the labels, and step 4 adds one to the magnitude
+/// of the quotient if the signs differ and there's a remainder.
+#[macro_export]
+macro_rules! arith_div {
+    // a given as an integer literal
+    (
+        @div
+        a: $a:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $a,
+            callback: [
+                name: $crate::arith_div,
+                pre: [@literal @a],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @a
+        num: $a:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_div! {
+            @div
+            a: $a,
+            $($rest)*
+        }
+    };
+    // b given as an integer literal
+    (
+        @div
+        a: $a:tt,
+        b: $b:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $b,
+            callback: [
+                name: $crate::arith_div,
+                pre: [@literal @b a: $a,],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @b
+        a: $a:tt,
+        num: $b:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_div! {
+            @div
+            a: $a,
+            b: $b,
+            $($rest)*
+        }
+    };
+    // truncating is what happens without `divmode:` anyway
+    (
+        @div
+        a: $a:tt,
+        b: $b:tt,
+        divmode: trunc,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_div! {
+            @div
+            a: $a,
+            b: $b,
+            $($rest)*
+        }
+    };
+    // a / 0 and 0 / b don't round, so they're the same in both modes
+    (
+        @div
+        a: $a:tt,
+        b: [$bsgn:tt []],
+        divmode: floor,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_div! {
+            @div
+            a: $a,
+            b: [$bsgn []],
+            $($rest)*
+        }
+    };
+    (
+        @div
+        a: [$asgn:tt []],
+        b: $b:tt,
+        divmode: floor,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_div! {
+            @div
+            a: [$asgn []],
+            b: $b,
+            $($rest)*
+        }
+    };
+    // a / b, rounding down
+    (
+        @div
+        a: [[$($asgn:tt)?] $a:tt],
+        b: [[$($bsgn:tt)?] $b:tt],
+        divmode: floor,
+        $(sockets: $sockets:tt,)?
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_div_mod! {
+            @divmod
+            a: $a,
+            b: $b,
+            callback: [
+                name: $crate::arith_div,
+                pre: [@floor @$($asgn)? @$($bsgn)?],
+                pst: [
+                    callback: $callback,
+                ],
+            ],
+        }
+    };
+    // a / 0, asking on `<sockets>.input`
+    (
+        @div
+        a: [$asgn:tt [$ahead:tt $($atail:tt)*]],
+        b: [$bsgn:tt []],
+        sockets: $sockets:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::befunge_pm::div_by_zero! {
+            socket: [$sockets ".input"],
+            callback: $callback,
+        }
+    };
+    // the socket prefix is only needed when dividing by 0
+    (
+        @div
+        a: $a:tt,
+        b: $b:tt,
+        sockets: $sockets:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_div! {
+            @div
+            a: $a,
+            b: $b,
+            callback: $callback,
+        }
+    };
+    // 0 / b = 0
+    (
+        @div
+        a: [$asgn:tt []],
+        b: $b:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] []],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // a / 0
+    (
+        @div
+        a: $a:tt,
+        b: [$bsgn:tt []],
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_div! {
+            @div
+            a: $a,
+            b: [$bsgn []],
+            sockets: "befunge",
+            callback: $callback,
+        }
+    };
+    // a / 1
+    (
+        @div
+        a: $a:tt,
+        b: [[$(pos)?] [[]]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: $a,
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // a / (-1)
+    (
+        @div
+        a: [[$(pos)?] $a:tt],
+        b: [[neg] [[]]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[neg] $a],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // (-a) / (-1)
+    (
+        @div
+        a: [[neg] $a:tt],
+        b: [[neg] [[]]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] $a],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // if |a| < |b|, then a / b = 0
+    (
+        @div
+        a: [[$($asgn:tt)?] $a:tt],
+        b: [[$($bsgn:tt)?] [$($b:tt)+]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        macro_rules! arith_div_lt_check {
+            ([$($b)+ $$($$_:tt)*]) => {
+                $crate::arith_div_mod! {
+                    @divmod
+                    a: $a,
+                    b: [$($b)+],
+                    callback: [
+                        name: $crate::arith_div,
+                        pre: [@$($asgn)? @$($bsgn)?],
+                        pst: [
+                            callback: [
+                                name: $name,
+                                pre: [$($pre)*],
+                                pst: [$($pst)*],
+                            ],
+                        ],
+                    ],
+                }
+            };
+            ($$($$_:tt)*) => {
+                $crate::arith_canonicalize! {
+                    @canonicalize
+                    a: [[pos] []],
+                    callback: [
+                        name: $name,
+                        pre: [$($pre)*],
+                        pst: [$($pst)*],
+                    ],
+                }
+            };
+        }
+        arith_div_lt_check! {
+            $a
+        }
+    };
+    // a / b
+    (
+        @$(pos)? @$(pos)?
+        div: $div:tt,
+        mod: $mod:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] $div],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // -a / b
+    (
+        @neg @$(pos)?
+        div: $div:tt,
+        mod: $mod:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[neg] $div],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // a / (-b)
+    (
+        @$(pos)? @neg
+        div: $div:tt,
+        mod: $mod:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[neg] $div],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // -a / -b
+    (
+        @neg @neg
+        div: $div:tt,
+        mod: $mod:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] $div],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // a / b and -a / -b, rounding down (the same as truncating)
+    (
+        @floor @$(pos)? @$(pos)?
+        div: $div:tt,
+        mod: $mod:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] $div],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    (
+        @floor @neg @neg
+        div: $div:tt,
+        mod: $mod:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] $div],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // -a / b and a / -b with no remainder, which don't need rounding
+    (
+        @floor @$($asgn:ident)? @$($bsgn:ident)?
+        div: $div:tt,
+        mod: [],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[neg] $div],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // -a / b and a / -b with a remainder, rounding down to one further from 0
+    (
+        @floor @$($asgn:ident)? @$($bsgn:ident)?
+        div: [$($div:tt)*],
+        mod: $mod:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[neg] [$($div)* []]],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+}
+
+/// Takes the modulus of two signed magnitude base 1 numbers.
+///
+/// If `a / 0` is attempted, it will defer to the [`befunge_pm::mod_by_zero!`] proc macro, which
+/// will cause the Befunge interface to prompt for a response. This response will then be used in
+/// the expansion of this macro. The prompt goes to `befunge.input`, or to `prefix.input` if
+/// `sockets: "prefix",` is given just before the callback.
+///
+/// Examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///         b: $b:tt,
+///     ) => {{
+///         befunge_dm::arith_mod! {
+///             @mod
+///             a: $a,
+///             b: $b,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // 2 % 5 = 2
+///     let tmp = wrapper!(
+///         a: [[pos] [[] []]],
+///         b: [[pos] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == 2);
+///     // (-2) % 5 = -2
+///     let tmp = wrapper!(
+///         a: [[neg] [[] []]],
+///         b: [[pos] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == -2);
+///     // 2 % (-5) = 2
+///     let tmp = wrapper!(
+///         a: [[pos] [[] []]],
+///         b: [[neg] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == 2);
+///     // (-2) % (-5) = -2
+///     let tmp = wrapper!(
+///         a: [[neg] [[] []]],
+///         b: [[neg] [[] [] [] [] []]],
+///     );
+///     assert!(tmp == -2);
+///     // 5 % 2 = 1
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///     );
+///     assert!(tmp == 1);
+///     // 5 % (-2) = 1
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///     );
+///     assert!(tmp == 1);
+///     // (-5) % 2 = -1
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///     );
+///     assert!(tmp == -1);
+///     // (-5) % (-2) = -1
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///     );
+///     assert!(tmp == -1);
+///
+///     // Either operand can also be given as an integer literal:
+///     // -9 % 2 = -1
+///     let tmp = {
+///         befunge_dm::arith_mod! {
+///             @mod
+///             a: -9,
+///             b: [[pos] [[] []]],
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == -1);
+///     // 7 % (-3) = 1
+///     let tmp = {
+///         befunge_dm::arith_mod! {
+///             @mod
+///             a: [[pos] [[] [] [] [] [] [] []]],
+///             b: -3,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     assert!(tmp == 1);
+/// };
+/// ```
+///
+/// Given `divmode: floor,` after `b:`, the remainder has the sign of `b` rather than `a` (see
+/// [the module documentation](crate::arith)). Every combination of signs, with and without a
+/// remainder, in both modes:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! num_to_lit {
+///     ([[$(pos)?] [$($num:tt)*]]) => {
+///         ${count($num)}
+///     };
+///     ([[neg] [$($num:tt)*]]) => {
+///         -${count($num)}
+///     };
+/// }
+///
+/// macro_rules! wrapper {
+///     (
+///         a: $a:tt,
+///         b: $b:tt,
+///         divmode: $divmode:ident,
+///     ) => {{
+///         befunge_dm::arith_mod! {
+///             @mod
+///             a: $a,
+///             b: $b,
+///             divmode: $divmode,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         res: $res:tt,
+///     ) => {
+///         num_to_lit!($res)
+///     };
+/// }
+///
+/// const _: () = {
+///     // 7 % 2 = 1
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 1);
+///     // 7 % 2 = 1
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 1);
+///     // (-7) % 2 = -1
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == -1);
+///     // (-7) % 2 = 1
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 1);
+///     // 7 % (-2) = 1
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 1);
+///     // 7 % (-2) = -1
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == -1);
+///     // (-7) % (-2) = -1
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == -1);
+///     // (-7) % (-2) = -1
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == -1);
+///     // 6 % 2 = 0
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 0);
+///     // 6 % 2 = 0
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 0);
+///     // (-6) % 2 = 0
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 0);
+///     // (-6) % 2 = 0
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] []]],
+///         b: [[pos] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 0);
+///     // 6 % (-2) = 0
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 0);
+///     // 6 % (-2) = 0
+///     let tmp = wrapper!(
+///         a: [[pos] [[] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 0);
+///     // (-6) % (-2) = 0
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: trunc,
+///     );
+///     assert!(tmp == 0);
+///     // (-6) % (-2) = 0
+///     let tmp = wrapper!(
+///         a: [[neg] [[] [] [] [] [] []]],
+///         b: [[neg] [[] []]],
+///         divmode: floor,
+///     );
+///     assert!(tmp == 0);
+/// };
+/// ```
+///
+/// Execution strategy:
+///   1. Check for `n % 0`. Handle this with [`befunge_pm::mod_by_zero!`].
+///   2. Check if `a > b` in `a % b`. If yes, return `a`. Otherwise, call [`crate::arith_div_mod`].
+///   3. Handle callback from [`crate::arith_div_mod`] and make callback given to this macro call.
+///
+/// With `divmode: floor,`, everything but `n % 0` goes straight to [`crate::arith_div_mod`], with
+/// `b` passed along to the callback. If the signs differ and there's a remainder, the result is
+/// `|b|` minus that remainder, with the sign of `b`.
+#[macro_export]
+macro_rules! arith_mod {
+    // a given as an integer literal
+    (
+        @mod
+        a: $a:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $a,
+            callback: [
+                name: $crate::arith_mod,
+                pre: [@literal @a],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @a
+        num: $a:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_mod! {
+            @mod
+            a: $a,
+            $($rest)*
+        }
+    };
+    // b given as an integer literal
+    (
+        @mod
+        a: $a:tt,
+        b: $b:literal,
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $b,
+            callback: [
+                name: $crate::arith_mod,
+                pre: [@literal @b a: $a,],
+                pst: [$($rest)*],
+            ],
+        }
+    };
+    (
+        @literal @b
+        a: $a:tt,
+        num: $b:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_mod! {
+            @mod
+            a: $a,
+            b: $b,
+            $($rest)*
+        }
+    };
+    // truncating is what happens without `divmode:` anyway
+    (
+        @mod
+        a: $a:tt,
+        b: $b:tt,
+        divmode: trunc,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_mod! {
+            @mod
+            a: $a,
+            b: $b,
+            $($rest)*
+        }
+    };
+    // a % 0 is the same in both modes
+    (
+        @mod
+        a: $a:tt,
+        b: [$bsgn:tt []],
+        divmode: floor,
+        $($rest:tt)*
+    ) => {
+        $crate::arith_mod! {
+            @mod
+            a: $a,
+            b: [$bsgn []],
+            $($rest)*
+        }
+    };
+    // a % b, with the sign of b
+    (
+        @mod
+        a: [[$($asgn:tt)?] $a:tt],
+        b: [[$($bsgn:tt)?] $b:tt],
+        divmode: floor,
+        $(sockets: $sockets:tt,)?
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_div_mod! {
+            @divmod
+            a: $a,
+            b: $b,
+            callback: [
+                name: $crate::arith_mod,
+                pre: [@floor @$($asgn)? @$($bsgn)?],
+                pst: [
+                    b: $b,
+                    callback: $callback,
+                ],
+            ],
+        }
+    };
+    // a % 0, asking on `<sockets>.input`
+    (
+        @mod
+        a: $a:tt,
+        b: [$bsgn:tt []],
+        sockets: $sockets:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::befunge_pm::mod_by_zero! {
+            socket: [$sockets ".input"],
+            callback: $callback,
+        }
+    };
+    // the socket prefix is only needed when dividing by 0
+    (
+        @mod
+        a: $a:tt,
+        b: $b:tt,
+        sockets: $sockets:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_mod! {
+            @mod
+            a: $a,
+            b: $b,
+            callback: $callback,
+        }
+    };
+    // a % 0
+    (
+        @mod
+        a: $a:tt,
+        b: [$bsgn:tt []],
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_mod! {
+            @mod
+            a: $a,
+            b: [$bsgn []],
+            sockets: "befunge",
+            callback: $callback,
+        }
+    };
+    // if |a| < |b|, then a % b = a
+    (
+        @mod
+        a: [$asgn:tt $a:tt],
+        b: [$bsgn:tt [$($b:tt)*]],
+        callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
             pst: [$($pst:tt)*],
         ],
     ) => {
-        macro_rules! arith_div_lt_check {
-            ([$($b)+ $$($$_:tt)*]) => {
+        macro_rules! arith_mod_lt_check {
+            ([$($b)* $$($$_:tt)*]) => {
                 $crate::arith_div_mod! {
                     @divmod
                     a: $a,
-                    b: [$($b)+],
+                    b: [$($b)*],
                     callback: [
-                        name: $crate::arith_div,
-                        pre: [@$($asgn)? @$($bsgn)?],
+                        name: $crate::arith_mod,
+                        pre: [
+                            @catch
+                            asgn: $asgn,
+                        ],
                         pst: [
                             callback: [
                                 name: $name,
@@ -958,21 +3949,72 @@ macro_rules! arith_div {
                     ],
                 }
             };
-            ($$($$_:tt)*) => {
-                $name! {
-                    $($pre)*
-                    res: [[pos] []],
-                    $($pst)*
+            (
+                @acc
+                rules: [$$($$rules:tt)*],
+                num: [[] $$($$rest:tt)+],
+            ) => {
+                arith_mod_lt_check! {
+                    @acc
+                    rules: [
+                        $$($$rules)*
+                        ([$$$$($($b)*)+ $$($$rest)+]) => {
+                            $crate::arith_canonicalize! {
+                                @canonicalize
+                                a: [$asgn [$$($$rest)+]],
+                                callback: [
+                                    name: $name,
+                                    pre: [$($pre)*],
+                                    pst: [$($pst)*],
+                                ],
+                            }
+                        };
+                    ],
+                    num: [$$($$rest)+],
+                }
+            };
+            (
+                @acc
+                rules: [$$($$rules:tt)*],
+                num: [[]],
+            ) => {
+                macro_rules! arith_mod_exec {
+                    $$($$rules)*
+                    ([$$$$($($b)*)+]) => {
+                        $crate::arith_canonicalize! {
+                            @canonicalize
+                            a: [$asgn []],
+                            callback: [
+                                name: $name,
+                                pre: [$($pre)*],
+                                pst: [$($pst)*],
+                            ],
+                        }
+                    };
+                }
+                arith_mod_exec! {
+                    $a
+                }
+            };
+            ([$$($$_:tt)*]) => {
+                $crate::arith_canonicalize! {
+                    @canonicalize
+                    a: [$asgn $a],
+                    callback: [
+                        name: $name,
+                        pre: [$($pre)*],
+                        pst: [$($pst)*],
+                    ],
                 }
             };
         }
-        arith_div_lt_check! {
+        arith_mod_lt_check! {
             $a
         }
     };
-    // a / b
     (
-        @$(pos)? @$(pos)?
+        @catch
+        asgn: $asgn:tt,
         div: $div:tt,
         mod: $mod:tt,
         callback: [
@@ -981,51 +4023,400 @@ macro_rules! arith_div {
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [[pos] $div],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [$asgn $mod],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
         }
     };
-    // -a / b
+    // no remainder, whatever the signs are
     (
-        @neg @$(pos)?
+        @floor @$($asgn:ident)? @$($bsgn:ident)?
+        div: $div:tt,
+        mod: [],
+        b: $b:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] []],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // a % b and -a % -b, which already have the sign of b
+    (
+        @floor @$(pos)? @$(pos)?
         div: $div:tt,
         mod: $mod:tt,
+        b: $b:tt,
         callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [[neg] $div],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[pos] $mod],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
         }
     };
-    // a / (-b)
     (
-        @$(pos)? @neg
+        @floor @neg @neg
         div: $div:tt,
         mod: $mod:tt,
+        b: $b:tt,
         callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
             pst: [$($pst:tt)*],
         ],
     ) => {
-        $name! {
-            $($pre)*
-            res: [[neg] $div],
-            $($pst)*
+        $crate::arith_canonicalize! {
+            @canonicalize
+            a: [[neg] $mod],
+            callback: [
+                name: $name,
+                pre: [$($pre)*],
+                pst: [$($pst)*],
+            ],
+        }
+    };
+    // -a % b and a % -b, where the remainder is |b| - (|a| % |b|) with the sign of b
+    (
+        @floor @neg @$(pos)?
+        div: $div:tt,
+        mod: [$($mod:tt)*],
+        b: $b:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        macro_rules! arith_mod_floor {
+            ([$($mod)* $$($$rest:tt)*]) => {
+                $crate::arith_canonicalize! {
+                    @canonicalize
+                    a: [[pos] [$$($$rest)*]],
+                    callback: [
+                        name: $name,
+                        pre: [$($pre)*],
+                        pst: [$($pst)*],
+                    ],
+                }
+            };
+        }
+        arith_mod_floor! {
+            $b
+        }
+    };
+    (
+        @floor @$(pos)? @neg
+        div: $div:tt,
+        mod: [$($mod:tt)*],
+        b: $b:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        macro_rules! arith_mod_floor {
+            ([$($mod)* $$($$rest:tt)*]) => {
+                $crate::arith_canonicalize! {
+                    @canonicalize
+                    a: [[neg] [$$($$rest)*]],
+                    callback: [
+                        name: $name,
+                        pre: [$($pre)*],
+                        pst: [$($pst)*],
+                    ],
+                }
+            };
+        }
+        arith_mod_floor! {
+            $b
+        }
+    };
+}
+
+/// Performs the division and modulus operations on two unsigned base 1 numbers simultaneously.
+///
+/// The caller must handle the case for division by 0, and must also handle returning the signs to
+/// the integers with the callback result. Callback is performed as:
+/// ```ignore
+/// name! {
+///     pre
+///     div: [/* result */],
+///     mod: [/* result */],
+///     pst
+/// }
+/// ```
+///
+/// Examples, with `a` built up with [`arith_mul`] first so it doesn't have to be written out:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! wrapper {
+///     (
+///         a: [$x:tt $y:tt $z:tt],
+///         b: $b:tt,
+///     ) => {{
+///         befunge_dm::arith_mul! {
+///             @mul
+///             a: $x,
+///             b: $y,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [@mul z: $z, b: $b,],
+///                 pst: [],
+///             ],
+///         }
+///     }};
+///     (
+///         @mul
+///         z: $z:tt,
+///         b: $b:tt,
+///         res: $xy:tt,
+///     ) => {
+///         befunge_dm::arith_mul! {
+///             @mul
+///             a: $xy,
+///             b: $z,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [@divmod b: $b,],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     (
+///         @divmod
+///         b: $b:tt,
+///         res: [$sgn:tt $a:tt],
+///     ) => {
+///         befunge_dm::arith_div_mod! {
+///             @divmod
+///             a: $a,
+///             b: $b,
+///             callback: [
+///                 name: wrapper,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     (
+///         div: [$($div:tt)*],
+///         mod: [$($mod:tt)*],
+///     ) => {
+///         (${count($div)}, ${count($mod)})
+///     };
+/// }
+///
+/// // None of these need a `#![recursion_limit]`.
+/// const _: () = {
+///     // 3000 / 7 = 428, 3000 % 7 = 4
+///     let tmp = wrapper!(
+///         a: [
+///             [[pos] [[] [] [] [] [] [] [] [] [] []]]
+///             [[pos] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] []]]
+///             [[pos] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]]
+///         ],
+///         b: [[] [] [] [] [] [] []],
+///     );
+///     assert!(tmp.0 == 428 && tmp.1 == 4);
+///     // 2000 / 3 = 666, 2000 % 3 = 2
+///     let tmp = wrapper!(
+///         a: [
+///             [[pos] [[] [] [] [] [] [] [] [] [] []]]
+///             [[pos] [[] [] [] [] [] [] [] [] [] []]]
+///             [[pos] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]]
+///         ],
+///         b: [[] [] []],
+///     );
+///     assert!(tmp.0 == 666 && tmp.1 == 2);
+///     // 4096 / 64 = 64, 4096 % 64 = 0
+///     let tmp = wrapper!(
+///         a: [
+///             [[pos] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]]
+///             [[pos] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]]
+///             [[pos] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]]
+///         ],
+///         b: [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///         ],
+///     );
+///     assert!(tmp.0 == 64 && tmp.1 == 0);
+///     // 1000 / 1 = 1000, 1000 % 1 = 0
+///     let tmp = wrapper!(
+///         a: [
+///             [[pos] [[] [] [] [] [] [] [] [] [] []]]
+///             [[pos] [[] [] [] [] [] [] [] [] [] []]]
+///             [[pos] [[] [] [] [] [] [] [] [] [] []]]
+///         ],
+///         b: [[]],
+///     );
+///     assert!(tmp.0 == 1000 && tmp.1 == 0);
+///     // 5 / 9 = 0, 5 % 9 = 5
+///     let tmp = wrapper!(
+///         a: [
+///             [[pos] [[]]]
+///             [[pos] [[]]]
+///             [[pos] [[] [] [] [] []]]
+///         ],
+///         b: [[] [] [] [] [] [] [] [] []],
+///     );
+///     assert!(tmp.0 == 0 && tmp.1 == 5);
+/// };
+/// ```
+///
+/// Execution strategy:
+///   1. Double `b` (along with a count of how many `b`s that is, starting at 1) for as long as the
+///      doubled copy still fits in `a`, keeping every copy. Whether it fits is checked with an
+///      ad-hoc macro named `arith_div_mod_exec` that matches `a` as the copy plus any number of
+///      additional token trees.
+///   2. Going back down from the biggest copy, subtract each one that fits in what's left of `a`
+///      (checked the same way), and add its count to the quotient.
+///   3. Once every copy has been tried, what's left of `a` is the remainder. Make the callback with
+///      the results.
+///
+/// Each copy is subtracted at most once, so this takes `O(log(a / b))` rounds rather than one for
+/// every `b` that fits in `a`.
+#[macro_export]
+macro_rules! arith_div_mod {
+    (
+        @divmod
+        a: $a:tt,
+        b: [],
+        callback: [
+            name: $name:path,
+            pre: $pre:tt,
+            pst: $pst:tt,
+        ],
+    ) => {
+        compile_error! {
+            concat!(
+                "This macro was called with `b = 0`!\n",
+                "Callback {\n",
+                "    name: ",
+                stringify!($name),
+                ",\n",
+                "    pre: ",
+                stringify!($pre),
+                ",\n",
+                "    pst: ",
+                stringify!($pst),
+                ",\n",
+                "}",
+            )
+        }
+    };
+    (
+        @divmod
+        a: $a:tt,
+        b: $b:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_div_mod! {
+            @doubling
+            a: $a,
+            copies: [[$b [[]]]],
+            callback: $callback,
+        }
+    };
+    // 1. double the biggest copy of b if that still fits in a
+    (
+        @doubling
+        a: [$($a:tt)*],
+        copies: [[[$($b:tt)*] [$($n:tt)*]] $($copies:tt)*],
+        callback: $callback:tt,
+    ) => {
+        macro_rules! arith_div_mod_exec {
+            ([$($b)* $($b)* $$($$_rest:tt)*]) => {
+                $crate::arith_div_mod! {
+                    @doubling
+                    a: [$($a)*],
+                    copies: [
+                        [[$($b)* $($b)*] [$($n)* $($n)*]]
+                        [[$($b)*] [$($n)*]]
+                        $($copies)*
+                    ],
+                    callback: $callback,
+                }
+            };
+            ($$_a:tt) => {
+                $crate::arith_div_mod! {
+                    @subtracting
+                    left: [$($a)*],
+                    div: [],
+                    copies: [[[$($b)*] [$($n)*]] $($copies)*],
+                    callback: $callback,
+                }
+            };
+        }
+        arith_div_mod_exec! {
+            [$($a)*]
+        }
+    };
+    // 2. subtract the biggest copy of b left if it fits in what's left of a
+    (
+        @subtracting
+        left: [$($left:tt)*],
+        div: [$($div:tt)*],
+        copies: [[[$($b:tt)*] [$($n:tt)*]] $($copies:tt)*],
+        callback: $callback:tt,
+    ) => {
+        macro_rules! arith_div_mod_exec {
+            ([$($b)* $$($$rest:tt)*]) => {
+                $crate::arith_div_mod! {
+                    @subtracting
+                    left: [$$($$rest)*],
+                    div: [$($div)* $($n)*],
+                    copies: [$($copies)*],
+                    callback: $callback,
+                }
+            };
+            ($$_left:tt) => {
+                $crate::arith_div_mod! {
+                    @subtracting
+                    left: [$($left)*],
+                    div: [$($div)*],
+                    copies: [$($copies)*],
+                    callback: $callback,
+                }
+            };
+        }
+        arith_div_mod_exec! {
+            [$($left)*]
         }
     };
-    // -a / -b
+    // 3. make the callback with the results
     (
-        @neg @neg
+        @subtracting
+        left: [$($left:tt)*],
         div: $div:tt,
-        mod: $mod:tt,
+        copies: [],
         callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
@@ -1034,40 +4425,51 @@ macro_rules! arith_div {
     ) => {
         $name! {
             $($pre)*
-            res: [[pos] $div],
+            div: $div,
+            mod: [$(${ignore($left)} [])*],
             $($pst)*
         }
-    }
+    };
 }
 
-/// Takes the modulus of two signed magnitude base 1 numbers.
-///
-/// If `a / 0` is attempted, it will defer to the [`befunge_pm::mod_by_zero!`] proc macro, which
-/// will cause the Befunge interface to prompt for a response. This response will then be used in
-/// the expansion of this macro.
+/// Turns a signed magnitude base 1 number into the ASCII codes of its decimal digits, most
+/// significant first, with the code for `'-'` in front of negative numbers. Each code is itself a
+/// signed magnitude base 1 number, ready to be handed to anything that prints ASCII. Callback is
+/// performed as:
+/// ```ignore
+/// name! {
+///     pre
+///     digits: [/* codes */],
+///     pst
+/// }
+/// ```
 ///
 /// Examples:
 /// ```
 /// #![feature(macro_metavar_expr)]
 ///
-/// macro_rules! num_to_lit {
-///     ([[$(pos)?] [$($num:tt)*]]) => {
-///         ${count($num)}
-///     };
-///     ([[neg] [$($num:tt)*]]) => {
-///         -${count($num)}
-///     };
-/// }
-///
+/// # const fn const_bytes_eq(a: &[u8], b: &[u8]) -> bool {
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
 /// macro_rules! wrapper {
 ///     (
-///         a: $a:tt,
-///         b: $b:tt,
+///         num: $num:tt,
 ///     ) => {{
-///         befunge_dm::arith_mod! {
-///             @mod
-///             a: $a,
-///             b: $b,
+///         befunge_dm::arith_to_ascii_digits! {
+///             @digits
+///             num: $num,
 ///             callback: [
 ///                 name: wrapper,
 ///                 pre: [],
@@ -1076,171 +4478,153 @@ macro_rules! arith_div {
 ///         }
 ///     }};
 ///     (
-///         res: $res:tt,
+///         digits: [$([[pos] [$($code:tt)*]])*],
 ///     ) => {
-///         num_to_lit!($res)
+///         [$(${count($code)} as u8),*]
 ///     };
 /// }
 ///
 /// const _: () = {
-///     // 2 % 5 = 2
-///     let tmp = wrapper!(
-///         a: [[pos] [[] []]],
-///         b: [[pos] [[] [] [] [] []]],
-///     );
-///     assert!(tmp == 2);
-///     // (-2) % 5 = -2
-///     let tmp = wrapper!(
-///         a: [[neg] [[] []]],
-///         b: [[pos] [[] [] [] [] []]],
-///     );
-///     assert!(tmp == -2);
-///     // 2 % (-5) = 2
-///     let tmp = wrapper!(
-///         a: [[pos] [[] []]],
-///         b: [[neg] [[] [] [] [] []]],
-///     );
-///     assert!(tmp == 2);
-///     // (-2) % (-5) = -2
-///     let tmp = wrapper!(
-///         a: [[neg] [[] []]],
-///         b: [[neg] [[] [] [] [] []]],
-///     );
-///     assert!(tmp == -2);
-///     // 5 % 2 = 1
-///     let tmp = wrapper!(
-///         a: [[pos] [[] [] [] [] []]],
-///         b: [[pos] [[] []]],
-///     );
-///     assert!(tmp == 1);
-///     // 5 % (-2) = 1
-///     let tmp = wrapper!(
-///         a: [[pos] [[] [] [] [] []]],
-///         b: [[neg] [[] []]],
-///     );
-///     assert!(tmp == 1);
-///     // (-5) % 2 = -1
-///     let tmp = wrapper!(
-///         a: [[neg] [[] [] [] [] []]],
-///         b: [[pos] [[] []]],
+///     // 0
+///     let tmp: &[u8] = &wrapper!(num: [[pos] []],);
+///     assert!(const_bytes_eq(tmp, b"0"));
+///     // -0 is still 0
+///     let tmp: &[u8] = &wrapper!(num: [[neg] []],);
+///     assert!(const_bytes_eq(tmp, b"0"));
+///     // 7
+///     let tmp: &[u8] = &wrapper!(num: [[pos] [[] [] [] [] [] [] []]],);
+///     assert!(const_bytes_eq(tmp, b"7"));
+///     // 10
+///     let tmp: &[u8] = &wrapper!(num: [[] [[] [] [] [] [] [] [] [] [] []]],);
+///     assert!(const_bytes_eq(tmp, b"10"));
+///     // 123
+///     let tmp: &[u8] = &wrapper!(
+///         num: [[pos] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] []
+///         ]],
 ///     );
-///     assert!(tmp == -1);
-///     // (-5) % (-2) = -1
-///     let tmp = wrapper!(
-///         a: [[neg] [[] [] [] [] []]],
-///         b: [[neg] [[] []]],
+///     assert!(const_bytes_eq(tmp, b"123"));
+///     // -3
+///     let tmp: &[u8] = &wrapper!(num: [[neg] [[] [] []]],);
+///     assert!(const_bytes_eq(tmp, b"-3"));
+///     // -20
+///     let tmp: &[u8] = &wrapper!(
+///         num: [[neg] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
 ///     );
-///     assert!(tmp == -1);
+///     assert!(const_bytes_eq(tmp, b"-20"));
 /// };
 /// ```
 ///
 /// Execution strategy:
-///   1. Check for `n % 0`. Handle this with [`befunge_pm::mod_by_zero!`].
-///   2. Check if `a > b` in `a % b`. If yes, return `a`. Otherwise, call [`crate::arith_div_mod`].
-///   3. Handle callback from [`crate::arith_div_mod`] and make callback given to this macro call.
+///   1. Divide the magnitude by ten with [`crate::arith_div_mod`].
+///   2. Add the code for `'0'` to the remainder and put it in front of the digits found so far.
+///   3. Repeat with the quotient until it is 0, then put a `'-'` in front if the number was negative.
 #[macro_export]
-macro_rules! arith_mod {
-    // a % 0
+macro_rules! arith_to_ascii_digits {
     (
-        @mod
-        a: $a:tt,
-        b: [$bsgn:tt []],
+        @digits
+        num: [[$($sgn:tt)?] [$($mag:tt)*]],
         callback: $callback:tt,
     ) => {
-        $crate::befunge_pm::mod_by_zero! {
-            socket: "befunge.input",
+        $crate::arith_to_ascii_digits! {
+            @loop
+            sgn: [$($sgn)?],
+            mag: [$($mag)*],
+            digits: [],
             callback: $callback,
         }
     };
-    // if |a| < |b|, then a % b = a
+    // 0 has no digits to divide out, and is never negative
     (
-        @mod
-        a: [$asgn:tt $a:tt],
-        b: [$bsgn:tt [$($b:tt)*]],
-        callback: [
-            name: $name:path,
-            pre: [$($pre:tt)*],
-            pst: [$($pst:tt)*],
-        ],
+        @loop
+        sgn: $sgn:tt,
+        mag: [],
+        digits: [],
+        callback: $callback:tt,
     ) => {
-        macro_rules! arith_mod_lt_check {
-            ([$($b)* $$($$_:tt)*]) => {
-                $crate::arith_div_mod! {
-                    @divmod
-                    a: $a,
-                    b: [$($b)*],
-                    callback: [
-                        name: $crate::arith_mod,
-                        pre: [
-                            @catch
-                            asgn: $asgn,
-                        ],
-                        pst: [
-                            callback: [
-                                name: $name,
-                                pre: [$($pre)*],
-                                pst: [$($pst)*],
-                            ],
-                        ],
-                    ],
-                }
-            };
-            (
-                @acc
-                rules: [$$($$rules:tt)*],
-                num: [[] $$($$rest:tt)+],
-            ) => {
-                arith_mod_lt_check! {
-                    @acc
-                    rules: [
-                        $$($$rules)*
-                        ([$$$$($($b)*)+ $$($$rest)+]) => {
-                            $name! {
-                                $($pre)*
-                                res: [$asgn [$$($$rest)+]],
-                                $($pst)*
-                            }
-                        };
-                    ],
-                    num: [$$($$rest)+],
-                }
-            };
-            (
-                @acc
-                rules: [$$($$rules:tt)*],
-                num: [[]],
-            ) => {
-                macro_rules! arith_mod_exec {
-                    $$($$rules)*
-                    ([$$$$($($b)*)+]) => {
-                        $name! {
-                            $($pre)*
-                            res: [$asgn []],
-                            $($pst)*
-                        }
-                    };
-                }
-                arith_mod_exec! {
-                    $a
-                }
-            };
-            ([$$($$_:tt)*]) => {
-                $name! {
-                    $($pre)*
-                    res: [$asgn $a],
-                    $($pst)*
-                }
-            };
+        $crate::arith_to_ascii_digits! {
+            @sign
+            sgn: [pos],
+            digits: [
+                [[pos] [
+                    [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+                    [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+                ]]
+            ],
+            callback: $callback,
         }
-        arith_mod_lt_check! {
-            $a
+    };
+    (
+        @loop
+        sgn: $sgn:tt,
+        mag: [],
+        digits: $digits:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_to_ascii_digits! {
+            @sign
+            sgn: $sgn,
+            digits: $digits,
+            callback: $callback,
+        }
+    };
+    (
+        @loop
+        sgn: $sgn:tt,
+        mag: $mag:tt,
+        digits: $digits:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_div_mod! {
+            @divmod
+            a: $mag,
+            b: [[] [] [] [] [] [] [] [] [] []],
+            callback: [
+                name: $crate::arith_to_ascii_digits,
+                pre: [
+                    @catch
+                    sgn: $sgn,
+                    digits: $digits,
+                ],
+                pst: [
+                    callback: $callback,
+                ],
+            ],
         }
     };
+    // '0' + remainder
     (
         @catch
-        asgn: $asgn:tt,
+        sgn: $sgn:tt,
+        digits: [$($digits:tt)*],
         div: $div:tt,
-        mod: $mod:tt,
+        mod: [$($mod:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::arith_to_ascii_digits! {
+            @loop
+            sgn: $sgn,
+            mag: $div,
+            digits: [
+                [[pos] [
+                    [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+                    [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+                    $($mod)*
+                ]]
+                $($digits)*
+            ],
+            callback: $callback,
+        }
+    };
+    // '-'
+    (
+        @sign
+        sgn: [neg],
+        digits: [$($digits:tt)*],
         callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
@@ -1249,98 +4633,30 @@ macro_rules! arith_mod {
     ) => {
         $name! {
             $($pre)*
-            res: [$asgn $mod],
+            digits: [
+                [[pos] [
+                    [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+                    [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+                ]]
+                $($digits)*
+            ],
             $($pst)*
         }
     };
-}
-
-/// Performs the division and modulus operations on two unsigned base 1 numbers simultaneously.
-///
-/// The caller must handle the case for division by 0, and must also handle returning the signs to
-/// the integers with the callback result. Callback is performed as:
-/// ```ignore
-/// name! {
-///     pre
-///     div: [/* result */],
-///     mod: [/* result */],
-///     pst
-/// }
-/// ```
-///
-/// Execution strategy:
-///   1. Define an ad-hoc macro named `arith_div_mod_exec` that repeatedly subtracts `b` from `a`
-///      while keeping track of how many times it has done so. It will then make a callback with the
-///      results.
-///   2. Call this macro with `a` and `b` as inputs.
-#[macro_export]
-macro_rules! arith_div_mod {
     (
-        @divmod
-        a: $a:tt,
-        b: [$($b:tt)*],
+        @sign
+        sgn: $sgn:tt,
+        digits: $digits:tt,
         callback: [
             name: $name:path,
             pre: [$($pre:tt)*],
             pst: [$($pst:tt)*],
         ],
     ) => {
-        macro_rules! arith_div_mod_exec {
-            (
-                @divmod
-                left: [$($b)* $$($$rest:tt)*],
-                div: [$$($$div:tt)*],
-            ) => {
-                arith_div_mod_exec! {
-                    @divmod
-                    left: [$$($$rest)*],
-                    div: [$$($$div)* []],
-                }
-            };
-            (
-                @divmod
-                left: [$$($$rest:tt)*],
-                div: $$div:tt,
-            ) => {
-                $name! {
-                    $($pre)*
-                    div: $$div,
-                    mod: [$$($${ignore($$rest)} [])*],
-                    $($pst)*
-                }
-            };
-        }
-        arith_div_mod_exec! {
-            @divmod
-            left: $a,
-            div: [],
-        }
-    };
-    (
-        @divmod
-        a: $a:tt,
-        b: [],
-        callback: [
-            name: $name:path,
-            pre: $pre:tt,
-            pst: $pst:tt,
-        ],
-    ) => {
-        compile_error! {
-            concat!(
-                "This macro was called with `b = 0`!\n",
-                "Callback {\n",
-                "    name: ",
-                stringify!($name),
-                ",\n",
-                "    pre: ",
-                stringify!($pre),
-                ",\n",
-                "    pst: ",
-                stringify!($pst),
-                ",\n",
-                "}",
-            )
+        $name! {
+            $($pre)*
+            digits: $digits,
+            $($pst)*
         }
     };
 }
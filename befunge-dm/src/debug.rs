@@ -51,15 +51,101 @@ macro_rules! dbg_const {
 /// assert!(baz);
 /// ```
 ///
+/// `lookfor:` can list several flags, in which case it's enough for any one of them to be found:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// let second = {
+///     befunge_dm::dbg_maybe_expand! {
+///         @dbg
+///         debug: [[noflush] [tracemove]],
+///         lookfor: [[traceinstr] [tracemove] [bridgedbg]],
+///         expand: [true],
+///         orelse: [false],
+///     }
+/// };
+///
+/// assert!(second);
+///
+/// let neither = {
+///     befunge_dm::dbg_maybe_expand! {
+///         @dbg
+///         debug: [[noflush] [postprog]],
+///         lookfor: [[traceinstr] [tracemove]],
+///         expand: [false],
+///         orelse: [true],
+///     }
+/// };
+///
+/// assert!(neither);
+/// ```
+///
 /// Execution strategy:
 ///     1. Expand to an ad-hoc equality checking macro ([`crate::def_eq`]) that checks if the
 ///        head of the debug token tree list is equal to the token tree we are looking for. If yes,
 ///        then expand to the contents of the desired token tree. Otherwise, recurse with the rest
-///        of the debug token trees.
+///        of the debug token trees. With more than one token tree to look for, each of them is
+///        checked against the head in turn before moving on to the rest.
 ///     2. If the debug token tree list is empty, expand to the contents of the "otherwise" token
 ///        if present.
 #[macro_export]
 macro_rules! dbg_maybe_expand {
+    (
+        @dbg
+        debug: [$debugh:tt $($debugt:tt)*],
+        lookfor: [$lookfor:tt $($lookfort:tt)+],
+        expand: $expand:tt,
+        $(orelse: $orelse:tt$(,)?)?
+    ) => {
+        $crate::dbg_maybe_expand! {
+            @dbg @alts
+            debug: [$debugh $($debugt)*],
+            alts: [$lookfor $($lookfort)+],
+            lookfor: [$lookfor $($lookfort)+],
+            expand: $expand,
+            $(orelse: $orelse,)?
+        }
+    };
+    (
+        @dbg @alts
+        debug: [$debugh:tt $($debugt:tt)*],
+        alts: [$alth:tt $($altt:tt)*],
+        lookfor: $lookfor:tt,
+        expand: $expand:tt,
+        $(orelse: $orelse:tt,)?
+    ) => {
+        $crate::def_eq! {
+            lookfor: [$alth],
+            input: [$debugh],
+            true: $expand,
+            false: [
+                $crate::dbg_maybe_expand! {
+                    @dbg @alts
+                    debug: [$debugh $($debugt)*],
+                    alts: [$($altt)*],
+                    lookfor: $lookfor,
+                    expand: $expand,
+                    $(orelse: $orelse,)?
+                }
+            ],
+        }
+    };
+    (
+        @dbg @alts
+        debug: [$debugh:tt $($debugt:tt)*],
+        alts: [],
+        lookfor: $lookfor:tt,
+        expand: $expand:tt,
+        $(orelse: $orelse:tt,)?
+    ) => {
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: [$($debugt)*],
+            lookfor: $lookfor,
+            expand: $expand,
+            $(orelse: $orelse,)?
+        }
+    };
     (
         @dbg
         debug: [$debugh:tt $($debugt:tt)*],
@@ -93,159 +179,1954 @@ macro_rules! dbg_maybe_expand {
     };
 }
 
-/// Prints the stack of a Befunge program for debugging purposes.
+/// Replaces each group of debugging flags in `debug:` with the flags in it, and makes a callback
+/// with `debug:` set to the result. The groups are:
+///
+/// - `[all]`: Every flag that only outputs something, which is `[initlines]`, `[postinit]`,
+///   `[getdbg]`, `[putdbg]`, `[poststack]`, `[postprog]`, `[underflow]`, `[traceinstr]`,
+///   `[tracemove]`, `[bridgedbg]`, `[profile]`, `[heatmap]`, and `[stepcount]`. Flags that change
+///   what the program does, like `[noflush]`, `[closeonend]`, `[collecterrors]`, and `[b98]`, and
+///   ones that need something from the user, like `[tag "name"]`, still have to be given on their
+///   own.
+/// - `[io]`: The program's reads and writes of its own memory, `[getdbg]` and `[putdbg]`.
+/// - `[trace]`: Where the PC goes and what it does there, `[traceinstr]`, `[tracemove]`,
+///   `[bridgedbg]`, and `[underflow]`.
+///
+/// Anything else is left as it is.
+///
+/// # Example
+/// ```
+/// macro_rules! flags {
+///     (debug: [$($flag:tt)*],) => {
+///         [$(stringify!($flag)),*]
+///     };
+/// }
+///
+/// let io = {
+///     befunge_dm::dbg_expand_groups! {
+///         debug: [[noflush] [io] [tag "first"]],
+///         callback: [
+///             name: flags,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+/// };
+///
+/// assert_eq!(io, ["[noflush]", "[getdbg]", "[putdbg]", r#"[tag "first"]"#]);
+///
+/// let all = {
+///     befunge_dm::dbg_expand_groups! {
+///         debug: [[noflush] [all]],
+///         callback: [
+///             name: flags,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+/// };
+///
+/// assert_eq!(
+///     all,
+///     [
+///         "[noflush]", "[initlines]", "[postinit]", "[getdbg]", "[putdbg]", "[poststack]",
+///         "[postprog]", "[underflow]", "[traceinstr]", "[tracemove]", "[bridgedbg]",
+///         "[profile]", "[heatmap]", "[stepcount]",
+///     ],
+/// );
+///
+/// let trace = {
+///     befunge_dm::dbg_expand_groups! {
+///         debug: [[trace] [noflush]],
+///         callback: [
+///             name: flags,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+/// };
+///
+/// assert_eq!(
+///     trace,
+///     ["[traceinstr]", "[tracemove]", "[bridgedbg]", "[underflow]", "[noflush]"],
+/// );
+/// ```
 #[macro_export]
-macro_rules! dbg_print_stack {
+macro_rules! dbg_expand_groups {
     (
-        @printstack
-        stack: $stack:tt,
+        debug: $debug:tt,
+        callback: $callback:tt,
     ) => {
-        $crate::dbg_print_stack! {
-            @printstack @loop
-            stack: $stack,
-            tokens: [],
+        $crate::dbg_expand_groups! {
+            @loop
+            debug: $debug,
+            out: [],
+            callback: $callback,
         }
     };
     (
-        @printstack @loop
-        stack: [],
-        tokens: [],
+        @loop
+        debug: [],
+        out: $out:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
     ) => {
-        const _: &str = "Empty stack!";
+        $name! {
+            $($pre)*
+            debug: $out,
+            $($pst)*
+        }
     };
     (
-        @printstack @loop
-        stack: [],
-        tokens: [[$hfst:tt$(, $hsnd:tt)?] $([$tfst:tt$(, $tsnd:tt)?])*],
+        @loop
+        debug: [[all] $($debugt:tt)*],
+        out: [$($out:tt)*],
+        callback: $callback:tt,
     ) => {
-        const _: &str = concat!(
-            "top: ",
-            $hfst,
-            $(
-                " (",
-                $hsnd,
-                ")",
-            )?
-        );
-        $(
-            const _: &str = concat!(
-                "     ",
-                $tfst,
-                $(
-                    " (",
-                    $tsnd,
-                    ")",
-                )?
-            );
-        )*
+        $crate::dbg_expand_groups! {
+            @loop
+            debug: [$($debugt)*],
+            out: [$($out)* [initlines] [postinit] [getdbg] [putdbg] [poststack] [postprog] [underflow] [traceinstr] [tracemove] [bridgedbg] [profile] [heatmap] [stepcount]],
+            callback: $callback,
+        }
     };
     (
-        @printstack @loop
-        stack: [$stackh:tt $($stackt:tt)*],
-        tokens: $tokens:tt,
+        @loop
+        debug: [[io] $($debugt:tt)*],
+        out: [$($out:tt)*],
+        callback: $callback:tt,
     ) => {
-        $crate::code_to_char_pretty! {
-            @match
-            num: $stackh,
-            callback: [
-                name: $crate::dbg_print_stack,
-                pre: [
-                    @printstack @loopcatch
-                    stack: [$($stackt)*],
-                    tokens: $tokens,
-                ],
-                pst: [],
-            ],
+        $crate::dbg_expand_groups! {
+            @loop
+            debug: [$($debugt)*],
+            out: [$($out)* [getdbg] [putdbg]],
+            callback: $callback,
         }
     };
     (
-        @printstack @loopcatch
-        stack: $stack:tt,
-        tokens: [$($token:tt)*],
-        char: $char:tt,
+        @loop
+        debug: [[trace] $($debugt:tt)*],
+        out: [$($out:tt)*],
+        callback: $callback:tt,
     ) => {
-        $crate::dbg_print_stack! {
-            @printstack @loop
-            stack: $stack,
-            tokens: [$($token)* $char],
+        $crate::dbg_expand_groups! {
+            @loop
+            debug: [$($debugt)*],
+            out: [$($out)* [traceinstr] [tracemove] [bridgedbg] [underflow]],
+            callback: $callback,
+        }
+    };
+    (
+        @loop
+        debug: [$debugh:tt $($debugt:tt)*],
+        out: [$($out:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_expand_groups! {
+            @loop
+            debug: [$($debugt)*],
+            out: [$($out)* $debugh],
+            callback: $callback,
         }
     };
 }
 
-/// Converts a signed magnitude base 1 number in the representation used by the interpreter to a
-/// literal number.
+/// Checks that every debugging flag is one that something looks for, and fails the build naming
+/// the first one that isn't, along with the ones there are. Without this, a misspelled flag like
+/// `[postack]` would just never be found.
 ///
 /// # Example
-///
 /// ```
-/// #![feature(macro_metavar_expr)]
-///
-/// macro_rules! wrapper {
-///     (num: $num:tt,) => { $num };
-///     (num: -$num:tt,) => { -$num };
+/// befunge_dm::dbg_validate_flags! {
+///     @validate
+///     debug: [[noflush] [poststack] [tag "first"] [random cycle up]],
+/// }
+/// ```
+/// ```compile_fail
+/// // Fails with "Unknown debugging flag `[postack]`, expected one of `[initlines]`, ...".
+/// befunge_dm::dbg_validate_flags! {
+///     @validate
+///     debug: [[noflush] [postack]],
 /// }
-///
-/// let foo = {
-///     befunge_dm::dbg_get_number! {
-///         num: [[neg] [[] [] [] [] [] [] []]],
-///         callback: [
-///             name: wrapper,
-///             pre: [],
-///             pst: [],
-///         ],
-///     }
-/// };
-///
-/// assert_eq!(foo, -7);
 /// ```
 #[macro_export]
-macro_rules! dbg_get_number {
+macro_rules! dbg_validate_flags {
     (
-        num: [[$(pos)?] [$($num:tt)*]],
-        callback: [
-            name: $name:path,
-            pre: [$($pre:tt)*],
-            pst: [$($pst:tt)*],
-        ],
+        @validate
+        debug: [],
+    ) => {};
+    (
+        @validate
+        debug: [[initlines] $($debugt:tt)*],
     ) => {
-        $name! {
-            $($pre)*
-            num: ${count($num)},
-            $($pst)*
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
         }
     };
     (
-        num: [[neg] [$($num:tt)*]],
-        callback: [
-            name: $name:path,
-            pre: [$($pre:tt)*],
-            pst: [$($pst:tt)*],
-        ],
+        @validate
+        debug: [[postinit] $($debugt:tt)*],
     ) => {
-        $name! {
-            $($pre)*
-            num: -${count($num)},
-            $($pst)*
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
         }
     };
-}
-
-#[cfg(feature = "socket_debug_default")]
-/// Sends a message to the default debugging socket (`befunge.debug`).
-#[macro_export]
-macro_rules! socket_debug_default {
-    ($($tt:tt)*) => {
-        $crate::befunge_pm::socket_debug! {
-            tokens: [$($tt)*],
-            socket: "befunge.debug",
+    (
+        @validate
+        debug: [[getdbg] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[putdbg] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[closeonend] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[poststack] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[postprog] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[noflush] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[collecterrors] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[underflow] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[traceinstr] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[tracemove] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[bridgedbg] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[profile] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[heatmap] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[stepcount] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[b98] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[compact] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[divmode floor] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[tag $tag:literal] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[stepdelay $millis:literal] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[breakpoints $($breakpoint:tt)*] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[random $($random:tt)*] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[result $name:path] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[onunknown skip] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[onunknown reflect] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    // Put at the front by the interpreter itself.
+    (
+        @validate
+        debug: [[kiter $($kiter:tt)*] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[observe $($observe:tt)*] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [[observed $($observed:tt)*] $($debugt:tt)*],
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: [$($debugt)*],
+        }
+    };
+    (
+        @validate
+        debug: [$debugh:tt $($debugt:tt)*],
+    ) => {
+        compile_error!(concat!(
+            "Unknown debugging flag `",
+            stringify!($debugh),
+            "`, expected one of ",
+            "`[initlines]`, `[postinit]`, `[getdbg]`, `[putdbg]`, `[closeonend]`, `[poststack]`, ",
+            "`[postprog]`, `[noflush]`, `[collecterrors]`, `[underflow]`, `[traceinstr]`, ",
+            "`[tracemove]`, `[bridgedbg]`, `[profile]`, `[heatmap]`, `[stepcount]`, `[b98]`, ",
+            "`[compact]`, `[divmode floor]`, `[tag \"name\"]`, ",
+            "`[stepdelay N]`, `[breakpoints [x y] ..]`, `[random cycle dir ..]`, ",
+            "`[random once dir ..]`, `[result name]`, `[onunknown skip]`, `[onunknown reflect]`",
+        ));
+    };
+}
+
+/// Looks through the debugging flags for a `[tag "name"]` and makes a callback with `tag: ["name"]`,
+/// or `tag: []` if there isn't one. Callback is performed as:
+/// ```ignore
+/// name! {
+///     pre
+///     tag: [/* "name" */],
+///     pst
+/// }
+/// ```
+#[macro_export]
+macro_rules! dbg_find_tag {
+    (
+        @tag
+        debug: [],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            tag: [],
+            $($pst)*
+        }
+    };
+    (
+        @tag
+        debug: [[tag $tag:literal] $($debugt:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            tag: [$tag],
+            $($pst)*
+        }
+    };
+    (
+        @tag
+        debug: [$debugh:tt $($debugt:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: [$($debugt)*],
+            callback: $callback,
+        }
+    };
+}
+
+/// Expands to a `const _: &str` holding the given message, which is prefixed with `[name] ` if the
+/// debugging flags contain `[tag "name"]`. This makes it possible to tell which program a message
+/// came from when several programs are run in the same module.
+///
+/// # Example
+/// ```
+/// // Expands to `const _: &str = "[first] Hello!";`
+/// befunge_dm::dbg_tagged! {
+///     @tagged
+///     debug: [[noflush] [tag "first"]],
+///     msg: ["Hello!"],
+/// }
+///
+/// // Expands to `const _: &str = "Hello!";`
+/// befunge_dm::dbg_tagged! {
+///     @tagged
+///     debug: [[noflush]],
+///     msg: [concat!("Hel", "lo!")],
+/// }
+/// ```
+#[macro_export]
+macro_rules! dbg_tagged {
+    (
+        @tagged
+        debug: [],
+        msg: [$($msg:tt)*],
+    ) => {
+        const _: &str = $($msg)*;
+    };
+    (
+        @tagged
+        debug: [[tag $tag:literal] $($debugt:tt)*],
+        msg: [$($msg:tt)*],
+    ) => {
+        const _: &str = concat!("[", $tag, "] ", $($msg)*);
+    };
+    (
+        @tagged
+        debug: [$debugh:tt $($debugt:tt)*],
+        msg: $msg:tt,
+    ) => {
+        $crate::dbg_tagged! {
+            @tagged
+            debug: [$($debugt)*],
+            msg: $msg,
+        }
+    };
+}
+
+/// Sleeps for `N` milliseconds if the debugging flags contain `[stepdelay N]`, and otherwise
+/// expands to nothing. Used after every move so that programs can be slowed down enough to watch.
+#[macro_export]
+macro_rules! dbg_step_delay {
+    (
+        @delay
+        debug: [],
+    ) => {};
+    (
+        @delay
+        debug: [[stepdelay $millis:literal] $($debugt:tt)*],
+    ) => {
+        $crate::befunge_pm::sleep! {
+            millis: $millis,
+        }
+    };
+    (
+        @delay
+        debug: [$debugh:tt $($debugt:tt)*],
+    ) => {
+        $crate::dbg_step_delay! {
+            @delay
+            debug: [$($debugt)*],
+        }
+    };
+}
+
+/// Expands to a `const _: &str` naming the instruction being executed and its coordinates if the
+/// debugging flags contain `[traceinstr]`, and otherwise to nothing. `pre` and `cpre` are the rows
+/// above the instruction and the cells to its left.
+///
+/// # Example
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "exec '+' at (1, 2)";`
+/// befunge_dm::dbg_trace_instr! {
+///     @trace
+///     debug: [[noflush] [traceinstr]],
+///     instr: '+',
+///     pre: [['>' 'v']],
+///     cpre: [' ' ' '],
+/// }
+/// ```
+#[macro_export]
+macro_rules! dbg_trace_instr {
+    (
+        @trace
+        debug: $debug:tt,
+        instr: $instr:tt,
+        pre: [$($pre:tt)*],
+        cpre: [$($cpre:tt)*],
+    ) => {
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[traceinstr]],
+            expand: [
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!(
+                        "exec ",
+                        stringify!($instr),
+                        " at (",
+                        ${count($pre)},
+                        ", ",
+                        ${count($cpre)},
+                        ")",
+                    )],
+                }
+            ],
+        }
+    };
+}
+
+/// Expands to a `const _: &str` naming the cell a `#` jumped over and its coordinates if the
+/// debugging flags contain `[bridgedbg]`, and otherwise to nothing. `pre` and `cpre` are the rows
+/// above the cell and the cells to its left.
+///
+/// # Example
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "bridge: skipped 'X' at (0, 3)";`
+/// befunge_dm::dbg_trace_bridge! {
+///     @bridge
+///     debug: [[noflush] [bridgedbg]],
+///     instr: 'X',
+///     pre: [],
+///     cpre: ['>' '1' '#'],
+/// }
+/// ```
+#[macro_export]
+macro_rules! dbg_trace_bridge {
+    (
+        @bridge
+        debug: $debug:tt,
+        instr: $instr:tt,
+        pre: [$($pre:tt)*],
+        cpre: [$($cpre:tt)*],
+    ) => {
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[bridgedbg]],
+            expand: [
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!(
+                        "bridge: skipped ",
+                        stringify!($instr),
+                        " at (",
+                        ${count($pre)},
+                        ", ",
+                        ${count($cpre)},
+                        ")",
+                    )],
+                }
+            ],
+        }
+    };
+}
+
+/// Warns that `instr` popped from an empty stack if the debugging flags contain `[underflow]`, and
+/// otherwise expands to nothing. `pops` has a `[]` for each value the instruction pops and `got` has
+/// a token tree for each of those that were actually on the stack, so the warning is only given
+/// when `got` is the shorter of the two. `pre` and `cpre` are the rows above the instruction and the
+/// cells to its left, which give its coordinates.
+///
+/// # Example
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "Stack underflow: '+' at (1, 2)";`
+/// befunge_dm::dbg_underflow! {
+///     @underflow
+///     debug: [[noflush] [underflow]],
+///     instr: '+',
+///     pops: [[] []],
+///     got: [[[] []]],
+///     pre: [['>' 'v']],
+///     cpre: [' ' ' '],
+/// }
+///
+/// // Expands to nothing, since the stack had enough on it.
+/// befunge_dm::dbg_underflow! {
+///     @underflow
+///     debug: [[noflush] [underflow]],
+///     instr: ':',
+///     pops: [[]],
+///     got: [[[] []]],
+///     pre: [],
+///     cpre: [],
+/// }
+/// ```
+#[macro_export]
+macro_rules! dbg_underflow {
+    (
+        @underflow
+        debug: $debug:tt,
+        instr: $instr:tt,
+        pops: $pops:tt,
+        got: $got:tt,
+        pre: $pre:tt,
+        cpre: $cpre:tt,
+    ) => {
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[underflow]],
+            expand: [
+                $crate::dbg_underflow! {
+                    @count
+                    debug: $debug,
+                    instr: $instr,
+                    pops: $pops,
+                    got: $got,
+                    pre: $pre,
+                    cpre: $cpre,
+                }
+            ],
+        }
+    };
+    (
+        @count
+        debug: $debug:tt,
+        instr: $instr:tt,
+        pops: [],
+        got: $got:tt,
+        pre: $pre:tt,
+        cpre: $cpre:tt,
+    ) => {};
+    (
+        @count
+        debug: $debug:tt,
+        instr: $instr:tt,
+        pops: [$popsh:tt $($popst:tt)*],
+        got: [],
+        pre: [$($pre:tt)*],
+        cpre: [$($cpre:tt)*],
+    ) => {
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!(
+                "Stack underflow: ",
+                stringify!($instr),
+                " at (",
+                ${count($pre)},
+                ", ",
+                ${count($cpre)},
+                ")",
+            )],
+        }
+    };
+    (
+        @count
+        debug: $debug:tt,
+        instr: $instr:tt,
+        pops: [$popsh:tt $($popst:tt)*],
+        got: [$goth:tt $($gott:tt)*],
+        pre: $pre:tt,
+        cpre: $cpre:tt,
+    ) => {
+        $crate::dbg_underflow! {
+            @count
+            debug: $debug,
+            instr: $instr,
+            pops: [$($popst)*],
+            got: [$($gott)*],
+            pre: $pre,
+            cpre: $cpre,
+        }
+    };
+}
+
+/// Prints the stack of a Befunge program for debugging purposes, one `const _: &str = "..."` for
+/// each value, top first. Values with a printable character are shown as the value followed by the
+/// character literal, e.g. `72 ('H')`, and the rest (control characters, negative values, and
+/// anything past `~`) as just the value.
+///
+/// # Example
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "top: 10";`, then `"     -3"` and `"     72 ('H')"`.
+/// befunge_dm::dbg_print_stack! {
+///     @printstack
+///     stack: [
+///         [[pos] [[] [] [] [] [] [] [] [] [] []]]
+///         [[neg] [[] [] []]]
+///         [[pos] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///         ]]
+///     ],
+/// }
+/// ```
+///
+/// `@printstack @joined` puts every value into the same `const`, one per line, named with `name:`:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::dbg_print_stack! {
+///     @printstack @joined
+///     name: STACK,
+///     stack: [
+///         [[pos] []]
+///         [[neg] [[] [] []]]
+///         [[pos] [
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///         ]]
+///         [[pos] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]]
+///     ],
+/// }
+///
+/// assert_eq!(STACK, "top: 0\n     -3\n     72 ('H')\n     32 (' ')");
+/// ```
+#[macro_export]
+macro_rules! dbg_print_stack {
+    (
+        @printstack
+        stack: $stack:tt,
+    ) => {
+        $crate::dbg_print_stack! {
+            @printstack @loop
+            stack: $stack,
+            tokens: [],
+            tag: [],
+        }
+    };
+    (
+        @printstack
+        stack: $stack:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: $debug,
+            callback: [
+                name: $crate::dbg_print_stack,
+                pre: [
+                    @printstack @loop
+                    stack: $stack,
+                    tokens: [],
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @printstack @joined
+        name: $name:ident,
+        stack: $stack:tt,
+    ) => {
+        $crate::dbg_print_stack! {
+            @printstack @loop
+            stack: $stack,
+            tokens: [],
+            tag: [@joined [$name]],
+        }
+    };
+    (
+        @printstack @loop
+        stack: [],
+        tokens: [],
+        tag: [@joined [$name:ident]],
+    ) => {
+        const $name: &str = "Empty stack!";
+    };
+    (
+        @printstack @loop
+        stack: [],
+        tokens: [],
+        tag: [$($tag:literal)?],
+    ) => {
+        const _: &str = concat!($("[", $tag, "] ",)? "Empty stack!");
+    };
+    (
+        @printstack @loop
+        stack: [],
+        tokens: [[[$($hnum:tt)+] $($hchar:tt)?] $([[$($tnum:tt)+] $($tchar:tt)?])*],
+        tag: [@joined [$name:ident]],
+    ) => {
+        const $name: &str = concat!(
+            "top: ",
+            $($hnum)+,
+            $(" (", stringify!($hchar), ")",)?
+            $(
+                "\n     ",
+                $($tnum)+,
+                $(" (", stringify!($tchar), ")",)?
+            )*
+        );
+    };
+    (
+        @printstack @loop
+        stack: [],
+        tokens: $tokens:tt,
+        tag: [],
+    ) => {
+        $crate::dbg_print_stack! {
+            @printstack @print
+            tokens: $tokens,
+            prefix: "",
+        }
+    };
+    (
+        @printstack @loop
+        stack: [],
+        tokens: $tokens:tt,
+        tag: [$tag:literal],
+    ) => {
+        $crate::dbg_print_stack! {
+            @printstack @print
+            tokens: $tokens,
+            prefix: concat!("[", $tag, "] "),
+        }
+    };
+    (
+        @printstack @print
+        tokens: [[[$($hnum:tt)+] $($hchar:tt)?] $([[$($tnum:tt)+] $($tchar:tt)?])*],
+        prefix: $prefix:expr,
+    ) => {
+        const _: &str = concat!(
+            $prefix,
+            "top: ",
+            $($hnum)+,
+            $(" (", stringify!($hchar), ")",)?
+        );
+        $(
+            const _: &str = concat!(
+                $prefix,
+                "     ",
+                $($tnum)+,
+                $(" (", stringify!($tchar), ")",)?
+            );
+        )*
+    };
+    (
+        @printstack @loop
+        stack: [$stackh:tt $($stackt:tt)*],
+        tokens: $tokens:tt,
+        tag: $tag:tt,
+    ) => {
+        $crate::dbg_get_number! {
+            num: $stackh,
+            callback: [
+                name: $crate::dbg_print_stack,
+                pre: [
+                    @printstack @loopnum
+                    stack: [$($stackt)*],
+                    tokens: $tokens,
+                    tag: $tag,
+                    code: $stackh,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    // `[[neg] []]`, which is 0.
+    (
+        @printstack @loopnum
+        stack: $stack:tt,
+        tokens: [$($token:tt)*],
+        tag: $tag:tt,
+        code: [[neg] []],
+        num: -$num:literal,
+    ) => {
+        $crate::dbg_print_stack! {
+            @printstack @loop
+            stack: $stack,
+            tokens: [$($token)* [[$num]]],
+            tag: $tag,
+        }
+    };
+    (
+        @printstack @loopnum
+        stack: $stack:tt,
+        tokens: [$($token:tt)*],
+        tag: $tag:tt,
+        code: [[neg] $mag:tt],
+        num: -$num:literal,
+    ) => {
+        $crate::dbg_print_stack! {
+            @printstack @loop
+            stack: $stack,
+            tokens: [$($token)* [[-$num]]],
+            tag: $tag,
+        }
+    };
+    // Past `~`, with no character.
+    (
+        @printstack @loopnum
+        stack: $stack:tt,
+        tokens: [$($token:tt)*],
+        tag: $tag:tt,
+        code: [$sgn:tt [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] $($more:tt)*]],
+        num: $num:literal,
+    ) => {
+        $crate::dbg_print_stack! {
+            @printstack @loop
+            stack: $stack,
+            tokens: [$($token)* [[$num]]],
+            tag: $tag,
+        }
+    };
+    // From ` ` to `~`, which have printable characters.
+    (
+        @printstack @loopnum
+        stack: $stack:tt,
+        tokens: $tokens:tt,
+        tag: $tag:tt,
+        code: [$sgn:tt [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] $($more:tt)*]],
+        num: $num:literal,
+    ) => {
+        $crate::code_to_char_pretty! {
+            @match
+            num: [$sgn [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] $($more)*]],
+            callback: [
+                name: $crate::dbg_print_stack,
+                pre: [
+                    @printstack @loopcatch
+                    stack: $stack,
+                    tokens: $tokens,
+                    tag: $tag,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    // Control characters.
+    (
+        @printstack @loopnum
+        stack: $stack:tt,
+        tokens: [$($token:tt)*],
+        tag: $tag:tt,
+        code: $code:tt,
+        num: $num:literal,
+    ) => {
+        $crate::dbg_print_stack! {
+            @printstack @loop
+            stack: $stack,
+            tokens: [$($token)* [[$num]]],
+            tag: $tag,
+        }
+    };
+    (
+        @printstack @loopcatch
+        stack: $stack:tt,
+        tokens: [$($token:tt)*],
+        tag: $tag:tt,
+        char: [$char:tt, $num:literal],
+    ) => {
+        $crate::dbg_print_stack! {
+            @printstack @loop
+            stack: $stack,
+            tokens: [$($token)* [[$num] $char]],
+            tag: $tag,
+        }
+    };
+}
+
+/// Converts a signed magnitude base 1 number in the representation used by the interpreter to a
+/// literal number.
+///
+/// # Example
+///
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! wrapper {
+///     (num: $num:tt,) => { $num };
+///     (num: -$num:tt,) => { -$num };
+/// }
+///
+/// let foo = {
+///     befunge_dm::dbg_get_number! {
+///         num: [[neg] [[] [] [] [] [] [] []]],
+///         callback: [
+///             name: wrapper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+/// };
+///
+/// assert_eq!(foo, -7);
+/// ```
+///
+/// Together with [`befunge_pm::base1_from_literal!`] this makes a round trip, which is handy for
+/// checking the arithmetic macros without counting brackets:
+///
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! wrapper {
+///     (num: $num:tt,) => { $num };
+///     (num: -$num:tt,) => { -$num };
+/// }
+///
+/// let foo = {
+///     befunge_dm::befunge_pm::base1_from_literal! {
+///         value: -12,
+///         callback: [
+///             name: befunge_dm::dbg_get_number,
+///             pst: [callback: [name: wrapper, pre: [], pst: [],],],
+///         ],
+///     }
+/// };
+///
+/// assert_eq!(foo, -12);
+/// ```
+#[macro_export]
+macro_rules! dbg_get_number {
+    (
+        num: [[$(pos)?] [$($num:tt)*]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            num: ${count($num)},
+            $($pst)*
+        }
+    };
+    (
+        num: [[neg] [$($num:tt)*]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            num: -${count($num)},
+            $($pst)*
+        }
+    };
+}
+
+/// Converts every number on a stack in the representation used by the interpreter to a literal
+/// number with [`dbg_get_number!`], and makes a callback with `stack:` set to the literals, the top
+/// of the stack first.
+///
+/// # Example
+///
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! wrapper {
+///     (stack: [$($num:literal)*],) => { [$($num),*] };
+/// }
+///
+/// let foo = {
+///     befunge_dm::dbg_get_stack! {
+///         stack: [[[pos] [[] [] []]] [[neg] [[]]] [[pos] []]],
+///         callback: [
+///             name: wrapper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+/// };
+///
+/// assert_eq!(foo, [3, -1, 0]);
+/// ```
+#[macro_export]
+macro_rules! dbg_get_stack {
+    (
+        stack: $stack:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_get_stack! {
+            @loop
+            stack: $stack,
+            nums: [],
+            callback: $callback,
+        }
+    };
+    (
+        @loop
+        stack: [],
+        nums: $nums:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            stack: $nums,
+            $($pst)*
+        }
+    };
+    (
+        @loop
+        stack: [$stackh:tt $($stackt:tt)*],
+        nums: $nums:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_get_number! {
+            num: $stackh,
+            callback: [
+                name: $crate::dbg_get_stack,
+                pre: [
+                    @catch
+                    stack: [$($stackt)*],
+                    nums: $nums,
+                ],
+                pst: [
+                    callback: $callback,
+                ],
+            ],
+        }
+    };
+    (
+        @catch
+        stack: $stack:tt,
+        nums: [$($nums:tt)*],
+        num: $num:literal,
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_get_stack! {
+            @loop
+            stack: $stack,
+            nums: [$($nums)* $num],
+            callback: $callback,
+        }
+    };
+}
+
+/// Checks a stack against the one that's expected, failing the build with a report of the first
+/// difference if they don't match. `expect:` is a list of integer literals, and `stack:` is either
+/// a stack in the representation used by the interpreter or a list of integer literals like the
+/// one `result:` hands over (see [`crate::befunge!`]), both with the top of the stack first. Every
+/// number is turned into a literal with [`dbg_get_number!`] before being compared.
+///
+/// # Example
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::dbg_assert_stack! {
+///     expect: [3 -1 0],
+///     stack: [[[pos] [[] [] []]] [[neg] [[]]] [[neg] []]],
+/// }
+/// ```
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// // Fails with "Stack differs at position 1 (the top is 0)!", then "expected: -1" and
+/// // "  actual: 1", then both stacks.
+/// befunge_dm::dbg_assert_stack! {
+///     expect: [3 -1 0],
+///     stack: [[[pos] [[] [] []]] [[pos] [[]]] [[pos] []]],
+/// }
+/// ```
+///
+/// Together with `result:`, a program's final stack can be checked without running anything:
+/// ```
+/// #![recursion_limit = "1024"]
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! factorial {
+///     (stack: $stack:tt,) => {
+///         befunge_dm::dbg_assert_stack! {
+///             expect: [120],
+///             stack: $stack,
+///         }
+///     };
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/factorial.bfg",
+///     result: factorial,
+///     debug: [[noflush]],
+/// }
+/// ```
+#[macro_export]
+macro_rules! dbg_assert_stack {
+    (
+        expect: [$($expect:literal)*],
+        stack: [$($stack:tt)*]$(,)?
+    ) => {
+        $crate::dbg_assert_stack! {
+            @loop
+            todo: [$($expect)*],
+            done: [],
+            then: [@expect stack: [$($stack)*]],
+        }
+    };
+    // Every expected number is done, so start on the stack.
+    (
+        @loop
+        todo: [],
+        done: $expected:tt,
+        then: [@expect stack: $stack:tt],
+    ) => {
+        $crate::dbg_assert_stack! {
+            @loop
+            todo: $stack,
+            done: [],
+            then: [@stack expected: $expected],
+        }
+    };
+    (
+        @loop
+        todo: [],
+        done: $actual:tt,
+        then: [@stack expected: $expected:tt],
+    ) => {
+        $crate::dbg_assert_stack! {
+            @cmp
+            index: [],
+            expected: $expected,
+            actual: $actual,
+            stacks: [$expected $actual],
+        }
+    };
+    // `[[neg] []]`, which is 0.
+    (
+        @loop
+        todo: [[[neg] []] $($todo:tt)*],
+        done: $done:tt,
+        then: $then:tt,
+    ) => {
+        $crate::dbg_assert_stack! {
+            @catch
+            todo: [$($todo)*],
+            done: $done,
+            then: $then,
+            num: 0,
+        }
+    };
+    (
+        @loop
+        todo: [[$sgn:tt $mag:tt] $($todo:tt)*],
+        done: $done:tt,
+        then: $then:tt,
+    ) => {
+        $crate::dbg_get_number! {
+            num: [$sgn $mag],
+            callback: [
+                name: $crate::dbg_assert_stack,
+                pre: [
+                    @catch
+                    todo: [$($todo)*],
+                    done: $done,
+                    then: $then,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    // A literal can't be matched against other tokens once it's been passed along as one, so it's
+    // turned into a base 1 number and back.
+    (
+        @loop
+        todo: [$num:literal $($todo:tt)*],
+        done: $done:tt,
+        then: $then:tt,
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $num,
+            callback: [
+                name: $crate::dbg_assert_stack,
+                pre: [
+                    @loop
+                    todo: [],
+                    done: $done,
+                    then: $then,
+                    rest: [$($todo)*],
+                ],
+            ],
+        }
+    };
+    (
+        @loop
+        todo: [],
+        done: $done:tt,
+        then: $then:tt,
+        rest: [$($todo:tt)*],
+        num: $num:tt,
+    ) => {
+        $crate::dbg_assert_stack! {
+            @loop
+            todo: [$num $($todo)*],
+            done: $done,
+            then: $then,
+        }
+    };
+    (
+        @catch
+        todo: $todo:tt,
+        done: [$($done:tt)*],
+        then: $then:tt,
+        num: - $num:tt,
+    ) => {
+        $crate::dbg_assert_stack! {
+            @loop
+            todo: $todo,
+            done: [$($done)* [- $num]],
+            then: $then,
+        }
+    };
+    (
+        @catch
+        todo: $todo:tt,
+        done: [$($done:tt)*],
+        then: $then:tt,
+        num: $num:tt,
+    ) => {
+        $crate::dbg_assert_stack! {
+            @loop
+            todo: $todo,
+            done: [$($done)* [$num]],
+            then: $then,
+        }
+    };
+    (
+        @cmp
+        index: $index:tt,
+        expected: [],
+        actual: [],
+        stacks: $stacks:tt,
+    ) => {};
+    (
+        @cmp
+        index: $index:tt,
+        expected: [],
+        actual: [$ah:tt $($at:tt)*],
+        stacks: $stacks:tt,
+    ) => {
+        $crate::dbg_assert_stack! {
+            @differ
+            index: $index,
+            expected: ["nothing"],
+            actual: $ah,
+            stacks: $stacks,
+        }
+    };
+    (
+        @cmp
+        index: $index:tt,
+        expected: [$eh:tt $($et:tt)*],
+        actual: [],
+        stacks: $stacks:tt,
+    ) => {
+        $crate::dbg_assert_stack! {
+            @differ
+            index: $index,
+            expected: $eh,
+            actual: ["nothing"],
+            stacks: $stacks,
+        }
+    };
+    (
+        @cmp
+        index: [$($index:tt)*],
+        expected: [$eh:tt $($et:tt)*],
+        actual: [$ah:tt $($at:tt)*],
+        stacks: $stacks:tt,
+    ) => {
+        macro_rules! dbg_assert_stack_eq {
+            ($eh) => {
+                $crate::dbg_assert_stack! {
+                    @cmp
+                    index: [$($index)* []],
+                    expected: [$($et)*],
+                    actual: [$($at)*],
+                    stacks: $stacks,
+                }
+            };
+            ($$($$_:tt)*) => {
+                $crate::dbg_assert_stack! {
+                    @differ
+                    index: [$($index)*],
+                    expected: $eh,
+                    actual: $ah,
+                    stacks: $stacks,
+                }
+            };
+        }
+        dbg_assert_stack_eq!($ah);
+    };
+    (
+        @differ
+        index: [$($index:tt)*],
+        expected: [$($expected:tt)*],
+        actual: [$($actual:tt)*],
+        stacks: [
+            [$([$($ehh:tt)+] $([$($eth:tt)+])*)?]
+            [$([$($ahh:tt)+] $([$($ath:tt)+])*)?]
+        ],
+    ) => {
+        compile_error!(concat!(
+            "Stack differs at position ", ${count($index)}, " (the top is 0)!\n",
+            "expected: ", concat!($($expected)*), "\n",
+            "  actual: ", concat!($($actual)*), "\n",
+            "expected stack: [",
+            $(concat!($($ehh)+), $(" ", concat!($($eth)+),)*)?
+            "]\n",
+            "  actual stack: [",
+            $(concat!($($ahh)+), $(" ", concat!($($ath)+),)*)?
+            "]",
+        ));
+    };
+}
+
+#[cfg(all(feature = "socket_debug_default", not(feature = "socket_debug_locations")))]
+/// Sends a message to the default debugging socket (`befunge.debug`). If the message starts with
+/// `sockets: "prefix",` then it is sent to `prefix.debug` instead. Messages are traces unless
+/// `level: info` or `level: warn` follows the sockets. They're sent without waiting for the
+/// interface to acknowledge them, so they can be lost if it goes down mid-build. A message starting
+/// with a literal, like `"catch: put2"`, has that literal as its category for
+/// `BEFUNGE_DEBUG_CATEGORY` (see [`befunge_pm::socket_debug!`]).
+#[macro_export]
+macro_rules! socket_debug_default {
+    (sockets: $sockets:tt, level: $level:ident, $category:literal $($tt:tt)*) => {
+        $crate::befunge_pm::socket_debug! {
+            tokens: [$category $($tt)*],
+            socket: [$sockets ".debug"],
+            level: $level,
+            noack: true,
+            category: $category,
+        }
+    };
+    (sockets: $sockets:tt, level: $level:ident, $($tt:tt)*) => {
+        $crate::befunge_pm::socket_debug! {
+            tokens: [$($tt)*],
+            socket: [$sockets ".debug"],
+            level: $level,
+            noack: true,
+        }
+    };
+    (sockets: $sockets:tt, $($tt:tt)*) => {
+        $crate::socket_debug_default!(sockets: $sockets, level: trace, $($tt)*);
+    };
+    ($($tt:tt)*) => {
+        $crate::socket_debug_default!(sockets: "befunge", level: trace, $($tt)*);
+    };
+}
+
+#[cfg(feature = "socket_debug_locations")]
+/// Redefinition of `socket_debug_default` for the `socket_debug_locations` feature, which prefixes
+/// each message with the file, line, and column it was sent from.
+#[macro_export]
+macro_rules! socket_debug_default {
+    (sockets: $sockets:tt, level: $level:ident, $category:literal $($tt:tt)*) => {
+        $crate::befunge_pm::socket_debug! {
+            tokens: [$category $($tt)*],
+            socket: [$sockets ".debug"],
+            level: $level,
+            locations: true,
+            noack: true,
+            category: $category,
+        }
+    };
+    (sockets: $sockets:tt, level: $level:ident, $($tt:tt)*) => {
+        $crate::befunge_pm::socket_debug! {
+            tokens: [$($tt)*],
+            socket: [$sockets ".debug"],
+            level: $level,
+            locations: true,
+            noack: true,
+        }
+    };
+    (sockets: $sockets:tt, $($tt:tt)*) => {
+        $crate::socket_debug_default!(sockets: $sockets, level: trace, $($tt)*);
+    };
+    ($($tt:tt)*) => {
+        $crate::socket_debug_default!(sockets: "befunge", level: trace, $($tt)*);
+    };
+}
+
+#[cfg(not(feature = "socket_debug_default"))]
+/// Redefinition of `socket_debug_default` for when debugging is not desired. This simply consumes
+/// all input tokens and expands to an empty tree.
+#[macro_export]
+macro_rules! socket_debug_default {
+    ($($tt:tt)*) => {};
+}
+
+#[cfg(feature = "socket_debug_default")]
+/// Outputs program memory for a debugging flag. With the `socket_debug_default` feature, it's sent
+/// to the debugging socket as one message (see `@stringify @tosocket` in
+/// [`crate::befunge_stringify!`]), and `pc:` is ignored. Otherwise, it's given to
+/// [`crate::befunge_stringify!`] with `mode` in front, along with `pc:` if there is one.
+#[macro_export]
+macro_rules! socket_debug_stringify {
+    (
+        sockets: $sockets:tt,
+        mode: $mode:tt,
+        lines: $lines:tt,
+        $(pc: $pc:tt,)?
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @tosocket
+            sockets: $sockets,
+            lines: $lines,
+            debug: $debug,
+        }
+    };
+}
+
+#[cfg(not(feature = "socket_debug_default"))]
+/// Redefinition of `socket_debug_stringify` for when debugging is not desired. This outputs
+/// program memory with [`crate::befunge_stringify!`], with `mode` in front and `pc:` (for
+/// `@stringify @framed`) passed along if there is one.
+#[macro_export]
+macro_rules! socket_debug_stringify {
+    (
+        sockets: $sockets:tt,
+        mode: [$($mode:tt)*],
+        lines: $lines:tt,
+        $(pc: $pc:tt,)?
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            $($mode)*
+            lines: $lines,
+            $(pc: $pc,)?
+            debug: $debug,
+        }
+    };
+}
+
+/// Compares two snapshots of program memory (given as lists of rows, in the same form as the
+/// `program:` given to [`crate::befunge_step!`]) and finds the cells that differ between them. Each
+/// difference is given as `(row, col, before, after)`. Rows of different lengths (and different
+/// numbers of rows) are padded with spaces, just like the interpreter does.
+///
+/// With `expect:`, the differences are checked against the given list, and the build fails with a
+/// report of both lists if they don't match:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// // identical
+/// befunge_dm::befunge_diff_memory! {
+///     @diff
+///     before: [['>' '1' 'v'] ['@' ' ' '<']],
+///     after: [['>' '1' 'v'] ['@' ' ' '<']],
+///     expect: [],
+/// }
+///
+/// // a single `p`
+/// befunge_dm::befunge_diff_memory! {
+///     @diff
+///     before: [['>' '1' 'v'] ['@' ' ' '<']],
+///     after: [['>' '1' 'v'] ['@' 'A' '<']],
+///     expect: [(1, 1, ' ', 'A')],
+/// }
+///
+/// // rows of different lengths
+/// befunge_dm::befunge_diff_memory! {
+///     @diff
+///     before: [['a' 'b'] ['c'] []],
+///     after: [['a' ' ' ' '] ['c' 'd']],
+///     expect: [(0, 1, 'b', ' ') (1, 1, ' ', 'd')],
+/// }
+/// ```
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_diff_memory! {
+///     @diff
+///     before: [['a' 'b']],
+///     after: [['a' 'c']],
+///     expect: [(0, 0, 'a', 'c')],
+/// }
+/// ```
+/// With `callback:`, the differences are handed on as `diff: [(row, col, before, after) ...]`
+/// instead.
+#[macro_export]
+macro_rules! befunge_diff_memory {
+    (
+        @diff
+        before: $before:tt,
+        after: $after:tt,
+        expect: [$($expect:tt)*]$(,)?
+    ) => {
+        $crate::befunge_diff_memory! {
+            @rows
+            row: [],
+            before: $before,
+            after: $after,
+            diffs: [],
+            then: [expect: [$($expect)*]],
+        }
+    };
+    (
+        @diff
+        before: $before:tt,
+        after: $after:tt,
+        callback: $callback:tt$(,)?
+    ) => {
+        $crate::befunge_diff_memory! {
+            @rows
+            row: [],
+            before: $before,
+            after: $after,
+            diffs: [],
+            then: [callback: $callback],
+        }
+    };
+    // no rows left in either
+    (
+        @rows
+        row: $row:tt,
+        before: [],
+        after: [],
+        diffs: $diffs:tt,
+        then: $then:tt,
+    ) => {
+        $crate::befunge_diff_memory! {
+            @done
+            diffs: $diffs,
+            then: $then,
+        }
+    };
+    // fewer rows before: compare with an empty row
+    (
+        @rows
+        row: $row:tt,
+        before: [],
+        after: [$($after:tt)+],
+        diffs: $diffs:tt,
+        then: $then:tt,
+    ) => {
+        $crate::befunge_diff_memory! {
+            @rows
+            row: $row,
+            before: [[]],
+            after: [$($after)+],
+            diffs: $diffs,
+            then: $then,
+        }
+    };
+    // fewer rows after: compare with an empty row
+    (
+        @rows
+        row: $row:tt,
+        before: [$($before:tt)+],
+        after: [],
+        diffs: $diffs:tt,
+        then: $then:tt,
+    ) => {
+        $crate::befunge_diff_memory! {
+            @rows
+            row: $row,
+            before: [$($before)+],
+            after: [[]],
+            diffs: $diffs,
+            then: $then,
+        }
+    };
+    (
+        @rows
+        row: $row:tt,
+        before: [$bh:tt $($bt:tt)*],
+        after: [$ah:tt $($at:tt)*],
+        diffs: $diffs:tt,
+        then: $then:tt,
+    ) => {
+        $crate::befunge_diff_memory! {
+            @cols
+            row: $row,
+            col: [],
+            before: $bh,
+            after: $ah,
+            rest: [
+                before: [$($bt)*],
+                after: [$($at)*],
+            ],
+            diffs: $diffs,
+            then: $then,
+        }
+    };
+    // end of both rows
+    (
+        @cols
+        row: [$($row:tt)*],
+        col: $col:tt,
+        before: [],
+        after: [],
+        rest: [
+            before: $before:tt,
+            after: $after:tt,
+        ],
+        diffs: $diffs:tt,
+        then: $then:tt,
+    ) => {
+        $crate::befunge_diff_memory! {
+            @rows
+            row: [$($row)* []],
+            before: $before,
+            after: $after,
+            diffs: $diffs,
+            then: $then,
+        }
+    };
+    // shorter row before: pad with a space
+    (
+        @cols
+        row: $row:tt,
+        col: $col:tt,
+        before: [],
+        after: [$($after:tt)+],
+        rest: $rest:tt,
+        diffs: $diffs:tt,
+        then: $then:tt,
+    ) => {
+        $crate::befunge_diff_memory! {
+            @cols
+            row: $row,
+            col: $col,
+            before: [' '],
+            after: [$($after)+],
+            rest: $rest,
+            diffs: $diffs,
+            then: $then,
+        }
+    };
+    // shorter row after: pad with a space
+    (
+        @cols
+        row: $row:tt,
+        col: $col:tt,
+        before: [$($before:tt)+],
+        after: [],
+        rest: $rest:tt,
+        diffs: $diffs:tt,
+        then: $then:tt,
+    ) => {
+        $crate::befunge_diff_memory! {
+            @cols
+            row: $row,
+            col: $col,
+            before: [$($before)+],
+            after: [' '],
+            rest: $rest,
+            diffs: $diffs,
+            then: $then,
+        }
+    };
+    (
+        @cols
+        row: [$($row:tt)*],
+        col: [$($col:tt)*],
+        before: [$bh:tt $($bt:tt)*],
+        after: [$ah:tt $($at:tt)*],
+        rest: $rest:tt,
+        diffs: [$($diffs:tt)*],
+        then: $then:tt,
+    ) => {
+        macro_rules! befunge_diff_memory_cell {
+            ($bh) => {
+                $crate::befunge_diff_memory! {
+                    @cols
+                    row: [$($row)*],
+                    col: [$($col)* []],
+                    before: [$($bt)*],
+                    after: [$($at)*],
+                    rest: $rest,
+                    diffs: [$($diffs)*],
+                    then: $then,
+                }
+            };
+            ($$($$_:tt)*) => {
+                $crate::befunge_diff_memory! {
+                    @cols
+                    row: [$($row)*],
+                    col: [$($col)* []],
+                    before: [$($bt)*],
+                    after: [$($at)*],
+                    rest: $rest,
+                    diffs: [$($diffs)* (${count($row)}, ${count($col)}, $bh, $ah)],
+                    then: $then,
+                }
+            };
+        }
+        befunge_diff_memory_cell!($ah);
+    };
+    (
+        @done
+        diffs: [$($diffs:tt)*],
+        then: [expect: [$($expect:tt)*]],
+    ) => {
+        macro_rules! befunge_diff_memory_check {
+            ($($diffs)*) => {};
+            ($$($$_:tt)*) => {
+                compile_error!(concat!(
+                    "Program memory changed differently than expected!\n",
+                    "expected: [", stringify!($($expect)*), "]\n",
+                    "  actual: [", stringify!($($diffs)*), "]",
+                ));
+            };
+        }
+        befunge_diff_memory_check!($($expect)*);
+    };
+    (
+        @done
+        diffs: [$($diffs:tt)*],
+        then: [callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ]],
+    ) => {
+        $name! {
+            $($pre)*
+            diff: [$($diffs)*],
+            $($pst)*
         }
     };
-}
-
-#[cfg(not(feature = "socket_debug_default"))]
-/// Redefinition of `socket_debug_default` for when debugging is not desired. This simply consumes
-/// all input tokens and expands to an empty tree.
-#[macro_export]
-macro_rules! socket_debug_default {
-    ($($tt:tt)*) => {};
 }
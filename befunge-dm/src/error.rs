@@ -3,7 +3,15 @@
 ///
 /// - `@initerr @rows`: Initialisation failed due to too many rows being read
 /// - `@initerr @cols`: Initialisation failed due to too many columns being read
+/// - `@initerr @start`: The program was given a start position outside of the playfield
+/// - `@initerr @direction`: The program was given a start direction other than `left`, `right`,
+///   `up`, or `down`
 /// - `@unknowninstr`: Unknown instruction encountered
+/// - `@collect`: Unknown instruction encountered with `[collecterrors]`, which is added to the
+///   `[collected ..]` list kept right after the flag before carrying on. It's an error straight
+///   away if the PC has been to the same cell before.
+/// - `@collected`: Every unknown instruction collected with `[collecterrors]`, at the end of the
+///   program
 ///
 /// Anything else is a helper rule for one of the above.
 ///
@@ -50,6 +58,28 @@ macro_rules! befunge_error {
             }
         }
     };
+    (
+        @initerr @start
+        start: [$x:tt $y:tt],
+    ) => {
+        compile_error!(concat!(
+            "Start position `[",
+            $x,
+            " ",
+            $y,
+            "]` is outside of the playfield, which is 80 columns by 25 rows",
+        ));
+    };
+    (
+        @initerr @direction
+        direction: $direction:ident,
+    ) => {
+        compile_error!(concat!(
+            "Unknown start direction `",
+            stringify!($direction),
+            "`, expected `left`, `right`, `up`, or `down`",
+        ));
+    };
     (
         @unknowninstr
         instr: $instr:tt,
@@ -66,6 +96,34 @@ macro_rules! befunge_error {
             dir: $dir,
             stack: $stack,
             tokens: [],
+            tag: [],
+        }
+    };
+    (
+        @unknowninstr
+        instr: $instr:tt,
+        row: $row:tt,
+        col: $col:tt,
+        stack: $stack:tt,
+        dir: $dir:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: $debug,
+            callback: [
+                name: $crate::befunge_error,
+                pre: [
+                    @unknowninstr @loop
+                    instr: $instr,
+                    row: $row,
+                    col: $col,
+                    dir: $dir,
+                    stack: $stack,
+                    tokens: [],
+                ],
+                pst: [],
+            ],
         }
     };
     (
@@ -76,9 +134,11 @@ macro_rules! befunge_error {
         dir: $dir:tt,
         stack: [],
         tokens: [$([$hfst:tt$(, $hsnd:tt)?] $([$tfst:tt$(, $tsnd:tt)?])*)?],
+        tag: [$($tag:literal)?],
     ) => {
         compile_error! {
             concat! {
+                $("[", $tag, "] ",)?
                 "Encountered unknown instruction `",
                 stringify!($instr),
                 "` at location (",
@@ -119,6 +179,7 @@ macro_rules! befunge_error {
         dir: $dir:tt,
         stack: [$stackh:tt $($stackt:tt)*],
         tokens: $tokens:tt,
+        tag: $tag:tt,
     ) => {
         $crate::code_to_char_pretty! {
             @match
@@ -133,6 +194,7 @@ macro_rules! befunge_error {
                     dir: $dir,
                     stack: [$($stackt)*],
                     tokens: $tokens,
+                    tag: $tag,
                 ],
                 pst: [],
             ],
@@ -146,6 +208,7 @@ macro_rules! befunge_error {
         dir: $dir:tt,
         stack: $stack:tt,
         tokens: [$($token:tt)*],
+        tag: $tag:tt,
         char: $char:tt,
     ) => {
         $crate::befunge_error! {
@@ -156,6 +219,184 @@ macro_rules! befunge_error {
             dir: $dir,
             stack: $stack,
             tokens: [$($token)* $char],
+            tag: $tag,
+        }
+    };
+    // Looks for `[collecterrors]`, and the `[collected ..]` after it once there is one.
+    (
+        @collect
+        error: [$instr:tt $row:tt $col:tt],
+        debug: [[collecterrors] [collected $([$einstr:tt $erow:tt $ecol:tt])*] $($debugt:tt)*],
+        seen: [$($seen:tt)*],
+        callback: $callback:tt,
+    ) => {
+        macro_rules! befunge_error_collected {
+            $(
+                ([$erow $ecol] $$($$rest:tt)*) => {
+                    $crate::befunge_error! {
+                        @collect @again
+                        $$($$rest)*
+                    }
+                };
+            )*
+            ($$pos:tt $$($$rest:tt)*) => {
+                $crate::befunge_error! {
+                    @collect @new
+                    $$($$rest)*
+                }
+            };
+        }
+        befunge_error_collected! {
+            [$row $col]
+            error: [$instr $row $col],
+            errors: [$([$einstr $erow $ecol])*],
+            seen: [$($seen)*],
+            rest: [$($debugt)*],
+            callback: $callback,
+        }
+    };
+    (
+        @collect
+        error: $error:tt,
+        debug: [[collecterrors] $($debugt:tt)*],
+        seen: [$($seen:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::befunge_error! {
+            @collect @new
+            error: $error,
+            errors: [],
+            seen: [$($seen)*],
+            rest: [$($debugt)*],
+            callback: $callback,
+        }
+    };
+    (
+        @collect
+        error: $error:tt,
+        debug: [$debugh:tt $($debugt:tt)*],
+        seen: [$($seen:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::befunge_error! {
+            @collect
+            error: $error,
+            debug: [$($debugt)*],
+            seen: [$($seen)* $debugh],
+            callback: $callback,
+        }
+    };
+    (
+        @collect @new
+        error: $error:tt,
+        errors: [$($errors:tt)*],
+        seen: [$($seen:tt)*],
+        rest: [$($rest:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($seen)* [collecterrors] [collected $($errors)* $error] $($rest)*],
+            $($pst)*
+        }
+    };
+    // Back at a cell that's already been reported. The program is likely to go around the same way
+    // again, so this is where it stops.
+    (
+        @collect @again
+        error: $error:tt,
+        errors: $errors:tt,
+        seen: [$($seen:tt)*],
+        rest: [$($rest:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: [$($seen)* $($rest)*],
+            callback: [
+                name: $crate::befunge_error,
+                pre: [
+                    @collected @report
+                    errors: $errors,
+                    again: [$error],
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @collected
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_error! {
+            @collected @find
+            find: $debug,
+            debug: $debug,
+        }
+    };
+    (
+        @collected @find
+        find: [],
+        debug: $debug:tt,
+    ) => {};
+    (
+        @collected @find
+        find: [[collected $($errors:tt)*] $($findt:tt)*],
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: $debug,
+            callback: [
+                name: $crate::befunge_error,
+                pre: [
+                    @collected @report
+                    errors: [$($errors)*],
+                    again: [],
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @collected @find
+        find: [$findh:tt $($findt:tt)*],
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_error! {
+            @collected @find
+            find: [$($findt)*],
+            debug: $debug,
+        }
+    };
+    (
+        @collected @report
+        errors: [$([$instr:tt $row:tt $col:tt])+],
+        again: [$([$ainstr:tt $arow:tt $acol:tt])?],
+        tag: [$($tag:literal)?],
+    ) => {
+        $crate::befunge_pm::error_at! {
+            at: [$($instr)+],
+            string: [
+                $("[", $tag, "] ",)?
+                "Encountered ",
+                ${count($instr)},
+                " unknown instruction(s) while stringmode was disabled:",
+                $("\n`'", $instr, "'` at location (", $row, ", ", $col, ")",)+
+                $(
+                    "\nStopped when the PC came back to `'",
+                    $ainstr,
+                    "'` at location (",
+                    $arow,
+                    ", ",
+                    $acol,
+                    "), which was already reported.",
+                )?
+            ],
         }
     };
 }
@@ -21,61 +21,244 @@
 ///
 /// - `[initlines]`: Output a `const _: &str = "..."` with the contents of each line of the
 ///   program as it is read in.
-/// - `[postinit]`: Output a `const _: &str = "..."` with the contents of the program memory
-///   once the whole program has been read in.
+/// - `[postinit]`: Output a single `const _: &str = "..."` with the contents of the program
+///   memory, one row per line, once the whole program has been read in. With the
+///   `socket_debug_default` feature, it's sent to `befunge.debug` instead.
 /// - `[getdbg]`: Output `const _: &str = "..."`s as the program performs `g` instructions.
-/// - `[putdbg]`: Output `const _: &str = "..."`s as the program performs `p` instructions.
+/// - `[putdbg]`: Output `const _: &str = "..."`s as the program performs `p` instructions. With
+///   `socket_debug_default`, the program memory after each one is sent to `befunge.debug` instead.
 /// - `[closeonend]`: Send a signal to the I/O programs to close when the program exits (hits a
 ///   `@` instruction).
-/// - `[poststack]`: Output the contents of the stack on exit (hitting a `@` instruction).
+/// - `[poststack]`: Output the contents of the stack on exit (hitting a `@` instruction), one
+///   `const _: &str = "..."` for each value, top first. Values with a printable character are
+///   shown as e.g. `72 ('H')`, and the rest as just the number.
+/// - `[postprog]`: Output a `const _: &str = "..."` with the contents of each row of the program
+///   memory on exit (hitting a `@` instruction), after anything `p` has changed.
+/// - `[underflow]`: Output a `const _: &str = "..."` naming the instruction and its coordinates
+///   whenever an instruction pops from an empty stack. The 0 it gets instead is the same either way.
+/// - `[traceinstr]`: Output a `const _: &str = "exec 'X' at (row, col)"` for every instruction as
+///   it's executed. Spaces and the cells skipped over by `#` or read in stringmode aren't
+///   executed, so they don't show up. This is easiest to follow with `cargo expand`.
+/// - `[bridgedbg]`: Output a `const _: &str = "bridge: skipped 'X' at (row, col)"` for every cell
+///   a `#` jumps over, wrapping around the edge of the playfield included.
+/// - `[profile]`: Count the instructions executed, by kind (arithmetic, movement, and so on), and
+///   output the counts as `const _: &str = "..."`s at the end (hitting a `@` instruction). Each
+///   instruction is counted every time it runs, so this points at the loops that use up the
+///   recursion limit, though counting needs a few more levels of recursion for each instruction.
+/// - `[breakpoints [x y] ..]`: Output the direction, stringmode, bridge, stack, and playfield as
+///   `const _: &str = "..."`s every time the PC lands on the cell in column `x` and row `y` of one
+///   of the `[x y]`s. With `socket_debug_default`, the playfield is sent to `befunge.debug`.
+///   Outputting the playfield needs a few thousand more levels of recursion.
+/// - `[random cycle dir ..]` or `[random once dir ..]`: Make `?` go in each of the directions
+///   (`left`, `right`, `up`, or `down`) in turn instead of picking one at random. With `cycle`,
+///   the list starts again from the beginning once it runs out, and with `once`, running out is an
+///   error.
+/// - `[heatmap]`: Count how many times the PC lands on each cell and output the playfield as
+///   `const _: &str = "..."`s at the end (hitting a `@` instruction), with each cell visited shown
+///   as one of `.:*#@` from the fewest visits to the most. Every visit is kept until then, so this
+///   slows down the build and needs a few more levels of recursion for each move; leave it off
+///   unless the heatmap is wanted.
+/// - `[stepcount]`: Count how many times the PC lands on a cell (every run of an instruction
+///   repeated by `k` included) and output `const _: &str = "Executed N steps"` at the end
+///   (hitting a `@` instruction).
 /// - `[noflush]`: Don't request interface programs to flush output on exit (hitting `@`
 ///   instruction).
+/// - `[collecterrors]`: Don't stop at the first unknown instruction. Each one is collected and then
+///   treated as a space, and all of them are reported together in one error at the end (hitting a
+///   `@` instruction). Coming back to an unknown instruction that was already collected reports
+///   them straight away instead, since the program would likely go around the same way forever.
+/// - `[tag "name"]`: Prefix every `const _: &str = "..."` and error message the program outputs
+///   with `[name] `, so that output from several programs in the same module can be told apart.
+/// - `[stepdelay N]`: Sleep for `N` milliseconds after every move, to slow the program down
+///   enough to watch. This only slows down the build.
+/// - `[b98]`: Recognise the Funge-98 instructions that have been implemented so far (`[`, `]`, `j`,
+///   `k`, `'`, `s`, `n`, `r`, and `;`), rather than treating them as unknown.
+/// - `[result name]`: Hand the stack to `name!` on exit (hitting a `@` instruction), as
+///   `stack: [..],` with each number on it as a literal, the top of the stack first.
+/// - `[onunknown skip]` or `[onunknown reflect]`: Treat unknown instructions as spaces, or turn
+///   the PC around at them (like `r`), instead of reporting them as errors.
+/// - `[compact]`: Store each run of two or more spaces in a row as one `[sp [[] ..]]`, and move
+///   the PC right across a run in one go, without landing on the spaces in between.
+/// - `[divmode floor]`: Round the quotients of `/` down and give the remainders of `%` the sign of
+///   the divisor, instead of truncating towards zero (see [`arith_div!`]).
+///
+/// `[all]`, `[io]`, and `[trace]` can be given in place of groups of these flags (see
+/// [`dbg_expand_groups!`]). `[all]` is every flag that only outputs something, so
+/// `debug: [[noflush] [all]]` turns on all of the output without changing what the program does.
+///
+/// Any other flag fails the build, so that a misspelled one doesn't just go unnoticed.
+///
+/// `start: [x y],` and `direction: dir,` lines may be given after `filecontents:` to start the PC
+/// in column `x` of row `y` going `left`, `right`, `up`, or `down`, rather than in the top left
+/// corner going right. A `sockets: "prefix",` line may be given before `debug:` to use
+/// `prefix.output`, `prefix.input`, and `prefix.debug` rather than the default `befunge.*` sockets.
 ///
 /// Additionally, this program may be compiled with the `socket_debug_default` feature, in which
 /// case it will expect a `befunge-if` process to be listening on `befunge.debug` to display
-/// debugging output.
+/// debugging output, unless it's also compiled with the `debug_fallback` feature.
 #[macro_export]
 macro_rules! befunge_init {
+    (
+        @init
+        filecontents: $filecontents:tt$(,)?
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_init! {
+            @init
+            filecontents: $filecontents,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            sockets: "befunge",
+            debug: $debug,
+        }
+    };
+    (
+        @init
+        filecontents: $filecontents:tt$(,)?
+        $(direction: $direction:ident,)?
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_init! {
+            @init
+            filecontents: $filecontents,
+            start: [0 0],
+            $(direction: $direction,)?
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @init
+        filecontents: $filecontents:tt$(,)?
+        start: $start:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_init! {
+            @init
+            filecontents: $filecontents,
+            start: $start,
+            direction: right,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
     (
         @init
         filecontents: [$($input:tt)*]$(,)?
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_expand_groups! {
+            debug: $debug,
+            callback: [
+                name: $crate::befunge_init,
+                pre: [
+                    @init
+                    @expanded
+                    filecontents: [$($input)*],
+                    start: $start,
+                    direction: $direction,
+                    sockets: $sockets,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @init
+        @expanded
+        filecontents: [$($input:tt)*],
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_validate_flags! {
+            @validate
+            debug: $debug,
+        }
+        // Every row starts out as 80 spaces, with the PC on the first one.
+        $crate::list_repeat! {
+            @repeat
+            item: [' '],
+            times: 79,
+            callback: [
+                name: $crate::befunge_init,
+                pre: [
+                    @init
+                    @blank
+                    filecontents: [$($input)*],
+                    start: $start,
+                    direction: $direction,
+                    sockets: $sockets,
+                ],
+                pst: [
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    (
+        @init
+        @blank
+        filecontents: $input:tt,
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        list: [$($blank:tt)*],
+        debug: $debug:tt,
+    ) => {
+        $crate::list_repeat! {
+            @repeat
+            item: [[' ' $($blank)*]],
+            times: 24,
+            callback: [
+                name: $crate::befunge_init,
+                pre: [
+                    @init
+                    @blankrows
+                    filecontents: $input,
+                    start: $start,
+                    direction: $direction,
+                    sockets: $sockets,
+                    blank: [$($blank)*],
+                ],
+                pst: [
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    (
+        @init
+        @blankrows
+        filecontents: $input:tt,
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        blank: $blank:tt,
+        list: $rows:tt,
         debug: $debug:tt,
     ) => {
         $crate::befunge_init! {
             @lines
-            input: [$($input)*],
+            input: $input,
             pre: [],
             cur: [
                 pre: [],
                 cur: [' '],
-                pst: [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' '],
-            ],
-            pst: [
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
-                [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ']
+                pst: $blank,
             ],
+            pst: $rows,
+            start: $start,
+            direction: $direction,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -86,10 +269,13 @@ macro_rules! befunge_init {
         pre: [$($pre:tt)*],
         cur: [
             pre: [$($cpre:tt)*],
-            cur: [$ccur:tt],
+            cur: [$($ccur:tt)?],
             pst: [$($cpst:tt)*],
         ],
         pst: [$($pst:tt)*],
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -97,18 +283,70 @@ macro_rules! befunge_init {
             debug: $debug,
             lookfor: [[postinit]],
             expand: [
-                const _: &str = "Successfully tokenised input! Result:";
-                $crate::befunge_stringify! {
-                    @stringify @raw
-                    lines: [$($pre)* [$($cpre)* $ccur $($cpst)*] $($pst)*],
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: ["Successfully tokenised input! Result:"],
+                }
+                $crate::socket_debug_stringify! {
+                    sockets: $sockets,
+                    mode: [@stringify @raw @joined],
+                    lines: [$($pre)* [$($cpre)* $($ccur)? $($cpst)*] $($pst)*],
+                    debug: $debug,
+                }
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: ["Now expanding to run program..."],
                 }
-                const _: &str = "Now expanding to run program...";
             ],
         }
         // expand to steps
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[compact]],
+            expand: [
+                $crate::befunge_pm::compact_rows! {
+                    rows: [$($pre)* [$($cpre)* $($ccur)? $($cpst)*] $($pst)*],
+                    callback: [
+                        name: $crate::befunge_init,
+                        pre: [@compacted],
+                        pst: [
+                            start: $start,
+                            direction: $direction,
+                            sockets: $sockets,
+                            debug: $debug,
+                        ],
+                    ],
+                }
+            ],
+            orelse: [
+                $crate::befunge_step! {
+                    @init
+                    program: [$($pre)* [$($cpre)* $($ccur)? $($cpst)*] $($pst)*],
+                    start: $start,
+                    direction: $direction,
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
+        }
+    };
+    (
+        @compacted
+        rows: $rows:tt,
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
         $crate::befunge_step! {
             @init
-            program: [$($pre)* [$($cpre)* $ccur $($cpst)*] $($pst)*],
+            program: $rows,
+            start: $start,
+            direction: $direction,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -119,15 +357,18 @@ macro_rules! befunge_init {
         pre: [$($pre:tt)*],
         cur: [
             pre: [$($cpre:tt)*],
-            cur: [$ccur:tt],
+            cur: [$($ccur:tt)?],
             pst: [$($cpst:tt)*],
         ],
         pst: [],
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::befunge_error! {
             @initerr @rows
-            program: [$($pre)* [$($cpre)* $ccur $($cpst)*]],
+            program: [$($pre)* [$($cpre)* $($ccur)? $($cpst)*]],
             left: [$($rest)+],
         }
     };
@@ -138,10 +379,13 @@ macro_rules! befunge_init {
         pre: [$($pre:tt)*],
         cur: [
             pre: [$($cpre:tt)*],
-            cur: [$ccur:tt],
+            cur: [$($ccur:tt)?],
             pst: [$($cpst:tt)*],
         ],
         pst: [[$psthh:tt $($pstht:tt)*] $($pstt:tt)*],
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -149,23 +393,31 @@ macro_rules! befunge_init {
             debug: $debug,
             lookfor: [[initlines]],
             expand: [
-                const _: &str = "Successfully read program line. Result:";
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: ["Successfully read program line. Result:"],
+                }
                 $crate::befunge_stringify! {
                     @stringify @raw
-                    lines: [[$($cpre)* $ccur $($cpst)*]],
+                    lines: [[$($cpre)* $($ccur)? $($cpst)*]],
+                    debug: $debug,
                 }
             ],
         }
         $crate::befunge_init! {
             @lines
             input: [$($rest)*],
-            pre: [$($pre)* [$($cpre)* $ccur $($cpst)*]],
+            pre: [$($pre)* [$($cpre)* $($ccur)? $($cpst)*]],
             cur: [
                 pre: [],
                 cur: [$psthh],
                 pst: [$($pstht)*],
             ],
             pst: [$($pstt)*],
+            start: $start,
+            direction: $direction,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -176,10 +428,13 @@ macro_rules! befunge_init {
         pre: $pre:tt,
         cur: [
             pre: [$($cpre:tt)*],
-            cur: [$ccur:tt],
+            cur: [$($ccur:tt)?],
             pst: [$($cpst:tt)*],
         ],
         pst: $pst:tt,
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -187,10 +442,15 @@ macro_rules! befunge_init {
             debug: $debug,
             lookfor: [[initlines]],
             expand: [
-                const _: &str = "Successfully read program line. Result:";
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: ["Successfully read program line. Result:"],
+                }
                 $crate::befunge_stringify! {
                     @stringify @raw
-                    lines: [[$($cpre)* $ccur $($cpst)*]],
+                    lines: [[$($cpre)* $($ccur)? $($cpst)*]],
+                    debug: $debug,
                 }
             ],
         }
@@ -200,10 +460,46 @@ macro_rules! befunge_init {
             pre: $pre,
             cur: [
                 pre: [$($cpre)*],
-                cur: [$ccur],
+                cur: [$($ccur)?],
                 pst: [$($cpst)*],
             ],
             pst: $pst,
+            start: $start,
+            direction: $direction,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // HEAD = _: last column. There's no blank cell after it to move on to, so `cur` is left empty
+    // until the line ends.
+    (
+        @lines
+        input: [$ihead:tt $($irest:tt)*],
+        pre: $pre:tt,
+        cur: [
+            pre: [$($cpre:tt)*],
+            cur: [$ccur:tt],
+            pst: [],
+        ],
+        pst: $pst:tt,
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_init! {
+            @lines
+            input: [$($irest)*],
+            pre: $pre,
+            cur: [
+                pre: [$($cpre)* $ihead],
+                cur: [],
+                pst: [],
+            ],
+            pst: $pst,
+            start: $start,
+            direction: $direction,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -214,15 +510,18 @@ macro_rules! befunge_init {
         pre: [$($pre:tt)*],
         cur: [
             pre: [$($cpre:tt)*],
-            cur: [$ccur:tt],
+            cur: [],
             pst: [],
         ],
         pst: [$($pst:tt)*],
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::befunge_error! {
             @initerr @cols
-            program: [$($pre)* [$($cpre)* $ccur] $($pst)*],
+            program: [$($pre)* [$($cpre)*] $($pst)*],
             left: [$ihead $($irest:tt)*],
         }
     };
@@ -237,6 +536,9 @@ macro_rules! befunge_init {
             pst: [$cpst_head:tt $($cpst_tail:tt)*],
         ],
         pst: $pst:tt,
+        start: $start:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::befunge_init! {
@@ -249,6 +551,9 @@ macro_rules! befunge_init {
                 pst: [$($cpst_tail)*],
             ],
             pst: $pst,
+            start: $start,
+            direction: $direction,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -15,24 +15,40 @@
 //!   token from the input tokens.
 //! - [`befunge_pm::print_integer!`]: outputs an integer on the output socket.
 //! - [`befunge_pm::print_ascii!`]: outputs an ASCII character on the output socket.
+//! - [`befunge_pm::print_string!`]: outputs a string of ASCII characters on the output socket in
+//!   a single round trip.
 //! - [`befunge_pm::get_integer!`]: asks for user input of a single digit integer.
 //! - [`befunge_pm::get_ascii!`]: asks for user input of a single ASCII character.
+//! - [`befunge_pm::get_string!`]: asks for user input of a line of ASCII characters.
 //! - [`befunge_pm::close_ui!`]: sends a signal to the input and output (and debug, if
 //!   `socket_debug_default` is enabled) interfaces to close.
 //! - [`befunge_pm::flush_output!`]: sends a signal to the output interface to flush its output
 //!   buffer.
 //! - [`befunge_pm::befunge_input!`]: reads a file and makes a callback with the file contents
 //!   as a space-separated list of character literals.
+//! - [`befunge_pm::sleep!`]: used to slow programs down when they're given `step_delay_ms:`.
+//! - [`befunge_pm::compact_rows!`]: stores runs of spaces in the playfield compactly when programs
+//!   are given `compact: true`.
+//! - [`befunge_pm::split_row!`]: splits a row of the playfield wherever the PC, `g`, or `p` needs
+//!   it split, with or without runs of spaces in it.
 //!
 //! On that note, when running Befunge programs with _any_ input or output (from the `/`, `%`, `.`,
 //! `,`, `&`, `?`, or `~` instructions), you must be running `befunge-if` on the corresponding
-//! socket (either `befunge.output` or `befunge.input`).
+//! socket (either `befunge.output` or `befunge.input`). The exception is when this crate is built
+//! with the `headless` feature, in which case nothing is connected to at all: output is thrown
+//! away, division and modulus by 0 give 0, `&` gives the value of `BEFUNGE_HEADLESS_INTEGER` (or
+//! 0), and `~` gives the value of `BEFUNGE_HEADLESS_ASCII` (or a newline). That's handy for
+//! checking that a program runs to the end with `cargo check` when its output doesn't matter.
 //!
 //! Running programs - which is done simply by building the program with `cargo check`,
 //! `cargo build`, or `cargo expand` - requires the `#![feature(macro_metavar_expr)]` feature, and
 //! typically also requires a higher-than-normal `#![recursion_limit = "..."]`. For some programs it
 //! may also be necessary to provide `RUST_MIN_STACK=A_BIG_NUMBER`.
 //!
+//! The interpreter is built on two sets of helper macros that can also be used by themselves:
+//! [`arith`] for arithmetic on base 1 numbers, and [`list`] for getting, setting, splitting, and
+//! reversing lists of token trees.
+//!
 //! For information on Befunge, it is recommended to refer to the following resources:
 //!
 //! - [The Befunge 93 specification](https://github.com/catseye/Befunge-93/blob/master/doc/Befunge-93.markdown)
@@ -48,7 +64,11 @@ mod error;
 #[macro_use]
 mod init;
 #[macro_use]
-mod list;
+pub mod list;
+#[macro_use]
+mod observe;
+#[macro_use]
+mod profile;
 #[macro_use]
 mod step;
 #[macro_use]
@@ -64,21 +84,21 @@ pub use befunge_pm;
 /// #![feature(macro_metavar_expr)]
 ///
 /// befunge_dm::befunge! {
-///     "example.bfg"
+///     "befunge-dm/examples/example.bfg"
 /// }
 /// ```
 /// ```ignore
 /// #![feature(macro_metavar_expr)]
 ///
 /// befunge_dm::befunge! {
-///     file: "example.bfg"
+///     file: "befunge-dm/examples/example.bfg"
 /// }
 /// ```
 /// ```ignore
 /// #![feature(macro_metavar_expr)]
 ///
 /// befunge_dm::befunge! {
-///     file: "example.bfg",
+///     file: "befunge-dm/examples/example.bfg",
 /// }
 /// ```
 /// ```
@@ -86,62 +106,1823 @@ pub use befunge_pm;
 /// #![feature(macro_metavar_expr)]
 ///
 /// befunge_dm::befunge! {
-///     file: "example.bfg",
+///     file: "befunge-dm/examples/example.bfg",
 ///     debug: [[noflush]],
 /// }
 /// ```
-/// For purposes of the above doctest, `example.bfg` contains the following:
+/// For purposes of the above doctest, `befunge-dm/examples/example.bfg` contains the following:
 /// ```befunge
-#[doc = include_str!("../../example.bfg")]
+#[doc = include_str!(concat!(env!("OUT_DIR"), "/example.bfg"))]
 /// ```
 /// The first three examples here are untested because any Befunge program that makes use of
 /// instructions that can produce output or require input (the `/`, `%`, `.`, `,`, `&`, or `~`
 /// instructions) requires that `befunge-if` be running on the `befunge.output` and `befunge.input`
 /// sockets respectively. Additionally, this crate can be compiled with the `socket_debug_default`
 /// feature, which will output debugging information on `befunge.debug` (and thus require another
-/// `befunge-if` process running on that socket).
+/// `befunge-if` process running on that socket). With the `debug_fallback` feature as well, the
+/// messages are output as `const _: &str = "..."`s if nothing is listening there, rather than
+/// failing the build.
 ///
 /// As you can see in the example, this program can accept debugging flags! Here are the recognised
 /// flags:
 ///
 /// - `[initlines]`: Output a `const _: &str = "..."` with the contents of each line of the
 ///   program as it is read in.
-/// - `[postinit]`: Output a `const _: &str = "..."` with the contents of the program memory
-///   once the whole program has been read in.
+/// - `[postinit]`: Output a single `const _: &str = "..."` with the contents of the program
+///   memory, one row per line, once the whole program has been read in. With the
+///   `socket_debug_default` feature, it's sent to `befunge.debug` instead.
 /// - `[getdbg]`: Output `const _: &str = "..."`s as the program performs `g` instructions.
-/// - `[putdbg]`: Output `const _: &str = "..."`s as the program performs `p` instructions.
+/// - `[putdbg]`: Output `const _: &str = "..."`s as the program performs `p` instructions. With
+///   `socket_debug_default`, the program memory after each one is sent to `befunge.debug` instead.
 /// - `[closeonend]`: Send a signal to the I/O programs to close when the program exits (hits a
 ///   `@` instruction).
-/// - `[poststack]`: Output the contents of the stack on exit (hitting a `@` instruction).
+/// - `[poststack]`: Output the contents of the stack on exit (hitting a `@` instruction), one
+///   `const _: &str = "..."` for each value, top first. Values with a printable character are
+///   shown as e.g. `72 ('H')`, and the rest as just the number.
+/// - `[postprog]`: Output a `const _: &str = "..."` with the contents of each row of the program
+///   memory on exit (hitting a `@` instruction), after anything `p` has changed.
+/// - `[underflow]`: Output a `const _: &str = "..."` naming the instruction and its coordinates
+///   whenever an instruction pops from an empty stack. The 0 it gets instead is the same either
+///   way.
+/// - `[traceinstr]`: Output a `const _: &str = "exec 'X' at (row, col)"` for every instruction as
+///   it's executed. Spaces and the cells skipped over by `#` or read in stringmode aren't
+///   executed, so they don't show up. This is easiest to follow with `cargo expand`.
+/// - `[bridgedbg]`: Output a `const _: &str = "bridge: skipped 'X' at (row, col)"` for every cell
+///   a `#` jumps over, wrapping around the edge of the playfield included.
+/// - `[profile]`: Count the instructions executed, by kind (arithmetic, movement, and so on), and
+///   output the counts as `const _: &str = "..."`s at the end (hitting a `@` instruction). Each
+///   instruction is counted every time it runs, so this points at the loops that use up the
+///   recursion limit, though counting needs a few more levels of recursion for each instruction.
+/// - `[breakpoints [x y] ..]`: Output the direction, stringmode, bridge, stack, and playfield as
+///   `const _: &str = "..."`s every time the PC lands on the cell in column `x` and row `y` of one
+///   of the `[x y]`s. The playfield is framed with `@stringify @framed`, with a `#` on the PC (see
+///   [`befunge_stringify!`]). With `socket_debug_default`, the playfield is sent to
+///   `befunge.debug` unframed instead. See `breakpoints:` below.
+/// - `[random cycle dir ..]` or `[random once dir ..]`: Make `?` go in each of the directions
+///   (`left`, `right`, `up`, or `down`) in turn instead of picking one at random. With `cycle`,
+///   the list starts again from the beginning once it runs out. See `random:` below.
+/// - `[heatmap]`: Count how many times the PC lands on each cell and output the playfield as
+///   `const _: &str = "..."`s at the end (hitting a `@` instruction), with each cell visited shown
+///   as one of `.:*#@` from the fewest visits to the most. Every visit is kept until then, so this
+///   slows down the build and needs a few more levels of recursion for each move; leave it off
+///   unless the heatmap is wanted.
+/// - `[stepcount]`: Count how many times the PC lands on a cell (every run of an instruction
+///   repeated by `k` included) and output `const _: &str = "Executed N steps"` at the end
+///   (hitting a `@` instruction).
 /// - `[noflush]`: Don't request interface programs to flush output on exit (hitting `@`
 ///   instruction).
+/// - `[collecterrors]`: Don't stop at the first unknown instruction. Each one is collected and then
+///   treated as a space, and all of them are reported together in one error at the end (hitting a
+///   `@` instruction). Coming back to an unknown instruction that was already collected reports
+///   them straight away instead, since the program would likely go around the same way forever.
+/// - `[tag "name"]`: Prefix every `const _: &str = "..."` and error message the program outputs
+///   with `[name] `, so that output from several programs in the same module can be told apart.
+/// - `[stepdelay N]`: Sleep for `N` milliseconds after every move. See `step_delay_ms:` below.
+/// - `[b98]`: Recognise the Funge-98 instructions that have been implemented so far, rather than
+///   treating them as unknown. See `dialect:` below.
+/// - `[result name]`: Hand the stack to `name!` on exit (hitting a `@` instruction). See `result:`
+///   below.
+/// - `[onunknown skip]` or `[onunknown reflect]`: Treat unknown instructions as spaces, or turn
+///   the PC around at them, instead of failing the build. See `on_unknown:` below.
+/// - `[compact]`: Store runs of spaces in the playfield as single token trees, and move the PC
+///   across them in one go. See `compact:` below.
+/// - `[divmode floor]`: Round quotients down for `/` and `%` instead of towards zero. See
+///   `divmode:` below.
+/// - `[all]`: Every flag above that only outputs something, which leaves out `[noflush]`,
+///   `[closeonend]`, `[collecterrors]`, `[b98]`, and the ones that take something after the name.
+/// - `[io]`: `[getdbg]` and `[putdbg]`.
+/// - `[trace]`: `[traceinstr]`, `[tracemove]`, `[bridgedbg]`, and `[underflow]`.
 ///
-/// Debugging flags should be given as a space-separated list.
-macro_rules! befunge {
-    ($(file: )?$file:literal$(,)?) => {
-        const _: &str = concat!("Using Befunge file: '", $file, "'");
-        $crate::befunge_pm::befunge_input! {
-            file: $file,
-            callback: [
-                name: $crate::befunge_init,
-                pre: [@init],
-                pst: [
-                    debug: [],
-                ],
-            ],
-        }
-    };
-    (
-        file: $file:literal,
-        debug: $debug:tt,
-    ) => {
-        const _: &str = concat!("Using Befunge file: '", $file, "'");
+/// The last three are groups, and are replaced with the flags in them before anything else looks
+/// at the list (see [`dbg_expand_groups!`]), so they can be mixed with other flags:
+/// ```
+/// #![recursion_limit = "8192"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/example.bfg",
+///     debug: [[noflush] [all]],
+/// }
+/// ```
+///
+/// Debugging flags should be given as a space-separated list. Anything else in the list, such as a
+/// misspelled flag, fails the build (see [`dbg_validate_flags!`]):
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/example.bfg",
+///     debug: [[noflush] [postack]],
+/// }
+/// ```
+///
+/// The sockets used for I/O can be renamed with `sockets: "prefix"`, given before `debug:`, in
+/// which case the program talks to `prefix.output`, `prefix.input`, and `prefix.debug` instead of
+/// `befunge.output`, `befunge.input`, and `befunge.debug`. This lets two crates (or two programs
+/// in one crate) be built at the same time without fighting over the same sockets:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/example.bfg",
+///     sockets: "first",
+///     debug: [[noflush]],
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/example.bfg",
+///     sockets: "second",
+///     debug: [[noflush]],
+/// }
+/// ```
+/// `befunge-if` then just needs to be started with `--socket prefix.output` and so on.
+///
+/// Built with the `headless` feature, programs that do input and output run without `befunge-if`
+/// at all. `befunge-dm/examples/io.bfg` reads an integer and a character, prints them along with
+/// `hi`, and divides and takes the modulus by 0:
+/// ```befunge
+#[doc = include_str!("../examples/io.bfg")]
+/// ```
+#[cfg_attr(feature = "headless", doc = "```")]
+#[cfg_attr(not(feature = "headless"), doc = "```ignore")]
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/io.bfg",
+///     debug: [[closeonend]],
+/// }
+/// ```
+///
+/// Programs run as fast as the compiler can expand them, which is too fast to watch anything they
+/// draw over the output socket. Giving `step_delay_ms: N` after `sockets:` (if that's given) makes
+/// the interpreter sleep for `N` milliseconds after every move. It's the same as giving the
+/// `[stepdelay N]` debugging flag. This only slows down the build, where the program is actually
+/// run; it has no effect on the built crate, and it's off unless asked for since it slows every
+/// build down:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/example.bfg",
+///     sockets: "paced",
+///     step_delay_ms: 1,
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// Programs are run as Befunge-93 unless they're given `dialect: b98` after `step_delay_ms:` (if
+/// that's given), which recognises the Funge-98 instructions that have been implemented so far.
+/// It's the same as giving the `[b98]` debugging flag, and `dialect: b93` can be given to say so
+/// explicitly. So far that's:
+///
+/// - `[` and `]`, which turn the PC left and right respectively.
+/// - `j`, which pops `n` and jumps over the next `n` cells (or back over `-n` of them if `n` is
+///   negative).
+/// - `k`, which pops `n` and runs the next instruction (past any spaces) `n` times where it is, and
+///   then carries on past it. If `n` is 0 it's skipped entirely.
+/// - `'`, which pushes the value of the next cell and skips over it.
+/// - `s`, which pops a value, writes it into the next cell, and skips over it.
+/// - `n`, which clears the stack.
+/// - `r`, which turns the PC around.
+/// - `;`, which skips everything up to and including the next `;` (even a `"`) without running it.
+///
+/// `befunge-dm/examples/spiral.bfg` spirals in to the `@` in the middle using only `[` (the first
+/// one wraps around to the bottom of the playfield):
+/// ```befunge
+#[doc = include_str!("../examples/spiral.bfg")]
+/// ```
+/// ```
+/// #![recursion_limit = "2048"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/spiral.bfg",
+///     dialect: b98,
+///     debug: [[noflush]],
+/// }
+/// ```
+/// As Befunge-93, the first `[` is an unknown instruction:
+/// ```compile_fail
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/spiral.bfg",
+///     dialect: b93,
+///     debug: [[noflush]],
+/// }
+/// ```
+/// Jumps wrap around the edges of the playfield like any other move. In
+/// `befunge-dm/tests/fixtures/jump_wrap.bfg`, the `j` at the right edge of the first row jumps over
+/// the `X` after it and the `v` and `X` at the start of the row, landing on the `@`:
+/// ```
+/// #![recursion_limit = "2048"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/jump_wrap.bfg",
+///     dialect: b98,
+///     debug: [[noflush]],
+/// }
+/// ```
+/// Jumping by 0 runs the next cell as usual, and jumping by -2 here lands on the `#` before the
+/// `@` it bridged over:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "0j@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [b98]],
+///         ],
+///     ],
+/// }
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "02-#@j",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [b98]],
+///         ],
+///     ],
+/// }
+/// ```
+/// Here, `2k:` duplicates the `1` twice, so adding up the three `1`s and taking away 3 leaves 0 on
+/// top of the 9. Any other number of `1`s ends up at one of the `X`s. After that, `1k` runs the `>`
+/// below it (past the space) to turn right onto the `@`, and `0k` skips the `X` after it:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "912k:++3-v
+///         X_9-v
+///            X_v
+///              1
+///              k
+///
+///              >0kX@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [b98]],
+///         ],
+///     ],
+/// }
+/// ```
+/// `'A,@` prints `A`. As with the other programs that do output, this needs `befunge-if` running
+/// unless the crate is built with the `headless` feature:
+#[cfg_attr(feature = "headless", doc = "```")]
+#[cfg_attr(not(feature = "headless"), doc = "```ignore")]
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "'A,@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [b98]],
+///         ],
+///     ],
+/// }
+/// ```
+/// `'@sX<` writes a `@` over the `X` and skips it, and then the `<` turns back around onto it:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "'@sX<",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [b98]],
+///         ],
+///     ],
+/// }
+/// ```
+/// `n` does nothing to an empty stack, and after `12n` the `_` finds the stack empty and goes right
+/// onto the `@`. Then `>#@r` would go round forever without the `r`, which turns around onto the
+/// `@` that the `#` jumps over:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "n12n_@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [b98]],
+///         ],
+///     ],
+/// }
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: ">#@r",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [b98]],
+///         ],
+///     ],
+/// }
+/// ```
+/// `;X"Y&~;@` skips straight over the junk between the `;`s to the `@`, and `0k` skips comments
+/// while looking for the instruction to skip, so `0k;X;X@` skips the second `X` rather than the
+/// `;`:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: ";X\"Y&~;@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [b98]],
+///         ],
+///     ],
+/// }
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "0k;X;X@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [b98]],
+///         ],
+///     ],
+/// }
+/// ```
+/// Comments wrap around too. In `befunge-dm/tests/fixtures/comment_wrap.bfg`, the `;` after the
+/// `>` comments out the rest of the second row and the start of it up to the `;` before the `@`:
+/// ```
+/// #![recursion_limit = "2048"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/comment_wrap.bfg",
+///     dialect: b98,
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// Giving `breakpoints: [[x y] ..]` after `dialect:` (if that's given) stops the interpreter to
+/// look around every time the PC lands on one of the cells listed, where `x` is the column and `y`
+/// is the row, both counting from 0. It doesn't actually stop, but it outputs the direction,
+/// stringmode, bridge, stack, and playfield as `const _: &str = "..."`s before carrying on. It's
+/// the same as giving the `[breakpoints [x y] ..]` debugging flag. Outputting the whole playfield
+/// takes a few thousand more levels of recursion. `befunge-dm/tests/fixtures/countdown.bfg` counts
+/// down from 10 in a loop, so a breakpoint on the `1` at the start of the loop is hit ten times,
+/// with 10, 9, and so on down to 1 on top of the stack:
+/// ```
+/// #![recursion_limit = "8192"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "Breakpoint at (0, 4):";`, then
+/// // `const _: &str = "dir: right, stringmode: false, bridge: false";`, the stack, and the
+/// // framed playfield with a `#` in place of the `1`, once for each time around the loop.
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/countdown.bfg",
+///     breakpoints: [[4 0]],
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// The direction `?` goes in is picked at random every time, which makes it hard to test programs
+/// that use it. Giving `random: [dir ..]` after `breakpoints:` (if that's given) makes `?` go in
+/// each of the directions listed in turn instead, each one being `left`, `right`, `up`, or `down`.
+/// Once they've all been used, it starts again from the first one, unless the list is followed by
+/// `once` (rather than `cycle`), in which case running out is an error. It's the same as giving the
+/// `[random cycle dir ..]` or `[random once dir ..]` debugging flag. In `example.bfg`, going up
+/// from the `?` ends the program at the `@` in column 7:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "exec '@' at (0, 7)";` last.
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/example.bfg",
+///     random: [up],
+///     debug: [[noflush] [traceinstr]],
+/// }
+/// ```
+/// The program only gets to the `?` once, so it can't run out of one direction, but it does run
+/// out of none:
+/// ```compile_fail
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/example.bfg",
+///     random: [] once,
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// Programs start in the top left corner, going right. Giving `start: [x y]` right after `file:`
+/// starts the PC in column `x` of row `y` instead, both counting from 0, and giving
+/// `direction: dir` after that (or right after `file:`) starts it going `left`, `right`, `up`, or
+/// `down`. `befunge-dm/tests/fixtures/start.bfg` goes around in circles forever from the corner,
+/// but going down from the space under the `X` gets to the `@` straight away:
+/// ```befunge
+#[doc = include_str!("../tests/fixtures/start.bfg")]
+/// ```
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/start.bfg",
+///     start: [4 1],
+///     direction: down,
+///     debug: [[noflush]],
+/// }
+/// ```
+/// ```compile_fail
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/start.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+/// Starting outside of the 80 by 25 playfield is an error:
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/start.bfg",
+///     start: [80 0],
+///     debug: [[noflush]],
+/// }
+/// ```
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/start.bfg",
+///     start: [0 -1],
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// A `#` on the edge of the playfield jumps over the cell on the other side, like any other move
+/// wraps around. In `befunge-dm/tests/fixtures/bridge_wrap.bfg`, starting on each of the `#`s
+/// going off the edge it's on jumps over the `X` on the other side, onto the `@` after it:
+/// ```befunge
+#[doc = include_str!("../tests/fixtures/bridge_wrap.bfg")]
+/// ```
+/// ```
+/// #![recursion_limit = "4096"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Outputs `const _: &str = "bridge: skipped 'X' at (0, 0)";`
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/bridge_wrap.bfg",
+///     start: [79 0],
+///     direction: right,
+///     debug: [[noflush] [bridgedbg]],
+/// }
+/// ```
+/// ```
+/// #![recursion_limit = "4096"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/bridge_wrap.bfg",
+///     start: [0 1],
+///     direction: left,
+///     debug: [[noflush]],
+/// }
+/// ```
+/// ```
+/// #![recursion_limit = "4096"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/bridge_wrap.bfg",
+///     start: [4 24],
+///     direction: down,
+///     debug: [[noflush]],
+/// }
+/// ```
+/// ```
+/// #![recursion_limit = "4096"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/bridge_wrap.bfg",
+///     start: [6 0],
+///     direction: up,
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// Giving `result: name` after `random:` (if that's given) hands the stack to the macro `name!` when
+/// the program ends, as `stack: [..],` with each number on it as a literal, the top of the stack
+/// first. It's the same as giving the `[result name]` debugging flag. That way the program's
+/// answer can be used by the rest of the crate, rather than just printed, or checked while building
+/// with [`dbg_assert_stack!`].
+/// `befunge-dm/tests/fixtures/factorial.bfg` works out 5! and leaves it on the stack:
+/// ```befunge
+#[doc = include_str!("../tests/fixtures/factorial.bfg")]
+/// ```
+/// ```
+/// #![recursion_limit = "1024"]
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! factorial {
+///     (stack: [$n:literal],) => {
+///         const FACTORIAL: isize = $n;
+///     };
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/factorial.bfg",
+///     result: factorial,
+///     debug: [[noflush]],
+/// }
+///
+/// assert_eq!(FACTORIAL, 120);
+/// ```
+///
+/// An unknown instruction fails the build, which is what `on_unknown: error` does. Giving
+/// `on_unknown: skip` after `result:` (if that's given) treats unknown instructions as spaces
+/// instead, and `on_unknown: reflect` turns the PC around at them, like Funge-98 does. They're the
+/// same as giving the `[onunknown skip]` and `[onunknown reflect]` debugging flags.
+/// `befunge-dm/tests/fixtures/stray.bfg` has a stray `Q` in it. Going past it pushes 2 before
+/// getting to the `@` at the end, while going back from it pushes another 1 before getting to the
+/// `@` at the start:
+/// ```befunge
+#[doc = include_str!("../tests/fixtures/stray.bfg")]
+/// ```
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! skipped {
+///     (stack: [$($n:literal)*],) => {
+///         const SKIPPED: &[isize] = &[$($n),*];
+///     };
+/// }
+///
+/// macro_rules! reflected {
+///     (stack: [$($n:literal)*],) => {
+///         const REFLECTED: &[isize] = &[$($n),*];
+///     };
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/stray.bfg",
+///     result: skipped,
+///     on_unknown: skip,
+///     debug: [[noflush]],
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/stray.bfg",
+///     result: reflected,
+///     on_unknown: reflect,
+///     debug: [[noflush]],
+/// }
+///
+/// assert_eq!(SKIPPED, [2, 1]);
+/// assert_eq!(REFLECTED, [1, 1]);
+/// ```
+/// ```compile_fail
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/stray.bfg",
+///     on_unknown: error,
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// Most of a playfield is usually spaces, and the PC normally lands on each one it goes past.
+/// Giving `compact: true` after `on_unknown:` (if that's given) stores each run of two or more
+/// spaces in a row as one `[sp [[] ..]]`, with a `[]` for each space (see
+/// [`befunge_pm::compact_rows!`]), and moving right onto a run takes the PC straight to the last
+/// space in it. It's the same as giving the `[compact]` debugging flag. The spaces in between are
+/// never landed on, so they aren't counted by `[stepcount]` or `[heatmap]`, traced by
+/// `[tracemove]`, stopped at by breakpoints, or slept after with `step_delay_ms:`. In stringmode,
+/// while jumping over cells, and going any other way than right, runs are written out and crossed
+/// one space at a time as usual. `g`, `p`, and everything that outputs the playfield see exactly
+/// the same spaces either way.
+///
+/// `befunge-dm/tests/fixtures/sparse.bfg` counts down from 3, going all the way across the top
+/// and bottom rows and down the right-hand edge each time, and leaves the 0 it stops at:
+/// ```befunge
+#[doc = include_str!("../tests/fixtures/sparse.bfg")]
+/// ```
+/// ```
+/// #![recursion_limit = "2048"]
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! sparse {
+///     (stack: [$($n:literal)*],) => {
+///         const SPARSE: &[isize] = &[$($n),*];
+///     };
+/// }
+///
+/// // Expands to `const _: &str = "Executed 96 steps";`, where it's 320 steps without
+/// // `compact: true`, which needs a recursion limit of 4096.
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/sparse.bfg",
+///     result: sparse,
+///     compact: true,
+///     debug: [[noflush] [stepcount]],
+/// }
+///
+/// assert_eq!(SPARSE, [0]);
+/// ```
+///
+/// Whether it's worth it depends on the program. Taking the median of five debug builds of a crate
+/// with nothing else in it, built with the `headless` feature (and `random: [right left up down]`
+/// for `example.bfg`):
+///
+/// | Program       | Default             | `compact: true`     |
+/// |---------------|---------------------|---------------------|
+/// | `sparse.bfg`  | 320 steps, 1.52 s   | 96 steps, 0.79 s    |
+/// | `example.bfg` | 28 steps, 0.35 s    | 28 steps, 0.49 s    |
+///
+/// `example.bfg` never moves right across more than one space at a time, so it only pays for
+/// compacting the playfield.
+///
+/// `/` and `%` truncate towards zero by default, so `-7 / 2` is `-3` and `-7 % 2` is `-1`. Giving
+/// `divmode: floor` after `compact:` (if that's given) rounds quotients down instead, so that
+/// `-7 / 2` is `-4` and the remainder takes the sign of the divisor, making `-7 % 2` come out as
+/// `1` (see [`arith`]). It's the same as giving the `[divmode floor]` debugging flag, and
+/// `divmode: trunc` is the same as leaving it out. `befunge-dm/tests/fixtures/divmode.bfg` works
+/// out `-7 / 2`, `-7 % 2`, `7 / -2`, and `7 % -2`, in that order:
+/// ```befunge
+#[doc = include_str!("../tests/fixtures/divmode.bfg")]
+/// ```
+/// ```
+/// #![recursion_limit = "1024"]
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! truncated {
+///     (stack: [$($n:literal)*],) => {
+///         const TRUNCATED: &[isize] = &[$($n),*];
+///     };
+/// }
+///
+/// macro_rules! floored {
+///     (stack: [$($n:literal)*],) => {
+///         const FLOORED: &[isize] = &[$($n),*];
+///     };
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/divmode.bfg",
+///     result: truncated,
+///     divmode: trunc,
+///     debug: [[noflush]],
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/divmode.bfg",
+///     result: floored,
+///     divmode: floor,
+///     debug: [[noflush]],
+/// }
+///
+/// // The top of the stack comes first.
+/// assert_eq!(TRUNCATED, [1, -3, -1, -3]);
+/// assert_eq!(FLOORED, [-1, -4, 1, -4]);
+/// ```
+///
+/// With `[underflow]`, `:+\$@` warns about the `:` (which duplicates the 0 it gets for the empty
+/// stack) and the `\` (which only has the one 0 left by `+` to swap), but not about the `+` or the
+/// `$`. `p@` warns about the `p`, which puts a 0 at (0, 0) with nothing at all to pop:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "Stack underflow: ':' at (0, 0)";` and
+/// // `const _: &str = "Stack underflow: '\\\\' at (0, 2)";`.
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: ":+\\$@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [underflow]],
+///         ],
+///     ],
+/// }
+///
+/// mod put {
+///     // Expands to `const _: &str = "Stack underflow: 'p' at (0, 0)";`.
+///     befunge_dm::befunge_pm::befunge_source! {
+///         source: "p@",
+///         callback: [
+///             name: befunge_dm::befunge_init,
+///             pre: [@init],
+///             pst: [
+///                 debug: [[noflush] [underflow]],
+///             ],
+///         ],
+///     }
+/// }
+/// ```
+///
+/// `p` instructions are reported with `[putdbg]`, even when they're abandoned. Here, `1001-p@`
+/// tries to put a 1 at column 0 of row -1 (`befunge-dm/tests/expand.rs` checks what each of these
+/// outputs):
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "Y index was out of bounds! Abandoning put attempt.";`.
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "1001-p@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [putdbg]],
+///         ],
+///     ],
+/// }
+///
+/// // Doesn't expand to anything about the `p`, since `[getdbg]` is only for `g` instructions.
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "1001-p@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [getdbg]],
+///         ],
+///     ],
+/// }
+/// ```
+///
+/// Coordinates past the right or bottom edge of the playfield are out of bounds too. A `p` there
+/// is abandoned in the same way and the program carries on, and a `g` there gets 0. Here
+/// `1"P"0p` tries to put a 1 in column 80 of row 0, and `"P"0g` tries to read it back:
+/// ```
+/// #![recursion_limit = "1024"]
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! outside {
+///     (stack: [$($n:literal)*],) => {
+///         const OUTSIDE: &[isize] = &[$($n),*];
+///     };
+/// }
+///
+/// // Expands to `const _: &str = "Index was out of bounds! Abandoning put attempt.";` and
+/// // `const _: &str = "Index was out of bounds! Pushed 0 to stack.";`, among others.
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "1\"P\"0p\"P\"0g@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [putdbg] [getdbg] [result outside]],
+///         ],
+///     ],
+/// }
+///
+/// assert_eq!(OUTSIDE, [0]);
+/// ```
+///
+/// With `[postprog]`, the program memory is output again at the end, so whatever a program draws
+/// with `p` can be seen. Here an `A` is put in column 2 of row 2, and the stack is output first
+/// since `[poststack]` is given too:
+/// ```
+/// #![recursion_limit = "8192"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "Stack at program '@':";`, then `"top: 7"`,
+/// // `"Program memory at program '@':"`, and a line for each row of the playfield: `"\"A\"22p7@"`,
+/// // a blank one, and `"  A"`, each padded with spaces to 80 columns, followed by 22 more blank
+/// // ones.
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "\"A\"22p7@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [poststack] [postprog]],
+///         ],
+///     ],
+/// }
+/// ```
+///
+/// Negative values and values without a printable character are output as just the number. Here
+/// the stack ends up as 0, -3, and 72:
+/// ```
+/// #![recursion_limit = "1024"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "Stack at program '@':";`, then `"top: 72 ('H')"`, `"     -3"`,
+/// // and `"     0"`.
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "003-89*@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [poststack]],
+///         ],
+///     ],
+/// }
+/// ```
+///
+/// With `[profile]`, this counts down from 10 and shows where the time went: the loop from the `>`
+/// to the `_` runs ten times, going back around through the `^` for all but the last.
+/// ```
+/// #![recursion_limit = "2048"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "Instructions executed: 73";`, then `"  arithmetic:   11"`,
+/// // `"  numbers:      12"`, `"  stack:        10"`, `"  movement:     29"`,
+/// // `"  conditionals: 10"`, `"  memory:       0"`, `"  I/O:          0"`, and
+/// // `"  other:        1"`.
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "25*>1-:v
+///    ^   _@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [profile]],
+///         ],
+///     ],
+/// }
+/// ```
+///
+/// With `[heatmap]`, the same program shows the loop as the hottest part of the playfield. Only the
+/// numbers at the start and the `@` at the end are visited once:
+/// ```
+/// #![recursion_limit = "2048"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "Cell visits (.:*#@ from fewest to most, 10 at most):";`, then
+/// // `const _: &str = "...@@@@@";` and `const _: &str = "   @@@@@.";`.
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "25*>1-:v
+///    ^   _@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [heatmap]],
+///         ],
+///     ],
+/// }
+/// ```
+///
+/// With `[stepcount]`, it outputs how many steps the run took. Unlike the 73 instructions counted
+/// by `[profile]`, this includes every time the PC passed over a space:
+/// ```
+/// #![recursion_limit = "2048"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Expands to `const _: &str = "Executed 100 steps";`.
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "25*>1-:v
+///    ^   _@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [stepcount]],
+///         ],
+///     ],
+/// }
+/// ```
+///
+/// When several programs are run in the same module, giving each of them a tag makes it possible to
+/// tell which one each line of debugging output came from. Here, the lines and stacks printed for
+/// the first program start with `[first] ` and those for the second with `[second] `:
+/// ```
+/// #![recursion_limit = "4096"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/example.bfg",
+///     sockets: "first",
+///     debug: [[noflush] [initlines] [poststack] [tag "first"]],
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/examples/example.bfg",
+///     sockets: "second",
+///     debug: [[noflush] [initlines] [poststack] [tag "second"]],
+/// }
+/// ```
+///
+/// Relative file paths are looked up in the directory of the crate being built first, so a program
+/// kept alongside a workspace member's manifest can be named the same way whether `cargo` is run
+/// from the member's directory or from the workspace root. If that doesn't find it, the path is
+/// tried against the current directory instead. A leading `~` stands for the home directory, and
+/// `${NAME}` is replaced with the value of the environment variable `NAME`:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Both of these are `befunge-dm/examples/example.bfg`, even though the tests are run from the
+/// // root of the workspace.
+/// befunge_dm::befunge! {
+///     file: "examples/example.bfg",
+///     debug: [[noflush]],
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "${CARGO_MANIFEST_DIR}/examples/example.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "${BEFUNGE_DM_UNSET_VARIABLE}/example.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// Files with Windows (`\r\n`) or old Mac (`\r`) line endings, or a mix of them, are read just like
+/// ones with `\n` line endings:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/crlf.bfg",
+///     debug: [[noflush]],
+/// }
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/mixed_line_endings.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+/// Trailing spaces are kept, though, and take up columns of program memory. A file with lines that
+/// only fit without them can be read with [`befunge_pm::befunge_input!`] and `trim: true`:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_input! {
+///     file: "befunge-dm/tests/fixtures/trailing_spaces.bfg",
+///     trim: true,
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush]],
+///         ],
+///     ],
+/// }
+/// ```
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/trailing_spaces.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// Tabs are rejected along with where the first one is, since there's no telling how wide the author
+/// meant them to be. Reading the file with `tabs: N` expands each tab to the next column that's a
+/// multiple of `N`, so `>  \tv` over `\t@` with `tabs: 4` lines the `v` up with the `@`:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_input! {
+///     file: "befunge-dm/tests/fixtures/tabs.bfg",
+///     tabs: 4,
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush]],
+///         ],
+///     ],
+/// }
+/// ```
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/tabs.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// Programs don't have to be kept in a file. [`befunge_pm::befunge_source!`] takes the source as a
+/// string instead, and reads it just like a file would be:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! source_chars {
+///     (filecontents: [$($c:literal)*],) => {
+///         const SOURCE: &[char] = &[$($c),*];
+///     };
+/// }
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "v\r\n>@",
+///     callback: [
+///         name: source_chars,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+///
+/// assert_eq!(SOURCE, ['v', '\n', '>', '@']);
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: ">  \tv\n\t@",
+///     tabs: 4,
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush]],
+///         ],
+///     ],
+/// }
+/// ```
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "v\n>é@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush]],
+///         ],
+///     ],
+/// }
+/// ```
+///
+/// Programs have to fit in the 80 by 25 playfield, so a line 81 characters long or a 26th line is
+/// reported along with the file it's in:
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/too_wide.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/too_tall.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+/// Lines that are exactly 80 characters long fit, whichever row they're on:
+/// ```
+/// #![recursion_limit = "512"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "v
+/// >                                                                              v
+///                                                                                @",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush]],
+///         ],
+///     ],
+/// }
+/// ```
+/// This check can be skipped with `allow_oversize: true`, for anyone reading programs with
+/// [`befunge_pm::befunge_input!`] into a playfield of their own:
+/// ```
+/// macro_rules! count_rows {
+///     (filecontents: [$($c:literal)*],) => {
+///         const ROWS: usize = 0 $(+ (($c == '\n') as usize))*;
+///     };
+/// }
+///
+/// befunge_dm::befunge_pm::befunge_input! {
+///     file: "befunge-dm/tests/fixtures/too_tall.bfg",
+///     allow_oversize: true,
+///     callback: [
+///         name: count_rows,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+///
+/// assert_eq!(ROWS, 26);
+/// ```
+///
+/// Programs may only contain ASCII characters. The first one that isn't is reported along with
+/// where it is in the file, e.g. `'é' at line 3, column 7`:
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/non_ascii.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+macro_rules! befunge {
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        dialect: $dialect:ident
+        $(, breakpoints: $breakpoints:tt)?
+        $(, random: $random:tt $($exhausted:ident)?)?
+        $(, result: $result:path)?
+        $(, on_unknown: $mode:ident)?
+        $(, compact: $compact:ident)?
+        $(, divmode: $divmode:ident)?$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            dialect: $dialect,
+            $(breakpoints: $breakpoints,)?
+            $(random: $random $($exhausted)?,)?
+            $(result: $result,)?
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        dialect: b93,
+        $(breakpoints: $breakpoints:tt,)?
+        $(random: $random:tt $($exhausted:ident)?,)?
+        $(result: $result:path,)?
+        $(on_unknown: $mode:ident,)?
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(breakpoints: $breakpoints,)?
+            $(random: $random $($exhausted)?,)?
+            $(result: $result,)?
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: $debug,
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        dialect: b98,
+        $(breakpoints: $breakpoints:tt,)?
+        $(random: $random:tt $($exhausted:ident)?,)?
+        $(result: $result:path,)?
+        $(on_unknown: $mode:ident,)?
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(breakpoints: $breakpoints,)?
+            $(random: $random $($exhausted)?,)?
+            $(result: $result,)?
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [$($debug)* [b98]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        dialect: $dialect:ident,
+        $(breakpoints: $breakpoints:tt,)?
+        $(random: $random:tt $($exhausted:ident)?,)?
+        $(result: $result:path,)?
+        $(on_unknown: $mode:ident,)?
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: $debug:tt,
+    ) => {
+        compile_error!(concat!(
+            "Unknown dialect `",
+            stringify!($dialect),
+            "`, expected `b93` or `b98`",
+        ));
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        breakpoints: $breakpoints:tt
+        $(, random: $random:tt $($exhausted:ident)?)?
+        $(, result: $result:path)?
+        $(, on_unknown: $mode:ident)?
+        $(, compact: $compact:ident)?
+        $(, divmode: $divmode:ident)?$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            breakpoints: $breakpoints,
+            $(random: $random $($exhausted)?,)?
+            $(result: $result,)?
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        breakpoints: [$($breakpoint:tt)*],
+        $(random: $random:tt $($exhausted:ident)?,)?
+        $(result: $result:path,)?
+        $(on_unknown: $mode:ident,)?
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(random: $random $($exhausted)?,)?
+            $(result: $result,)?
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [$($debug)* [breakpoints $($breakpoint)*]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        random: $random:tt $($exhausted:ident)?
+        $(, result: $result:path)?
+        $(, on_unknown: $mode:ident)?
+        $(, compact: $compact:ident)?
+        $(, divmode: $divmode:ident)?$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            random: $random $($exhausted)?,
+            $(result: $result,)?
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        random: [$($dir:ident)*],
+        $(result: $result:path,)?
+        $(on_unknown: $mode:ident,)?
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(result: $result,)?
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [$($debug)* [random cycle $($dir)*]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        random: [$($dir:ident)*] $exhausted:ident,
+        $(result: $result:path,)?
+        $(on_unknown: $mode:ident,)?
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(result: $result,)?
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [$($debug)* [random $exhausted $($dir)*]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        result: $result:path
+        $(, on_unknown: $mode:ident)?
+        $(, compact: $compact:ident)?
+        $(, divmode: $divmode:ident)?$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            result: $result,
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        result: $result:path,
+        $(on_unknown: $mode:ident,)?
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(on_unknown: $mode,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [$($debug)* [result $result]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        on_unknown: $mode:ident
+        $(, compact: $compact:ident)?
+        $(, divmode: $divmode:ident)?$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            on_unknown: $mode,
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        on_unknown: error,
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: $debug,
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        on_unknown: skip,
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [$($debug)* [onunknown skip]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        on_unknown: reflect,
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(compact: $compact,)?
+            $(divmode: $divmode,)?
+            debug: [$($debug)* [onunknown reflect]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        on_unknown: $mode:ident,
+        $(compact: $compact:ident,)?
+        $(divmode: $divmode:ident,)?
+        debug: $debug:tt,
+    ) => {
+        compile_error!(concat!(
+            "Unknown `on_unknown: ",
+            stringify!($mode),
+            "`, expected `error`, `skip`, or `reflect`",
+        ));
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        compact: $compact:ident
+        $(, divmode: $divmode:ident)?$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            compact: $compact,
+            $(divmode: $divmode,)?
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        compact: true,
+        $(divmode: $divmode:ident,)?
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(divmode: $divmode,)?
+            debug: [$($debug)* [compact]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        compact: false,
+        $(divmode: $divmode:ident,)?
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            $(divmode: $divmode,)?
+            debug: $debug,
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        compact: $compact:ident,
+        $(divmode: $divmode:ident,)?
+        debug: $debug:tt,
+    ) => {
+        compile_error!(concat!(
+            "Unknown `compact: ",
+            stringify!($compact),
+            "`, expected `true` or `false`",
+        ));
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        divmode: $divmode:ident$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            divmode: $divmode,
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        divmode: floor,
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            debug: [$($debug)* [divmode floor]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        divmode: trunc,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            $(step_delay_ms: $delay,)?
+            debug: $debug,
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        $(step_delay_ms: $delay:literal,)?
+        divmode: $divmode:ident,
+        debug: $debug:tt,
+    ) => {
+        compile_error!(concat!(
+            "Unknown `divmode: ",
+            stringify!($divmode),
+            "`, expected `trunc` or `floor`",
+        ));
+    };
+    (
+        file: $file:literal,
+        start: $start:tt$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            start: $start,
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        direction: $direction:ident$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            direction: $direction,
+            debug: [],
+        }
+    };
+    ($(file: )?$file:literal$(,)?) => {
+        $crate::befunge! {
+            file: $file,
+            sockets: "befunge",
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        sockets: $sockets:literal$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            sockets: $sockets,
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        $(sockets: $sockets:literal,)?
+        step_delay_ms: $delay:literal$(,)?
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            $(sockets: $sockets,)?
+            step_delay_ms: $delay,
+            debug: [],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        step_delay_ms: $delay:literal,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            sockets: "befunge",
+            step_delay_ms: $delay,
+            debug: $debug,
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        sockets: $sockets:literal,
+        step_delay_ms: $delay:literal,
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            sockets: $sockets,
+            debug: [$($debug)* [stepdelay $delay]],
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge! {
+            file: $file,
+            $(start: $start,)?
+            $(direction: $direction,)?
+            sockets: "befunge",
+            debug: $debug,
+        }
+    };
+    (
+        file: $file:literal,
+        $(start: $start:tt,)?
+        $(direction: $direction:ident,)?
+        sockets: $sockets:literal,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("Using Befunge file: '", $file, "'")],
+        }
         $crate::befunge_pm::befunge_input! {
             file: $file,
             callback: [
                 name: $crate::befunge_init,
                 pre: [@init],
                 pst: [
+                    $(start: $start,)?
+                    $(direction: $direction,)?
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
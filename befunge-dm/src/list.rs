@@ -1,7 +1,25 @@
+//! Macros for working with lists of token trees, like the rows of the playfield and the cells in
+//! each of them.
+//!
+//! - [`crate::list_init_last`]: splits the last item off a list.
+//! - [`crate::list_split_at_length_of`]: splits a list after as many items as another list has.
+//! - [`crate::list_get_at`]: gets the item at an index.
+//! - [`crate::list_set_at`]: replaces the item at an index.
+//! - [`crate::list_reverse`]: reverses a list.
+//! - [`crate::list_pad_to`]: pads a list to a width with a fill token.
+//! - [`crate::list_concat`]: puts one list after another.
+//! - [`crate::list_repeat`]: makes a list of one item repeated a number of times.
+//! - [`crate::list_len`]: gives the length of a list, as an integer literal or a base 1 number.
+//! - [`crate::bounds_check`]: checks whether a pair of coordinates is inside the playfield.
+//!
+//! Indices and lengths are given by the length of a list, the same as the magnitudes of the
+//! numbers in [`crate::arith`]. Every macro here calls back with the standard
+//! `callback: [name: .., pre: [..], pst: [..]]` convention.
+
 #[macro_export]
 /// Gives the last and init of a list
 ///
-/// Gives an empty `init` if the list is of length 1.
+/// Gives an empty `init` if the list is of length 1. An empty list fails the build.
 ///
 /// Call examples:
 /// ```
@@ -73,8 +91,10 @@ macro_rules! list_init_last {
     ) => {
         compile_error! {
             concat! {
-                "Attempted to 'g' init and last of empty list. Callback:\n",
-                stringify($callback),
+                "Attempted to get the init and last of an empty list. This usually means that the ",
+                "program tried to move left or up on a row or playfield with nothing in it. ",
+                "Callback:\n",
+                stringify!($callback),
             }
         }
     };
@@ -127,6 +147,8 @@ macro_rules! list_init_last {
 ///
 /// Call examples:
 /// ```
+/// #![feature(macro_metavar_expr)]
+///
 /// # use befunge_dm::list_split_at_length_of;
 /// #
 /// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
@@ -205,6 +227,108 @@ macro_rules! list_init_last {
 ///     assert!(const_str_eq(RSIDE, "[a b c d e]"));
 /// };
 /// ```
+///
+/// With `pad`, a list that is too short to split is padded with the given token so that the right
+/// side always has at least one element. This is what keeps movement and `g`/`p` working on rows
+/// of different lengths:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// # use befunge_dm::list_split_at_length_of;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// # macro_rules! helper {
+/// #     (
+/// #         l: $lside:tt,
+/// #         r: $rside:tt,
+/// #     ) => {
+/// #         const LSIDE: &str = stringify!($lside);
+/// #         const RSIDE: &str = stringify!($rside);
+/// #     };
+/// # }
+/// #
+/// const _: () = {
+///     list_split_at_length_of! {
+///         @init
+///         pad: z,
+///         lenof: [a b c d e],
+///         split: [a b],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(LSIDE, "[a b z z z]"));
+///     assert!(const_str_eq(RSIDE, "[z]"));
+/// };
+///
+/// const _: () = {
+///     list_split_at_length_of! {
+///         @init
+///         pad: z,
+///         lenof: [a b],
+///         split: [a b c d e],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(LSIDE, "[a b]"));
+///     assert!(const_str_eq(RSIDE, "[c d e]"));
+/// };
+/// ```
+///
+/// Without `pad`, a list that is too short to split fails the build, saying how many items it was
+/// short by and what had been split off before it ran out:
+/// ```compile_fail
+/// #![feature(macro_metavar_expr)]
+///
+/// # use befunge_dm::list_split_at_length_of;
+/// #
+/// # macro_rules! helper {
+/// #     (
+/// #         l: $lside:tt,
+/// #         r: $rside:tt,
+/// #     ) => {};
+/// # }
+/// #
+/// // error: Failed to split list copying length of another! It was 3 item(s) too short. Split off
+/// //        so far: [a b]
+/// //        Callback:
+/// //        - Name: helper
+/// //        - Pre: []
+/// //        - Pst: []
+/// list_split_at_length_of! {
+///     @init
+///     lenof: [a b c d e],
+///     split: [a b],
+///     callback: [
+///         name: helper,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+/// ```
 macro_rules! list_split_at_length_of {
     (
         @init
@@ -220,6 +344,88 @@ macro_rules! list_split_at_length_of {
             callback: $callback,
         }
     };
+    (
+        @init
+        pad: $pad:tt,
+        lenof: $lenof:tt,
+        split: $split:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::list_split_at_length_of! {
+            @split @pad
+            pad: $pad,
+            lenof: $lenof,
+            l: [],
+            r: $split,
+            callback: $callback,
+        }
+    };
+    (
+        @split @pad
+        pad: $pad:tt,
+        lenof: [$lenofh:tt $($lenoft:tt)*],
+        l: [$($l:tt)*],
+        r: [$rh:tt $($rt:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::list_split_at_length_of! {
+            @split @pad
+            pad: $pad,
+            lenof: [$($lenoft)*],
+            l: [$($l)* $rh],
+            r: [$($rt)*],
+            callback: $callback,
+        }
+    };
+    (
+        @split @pad
+        pad: $pad:tt,
+        lenof: [$lenofh:tt $($lenoft:tt)*],
+        l: [$($l:tt)*],
+        r: [],
+        callback: $callback:tt,
+    ) => {
+        $crate::list_split_at_length_of! {
+            @split @pad
+            pad: $pad,
+            lenof: [$($lenoft)*],
+            l: [$($l)* $pad],
+            r: [],
+            callback: $callback,
+        }
+    };
+    (
+        @split @pad
+        pad: $pad:tt,
+        lenof: [],
+        l: $l:tt,
+        r: [],
+        callback: $callback:tt,
+    ) => {
+        $crate::list_split_at_length_of! {
+            @split
+            lenof: [],
+            l: $l,
+            r: [$pad],
+            callback: $callback,
+        }
+    };
+    (
+        @split @pad
+        pad: $pad:tt,
+        lenof: [],
+        l: $l:tt,
+        r: $r:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::list_split_at_length_of! {
+            @split
+            lenof: [],
+            l: $l,
+            r: $r,
+            callback: $callback,
+        }
+    };
     (
         @split
         lenof: [$lenofh:tt $($lenoft:tt)*],
@@ -256,7 +462,8 @@ macro_rules! list_split_at_length_of {
     (
         @split
         lenof: [$($lenof:tt)+],
-        split: [],
+        l: $l:tt,
+        r: [],
         callback: [
             name: $name:path,
             pre: $pre:tt,
@@ -265,7 +472,11 @@ macro_rules! list_split_at_length_of {
     ) => {
         compile_error! {
             concat! {
-                "Failed to split list copying length of another! Callback:\n- Name: ",
+                "Failed to split list copying length of another! It was ",
+                ${count($lenof)},
+                " item(s) too short. Split off so far: ",
+                stringify!($l),
+                "\nCallback:\n- Name: ",
                 stringify!($name),
                 "\n- Pre: ",
                 stringify!($pre),
@@ -275,3 +486,1310 @@ macro_rules! list_split_at_length_of {
         }
     };
 }
+
+#[macro_export]
+/// Gets the item at position `index` in `list`, where `index` is given by the length of a list (the
+/// tokens in it don't matter). Expands to a call to the callback as:
+/// ```ignore
+/// name! {
+///     pre
+///     item: [item],
+///     pst
+/// }
+/// ```
+/// or, if `index` is past the end of `list`, as:
+/// ```ignore
+/// name! {
+///     pre
+///     oob: [],
+///     pst
+/// }
+/// ```
+///
+/// Call examples:
+/// ```
+/// # use befunge_dm::list_get_at;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// macro_rules! helper {
+///     (item: [$item:tt],) => {
+///         const ITEM: &str = stringify!($item);
+///     };
+///     (oob: [],) => {
+///         const ITEM: &str = "oob";
+///     };
+/// }
+///
+/// // anonymous namespace
+/// const _: () = {
+///     list_get_at! {
+///         @get
+///         index: [],
+///         list: [a [b c] d],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(ITEM, "a"));
+/// };
+///
+/// const _: () = {
+///     list_get_at! {
+///         @get
+///         index: [x x],
+///         list: [a [b c] d],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(ITEM, "d"));
+/// };
+///
+/// const _: () = {
+///     list_get_at! {
+///         @get
+///         index: [x],
+///         list: [a [b c] d],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(ITEM, "[b c]"));
+/// };
+///
+/// const _: () = {
+///     list_get_at! {
+///         @get
+///         index: [x x x],
+///         list: [a [b c] d],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(ITEM, "oob"));
+/// };
+/// ```
+macro_rules! list_get_at {
+    (
+        @get
+        index: [$indexh:tt $($indext:tt)*],
+        list: [$listh:tt $($listt:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::list_get_at! {
+            @get
+            index: [$($indext)*],
+            list: [$($listt)*],
+            callback: $callback,
+        }
+    };
+    (
+        @get
+        index: [],
+        list: [$item:tt $($_rest:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            item: [$item],
+            $($pst)*
+        }
+    };
+    (
+        @get
+        index: $index:tt,
+        list: [],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            oob: [],
+            $($pst)*
+        }
+    };
+}
+
+#[macro_export]
+/// Replaces the item at position `index` in `list` with `value`, where `index` is given by the
+/// length of a list (the tokens in it don't matter). Every other item stays where it was. Expands
+/// to a call to the callback as:
+/// ```ignore
+/// name! {
+///     pre
+///     list: [...],
+///     pst
+/// }
+/// ```
+/// or, if `index` is past the end of `list`, as:
+/// ```ignore
+/// name! {
+///     pre
+///     oob: [],
+///     pst
+/// }
+/// ```
+///
+/// Call examples:
+/// ```
+/// # use befunge_dm::list_set_at;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// # const fn const_list_eq(a: &[&'static str], b: &[&'static str]) -> bool {
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if !const_str_eq(a[i], b[i]) {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// macro_rules! helper {
+///     (list: [$($item:tt)*],) => {
+///         const LIST: &[&str] = &[$(stringify!($item)),*];
+///     };
+///     (oob: [],) => {
+///         const LIST: &[&str] = &["oob"];
+///     };
+/// }
+///
+/// // anonymous namespace
+/// const _: () = {
+///     list_set_at! {
+///         @set
+///         index: [],
+///         list: [a [b c] d],
+///         value: [z],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["z", "[b c]", "d"]));
+/// };
+///
+/// const _: () = {
+///     list_set_at! {
+///         @set
+///         index: [x],
+///         list: [a [b c] d],
+///         value: [[y z]],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["a", "[y z]", "d"]));
+/// };
+///
+/// const _: () = {
+///     list_set_at! {
+///         @set
+///         index: [x x],
+///         list: [a [b c] d],
+///         value: [z],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["a", "[b c]", "z"]));
+/// };
+///
+/// const _: () = {
+///     list_set_at! {
+///         @set
+///         index: [x x x],
+///         list: [a [b c] d],
+///         value: [z],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["oob"]));
+/// };
+/// ```
+macro_rules! list_set_at {
+    (
+        @set
+        index: $index:tt,
+        list: $list:tt,
+        value: [$value:tt],
+        callback: $callback:tt,
+    ) => {
+        $crate::list_set_at! {
+            @set
+            index: $index,
+            value: $value,
+            l: [],
+            r: $list,
+            callback: $callback,
+        }
+    };
+    (
+        @set
+        index: [$indexh:tt $($indext:tt)*],
+        value: $value:tt,
+        l: [$($l:tt)*],
+        r: [$rh:tt $($rt:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::list_set_at! {
+            @set
+            index: [$($indext)*],
+            value: $value,
+            l: [$($l)* $rh],
+            r: [$($rt)*],
+            callback: $callback,
+        }
+    };
+    (
+        @set
+        index: [],
+        value: $value:tt,
+        l: [$($l:tt)*],
+        r: [$_old:tt $($rt:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            list: [$($l)* $value $($rt)*],
+            $($pst)*
+        }
+    };
+    (
+        @set
+        index: $index:tt,
+        value: $value:tt,
+        l: $l:tt,
+        r: [],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            oob: [],
+            $($pst)*
+        }
+    };
+}
+
+#[macro_export]
+/// Reverses a list. Each item is moved onto the front of an accumulator in turn, so this takes
+/// one expansion per item. Expands to a call to the callback as:
+/// ```ignore
+/// name! {
+///     pre
+///     list: [...],
+///     pst
+/// }
+/// ```
+///
+/// Call examples:
+/// ```
+/// # use befunge_dm::list_reverse;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// macro_rules! helper {
+///     (list: $list:tt,) => {
+///         const LIST: &str = stringify!($list);
+///     };
+/// }
+///
+/// // anonymous namespace
+/// const _: () = {
+///     list_reverse! {
+///         @reverse
+///         list: [],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(LIST, "[]"));
+/// };
+///
+/// const _: () = {
+///     list_reverse! {
+///         @reverse
+///         list: [a],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(LIST, "[a]"));
+/// };
+///
+/// const _: () = {
+///     list_reverse! {
+///         @reverse
+///         list: [a b c d e],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(LIST, "[e d c b a]"));
+/// };
+/// ```
+macro_rules! list_reverse {
+    (
+        @reverse
+        list: $list:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::list_reverse! {
+            @reverse
+            list: $list,
+            rev: [],
+            callback: $callback,
+        }
+    };
+    (
+        @reverse
+        list: [$h:tt $($t:tt)*],
+        rev: [$($rev:tt)*],
+        callback: $callback:tt,
+    ) => {
+        $crate::list_reverse! {
+            @reverse
+            list: [$($t)*],
+            rev: [$h $($rev)*],
+            callback: $callback,
+        }
+    };
+    (
+        @reverse
+        list: [],
+        rev: $rev:tt,
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            list: $rev,
+            $($pst)*
+        }
+    };
+}
+
+#[macro_export]
+/// Gives the length of a list, so that it can be used outside of the rule that bound the list.
+/// With `@len`, expands to a call to the callback as:
+/// ```ignore
+/// name! {
+///     pre
+///     len: N,
+///     pst
+/// }
+/// ```
+/// where `N` is an integer literal. With `@len_base1`, it's given as a base 1 number instead, which
+/// is what the macros in [`crate::arith`] take:
+/// ```ignore
+/// name! {
+///     pre
+///     len_base1: [[pos] [[] ..]],
+///     pst
+/// }
+/// ```
+///
+/// Call examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// # use befunge_dm::list_len;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// macro_rules! helper {
+///     (len: $len:literal,) => {
+///         const LEN: usize = $len;
+///     };
+///     (len_base1: $len:tt,) => {
+///         const LEN_BASE1: &str = stringify!($len);
+///     };
+/// }
+///
+/// // anonymous namespace
+/// const _: () = {
+///     list_len! {
+///         @len
+///         list: [],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(LEN == 0);
+/// };
+///
+/// const _: () = {
+///     list_len! {
+///         @len
+///         list: [a [b c] d],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(LEN == 3);
+/// };
+///
+/// const _: () = {
+///     list_len! {
+///         @len_base1
+///         list: [],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(LEN_BASE1, "[[pos] []]"));
+/// };
+///
+/// const _: () = {
+///     list_len! {
+///         @len_base1
+///         list: [a [b c] d],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(LEN_BASE1, "[[pos] [[] [] []]]"));
+/// };
+/// ```
+macro_rules! list_len {
+    (
+        @len
+        list: [$($item:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            len: ${count($item)},
+            $($pst)*
+        }
+    };
+    (
+        @len_base1
+        list: [$($item:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            len_base1: [[pos] [$(${ignore($item)} [])*]],
+            $($pst)*
+        }
+    };
+}
+
+#[macro_export]
+/// Pads `list` with `fill` until it's as long as `width`, where `width` is given by the length of a
+/// list (the tokens in it don't matter). A list that's already at least that long is left as it is.
+/// The padding is worked out in a single expansion rather than one per item. Expands to a call to
+/// the callback as:
+/// ```ignore
+/// name! {
+///     pre
+///     list: [...],
+///     pst
+/// }
+/// ```
+///
+/// Call examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// # use befunge_dm::list_pad_to;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// # const fn const_list_eq(a: &[&'static str], b: &[&'static str]) -> bool {
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if !const_str_eq(a[i], b[i]) {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// macro_rules! helper {
+///     (list: [$($item:tt)*],) => {
+///         const LIST: &[&str] = &[$(stringify!($item)),*];
+///     };
+/// }
+///
+/// // anonymous namespace
+/// const _: () = {
+///     list_pad_to! {
+///         @pad
+///         list: [],
+///         width: [[] [] []],
+///         fill: [' '],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["' '", "' '", "' '"]));
+/// };
+///
+/// const _: () = {
+///     list_pad_to! {
+///         @pad
+///         list: ['a' 'b' 'c'],
+///         width: [[] [] []],
+///         fill: [' '],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["'a'", "'b'", "'c'"]));
+/// };
+///
+/// const _: () = {
+///     list_pad_to! {
+///         @pad
+///         list: ['a' 'b'],
+///         width: [[] [] [] [] []],
+///         fill: [' '],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["'a'", "'b'", "' '", "' '", "' '"]));
+/// };
+/// ```
+macro_rules! list_pad_to {
+    (
+        @pad
+        list: [$($item:tt)*],
+        width: [$($width:tt)*],
+        fill: [$fill:tt],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        macro_rules! list_pad_to_width {
+            (width: [$(${ignore($item)} [])* $$($$pad:tt)*]) => {
+                $name! {
+                    $($pre)*
+                    list: [$($item)* $$($${ignore($$pad)} $fill)*],
+                    $($pst)*
+                }
+            };
+            (width: $$_width:tt) => {
+                $name! {
+                    $($pre)*
+                    list: [$($item)*],
+                    $($pst)*
+                }
+            };
+        }
+        list_pad_to_width! {
+            width: [$(${ignore($width)} [])*]
+        }
+    };
+}
+
+#[macro_export]
+/// Puts the items of `b` after the items of `a`. Expands to a call to the callback as:
+/// ```ignore
+/// name! {
+///     pre
+///     list: [...],
+///     pst
+/// }
+/// ```
+///
+/// Call examples:
+/// ```
+/// # use befunge_dm::list_concat;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// # const fn const_list_eq(a: &[&'static str], b: &[&'static str]) -> bool {
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if !const_str_eq(a[i], b[i]) {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// macro_rules! helper {
+///     (list: [$($item:tt)*],) => {
+///         const LIST: &[&str] = &[$(stringify!($item)),*];
+///     };
+/// }
+///
+/// // anonymous namespace
+/// const _: () = {
+///     list_concat! {
+///         @concat
+///         a: [a [b c]],
+///         b: [d e],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["a", "[b c]", "d", "e"]));
+/// };
+///
+/// const _: () = {
+///     list_concat! {
+///         @concat
+///         a: [],
+///         b: [d e],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["d", "e"]));
+/// };
+///
+/// const _: () = {
+///     list_concat! {
+///         @concat
+///         a: [],
+///         b: [],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &[]));
+/// };
+/// ```
+macro_rules! list_concat {
+    (
+        @concat
+        a: [$($a:tt)*],
+        b: [$($b:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            list: [$($a)* $($b)*],
+            $($pst)*
+        }
+    };
+}
+
+#[macro_export]
+/// Makes a list with `item` in it as many times as the length of `times`. `times` can also be given
+/// as an integer literal, which is turned into a list with [`befunge_pm::base1_from_literal!`]
+/// first. Expands to a call to the callback as:
+/// ```ignore
+/// name! {
+///     pre
+///     list: [...],
+///     pst
+/// }
+/// ```
+///
+/// Call examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// # use befunge_dm::list_repeat;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// # const fn const_list_eq(a: &[&'static str], b: &[&'static str]) -> bool {
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if !const_str_eq(a[i], b[i]) {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// macro_rules! helper {
+///     (list: [$($item:tt)*],) => {
+///         const LIST: &[&str] = &[$(stringify!($item)),*];
+///     };
+/// }
+///
+/// // anonymous namespace
+/// const _: () = {
+///     list_repeat! {
+///         @repeat
+///         item: [' '],
+///         times: [[] [] []],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["' '", "' '", "' '"]));
+/// };
+///
+/// const _: () = {
+///     list_repeat! {
+///         @repeat
+///         item: [[a b]],
+///         times: 2,
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &["[a b]", "[a b]"]));
+/// };
+///
+/// const _: () = {
+///     list_repeat! {
+///         @repeat
+///         item: [' '],
+///         times: [],
+///         callback: [
+///             name: helper,
+///             pre: [],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_list_eq(LIST, &[]));
+/// };
+/// ```
+macro_rules! list_repeat {
+    (
+        @repeat
+        item: $item:tt,
+        times: $times:literal,
+        callback: $callback:tt,
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $times,
+            callback: [
+                name: $crate::list_repeat,
+                pre: [@literal item: $item,],
+                pst: [callback: $callback,],
+            ],
+        }
+    };
+    (
+        @literal
+        item: $item:tt,
+        num: [[pos] $times:tt],
+        callback: $callback:tt,
+    ) => {
+        $crate::list_repeat! {
+            @repeat
+            item: $item,
+            times: $times,
+            callback: $callback,
+        }
+    };
+    (
+        @literal
+        item: $item:tt,
+        num: [[neg] $times:tt],
+        callback: $callback:tt,
+    ) => {
+        compile_error!("`list_repeat!` can't repeat an item a negative number of times");
+    };
+    (
+        @repeat
+        item: [$item:tt],
+        times: [$($times:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            list: [$(${ignore($times)} $item)*],
+            $($pst)*
+        }
+    };
+}
+
+#[macro_export]
+/// Checks whether the coordinates `x` and `y` are inside the playfield, where each of them is
+/// given by the length of a list (the tokens in it don't matter). `max_x` and `max_y` are the
+/// largest coordinates that are still inside the playfield, given the same way, so a coordinate
+/// exactly on the edge is in bounds. `max_x` can be left out, in which case it's the width of the
+/// Befunge-93 playfield, `79`. Expands to a call to the `inbounds` callback if both coordinates are
+/// in bounds, and to the `outofbounds` callback otherwise, each as:
+/// ```ignore
+/// name! {
+///     pre
+///     pst
+/// }
+/// ```
+///
+/// Call examples:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// # use befunge_dm::bounds_check;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// macro_rules! helper {
+///     (where: $where:literal) => {
+///         const WHERE: &str = $where;
+///     };
+/// }
+///
+/// // anonymous namespace
+/// const _: () = {
+///     bounds_check! {
+///         @check
+///         x: [a b],
+///         y: [a],
+///         max_x: [a b c d],
+///         max_y: [a b c],
+///         inbounds: [
+///             name: helper,
+///             pre: [where: "in"],
+///             pst: [],
+///         ],
+///         outofbounds: [
+///             name: helper,
+///             pre: [where: "out"],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(WHERE, "in"));
+/// };
+///
+/// // On the edge of the playfield
+/// const _: () = {
+///     bounds_check! {
+///         @check
+///         x: [a b c d],
+///         y: [a b c],
+///         max_x: [a b c d],
+///         max_y: [a b c],
+///         inbounds: [
+///             name: helper,
+///             pre: [where: "in"],
+///             pst: [],
+///         ],
+///         outofbounds: [
+///             name: helper,
+///             pre: [where: "out"],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(WHERE, "in"));
+/// };
+///
+/// // Just off the right edge
+/// const _: () = {
+///     bounds_check! {
+///         @check
+///         x: [a b c d e],
+///         y: [],
+///         max_x: [a b c d],
+///         max_y: [a b c],
+///         inbounds: [
+///             name: helper,
+///             pre: [where: "in"],
+///             pst: [],
+///         ],
+///         outofbounds: [
+///             name: helper,
+///             pre: [where: "out"],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(WHERE, "out"));
+/// };
+///
+/// // Just off the bottom edge
+/// const _: () = {
+///     bounds_check! {
+///         @check
+///         x: [],
+///         y: [a b c d],
+///         max_x: [a b c d],
+///         max_y: [a b c],
+///         inbounds: [
+///             name: helper,
+///             pre: [where: "in"],
+///             pst: [],
+///         ],
+///         outofbounds: [
+///             name: helper,
+///             pre: [where: "out"],
+///             pst: [],
+///         ],
+///     }
+///
+///     assert!(const_str_eq(WHERE, "out"));
+/// };
+/// ```
+///
+/// Without `max_x`, column 79 is the last one in bounds:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// # use befunge_dm::bounds_check;
+/// #
+/// # const fn const_str_eq(a: &'static str, b: &'static str) -> bool {
+/// #     let a = a.as_bytes();
+/// #     let b = b.as_bytes();
+/// #     if a.len() != b.len() {
+/// #         return false;
+/// #     }
+/// #     let mut i = 0;
+/// #     while i < a.len() {
+/// #         if a[i] != b[i] {
+/// #             return false;
+/// #         } else {
+/// #             i += 1;
+/// #         }
+/// #     }
+/// #     true
+/// # }
+/// #
+/// # macro_rules! helper {
+/// #     (where: $where:literal) => {
+/// #         const WHERE: &str = $where;
+/// #     };
+/// # }
+/// #
+/// macro_rules! check_column {
+///     ($($x:tt)*) => {
+///         bounds_check! {
+///             @check
+///             x: [$($x)*],
+///             y: [],
+///             max_y: [],
+///             inbounds: [
+///                 name: helper,
+///                 pre: [where: "in"],
+///                 pst: [],
+///             ],
+///             outofbounds: [
+///                 name: helper,
+///                 pre: [where: "out"],
+///                 pst: [],
+///             ],
+///         }
+///     };
+/// }
+///
+/// const _: () = {
+///     // 79 `x`s
+///     check_column! {
+///         x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x
+///         x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x
+///     }
+///
+///     assert!(const_str_eq(WHERE, "in"));
+/// };
+///
+/// const _: () = {
+///     // 80 `x`s
+///     check_column! {
+///         x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x
+///         x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x x
+///     }
+///
+///     assert!(const_str_eq(WHERE, "out"));
+/// };
+/// ```
+macro_rules! bounds_check {
+    (
+        @check
+        x: $x:tt,
+        y: $y:tt,
+        max_y: $maxy:tt,
+        inbounds: $inbounds:tt,
+        outofbounds: $outofbounds:tt,
+    ) => {
+        $crate::bounds_check! {
+            @check
+            x: $x,
+            y: $y,
+            max_x: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []],
+            max_y: $maxy,
+            inbounds: $inbounds,
+            outofbounds: $outofbounds,
+        }
+    };
+    (
+        @check
+        x: [$($x:tt)*],
+        y: [$($y:tt)*],
+        max_x: [$($maxx:tt)*],
+        max_y: [$($maxy:tt)*],
+        inbounds: [
+            name: $inname:path,
+            pre: [$($inpre:tt)*],
+            pst: [$($inpst:tt)*],
+        ],
+        outofbounds: [
+            name: $outname:path,
+            pre: [$($outpre:tt)*],
+            pst: [$($outpst:tt)*],
+        ],
+    ) => {
+        macro_rules! bounds_check_lengths {
+            (
+                max_x: [$(${ignore($x)} [])* $$($$_maxx:tt)*],
+                max_y: [$(${ignore($y)} [])* $$($$_maxy:tt)*],
+            ) => {
+                $inname! {
+                    $($inpre)*
+                    $($inpst)*
+                }
+            };
+            ($$($$_:tt)*) => {
+                $outname! {
+                    $($outpre)*
+                    $($outpst)*
+                }
+            };
+        }
+        bounds_check_lengths! {
+            max_x: [$(${ignore($maxx)} [])*],
+            max_y: [$(${ignore($maxy)} [])*],
+        }
+    };
+}
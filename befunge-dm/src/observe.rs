@@ -0,0 +1,607 @@
+/// Keeps track of what the PC does for the debugging flags that watch a whole run, `[profile]` (see
+/// [`dbg_profile!`]), `[heatmap]`, `[breakpoints ..]`, and `[stepcount]`, and outputs what they
+/// found. With any of them, `[observe profile: profile heat: heat breaks: breaks steps: steps]` is
+/// put at the front of the debugging flags, where `profile` is `[]` without `[profile]`, `heat` is
+/// `[]` without `[heatmap]`, `breaks` is `[]` without `[breakpoints ..]`, and `steps` is `[]`
+/// without `[stepcount]`. Otherwise, `heat` is `[visits [row col]..]`, with one `[row col]` for
+/// each time the PC landed on a cell, `breaks` is the `[x y]`s from `[breakpoints ..]`, and `steps`
+/// is `[count []..]`, with one `[]` for each time the PC landed on a cell.
+///
+/// Every time the PC lands on a cell, the interpreter hands the flag to `@break` and `@visit`
+/// before doing anything else. While the cell is being dealt with, it's hidden as `[observed ..]`
+/// so that it isn't seen again, and it's put back once the PC moves. None of this is carried
+/// without `[profile]`, `[heatmap]`, `[breakpoints ..]`, or `[stepcount]`.
+///
+/// The internal rules are:
+///
+/// - `@init`: Makes a callback with `debug:` set to the given flags, with `[observe ..]` in front
+///   of them if it's needed.
+/// - `@break`: Outputs the state of the interpreter as `const _: &str = "..."`s if the cell at
+///   `row` and `col` is one of `breaks`.
+/// - `@visit`: Adds the cell at `row` and `col` to the heatmap, counts the step, counts `instr` if
+///   it's about to be executed, and makes a callback with `debug:` set to
+///   `[before.. [observed ..] after..]`.
+/// - `@print`: Outputs the number of steps, the counts, and the heatmap as
+///   `const _: &str = "..."`s, if there are any.
+///
+/// # Example
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! observed {
+///     (debug: $debug:tt,) => {
+///         befunge_dm::dbg_observe! {
+///             @print
+///             debug: $debug,
+///         }
+///     };
+/// }
+///
+/// // Expands to `const _: &str = "Cell visits (.:*#@ from fewest to most, 2 at most):";`, then
+/// // `const _: &str = "  @";`.
+/// befunge_dm::dbg_observe! {
+///     @visit
+///     stringmode: [false],
+///     bridge: [false],
+///     instr: '+',
+///     row: 0,
+///     col: 2,
+///     profile: [],
+///     heat: [visits [0 2]],
+///     breaks: [],
+///     steps: [],
+///     before: [],
+///     after: [[noflush] [heatmap]],
+///     callback: [
+///         name: observed,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+/// ```
+#[macro_export]
+macro_rules! dbg_observe {
+    (
+        @init
+        debug: $debug:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[profile]],
+            expand: [
+                $crate::dbg_observe! {
+                    @init @heat
+                    profile: [[] [] [] [] [] [] [] []],
+                    debug: $debug,
+                    callback: $callback,
+                }
+            ],
+            orelse: [
+                $crate::dbg_observe! {
+                    @init @heat
+                    profile: [],
+                    debug: $debug,
+                    callback: $callback,
+                }
+            ],
+        }
+    };
+    (
+        @init @heat
+        profile: $profile:tt,
+        debug: $debug:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[heatmap]],
+            expand: [
+                $crate::dbg_observe! {
+                    @init @steps
+                    profile: $profile,
+                    heat: [visits],
+                    debug: $debug,
+                    callback: $callback,
+                }
+            ],
+            orelse: [
+                $crate::dbg_observe! {
+                    @init @steps
+                    profile: $profile,
+                    heat: [],
+                    debug: $debug,
+                    callback: $callback,
+                }
+            ],
+        }
+    };
+    (
+        @init @steps
+        profile: $profile:tt,
+        heat: $heat:tt,
+        debug: $debug:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[stepcount]],
+            expand: [
+                $crate::dbg_observe! {
+                    @init @breaks
+                    profile: $profile,
+                    heat: $heat,
+                    steps: [count],
+                    find: $debug,
+                    debug: $debug,
+                    callback: $callback,
+                }
+            ],
+            orelse: [
+                $crate::dbg_observe! {
+                    @init @breaks
+                    profile: $profile,
+                    heat: $heat,
+                    steps: [],
+                    find: $debug,
+                    debug: $debug,
+                    callback: $callback,
+                }
+            ],
+        }
+    };
+    (
+        @init @breaks
+        profile: $profile:tt,
+        heat: $heat:tt,
+        steps: $steps:tt,
+        find: [],
+        debug: $debug:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_observe! {
+            @init @start
+            profile: $profile,
+            heat: $heat,
+            breaks: [],
+            steps: $steps,
+            debug: $debug,
+            callback: $callback,
+        }
+    };
+    (
+        @init @breaks
+        profile: $profile:tt,
+        heat: $heat:tt,
+        steps: $steps:tt,
+        find: [[breakpoints $($breaks:tt)*] $($findt:tt)*],
+        debug: $debug:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_observe! {
+            @init @start
+            profile: $profile,
+            heat: $heat,
+            breaks: [$($breaks)*],
+            steps: $steps,
+            debug: $debug,
+            callback: $callback,
+        }
+    };
+    (
+        @init @breaks
+        profile: $profile:tt,
+        heat: $heat:tt,
+        steps: $steps:tt,
+        find: [$findh:tt $($findt:tt)*],
+        debug: $debug:tt,
+        callback: $callback:tt,
+    ) => {
+        $crate::dbg_observe! {
+            @init @breaks
+            profile: $profile,
+            heat: $heat,
+            steps: $steps,
+            find: [$($findt)*],
+            debug: $debug,
+            callback: $callback,
+        }
+    };
+    (
+        @init @start
+        profile: [],
+        heat: [],
+        breaks: [],
+        steps: [],
+        debug: [$($debug:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($debug)*],
+            $($pst)*
+        }
+    };
+    (
+        @init @start
+        profile: $profile:tt,
+        heat: $heat:tt,
+        breaks: $breaks:tt,
+        steps: $steps:tt,
+        debug: [$($debug:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [[observe profile: $profile heat: $heat breaks: $breaks steps: $steps] $($debug)*],
+            $($pst)*
+        }
+    };
+    (
+        @break
+        row: $row:tt,
+        col: $col:tt,
+        breaks: [],
+        $($state:tt)*
+    ) => {};
+    (
+        @break
+        row: $row:tt,
+        col: $col:tt,
+        breaks: [$($breakpoint:tt)+],
+        $($state:tt)*
+    ) => {
+        macro_rules! befunge_observe_break_check {
+            ([$col $row]) => {
+                $crate::dbg_observe! {
+                    @break @dump
+                    row: $row,
+                    col: $col,
+                    $($state)*
+                }
+            };
+            ($$($$_:tt)*) => {};
+        }
+        $(befunge_observe_break_check! { $breakpoint })+
+    };
+    (
+        @break @dump
+        row: $row:literal,
+        col: $col:literal,
+        stack: $stack:tt,
+        dir: [$dir:ident],
+        stringmode: [$stringmode:ident],
+        bridge: [$($bridge:tt)*],
+        lines: $lines:tt,
+        pc: $pc:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("Breakpoint at (", $row, ", ", $col, "):")],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!(
+                "dir: ",
+                stringify!($dir),
+                ", stringmode: ",
+                stringify!($stringmode),
+                ", bridge: ",
+                stringify!($($bridge)*),
+            )],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: ["Stack:"],
+        }
+        $crate::dbg_print_stack! {
+            @printstack
+            stack: $stack,
+            debug: $debug,
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: ["Playfield:"],
+        }
+        $crate::socket_debug_stringify! {
+            sockets: $sockets,
+            mode: [@stringify @framed],
+            lines: $lines,
+            pc: $pc,
+            debug: $debug,
+        }
+    };
+    (
+        @visit
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        instr: $instr:tt,
+        row: $row:literal,
+        col: $col:literal,
+        profile: $profile:tt,
+        heat: [visits $($visits:tt)*],
+        breaks: $breaks:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_observe! {
+            @tick
+            stringmode: $stringmode,
+            bridge: $bridge,
+            instr: $instr,
+            profile: $profile,
+            heat: [visits $($visits)* [$row $col]],
+            breaks: $breaks,
+            $($rest)*
+        }
+    };
+    (
+        @visit
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        instr: $instr:tt,
+        row: $row:literal,
+        col: $col:literal,
+        profile: $profile:tt,
+        heat: [],
+        breaks: $breaks:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_observe! {
+            @tick
+            stringmode: $stringmode,
+            bridge: $bridge,
+            instr: $instr,
+            profile: $profile,
+            heat: [],
+            breaks: $breaks,
+            $($rest)*
+        }
+    };
+    (
+        @tick
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        instr: $instr:tt,
+        profile: $profile:tt,
+        heat: $heat:tt,
+        breaks: $breaks:tt,
+        steps: [count $($steps:tt)*],
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_observe! {
+            @count
+            stringmode: $stringmode,
+            bridge: $bridge,
+            instr: $instr,
+            profile: $profile,
+            heat: $heat,
+            breaks: $breaks,
+            steps: [count $($steps)* []],
+            $($rest)*
+        }
+    };
+    (
+        @tick
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_observe! {
+            @count
+            $($rest)*
+        }
+    };
+    // Only instructions that are about to be executed are counted, not the cells skipped over by a
+    // bridge or read in stringmode.
+    (
+        @count
+        stringmode: [false],
+        bridge: [false],
+        instr: $instr:tt,
+        profile: [$($counts:tt)+],
+        heat: $heat:tt,
+        breaks: $breaks:tt,
+        steps: $steps:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @class
+            instr: $instr,
+            counts: [$($counts)+],
+            others: [heat: $heat breaks: $breaks steps: $steps],
+            $($rest)*
+        }
+    };
+    (
+        @count
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        instr: $instr:tt,
+        profile: $profile:tt,
+        heat: $heat:tt,
+        breaks: $breaks:tt,
+        steps: $steps:tt,
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [
+                $($before)*
+                [observed profile: $profile heat: $heat breaks: $breaks steps: $steps]
+                $($after)*
+            ],
+            $($pst)*
+        }
+    };
+    (
+        @print
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_observe! {
+            @print
+            debug: $debug,
+            find: $debug,
+        }
+    };
+    (
+        @print
+        debug: $debug:tt,
+        find: [],
+    ) => {};
+    (
+        @print
+        debug: $debug:tt,
+        find: [
+            [observe profile: $profile:tt heat: $heat:tt breaks: $breaks:tt steps: $steps:tt]
+            $($findt:tt)*
+        ],
+    ) => {
+        $crate::dbg_observe! {
+            @print @steps
+            debug: $debug,
+            steps: $steps,
+        }
+        $crate::dbg_observe! {
+            @print @found
+            debug: $debug,
+            profile: $profile,
+            heat: $heat,
+        }
+    };
+    (
+        @print
+        debug: $debug:tt,
+        find: [
+            [observed profile: $profile:tt heat: $heat:tt breaks: $breaks:tt steps: $steps:tt]
+            $($findt:tt)*
+        ],
+    ) => {
+        $crate::dbg_observe! {
+            @print @steps
+            debug: $debug,
+            steps: $steps,
+        }
+        $crate::dbg_observe! {
+            @print @found
+            debug: $debug,
+            profile: $profile,
+            heat: $heat,
+        }
+    };
+    (
+        @print
+        debug: $debug:tt,
+        find: [$findh:tt $($findt:tt)*],
+    ) => {
+        $crate::dbg_observe! {
+            @print
+            debug: $debug,
+            find: [$($findt)*],
+        }
+    };
+    (
+        @print @steps
+        debug: $debug:tt,
+        steps: [],
+    ) => {};
+    (
+        @print @steps
+        debug: $debug:tt,
+        steps: [count $($steps:tt)*],
+    ) => {
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("Executed ", ${count($steps)}, " steps")],
+        }
+    };
+    (
+        @print @found
+        debug: $debug:tt,
+        profile: [],
+        heat: $heat:tt,
+    ) => {
+        $crate::dbg_observe! {
+            @print @heat
+            debug: $debug,
+            heat: $heat,
+        }
+    };
+    (
+        @print @found
+        debug: $debug:tt,
+        profile: $counts:tt,
+        heat: $heat:tt,
+    ) => {
+        $crate::dbg_profile! {
+            @table
+            debug: $debug,
+            counts: $counts,
+        }
+        $crate::dbg_observe! {
+            @print @heat
+            debug: $debug,
+            heat: $heat,
+        }
+    };
+    (
+        @print @heat
+        debug: $debug:tt,
+        heat: [],
+    ) => {};
+    (
+        @print @heat
+        debug: $debug:tt,
+        heat: [visits $($visits:tt)*],
+    ) => {
+        $crate::befunge_pm::heatmap! {
+            visits: [$($visits)*],
+            callback: [
+                name: $crate::dbg_observe,
+                pre: [
+                    @print @heatmap
+                    debug: $debug,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @print @heatmap
+        debug: $debug:tt,
+        most: $most:literal,
+        lines: $lines:tt,
+    ) => {
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("Cell visits (.:*#@ from fewest to most, ", $most, " at most):")],
+        }
+        $crate::befunge_stringify! {
+            @stringify @raw
+            lines: $lines,
+            debug: $debug,
+        }
+    };
+}
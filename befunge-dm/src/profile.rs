@@ -0,0 +1,794 @@
+/// Counts the instructions a program executes for the `[profile]` debugging flag, and outputs the
+/// counts when it ends. The counts are kept in base 1 as `profile: [arith number stack move cond
+/// mem io other]` in the flag that [`dbg_observe!`] puts at the front of the debugging flags, one
+/// count for each class of instruction:
+///
+/// - arithmetic: `+`, `-`, `*`, `/`, `%`, `!`, and `` ` ``
+/// - numbers: `0` to `9`
+/// - stack: `:`, `\`, `$`, and `n`
+/// - movement: `>`, `<`, `^`, `v`, `?`, `#`, `[`, `]`, `r`, `j`, `;`, and `k`
+/// - conditionals: `_` and `|`
+/// - memory: `g`, `p`, `'`, and `s`
+/// - I/O: `.`, `,`, `&`, and `~`
+/// - other: `"`, `@`, and anything unknown
+///
+/// The internal rules are:
+///
+/// - `@class`: Counts `instr` and makes a callback with `debug:` set to
+///   `[before.. [observed profile: [counts..] others..] after..]`, where `others` is the rest of
+///   what [`dbg_observe!`] keeps.
+/// - `@table`: Outputs the counts as a table of `const _: &str = "..."`s.
+///
+/// # Example
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! counts {
+///     (debug: [[observed profile: $counts:tt heat: [] breaks: [] steps: []] [noflush] [profile]],) => {
+///         befunge_dm::dbg_profile! {
+///             @table
+///             debug: [[noflush] [profile]],
+///             counts: $counts,
+///         }
+///     };
+/// }
+///
+/// // Expands to `const _: &str = "Instructions executed: 1";`, then a line for each class:
+/// // `"  arithmetic:   1"`, `"  numbers:      0"`, and so on.
+/// befunge_dm::dbg_profile! {
+///     @class
+///     instr: '+',
+///     counts: [[] [] [] [] [] [] [] []],
+///     others: [heat: [] breaks: [] steps: []],
+///     before: [],
+///     after: [[noflush] [profile]],
+///     callback: [
+///         name: counts,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+/// ```
+#[macro_export]
+macro_rules! dbg_profile {
+    (
+        @class
+        instr: '+',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [arith],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '-',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [arith],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '*',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [arith],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '/',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [arith],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '%',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [arith],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '!',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [arith],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '`',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [arith],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '0',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '1',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '2',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '3',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '4',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '5',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '6',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '7',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '8',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '9',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [number],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: ':',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [stack],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '\\',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [stack],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '$',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [stack],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: 'n',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [stack],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '>',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '<',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '^',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: 'v',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '?',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '#',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '[',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: ']',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: 'r',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: 'j',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: ';',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: 'k',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [move],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '_',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [cond],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '|',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [cond],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: 'g',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [mem],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: 'p',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [mem],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '\'',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [mem],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: 's',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [mem],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '.',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [io],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: ',',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [io],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '&',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [io],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '~',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [io],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '"',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [other],
+            $($rest)*
+        }
+    };
+    (
+        @class
+        instr: '@',
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [other],
+            $($rest)*
+        }
+    };
+    // Spaces aren't instructions, so they aren't counted.
+    (
+        @class
+        instr: ' ',
+        counts: [$($counts:tt)*],
+        others: [$($others:tt)*],
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($before)* [observed profile: [$($counts)*] $($others)*] $($after)*],
+            $($pst)*
+        }
+    };
+    (
+        @class
+        instr: $unknown:tt,
+        $($rest:tt)*
+    ) => {
+        $crate::dbg_profile! {
+            @bump
+            class: [other],
+            $($rest)*
+        }
+    };
+    (
+        @bump
+        class: [arith],
+        counts: [[$($arith:tt)*] $number:tt $stack:tt $move:tt $cond:tt $mem:tt $io:tt $other:tt],
+        others: [$($others:tt)*],
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($before)* [observed profile: [[$($arith)* []] $number $stack $move $cond $mem $io $other] $($others)*] $($after)*],
+            $($pst)*
+        }
+    };
+    (
+        @bump
+        class: [number],
+        counts: [$arith:tt [$($number:tt)*] $stack:tt $move:tt $cond:tt $mem:tt $io:tt $other:tt],
+        others: [$($others:tt)*],
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($before)* [observed profile: [$arith [$($number)* []] $stack $move $cond $mem $io $other] $($others)*] $($after)*],
+            $($pst)*
+        }
+    };
+    (
+        @bump
+        class: [stack],
+        counts: [$arith:tt $number:tt [$($stack:tt)*] $move:tt $cond:tt $mem:tt $io:tt $other:tt],
+        others: [$($others:tt)*],
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($before)* [observed profile: [$arith $number [$($stack)* []] $move $cond $mem $io $other] $($others)*] $($after)*],
+            $($pst)*
+        }
+    };
+    (
+        @bump
+        class: [move],
+        counts: [$arith:tt $number:tt $stack:tt [$($move:tt)*] $cond:tt $mem:tt $io:tt $other:tt],
+        others: [$($others:tt)*],
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($before)* [observed profile: [$arith $number $stack [$($move)* []] $cond $mem $io $other] $($others)*] $($after)*],
+            $($pst)*
+        }
+    };
+    (
+        @bump
+        class: [cond],
+        counts: [$arith:tt $number:tt $stack:tt $move:tt [$($cond:tt)*] $mem:tt $io:tt $other:tt],
+        others: [$($others:tt)*],
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($before)* [observed profile: [$arith $number $stack $move [$($cond)* []] $mem $io $other] $($others)*] $($after)*],
+            $($pst)*
+        }
+    };
+    (
+        @bump
+        class: [mem],
+        counts: [$arith:tt $number:tt $stack:tt $move:tt $cond:tt [$($mem:tt)*] $io:tt $other:tt],
+        others: [$($others:tt)*],
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($before)* [observed profile: [$arith $number $stack $move $cond [$($mem)* []] $io $other] $($others)*] $($after)*],
+            $($pst)*
+        }
+    };
+    (
+        @bump
+        class: [io],
+        counts: [$arith:tt $number:tt $stack:tt $move:tt $cond:tt $mem:tt [$($io:tt)*] $other:tt],
+        others: [$($others:tt)*],
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($before)* [observed profile: [$arith $number $stack $move $cond $mem [$($io)* []] $other] $($others)*] $($after)*],
+            $($pst)*
+        }
+    };
+    (
+        @bump
+        class: [other],
+        counts: [$arith:tt $number:tt $stack:tt $move:tt $cond:tt $mem:tt $io:tt [$($other:tt)*]],
+        others: [$($others:tt)*],
+        before: [$($before:tt)*],
+        after: [$($after:tt)*],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            debug: [$($before)* [observed profile: [$arith $number $stack $move $cond $mem $io [$($other)* []]] $($others)*] $($after)*],
+            $($pst)*
+        }
+    };
+    (
+        @table
+        debug: $debug:tt,
+        counts: [[$($arith:tt)*] [$($number:tt)*] [$($stack:tt)*] [$($move:tt)*] [$($cond:tt)*] [$($mem:tt)*] [$($io:tt)*] [$($other:tt)*]],
+    ) => {
+        $crate::dbg_profile! {
+            @total
+            debug: $debug,
+            total: [$($arith)* $($number)* $($stack)* $($move)* $($cond)* $($mem)* $($io)* $($other)*],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("  arithmetic:   ", ${count($arith)})],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("  numbers:      ", ${count($number)})],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("  stack:        ", ${count($stack)})],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("  movement:     ", ${count($move)})],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("  conditionals: ", ${count($cond)})],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("  memory:       ", ${count($mem)})],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("  I/O:          ", ${count($io)})],
+        }
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("  other:        ", ${count($other)})],
+        }
+    };
+    (
+        @total
+        debug: $debug:tt,
+        total: [$($total:tt)*],
+    ) => {
+        $crate::dbg_tagged! {
+            @tagged
+            debug: $debug,
+            msg: [concat!("Instructions executed: ", ${count($total)})],
+        }
+    };
+}
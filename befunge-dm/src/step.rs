@@ -21,6 +21,153 @@
 ///      same time as step 2)
 ///   2. Instructions are executed
 ///   3. Movement occurs
+///
+/// # Self-test
+///
+/// `befunge-dm/tests/fixtures/torture.bfg` is a program that checks its own work as it runs.
+/// It exercises every instruction that doesn't need I/O: all of the arithmetic (including negative
+/// operands), `!`, `` ` ``, both conditionals in both directions, `?`, `:`, `\`, `$`, stringmode,
+/// `#`, `g`, `p` (including self-modification), and `@`, as well as wrapping off all four edges of
+/// the playfield. The `@` is only reached by wrapping off the left edge into the last column of the
+/// bottom row. Each check is laid out so that a wrong result sends the PC into an `X`, which fails
+/// the build with the location of that check. Any change to this macro should keep it running to
+/// completion:
+/// ```
+/// #![recursion_limit = "16384"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/torture.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// `befunge-dm/tests/fixtures/greater.bfg` checks `` ` `` on its own for every combination of
+/// positive, negative, and zero operands, including equal ones, with a 9 underneath each pair that
+/// has to still be there afterwards. The first row also checks `` ` `` with fewer than two values
+/// on the stack, where the missing ones are 0:
+/// ```befunge
+#[doc = include_str!("../tests/fixtures/greater.bfg")]
+/// ```
+/// ```
+/// #![recursion_limit = "8192"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/greater.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// `befunge-dm/tests/fixtures/zero.bfg` works out 0 in five ways (`0 - 0`, `2 - 2`, `-2 + 2`,
+/// `-2 - -2`, and `0 * -3`) and checks that each one goes right at `_`, down at `|`, and isn't less
+/// than 0 according to `` ` ``. The sums for the last two would come out as negative zero, which
+/// [`arith_canonicalize!`] turns into 0 before it goes on the stack:
+/// ```befunge
+#[doc = include_str!("../tests/fixtures/zero.bfg")]
+/// ```
+/// ```
+/// #![recursion_limit = "4096"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/zero.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// # Ragged rows
+///
+/// Rows of program memory don't all have to be the same length. Moving onto a row that is too
+/// short, or reading or writing past its end with `g` or `p`, treats the missing cells as spaces
+/// (and `p` grows the row to fit). Here the PC puts an `A` past the end of the third row, moves
+/// down through an empty row, reads the `A` back, reads a space off the end of the empty row, and
+/// then moves up through all of the short rows and wraps around to the `@`:
+/// ```befunge
+/// "A"52pv
+///
+/// X
+///       >52g"A"-#X_71g" "-#X_^
+///                            @
+/// ```
+/// Or, passed straight to this macro:
+/// ```
+/// #![recursion_limit = "1024"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_step! {
+///     @instr
+///     stack: [],
+///     dir: [right],
+///     stringmode: [false],
+///     bridge: [false],
+///     progstate: [
+///         pre: [],
+///         cur: [
+///             pre: [],
+///             cur: ['"'],
+///             pst: ['A' '"' '5' '2' 'p' 'v'],
+///         ],
+///         pst: [
+///             []
+///             ['X']
+///             [' ' ' ' ' ' ' ' ' ' ' ' '>' '5' '2' 'g' '"' 'A' '"' '-' '#' 'X' '_' '7' '1' 'g' '"' ' ' '"' '-' '#' 'X' '_' '^']
+///             [' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' ' '@']
+///         ],
+///     ],
+///     sockets: "befunge",
+///     debug: [[noflush]],
+/// }
+/// ```
+///
+/// # Numbers in program memory
+///
+/// `p` stores values from 0 to 127 as the characters they're the codes of, and anything else as
+/// the number itself, so `g` always gets back exactly what was put. Here 200 and -7 are each put
+/// at column 1 of row 0 and read back, and a difference from the value put sends the PC into an
+/// `X`:
+/// ```
+/// #![recursion_limit = "1024"]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "55*8*:10p10g-#X_07-:10p10g-#X_@",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush]],
+///         ],
+///     ],
+/// }
+/// ```
+///
+/// Each of the three kinds of value is put the same way every time. Here -7 and 200 are put at
+/// columns 1 and 2 of row 0 and read back onto the stack, and then 64 is put over the `X` at the
+/// end, which only stops the program if it's stored as the character `@`:
+/// ```
+/// #![recursion_limit = "1024"]
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! roundtrip {
+///     (stack: [$($n:literal)*],) => {
+///         const ROUNDTRIP: &[isize] = &[$($n),*];
+///     };
+/// }
+///
+/// befunge_dm::befunge_pm::befunge_source! {
+///     source: "07-10p55*8*20p10g20g88*74*0pX",
+///     callback: [
+///         name: befunge_dm::befunge_init,
+///         pre: [@init],
+///         pst: [
+///             debug: [[noflush] [result roundtrip]],
+///         ],
+///     ],
+/// }
+///
+/// assert_eq!(ROUNDTRIP, [200, -7]);
+/// ```
 /*
     Comments in this macro are formatted as:
 
@@ -37,31 +184,429 @@
     some additional modifications made by me.
 */
 macro_rules! befunge_step {
+    (
+        @init
+        program: $program:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @init
+            program: $program,
+            sockets: "befunge",
+            debug: $debug,
+        }
+    };
     (
         @init
         program: [
             [$hh:tt $($ht:tt)+]
             $($t:tt)+
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("init");
-        $crate::befunge_step! {
-            @instr
-            stack: [],
-            dir: [right],
-            stringmode: [false],
-            bridge: [false],
-            progstate: [
-                pre: [],
-                cur: [
-                    pre: [],
-                    cur: [$hh],
-                    pst: [$($ht)+],
+        $crate::socket_debug_default!(sockets: $sockets, level: info, "init");
+        $crate::dbg_observe! {
+            @init
+            debug: $debug,
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @instr
+                    stack: [],
+                    dir: [right],
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: [],
+                        cur: [
+                            pre: [],
+                            cur: [$hh],
+                            pst: [$($ht)+],
+                        ],
+                        pst: [$($t)+],
+                    ],
+                    sockets: $sockets,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    // Starting somewhere other than the top left corner, or going some other way than right. The
+    // direction is checked first, then the rows are split at `y` and the row that's left at the
+    // front is split at `x`. Both splits are padded with `oob`, which is only ever at the front of
+    // what's left when the start is outside of the playfield.
+    (
+        @init
+        program: $program:tt,
+        start: [$x:literal $y:literal],
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        macro_rules! befunge_step_start_check {
+            (left) => {
+                befunge_step_start_check!(@go left);
+            };
+            (right) => {
+                befunge_step_start_check!(@go right);
+            };
+            (up) => {
+                befunge_step_start_check!(@go up);
+            };
+            (down) => {
+                befunge_step_start_check!(@go down);
+            };
+            (@go $$dir:ident) => {
+                $crate::befunge_pm::base1_from_literal! {
+                    value: $y,
+                    callback: [
+                        name: $crate::befunge_step,
+                        pre: [
+                            @init @row
+                            program: $program,
+                            start: [$x $y],
+                        ],
+                        pst: [
+                            direction: $$dir,
+                            sockets: $sockets,
+                            debug: $debug,
+                        ],
+                    ],
+                }
+            };
+            ($$other:ident) => {
+                $crate::befunge_error! {
+                    @initerr @direction
+                    direction: $$other,
+                }
+            };
+        }
+        befunge_step_start_check!($direction);
+    };
+    (
+        @init @row
+        program: $program:tt,
+        start: [$x:tt $y:tt],
+        num: [[neg] $mag:tt],
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_error! {
+            @initerr @start
+            start: [$x $y],
+        }
+    };
+    (
+        @init @row
+        program: $program:tt,
+        start: $start:tt,
+        num: [$sign:tt [$($rows:tt)*]],
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::list_split_at_length_of! {
+            @init
+            pad: oob,
+            lenof: [$($rows)*],
+            split: $program,
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @init @col
+                    start: $start,
+                ],
+                pst: [
+                    direction: $direction,
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    (
+        @init @col
+        start: $start:tt,
+        l: $l:tt,
+        r: [oob],
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_error! {
+            @initerr @start
+            start: $start,
+        }
+    };
+    (
+        @init @col
+        start: [$x:tt $y:tt],
+        l: $l:tt,
+        r: [$row:tt $($pst:tt)*],
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_pm::base1_from_literal! {
+            value: $x,
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @init @split
+                    start: [$x $y],
+                    pre: $l,
+                    row: $row,
+                    pst: [$($pst)*],
+                ],
+                pst: [
+                    direction: $direction,
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    (
+        @init @split
+        start: $start:tt,
+        pre: $pre:tt,
+        row: $row:tt,
+        pst: $pst:tt,
+        num: [[neg] $mag:tt],
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_error! {
+            @initerr @start
+            start: $start,
+        }
+    };
+    (
+        @init @split
+        start: $start:tt,
+        pre: $pre:tt,
+        row: $row:tt,
+        pst: $pst:tt,
+        num: [$sign:tt [$($cols:tt)*]],
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_pm::split_row! {
+            pad: oob,
+            lenof: [$($cols)*],
+            split: $row,
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @init @start
+                    start: $start,
+                    pre: $pre,
+                ],
+                pst: [
+                    pst: $pst,
+                    direction: $direction,
+                    sockets: $sockets,
+                    debug: $debug,
                 ],
-                pst: [$($t)+],
             ],
+        }
+    };
+    (
+        @init @start
+        start: $start:tt,
+        pre: $pre:tt,
+        l: $l:tt,
+        r: [oob $($marker:tt)?],
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_error! {
+            @initerr @start
+            start: $start,
+        }
+    };
+    (
+        @init @start
+        start: $start:tt,
+        pre: $pre:tt,
+        l: $l:tt,
+        r: [$cur:tt $($cpst:tt)*],
+        pst: $pst:tt,
+        direction: $direction:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, level: info, "init");
+        $crate::dbg_observe! {
+            @init
             debug: $debug,
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @instr
+                    stack: [],
+                    dir: [$direction],
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $l,
+                            cur: [$cur],
+                            pst: [$($cpst)*],
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    /*
+        ####### ######   #####  ####### ######  #     # ### #     #  #####
+        #     # #     # #     # #       #     # #     #  #  ##    # #     #
+        #     # #     # #       #       #     # #     #  #  # #   # #
+        #     # ######   #####  #####   ######  #     #  #  #  #  # #  ####
+        #     # #     #       # #       #   #    #   #   #  #   # # #     #
+        #     # #     # #     # #       #    #    # #    #  #    ## #     #
+        ####### ######   #####  ####### #     #    #    ### #     #  #####
+
+        OBSERVING
+        With `[profile]`, `[heatmap]`, `[breakpoints ..]`, or `[stepcount]`, note down each cell
+        the PC lands on before dealing with it (see `dbg_observe!`)
+    */
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        progstate: [
+            pre: [$($pre:tt)*],
+            cur: [
+                pre: [$($cpre:tt)*],
+                cur: [$instr:tt],
+                pst: [$($cpst:tt)*],
+            ],
+            pst: [$($pst:tt)*],
+        ],
+        sockets: $sockets:tt,
+        debug: [[observe profile: $profile:tt heat: $heat:tt breaks: $breaks:tt steps: $steps:tt] $($debug:tt)*],
+    ) => {
+        $crate::dbg_observe! {
+            @break
+            row: ${count($pre)},
+            col: ${count($cpre)},
+            breaks: $breaks,
+            stack: $stack,
+            dir: $dir,
+            stringmode: $stringmode,
+            bridge: $bridge,
+            lines: [$($pre)* [$($cpre)* $instr $($cpst)*] $($pst)*],
+            pc: [[[pos] [$(${ignore($cpre)} [])*]] [[pos] [$(${ignore($pre)} [])*]]],
+            sockets: $sockets,
+            debug: [[observe profile: $profile heat: $heat breaks: $breaks steps: $steps] $($debug)*],
+        }
+        $crate::dbg_observe! {
+            @visit
+            stringmode: $stringmode,
+            bridge: $bridge,
+            instr: $instr,
+            row: ${count($pre)},
+            col: ${count($cpre)},
+            profile: $profile,
+            heat: $heat,
+            breaks: $breaks,
+            steps: $steps,
+            before: [],
+            after: [$($debug)*],
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @instr
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: $stringmode,
+                    bridge: $bridge,
+                    progstate: [
+                        pre: [$($pre)*],
+                        cur: [
+                            pre: [$($cpre)*],
+                            cur: [$instr],
+                            pst: [$($cpst)*],
+                        ],
+                        pst: [$($pst)*],
+                    ],
+                    sockets: $sockets,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        progstate: [
+            pre: [$($pre:tt)*],
+            cur: [
+                pre: [$($cpre:tt)*],
+                cur: [$instr:tt],
+                pst: [$($cpst:tt)*],
+            ],
+            pst: [$($pst:tt)*],
+        ],
+        sockets: $sockets:tt,
+        debug: [[kiter $kiter:tt] [observe profile: $profile:tt heat: $heat:tt breaks: $breaks:tt steps: $steps:tt] $($debug:tt)*],
+    ) => {
+        $crate::dbg_observe! {
+            @break
+            row: ${count($pre)},
+            col: ${count($cpre)},
+            breaks: $breaks,
+            stack: $stack,
+            dir: $dir,
+            stringmode: $stringmode,
+            bridge: $bridge,
+            lines: [$($pre)* [$($cpre)* $instr $($cpst)*] $($pst)*],
+            pc: [[[pos] [$(${ignore($cpre)} [])*]] [[pos] [$(${ignore($pre)} [])*]]],
+            sockets: $sockets,
+            debug: [[kiter $kiter] [observe profile: $profile heat: $heat breaks: $breaks steps: $steps] $($debug)*],
+        }
+        $crate::dbg_observe! {
+            @visit
+            stringmode: $stringmode,
+            bridge: $bridge,
+            instr: $instr,
+            row: ${count($pre)},
+            col: ${count($cpre)},
+            profile: $profile,
+            heat: $heat,
+            breaks: $breaks,
+            steps: $steps,
+            before: [[kiter $kiter]],
+            after: [$($debug)*],
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @instr
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: $stringmode,
+                    bridge: $bridge,
+                    progstate: [
+                        pre: [$($pre)*],
+                        cur: [
+                            pre: [$($cpre)*],
+                            cur: [$instr],
+                            pst: [$($cpst)*],
+                        ],
+                        pst: [$($pst)*],
+                    ],
+                    sockets: $sockets,
+                ],
+                pst: [],
+            ],
         }
     };
     /*
@@ -90,9 +635,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("stringmode: off");
+        $crate::socket_debug_default!(sockets: $sockets, "stringmode: off");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '"',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: $stack,
@@ -108,6 +661,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -127,9 +681,10 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("stringmode: numeric");
+        $crate::socket_debug_default!(sockets: $sockets, "stringmode: numeric");
         $crate::befunge_step! {
             @move
             stack: [[] $($stack)*],
@@ -145,6 +700,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst:tt,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -164,9 +720,10 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("stringmode: char");
+        $crate::socket_debug_default!(sockets: $sockets, "stringmode: char");
         $crate::char_to_code! {
             @match
             char: $char,
@@ -189,6 +746,7 @@ macro_rules! befunge_step {
                     ],
                 ],
                 pst: [
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -210,9 +768,10 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("stringmode: other num???");
+        $crate::socket_debug_default!(sockets: $sockets, "stringmode: other num???");
         $crate::befunge_step! {
             @move
             stack: [[[$cursgn] [$($curnum)*]] $($stack)*],
@@ -228,6 +787,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -249,17 +809,446 @@ macro_rules! befunge_step {
         dir: $dir:tt,
         stringmode: [false],
         bridge: [true],
-        progstate: $progstate:tt,
-        debug: $debug:tt,
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: [$cur:tt],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "bridge: jumping over instruction");
+        $crate::dbg_trace_bridge! {
+            @bridge
+            debug: $debug,
+            instr: $cur,
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: $cpre,
+                    cur: [$cur],
+                    pst: $cpst,
+                ],
+                pst: $pst,
+            ],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // catch jumps (see `j`). `[jump n]` jumps over `n` more instructions before running the one
+    // after them. `[jumpback n]` is for negative jumps, which go the other way with the direction
+    // turned around. Once it's jumped back far enough, the direction is turned back around again
+    // for the move onto the instruction to run.
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [jump [$jumph:tt $($jumpt:tt)*]],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "jump: jumping over instruction");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [jump [$($jumpt)*]],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [jump []],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "jump: landed");
+        $crate::befunge_step! {
+            @instr
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [jumpback [$jumph:tt $($jumpt:tt)*]],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "jump: jumping back over instruction");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [jumpback [$($jumpt)*]],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [jumpback []],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "jump: landed behind");
+        $crate::befunge_step! {
+            @catch @turn @back
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // catch iterations (see `k`). `[iterate n]` moves over spaces and comments (see `;`) to the
+    // instruction to run, then runs it `n` times by putting `[kiter n-1]` at the front of the
+    // debugging flags, which the movement rules count down instead of moving. `n` of 0 just moves
+    // past the instruction.
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [iterate $count:tt],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: [' '],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "iterate: looking for instruction");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [iterate $count],
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: $cpre,
+                    cur: [' '],
+                    pst: $cpst,
+                ],
+                pst: $pst,
+            ],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [iterate $count:tt],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: [';'],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "iterate: skipping comment");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [comment [iterate $count]],
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: $cpre,
+                    cur: [';'],
+                    pst: $cpst,
+                ],
+                pst: $pst,
+            ],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [iterate []],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "iterate: skipping instruction");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [iterate [$counth:tt $($countt:tt)*]],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: [$($debug:tt)*],
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "iterate: found instruction");
+        $crate::befunge_step! {
+            @instr
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: [[kiter [$($countt)*]] $($debug)*],
+        }
+    };
+    // catch fetches (see `'`) and stores (see `s`). `[fetch]` pushes the value of the cell it lands
+    // on and `[store x]` overwrites it with `x`, and either way it's skipped over afterwards.
+    (
+        @instr
+        stack: [$($stack:tt)*],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [fetch],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: [[$sgn:tt $val:tt]],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "fetch: numeric");
+        $crate::befunge_step! {
+            @move
+            stack: [[$sgn $val] $($stack)*],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: $cpre,
+                    cur: [[$sgn $val]],
+                    pst: $cpst,
+                ],
+                pst: $pst,
+            ],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [fetch],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: [$char:tt],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "fetch: char");
+        $crate::char_to_code! {
+            @match
+            char: $char,
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @catch @char_to_code
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: [$char],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                ],
+                pst: [
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [store $new:tt],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: [$old:tt],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "store: overwriting cell");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: $cpre,
+                    cur: [$new],
+                    pst: $cpst,
+                ],
+                pst: $pst,
+            ],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // catch comments (see `;`). `[comment b]` moves over everything, `"` included, until it lands
+    // on the closing `;`, and then keeps moving with the bridge `b` it was put in front of. That's
+    // `[false]` unless `k` was still looking for its instruction.
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [comment $after:tt],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: [';'],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "comment: end");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: $after,
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: $cpre,
+                    cur: [';'],
+                    pst: $cpst,
+                ],
+                pst: $pst,
+            ],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [comment $after:tt],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("bridge: jumping over instruction");
+        $crate::socket_debug_default!(sockets: $sockets, "comment: skipping");
         $crate::befunge_step! {
             @move
             stack: $stack,
             dir: $dir,
             stringmode: [false],
-            bridge: [false],
+            bridge: [comment $after],
             progstate: $progstate,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -290,9 +1279,10 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("empty cell");
+        $crate::socket_debug_default!(sockets: $sockets, "empty cell");
         $crate::befunge_step! {
             @move
             stack: $stack,
@@ -308,6 +1298,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -346,13 +1337,31 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::socket_debug_default!(
+            sockets: $sockets,
             "add",
             $($($stack0sgn)? ${count($stack0val)}, )?
             $($($($stack1sgn)? ${count($stack1val)})?)?
         );
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '+',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '+',
+            pops: [[] []],
+            got: [$([$($stack0val)*] $([$($stack1val)*])?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::arith_add! {
             @add
             a: [[$($($stack0sgn)?)?] [$($($stack0val)*)?]],
@@ -376,6 +1385,7 @@ macro_rules! befunge_step {
                     ],
                 ],
                 pst: [
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -416,13 +1426,31 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::socket_debug_default!(
+            sockets: $sockets,
             "sub",
             $($($stack0sgn)? ${count($stack0val)}, )?
             $($($($stack1sgn)? ${count($stack1val)})?)?
         );
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '-',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '-',
+            pops: [[] []],
+            got: [$([$($stack0val)*] $([$($stack1val)*])?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::arith_sub! {
             @sub
             a: [[$($($($stack1sgn)?)?)?] [$($($($stack1val)*)?)?]],
@@ -442,36 +1470,388 @@ macro_rules! befunge_step {
                             cur: ['-'],
                             pst: $cpst,
                         ],
-                        pst: $pst,
+                        pst: $pst,
+                    ],
+                ],
+                pst: [
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    /*
+                    #     #     # #     # #
+         #   #     ###    ##   ## #     # #
+          # #       #     # # # # #     # #
+        #######           #  #  # #     # #
+          # #       #     #     # #     # #
+         #   #     ###    #     # #     # #
+                    #     #     #  #####  #######
+
+        * : MUL
+        push(stack[0] * stack[1])
+    */
+    (
+        @instr
+        stack: [
+            $(
+                [[$($stack0sgn:tt)?] [$($stack0val:tt)*]]
+                $(
+                    [[$($stack1sgn:tt)?] [$($stack1val:tt)*]]
+                    $($stackrest:tt)*
+                )?
+            )?
+        ],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: ['*'],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(
+            sockets: $sockets,
+            "mul",
+            $($($stack0sgn)? ${count($stack0val)}, )?
+            $($($($stack1sgn)? ${count($stack1val)})?)?
+        );
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '*',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '*',
+            pops: [[] []],
+            got: [$([$($stack0val)*] $([$($stack1val)*])?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::arith_mul! {
+            @mul
+            a: [[$($($stack0sgn)?)?] [$($($stack0val)*)?]],
+            b: [[$($($($stack1sgn)?)?)?] [$($($($stack1val)*)?)?]],
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @catch @arith
+                    stack: [$($($($stackrest)*)?)?],
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['*'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                ],
+                pst: [
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    /*
+              #     #     ######  ### #     #
+             #     ###    #     #  #  #     #
+            #       #     #     #  #  #     #
+           #              #     #  #  #     #
+          #         #     #     #  #   #   #
+         #         ###    #     #  #    # #
+        #           #     ######  ###    #
+
+        / : DIV
+        push(stack[1] / stack[0])
+    */
+    (
+        @instr
+        stack: [
+            $(
+                [[$stack0sgn:tt] [$($stack0val:tt)*]]
+                $(
+                    [[$stack1sgn:tt] [$($stack1val:tt)*]]
+                    $($stackrest:tt)*
+                )?
+            )?
+        ],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: ['/'],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(
+            sockets: $sockets,
+            "div",
+            $($stack0sgn ${count($stack0val)}, )?
+            $($($stack1sgn ${count($stack1val)})?)?
+        );
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '/',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '/',
+            pops: [[] []],
+            got: [$($stack0sgn $($stack1sgn)?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
+        // With `[divmode floor]`, the quotient is rounded down rather than towards zero.
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[divmode floor]],
+            expand: [
+                $crate::arith_div! {
+                    @div
+                    a: [[$($($stack1sgn)?)?] [$($($($stack1val)*)?)?]],
+                    b: [[$($stack0sgn)?] [$($($stack0val)*)?]],
+                    divmode: floor,
+                    sockets: $sockets,
+                    callback: [
+                        name: $crate::befunge_step,
+                        pre: [
+                            @catch @arith
+                            stack: [$($($($stackrest)*)?)?],
+                            dir: $dir,
+                            stringmode: [false],
+                            bridge: [false],
+                            progstate: [
+                                pre: $pre,
+                                cur: [
+                                    pre: $cpre,
+                                    cur: ['/'],
+                                    pst: $cpst,
+                                ],
+                                pst: $pst,
+                            ],
+                        ],
+                        pst: [
+                            sockets: $sockets,
+                            debug: $debug,
+                        ],
+                    ],
+                }
+            ],
+            orelse: [
+                $crate::arith_div! {
+                    @div
+                    a: [[$($($stack1sgn)?)?] [$($($($stack1val)*)?)?]],
+                    b: [[$($stack0sgn)?] [$($($stack0val)*)?]],
+                    sockets: $sockets,
+                    callback: [
+                        name: $crate::befunge_step,
+                        pre: [
+                            @catch @arith
+                            stack: [$($($($stackrest)*)?)?],
+                            dir: $dir,
+                            stringmode: [false],
+                            bridge: [false],
+                            progstate: [
+                                pre: $pre,
+                                cur: [
+                                    pre: $cpre,
+                                    cur: ['/'],
+                                    pst: $cpst,
+                                ],
+                                pst: $pst,
+                            ],
+                        ],
+                        pst: [
+                            sockets: $sockets,
+                            debug: $debug,
+                        ],
+                    ],
+                }
+            ],
+        }
+    };
+    /*
+        ###   #     #     #     # ####### ######
+        # #  #     ###    ##   ## #     # #     #
+        ### #       #     # # # # #     # #     #
+           #              #  #  # #     # #     #
+          # ###     #     #     # #     # #     #
+         #  # #    ###    #     # #     # #     #
+        #   ###     #     #     # ####### ######
+
+        % : MOD
+        push(stack[1] % stack[0])
+    */
+    (
+        @instr
+        stack: [
+            $(
+                [[$stack0sgn:tt] [$($stack0val:tt)*]]
+                $(
+                    [[$stack1sgn:tt] [$($stack1val:tt)*]]
+                    $($stackrest:tt)*
+                )?
+            )?
+        ],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: ['%'],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(
+            sockets: $sockets,
+            "div",
+            $($stack0sgn ${count($stack0val)}, )?
+            $($($stack1sgn ${count($stack1val)})?)?
+        );
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '%',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '%',
+            pops: [[] []],
+            got: [$($stack0sgn $($stack1sgn)?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
+        // With `[divmode floor]`, the remainder takes the sign of the divisor rather than the
+        // dividend.
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[divmode floor]],
+            expand: [
+                $crate::arith_mod! {
+                    @mod
+                    a: [[$($($stack1sgn)?)?] [$($($($stack1val)*)?)?]],
+                    b: [[$($stack0sgn)?] [$($($stack0val)*)?]],
+                    divmode: floor,
+                    sockets: $sockets,
+                    callback: [
+                        name: $crate::befunge_step,
+                        pre: [
+                            @catch @arith
+                            stack: [$($($($stackrest)*)?)?],
+                            dir: $dir,
+                            stringmode: [false],
+                            bridge: [false],
+                            progstate: [
+                                pre: $pre,
+                                cur: [
+                                    pre: $cpre,
+                                    cur: ['%'],
+                                    pst: $cpst,
+                                ],
+                                pst: $pst,
+                            ],
+                        ],
+                        pst: [
+                            sockets: $sockets,
+                            debug: $debug,
+                        ],
                     ],
-                ],
-                pst: [
-                    debug: $debug,
-                ],
+                }
+            ],
+            orelse: [
+                $crate::arith_mod! {
+                    @mod
+                    a: [[$($($stack1sgn)?)?] [$($($($stack1val)*)?)?]],
+                    b: [[$($stack0sgn)?] [$($($stack0val)*)?]],
+                    sockets: $sockets,
+                    callback: [
+                        name: $crate::befunge_step,
+                        pre: [
+                            @catch @arith
+                            stack: [$($($($stackrest)*)?)?],
+                            dir: $dir,
+                            stringmode: [false],
+                            bridge: [false],
+                            progstate: [
+                                pre: $pre,
+                                cur: [
+                                    pre: $cpre,
+                                    cur: ['%'],
+                                    pst: $cpst,
+                                ],
+                                pst: $pst,
+                            ],
+                        ],
+                        pst: [
+                            sockets: $sockets,
+                            debug: $debug,
+                        ],
+                    ],
+                }
             ],
         }
     };
     /*
-                    #     #     # #     # #
-         #   #     ###    ##   ## #     # #
-          # #       #     # # # # #     # #
-        #######           #  #  # #     # #
-          # #       #     #     # #     # #
-         #   #     ###    #     # #     # #
-                    #     #     #  #####  #######
+          ###       #     #     # ####### #######
+          ###      ###    ##    # #     #    #
+          ###       #     # #   # #     #    #
+           #              #  #  # #     #    #
+                    #     #   # # #     #    #
+          ###      ###    #    ## #     #    #
+          ###       #     #     # #######    #
 
-        * : MUL
-        push(stack[0] * stack[1])
+        ! : NOT
+        if stack[0] == 0 {
+            push(1)
+        } else {
+            push(0)
+        }
     */
     (
         @instr
         stack: [
             $(
-                [[$($stack0sgn:tt)?] [$($stack0val:tt)*]]
-                $(
-                    [[$($stack1sgn:tt)?] [$($stack1val:tt)*]]
-                    $($stackrest:tt)*
-                )?
+                [[$($stack0sgn:tt)?] []]
+                $($stackrest:tt)*
             )?
         ],
         dir: $dir:tt,
@@ -481,68 +1861,55 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['*'],
+                cur: ['!'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!(
-            "mul",
-            $($($stack0sgn)? ${count($stack0val)}, )?
-            $($($($stack1sgn)? ${count($stack1val)})?)?
-        );
-        $crate::arith_mul! {
-            @mul
-            a: [[$($($stack0sgn)?)?] [$($($stack0val)*)?]],
-            b: [[$($($($stack1sgn)?)?)?] [$($($($stack1val)*)?)?]],
-            callback: [
-                name: $crate::befunge_step,
-                pre: [
-                    @catch @arith
-                    stack: [$($($($stackrest)*)?)?],
-                    dir: $dir,
-                    stringmode: [false],
-                    bridge: [false],
-                    progstate: [
-                        pre: $pre,
-                        cur: [
-                            pre: $cpre,
-                            cur: ['*'],
-                            pst: $cpst,
-                        ],
-                        pst: $pst,
-                    ],
-                ],
-                pst: [
-                    debug: $debug,
+        $crate::socket_debug_default!(sockets: $sockets, "not0 (stack head is zero)");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '!',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '!',
+            pops: [[]],
+            got: [$([$($stack0sgn)?])?],
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::befunge_step! {
+            @move
+            stack: [[[pos] [[]]] $($($stackrest)*)?],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: $cpre,
+                    cur: ['!'],
+                    pst: $cpst,
                 ],
+                pst: $pst,
             ],
+            sockets: $sockets,
+            debug: $debug,
         }
     };
-    /*
-              #     #     ######  ### #     #
-             #     ###    #     #  #  #     #
-            #       #     #     #  #  #     #
-           #              #     #  #  #     #
-          #         #     #     #  #   #   #
-         #         ###    #     #  #    # #
-        #           #     ######  ###    #
-
-        / : DIV
-        push(stack[1] / stack[0])
-    */
     (
         @instr
         stack: [
-            $(
-                [[$stack0sgn:tt] [$($stack0val:tt)*]]
-                $(
-                    [[$stack1sgn:tt] [$($stack1val:tt)*]]
-                    $($stackrest:tt)*
-                )?
-            )?
+            [[$stack0sgn:tt] [$($stack0val:tt)+]]
+            $($stackrest:tt)*
         ],
         dir: $dir:tt,
         stringmode: [false],
@@ -551,57 +1918,56 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['/'],
+                cur: ['!'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!(
-            "div",
-            $($($stack0sgn)? ${count($stack0val)}, )?
-            $($($($stack1sgn)? ${count($stack1val)})?)?
-        );
-        $crate::arith_div! {
-            @div
-            a: [[$($($stack1sgn)?)?] [$($($($stack1val)*)?)?]],
-            b: [[$($stack0sgn)?] [$($($stack0val)*)?]],
-            callback: [
-                name: $crate::befunge_step,
-                pre: [
-                    @catch @arith
-                    stack: [$($($($stackrest)*)?)?],
-                    dir: $dir,
-                    stringmode: [false],
-                    bridge: [false],
-                    progstate: [
-                        pre: $pre,
-                        cur: [
-                            pre: $cpre,
-                            cur: ['/'],
-                            pst: $cpst,
-                        ],
-                        pst: $pst,
-                    ],
-                ],
-                pst: [
-                    debug: $debug,
+        $crate::socket_debug_default!(sockets: $sockets, "not1 (stack head is nonzero)");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '!',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::befunge_step! {
+            @move
+            stack: [[[pos] []] $($stackrest)*],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: $cpre,
+                    cur: ['!'],
+                    pst: $cpst,
                 ],
+                pst: $pst,
             ],
+            sockets: $sockets,
+            debug: $debug,
         }
     };
     /*
-        ###   #     #     #     # ####### ######
-        # #  #     ###    ##   ## #     # #     #
-        ### #       #     # # # # #     # #     #
-           #              #  #  # #     # #     #
-          # ###     #     #     # #     # #     #
-         #  # #    ###    #     # #     # #     #
-        #   ###     #     #     # ####### ######
+          ###       #      #####  ######  #######
+          ###      ###    #     # #     #    #
+           #        #     #       #     #    #
+            #             #  #### ######     #
+                    #     #     # #   #      #
+                   ###    #     # #    #     #
+                    #      #####  #     #    #
 
-        % : MOD
-        push(stack[1] % stack[0])
+        ` : GRT
+        if stack[1] > stack[0] {
+            push(1)
+        } else {
+            push(0)
+        }
     */
     (
         @instr
@@ -621,26 +1987,44 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['%'],
+                cur: ['`'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::socket_debug_default!(
-            "div",
-            $($($stack0sgn)? ${count($stack0val)}, )?
-            $($($($stack1sgn)? ${count($stack1val)})?)?
+            sockets: $sockets,
+            "grt",
+            $($stack0sgn ${count($stack0val)}, )?
+            $($($stack1sgn ${count($stack1val)})?)?
         );
-        $crate::arith_mod! {
-            @mod
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '`',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '`',
+            pops: [[] []],
+            got: [$($stack0sgn $($stack1sgn)?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::arith_cmp! {
+            @cmp
             a: [[$($($stack1sgn)?)?] [$($($($stack1val)*)?)?]],
             b: [[$($stack0sgn)?] [$($($stack0val)*)?]],
             callback: [
                 name: $crate::befunge_step,
                 pre: [
-                    @catch @arith
+                    @catch @grt
                     stack: [$($($($stackrest)*)?)?],
                     dir: $dir,
                     stringmode: [false],
@@ -649,42 +2033,34 @@ macro_rules! befunge_step {
                         pre: $pre,
                         cur: [
                             pre: $cpre,
-                            cur: ['%'],
+                            cur: ['`'],
                             pst: $cpst,
                         ],
                         pst: $pst,
                     ],
                 ],
                 pst: [
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
         }
     };
     /*
-          ###       #     #     # ####### #######
-          ###      ###    ##    # #     #    #
-          ###       #     # #   # #     #    #
-           #              #  #  # #     #    #
-                    #     #   # # #     #    #
-          ###      ###    #    ## #     #    #
-          ###       #     #     # #######    #
+          #         #     ######   #####  ######
+           #       ###    #     # #     # #     #
+            #       #     #     # #       #     #
+             #            ######  #       ######
+            #       #     #       #       #   #
+           #       ###    #       #     # #    #
+          #         #     #        #####  #     #
 
-        ! : NOT
-        if stack[0] == 0 {
-            push(1)
-        } else {
-            push(0)
-        }
+        > : PCR
+        pc = right
     */
-    (
-        @instr
-        stack: [
-            $(
-                [[$($stack0sgn:tt)?] []]
-                $($stackrest:tt)*
-            )?
-        ],
+    (
+        @instr
+        stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -692,38 +2068,56 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['!'],
+                cur: ['>'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("not0 (stack head is zero)");
+        $crate::socket_debug_default!(sockets: $sockets, "pcr");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '>',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
-            stack: [[[pos] [[]]] $($($stackrest)*)?],
-            dir: $dir,
+            stack: $stack,
+            dir: [right],
             stringmode: [false],
             bridge: [false],
             progstate: [
                 pre: $pre,
                 cur: [
                     pre: $cpre,
-                    cur: ['!'],
+                    cur: ['>'],
                     pst: $cpst,
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
+    /*
+             #      #     ######   #####  #
+            #      ###    #     # #     # #
+           #        #     #     # #       #
+          #               ######  #       #
+           #        #     #       #       #
+            #      ###    #       #     # #
+             #      #     #        #####  #######
+
+        < : PCL
+        pc = left
+    */
     (
         @instr
-        stack: [
-            [[$stack0sgn:tt] [$($stack0val:tt)+]]
-            $($stackrest:tt)*
-        ],
+        stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -731,54 +2125,56 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['!'],
+                cur: ['<'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("not1 (stack head is nonzero)");
+        $crate::socket_debug_default!(sockets: $sockets, "pcl");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '<',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
-            stack: [[[pos] []] $($stackrest)*],
-            dir: $dir,
+            stack: $stack,
+            dir: [left],
             stringmode: [false],
             bridge: [false],
             progstate: [
                 pre: $pre,
                 cur: [
                     pre: $cpre,
-                    cur: ['!'],
+                    cur: ['<'],
                     pst: $cpst,
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
     /*
-          ###       #      #####  ######  #######
-          ###      ###    #     # #     #    #
-           #        #     #       #     #    #
-            #             #  #### ######     #
-                    #     #     # #   #      #
-                   ###    #     # #    #     #
-                    #      #####  #     #    #
+           #        #     ######   #####  #     #
+          # #      ###    #     # #     # #     #
+         #   #      #     #     # #       #     #
+                          ######  #       #     #
+                    #     #       #       #     #
+                   ###    #       #     # #     #
+                    #     #        #####   #####
 
-        ` : GRT
-        if stack[1] > stack[0] {
-            push(1)
-        } else {
-            push(0)
-        }
+        ^ : PCU
+        pc = up
     */
     (
-        // Cover two cases:
-        //   - stack = []
-        //   - head(stack) = 0
         @instr
-        stack: [$([[$($sgn:tt)?] []])?],
+        stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -786,37 +2182,56 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['`'],
+                cur: ['^'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("grt0 (empty stack or head is zero)");
+        $crate::socket_debug_default!(sockets: $sockets, "pcu");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '^',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
-            stack: [[[pos] []]],
-            dir: $dir,
+            stack: $stack,
+            dir: [up],
             stringmode: [false],
             bridge: [false],
             progstate: [
                 pre: $pre,
                 cur: [
                     pre: $cpre,
-                    cur: ['`'],
+                    cur: ['^'],
                     pst: $cpst,
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
+    /*
+                    #     ######   #####  ######
+         #    #    ###    #     # #     # #     #
+         #    #     #     #     # #       #     #
+         #    #           ######  #       #     #
+         #    #     #     #       #       #     #
+          #  #     ###    #       #     # #     #
+           ##       #     #        #####  ######
+
+        v : PCD
+        pc = up
+    */
     (
-        // Cover one case:
-        //   - stack = [n] where n > 0
         @instr
-        stack: [[[$(pos)?] [$($topval:tt)+]]],
+        stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -824,38 +2239,57 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['`'],
+                cur: ['v'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("grt1", ${count($topval)});
+        $crate::socket_debug_default!(sockets: $sockets, "pcd");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: 'v',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
-            stack: [[[pos] []]],
-            dir: $dir,
+            stack: $stack,
+            dir: [down],
             stringmode: [false],
             bridge: [false],
             progstate: [
                 pre: $pre,
                 cur: [
                     pre: $cpre,
-                    cur: ['`'],
+                    cur: ['v'],
                     pst: $cpst,
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
+    /*
+         #####      #     ######   #####
+        #     #    ###    #     # #     #
+              #     #     #     # #
+           ###            ######  #
+           #        #     #       #
+                   ###    #       #     #
+           #        #     #        #####
+                                          #######
+
+        ? : PC_
+        pc = random(up, down, left, right), or the next direction from `[random ..]`
+    */
     (
-        // Cover one case:
-        //   - stack = [n] where n is negative
-        // This would have stack[0] = n and stack[1] = 0, so unconditionally push 1 to the stack.
         @instr
-        stack: [[[neg] [$($topval:tt)*]]],
+        stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -863,42 +2297,324 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['`'],
+                cur: ['?'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("grt2", -${count($topval)});
+        $crate::socket_debug_default!(sockets: $sockets, "pc_");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '?',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
-            @move
-            stack: [[[pos] [[]]]],
+            @pc_ @pick
+            stack: $stack,
             dir: $dir,
-            stringmode: [false],
-            bridge: [false],
             progstate: [
                 pre: $pre,
                 cur: [
                     pre: $cpre,
-                    cur: ['`'],
+                    cur: ['?'],
                     pst: $cpst,
                 ],
                 pst: $pst,
             ],
-            debug: $debug,
+            find: $debug,
+            seen: [],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // Without `[random ..]` in the debugging flags, the direction is picked at random.
+    (
+        @pc_ @pick
+        stack: $stack:tt,
+        dir: $dir:tt,
+        progstate: $progstate:tt,
+        find: [],
+        seen: $seen:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_pm::choose_random! {
+            choices: [[left] [right] [up] [down]],
+            unwrap: true,
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @catch @pc_
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: $progstate,
+                ],
+                pst: [
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    // With `[random cycle ..]`, the direction is taken from the front of the list and put back at
+    // the end of it. With `[random once ..]`, it isn't put back, so running out is an error.
+    (
+        @pc_ @pick
+        stack: $stack:tt,
+        dir: $dir:tt,
+        progstate: $progstate:tt,
+        find: [[random cycle $next:ident $($dirs:ident)*] $($findt:tt)*],
+        seen: [$($seen:tt)*],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @pc_ @next
+            stack: $stack,
+            dir: $dir,
+            progstate: $progstate,
+            next: $next,
+            sockets: $sockets,
+            debug: [$($seen)* [random cycle $($dirs)* $next] $($findt)*],
+        }
+    };
+    (
+        @pc_ @pick
+        stack: $stack:tt,
+        dir: $dir:tt,
+        progstate: $progstate:tt,
+        find: [[random once $next:ident $($dirs:ident)*] $($findt:tt)*],
+        seen: [$($seen:tt)*],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @pc_ @next
+            stack: $stack,
+            dir: $dir,
+            progstate: $progstate,
+            next: $next,
+            sockets: $sockets,
+            debug: [$($seen)* [random once $($dirs)*] $($findt)*],
+        }
+    };
+    (
+        @pc_ @pick
+        stack: $stack:tt,
+        dir: $dir:tt,
+        progstate: [
+            pre: [$($pre:tt)*],
+            cur: [
+                pre: [$($cpre:tt)*],
+                cur: ['?'],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        find: [[random $exhausted:ident] $($findt:tt)*],
+        seen: $seen:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        compile_error!(concat!(
+            "Ran out of directions from `[random ",
+            stringify!($exhausted),
+            " ..]` for '?' at (",
+            ${count($pre)},
+            ", ",
+            ${count($cpre)},
+            ")",
+        ));
+    };
+    (
+        @pc_ @pick
+        stack: $stack:tt,
+        dir: $dir:tt,
+        progstate: $progstate:tt,
+        find: [[random $exhausted:ident $($dirs:tt)*] $($findt:tt)*],
+        seen: $seen:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        compile_error!(concat!(
+            "Unknown `[random ",
+            stringify!($exhausted),
+            " ..]`, expected `[random cycle ..]` or `[random once ..]`",
+        ));
+    };
+    (
+        @pc_ @pick
+        stack: $stack:tt,
+        dir: $dir:tt,
+        progstate: $progstate:tt,
+        find: [$findh:tt $($findt:tt)*],
+        seen: [$($seen:tt)*],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @pc_ @pick
+            stack: $stack,
+            dir: $dir,
+            progstate: $progstate,
+            find: [$($findt)*],
+            seen: [$($seen)* $findh],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @pc_ @next
+        stack: $stack:tt,
+        dir: $dir:tt,
+        progstate: $progstate:tt,
+        next: $next:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        macro_rules! befunge_step_random_check {
+            (left) => {
+                befunge_step_random_check!(@go left);
+            };
+            (right) => {
+                befunge_step_random_check!(@go right);
+            };
+            (up) => {
+                befunge_step_random_check!(@go up);
+            };
+            (down) => {
+                befunge_step_random_check!(@go down);
+            };
+            (@go $$newdir:ident) => {
+                $crate::befunge_step! {
+                    @catch @pc_
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: $progstate,
+                    rand: $$newdir,
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            };
+            ($$other:ident) => {
+                compile_error!(concat!(
+                    "Unknown direction `",
+                    stringify!($$other),
+                    "` in `[random ..]`, expected `left`, `right`, `up`, or `down`",
+                ));
+            };
+        }
+        befunge_step_random_check!($next);
+    };
+    /*
+           ####     #     #######  #####  #
+           #       ###       #    #     # #
+           #        #        #    #       #
+           #                 #    #       #
+           #        #        #    #       #
+           #       ###       #    #     # #
+           ####     #        #     #####  #######
+
+        [ : TCL
+        pc = turn_left(pc) (Funge-98 only)
+    */
+    (
+        @instr
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: ['['],
+                pst: $cpst:tt,
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        // Befunge-93 doesn't have `[`, so it's only turned on by `[b98]`.
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[b98]],
+            expand: [
+                $crate::socket_debug_default!(sockets: $sockets, "tcl");
+                $crate::dbg_trace_instr! {
+                    @trace
+                    debug: $debug,
+                    instr: '[',
+                    pre: $pre,
+                    cpre: $cpre,
+                }
+                $crate::befunge_step! {
+                    @catch @turn @left
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['['],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
+            orelse: [
+                $crate::befunge_step! {
+                    @unknown
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['['],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
         }
     };
+    /*
+           ####     #     #######  #####  ######
+              #    ###       #    #     # #     #
+              #     #        #    #       #     #
+              #              #    #       ######
+              #     #        #    #       #   #
+              #    ###       #    #     # #    #
+           ####     #        #     #####  #     #
+
+        ] : TCR
+        pc = turn_right(pc) (Funge-98 only)
+    */
     (
-        // Cover one case:
-        //   - stack = [a, b, ...] a is positive and b is negative
-        // This checks if `b > a`, so unconditionally push 0 to the stack.
         @instr
-        stack: [
-            [[$(pos)?] [$($topval:tt)*]]
-            [[neg] [$($botval:tt)*]]
-            $($stackrest:tt)*
-        ],
+        stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -906,42 +2622,87 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['`'],
+                cur: [']'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("grt3", -${count($botval)}, ${count($topval)});
-        $crate::befunge_step! {
-            @move
-            stack: [[[pos] []] $($stackrest)*],
-            dir: $dir,
-            stringmode: [false],
-            bridge: [false],
-            progstate: [
-                pre: $pre,
-                cur: [
-                    pre: $cpre,
-                    cur: ['`'],
-                    pst: $cpst,
-                ],
-                pst: $pst,
-            ],
+        // Befunge-93 doesn't have `]`, so it's only turned on by `[b98]`.
+        $crate::dbg_maybe_expand! {
+            @dbg
             debug: $debug,
+            lookfor: [[b98]],
+            expand: [
+                $crate::socket_debug_default!(sockets: $sockets, "tcr");
+                $crate::dbg_trace_instr! {
+                    @trace
+                    debug: $debug,
+                    instr: ']',
+                    pre: $pre,
+                    cpre: $cpre,
+                }
+                $crate::befunge_step! {
+                    @catch @turn @right
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: [']'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
+            orelse: [
+                $crate::befunge_step! {
+                    @unknown
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: [']'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
         }
     };
+    /*
+                    #           # #     # ######
+             #     ###          # ##   ## #     #
+                    #           # # # # # #     #
+             #                  # #  #  # ######
+             #      #     #     # #     # #
+             #     ###    #     # #     # #
+             #      #      #####  #     # #
+        #    #
+         ####
+
+        j : JMP
+        n = stack.pop()
+        pc += n (Funge-98 only)
+    */
     (
-        // Cover one case:
-        //   - stack = [a, b, ...] a is negative and b is positive
-        // This checks if `b > a`, so unconditionally push 1 to the stack.
         @instr
-        stack: [
-            [[neg] [$($topval:tt)*]]
-            [[$(pos)?] [$($botval:tt)*]]
-            $($stackrest:tt)*
-        ],
+        stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -949,40 +2710,85 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['`'],
+                cur: ['j'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("grt4", ${count($botval)}, -${count($topval)});
-        $crate::befunge_step! {
-            @move
-            stack: [[[pos] [[]]] $($stackrest)*],
-            dir: $dir,
-            stringmode: [false],
-            bridge: [false],
-            progstate: [
-                pre: $pre,
-                cur: [
-                    pre: $cpre,
-                    cur: ['`'],
-                    pst: $cpst,
-                ],
-                pst: $pst,
-            ],
+        // Befunge-93 doesn't have `j`, so it's only turned on by `[b98]`.
+        $crate::dbg_maybe_expand! {
+            @dbg
             debug: $debug,
+            lookfor: [[b98]],
+            expand: [
+                $crate::socket_debug_default!(sockets: $sockets, "jmp");
+                $crate::dbg_trace_instr! {
+                    @trace
+                    debug: $debug,
+                    instr: 'j',
+                    pre: $pre,
+                    cpre: $cpre,
+                }
+                $crate::befunge_step! {
+                    @move @jump
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['j'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
+            orelse: [
+                $crate::befunge_step! {
+                    @unknown
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['j'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
         }
     };
+    /*
+         #          #     ### ####### ######
+         #         ###     #     #    #     #
+         #   #      #      #     #    #     #
+         #  #              #     #    ######
+         ###        #      #     #    #   #
+         #  #      ###     #     #    #    #
+         #   #      #     ###    #    #     #
+
+        k : ITR
+        n = stack.pop()
+        repeat n times: run the next instruction (Funge-98 only)
+    */
     (
-        // Cover all cases where the top two values are positive
         @instr
-        stack: [
-            [[$(pos)?] [$($topval:tt)*]]
-            [[$(pos)?] [$($botval:tt)*]]
-            $($stackrest:tt)*
-        ],
+        stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -990,20 +2796,31 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['`'],
+                cur: ['k'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("grt5", ${count($botval)}, ${count($topval)});
-        macro_rules! befunge_step_grt_exec {
-            ($($topval)* $$($$_:tt)+) => {
-                $crate::socket_debug_default!("    => true");
+        // Befunge-93 doesn't have `k`, so it's only turned on by `[b98]`.
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[b98]],
+            expand: [
+                $crate::socket_debug_default!(sockets: $sockets, "itr");
+                $crate::dbg_trace_instr! {
+                    @trace
+                    debug: $debug,
+                    instr: 'k',
+                    pre: $pre,
+                    cpre: $cpre,
+                }
                 $crate::befunge_step! {
-                    @move
-                    stack: [[[pos] [[]]] $($stackrest)*],
+                    @move @iterate
+                    stack: $stack,
                     dir: $dir,
                     stringmode: [false],
                     bridge: [false],
@@ -1011,19 +2828,19 @@ macro_rules! befunge_step {
                         pre: $pre,
                         cur: [
                             pre: $cpre,
-                            cur: ['`'],
+                            cur: ['k'],
                             pst: $cpst,
                         ],
                         pst: $pst,
                     ],
+                    sockets: $sockets,
                     debug: $debug,
                 }
-            };
-            ($$($$_:tt)*) => {
-                $crate::socket_debug_default!("    => false");
+            ],
+            orelse: [
                 $crate::befunge_step! {
-                    @move
-                    stack: [[[pos] []] $($stackrest)*],
+                    @unknown
+                    stack: $stack,
                     dir: $dir,
                     stringmode: [false],
                     bridge: [false],
@@ -1031,27 +2848,32 @@ macro_rules! befunge_step {
                         pre: $pre,
                         cur: [
                             pre: $cpre,
-                            cur: ['`'],
+                            cur: ['k'],
                             pst: $cpst,
                         ],
                         pst: $pst,
                     ],
+                    sockets: $sockets,
                     debug: $debug,
                 }
-            }
-        }
-        befunge_step_grt_exec! {
-            $($botval)*
+            ],
         }
     };
+    /*
+         ###        #     #######  #####  #     #
+         ###       ###    #       #     # #     #
+          #         #     #       #       #     #
+         #                #####   #       #######
+                    #     #       #       #     #
+                   ###    #       #     # #     #
+                    #     #        #####  #     #
+
+        ' : FCH
+        stack.push(next cell), then skip it (Funge-98 only)
+    */
     (
-        // Cover all cases where the top two values are negative
         @instr
-        stack: [
-            [[$topsgn:tt] [$($topval:tt)*]]
-            [[$botsgn:tt] [$($botval:tt)*]]
-            $($stackrest:tt)*
-        ],
+        stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -1059,19 +2881,31 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['`'],
+                cur: ['\''],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("grt6", -${count($botval)}, -${count($topval)});
-        macro_rules! befunge_step_lt_exec {
-            ($($topval)* $$($$_:tt)+) => {
+        // Befunge-93 doesn't have `'`, so it's only turned on by `[b98]`.
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[b98]],
+            expand: [
+                $crate::socket_debug_default!(sockets: $sockets, "fch");
+                $crate::dbg_trace_instr! {
+                    @trace
+                    debug: $debug,
+                    instr: '\'',
+                    pre: $pre,
+                    cpre: $cpre,
+                }
                 $crate::befunge_step! {
-                    @move
-                    stack: [[[pos] []] $($stackrest)*],
+                    @move @fch
+                    stack: $stack,
                     dir: $dir,
                     stringmode: [false],
                     bridge: [false],
@@ -1079,18 +2913,19 @@ macro_rules! befunge_step {
                         pre: $pre,
                         cur: [
                             pre: $cpre,
-                            cur: ['`'],
+                            cur: ['\''],
                             pst: $cpst,
                         ],
                         pst: $pst,
                     ],
+                    sockets: $sockets,
                     debug: $debug,
                 }
-            };
-            ($$($$_:tt)*) => {
+            ],
+            orelse: [
                 $crate::befunge_step! {
-                    @move
-                    stack: [[[pos] [[]]] $($stackrest)*],
+                    @unknown
+                    stack: $stack,
                     dir: $dir,
                     stringmode: [false],
                     bridge: [false],
@@ -1098,30 +2933,28 @@ macro_rules! befunge_step {
                         pre: $pre,
                         cur: [
                             pre: $cpre,
-                            cur: ['`'],
+                            cur: ['\''],
                             pst: $cpst,
                         ],
                         pst: $pst,
                     ],
+                    sockets: $sockets,
                     debug: $debug,
                 }
-            }
-        }
-        befunge_step_lt_exec! {
-            $($botval)*
+            ],
         }
     };
     /*
-          #         #     ######   #####  ######
-           #       ###    #     # #     # #     #
-            #       #     #     # #       #     #
-             #            ######  #       ######
-            #       #     #       #       #   #
-           #       ###    #       #     # #    #
-          #         #     #        #####  #     #
+                    #      #####  #######  #####
+                   ###    #     #    #    #     #
+         ####       #     #          #    #
+        #                  #####     #    #
+         ####       #           #    #    #
+             #     ###    #     #    #    #     #
+         ####       #      #####     #     #####
 
-        > : PCR
-        pc = right
+        s : STC
+        next cell = stack.pop(), then skip it (Funge-98 only)
     */
     (
         @instr
@@ -1133,43 +2966,80 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['>'],
+                cur: ['s'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("pcr");
-        $crate::befunge_step! {
-            @move
-            stack: $stack,
-            dir: [right],
-            stringmode: [false],
-            bridge: [false],
-            progstate: [
-                pre: $pre,
-                cur: [
-                    pre: $cpre,
-                    cur: ['>'],
-                    pst: $cpst,
-                ],
-                pst: $pst,
+        // Befunge-93 doesn't have `s`, so it's only turned on by `[b98]`.
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[b98]],
+            expand: [
+                $crate::socket_debug_default!(sockets: $sockets, "stc");
+                $crate::dbg_trace_instr! {
+                    @trace
+                    debug: $debug,
+                    instr: 's',
+                    pre: $pre,
+                    cpre: $cpre,
+                }
+                $crate::befunge_step! {
+                    @move @stc
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['s'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
+            orelse: [
+                $crate::befunge_step! {
+                    @unknown
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['s'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
             ],
-            debug: $debug,
         }
     };
     /*
-             #      #     ######   #####  #
-            #      ###    #     # #     # #
-           #        #     #     # #       #
-          #               ######  #       #
-           #        #     #       #       #
-            #      ###    #       #     # #
-             #      #     #        #####  #######
+                    #      #####  #       ######
+                   ###    #     # #       #     #
+        # ####      #     #       #       #     #
+        ##    #           #       #       ######
+        #     #     #     #       #       #   #
+        #     #    ###    #     # #       #    #
+        #     #     #      #####  ####### #     #
 
-        < : PCL
-        pc = left
+        n : CLR
+        stack.clear() (Funge-98 only)
     */
     (
         @instr
@@ -1181,91 +3051,80 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['<'],
+                cur: ['n'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("pcl");
-        $crate::befunge_step! {
-            @move
-            stack: $stack,
-            dir: [left],
-            stringmode: [false],
-            bridge: [false],
-            progstate: [
-                pre: $pre,
-                cur: [
-                    pre: $cpre,
-                    cur: ['<'],
-                    pst: $cpst,
-                ],
-                pst: $pst,
-            ],
+        // Befunge-93 doesn't have `n`, so it's only turned on by `[b98]`.
+        $crate::dbg_maybe_expand! {
+            @dbg
             debug: $debug,
-        }
-    };
-    /*
-           #        #     ######   #####  #     #
-          # #      ###    #     # #     # #     #
-         #   #      #     #     # #       #     #
-                          ######  #       #     #
-                    #     #       #       #     #
-                   ###    #       #     # #     #
-                    #     #        #####   #####
-
-        ^ : PCU
-        pc = up
-    */
-    (
-        @instr
-        stack: $stack:tt,
-        dir: $dir:tt,
-        stringmode: [false],
-        bridge: [false],
-        progstate: [
-            pre: $pre:tt,
-            cur: [
-                pre: $cpre:tt,
-                cur: ['^'],
-                pst: $cpst:tt,
+            lookfor: [[b98]],
+            expand: [
+                $crate::socket_debug_default!(sockets: $sockets, "clr");
+                $crate::dbg_trace_instr! {
+                    @trace
+                    debug: $debug,
+                    instr: 'n',
+                    pre: $pre,
+                    cpre: $cpre,
+                }
+                $crate::befunge_step! {
+                    @move
+                    stack: [],
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['n'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
             ],
-            pst: $pst:tt,
-        ],
-        debug: $debug:tt,
-    ) => {
-        $crate::socket_debug_default!("pcu");
-        $crate::befunge_step! {
-            @move
-            stack: $stack,
-            dir: [up],
-            stringmode: [false],
-            bridge: [false],
-            progstate: [
-                pre: $pre,
-                cur: [
-                    pre: $cpre,
-                    cur: ['^'],
-                    pst: $cpst,
-                ],
-                pst: $pst,
+            orelse: [
+                $crate::befunge_step! {
+                    @unknown
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['n'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
             ],
-            debug: $debug,
         }
     };
     /*
-                    #     ######   #####  ######
-         #    #    ###    #     # #     # #     #
-         #    #     #     #     # #       #     #
-         #    #           ######  #       #     #
-         #    #     #     #       #       #     #
-          #  #     ###    #       #     # #     #
-           ##       #     #        #####  ######
+                    #     ######  ####### #
+                   ###    #     # #       #
+        # ####      #     #     # #       #
+        ##    #           ######  #####   #
+        #           #     #   #   #       #
+        #          ###    #    #  #       #
+        #           #     #     # #       #######
 
-        v : PCD
-        pc = up
+        r : RFL
+        pc = reverse(pc) (Funge-98 only)
     */
     (
         @instr
@@ -1277,44 +3136,81 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['v'],
+                cur: ['r'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("pcd");
-        $crate::befunge_step! {
-            @move
-            stack: $stack,
-            dir: [down],
-            stringmode: [false],
-            bridge: [false],
-            progstate: [
-                pre: $pre,
-                cur: [
-                    pre: $cpre,
-                    cur: ['v'],
-                    pst: $cpst,
-                ],
-                pst: $pst,
-            ],
+        // Befunge-93 doesn't have `r`, so it's only turned on by `[b98]`.
+        $crate::dbg_maybe_expand! {
+            @dbg
             debug: $debug,
+            lookfor: [[b98]],
+            expand: [
+                $crate::socket_debug_default!(sockets: $sockets, "rfl");
+                $crate::dbg_trace_instr! {
+                    @trace
+                    debug: $debug,
+                    instr: 'r',
+                    pre: $pre,
+                    cpre: $cpre,
+                }
+                $crate::befunge_step! {
+                    @catch @turn @back
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['r'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
+            orelse: [
+                $crate::befunge_step! {
+                    @unknown
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: ['r'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
         }
     };
     /*
-         #####      #     ######   #####
-        #     #    ###    #     # #     #
-              #     #     #     # #
-           ###            ######  #
-           #        #     #       #
-                   ###    #       #     #
-           #        #     #        #####
-                                          #######
+            #             #####  #     # #######
+           ###     ###    #     # ##   ##    #
+            #      ###    #       # # # #    #
+                          #       #  #  #    #
+            #      ###    #       #     #    #
+           ###     ###    #     # #     #    #
+            #       #      #####  #     #    #
+                   #
 
-        ? : PC_
-        pc = random(up, down, left, right)
+        ; : CMT
+        skip to after the next ; (Funge-98 only)
     */
     (
         @instr
@@ -1326,20 +3222,50 @@ macro_rules! befunge_step {
             pre: $pre:tt,
             cur: [
                 pre: $cpre:tt,
-                cur: ['?'],
+                cur: [';'],
                 pst: $cpst:tt,
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("pc_");
-        $crate::befunge_pm::choose_random! {
-            choices: [[left] [right] [up] [down]],
-            callback: [
-                name: $crate::befunge_step,
-                pre: [
-                    @catch @pc_
+        // Befunge-93 doesn't have `;`, so it's only turned on by `[b98]`.
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[b98]],
+            expand: [
+                $crate::socket_debug_default!(sockets: $sockets, "cmt");
+                $crate::dbg_trace_instr! {
+                    @trace
+                    debug: $debug,
+                    instr: ';',
+                    pre: $pre,
+                    cpre: $cpre,
+                }
+                $crate::befunge_step! {
+                    @move
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [comment [false]],
+                    progstate: [
+                        pre: $pre,
+                        cur: [
+                            pre: $cpre,
+                            cur: [';'],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
+            orelse: [
+                $crate::befunge_step! {
+                    @unknown
                     stack: $stack,
                     dir: $dir,
                     stringmode: [false],
@@ -1348,15 +3274,14 @@ macro_rules! befunge_step {
                         pre: $pre,
                         cur: [
                             pre: $cpre,
-                            cur: ['?'],
+                            cur: [';'],
                             pst: $cpst,
                         ],
                         pst: $pst,
                     ],
-                ],
-                pst: [
+                    sockets: $sockets,
                     debug: $debug,
-                ],
+                }
             ],
         }
     };
@@ -1395,9 +3320,26 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("ifh0 (right)");
+        $crate::socket_debug_default!(sockets: $sockets, "ifh0 (right)");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '_',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '_',
+            pops: [[]],
+            got: [$($zerosgn)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [$($($stackrest)*)?],
@@ -1413,6 +3355,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -1431,9 +3374,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("ifh1 (left)");
+        $crate::socket_debug_default!(sockets: $sockets, "ifh1 (left)");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '_',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [$($stacktail)*],
@@ -1449,6 +3400,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -1486,9 +3438,26 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("ifv0 (down)");
+        $crate::socket_debug_default!(sockets: $sockets, "ifv0 (down)");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '|',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '|',
+            pops: [[]],
+            got: [$($zerosgn)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [$($($stackrest)*)?],
@@ -1504,6 +3473,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -1525,9 +3495,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("ifv1 (up)");
+        $crate::socket_debug_default!(sockets: $sockets, "ifv1 (up)");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '|',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [$($stacktail)*],
@@ -1543,6 +3521,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -1573,9 +3552,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("stringmode enabled");
+        $crate::socket_debug_default!(sockets: $sockets, "stringmode enabled");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '"',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: $stack,
@@ -1591,6 +3578,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -1626,12 +3614,30 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::socket_debug_default!(
+            sockets: $sockets,
             "dup",
             $($($stack0sgn)? ${count($stack0val)})?
         );
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: ':',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: ':',
+            pops: [[]],
+            got: [$([$($stack0val)*])?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [
@@ -1651,6 +3657,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -1689,18 +3696,36 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::socket_debug_default!(
+            sockets: $sockets,
             "swp",
             $($($stack0sgn)? ${count($stack0val)}, )?
             $($($($stack1sgn)? ${count($stack1val)})?)?
         );
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '\\',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '\\',
+            pops: [[] []],
+            got: [$([$($stack0val)*] $([$($stack1val)*])?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [
-                [[$($($stack1sgn)?)?] [$($($($stack1val)*)?)?]]
-                [[$($stack0sgn)?] [$($($stack0val)*)?]]
+                [[$($($($stack1sgn)?)?)?] [$($($($stack1val)*)?)?]]
+                [[$($($stack0sgn)?)?] [$($($stack0val)*)?]]
                 $($($($stackrest)*)?)?
             ],
             dir: $dir,
@@ -1715,6 +3740,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -1732,7 +3758,12 @@ macro_rules! befunge_step {
     */
     (
         @instr
-        stack: [$($stackhead:tt $($stackrest:tt)*)?],
+        stack: [
+            $(
+                [[$($stack0sgn:tt)?] [$($stack0val:tt)*]]
+                $($stackrest:tt)*
+            )?
+        ],
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
@@ -1745,12 +3776,30 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::socket_debug_default!(
+            sockets: $sockets,
             "pop",
             $($($stack0sgn)? ${count($stack0val)})?
         );
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '$',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '$',
+            pops: [[]],
+            got: [$([$($stack0val)*])?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [$($($stackrest)*)?],
@@ -1766,6 +3815,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -1796,12 +3846,29 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("int (pos)", $(${count($stack0val)})?);
+        $crate::socket_debug_default!(sockets: $sockets, "int (pos)", $(${count($stack0val)})?);
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '.',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '.',
+            pops: [[]],
+            got: [$([$($stack0val)*])?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_pm::print_integer! {
             number: ${count($stack0val)},
-            socket: "befunge.output",
+            socket: [$sockets ".output"],
             callback: [
                 name: $crate::befunge_step,
                 pre: [
@@ -1819,6 +3886,7 @@ macro_rules! befunge_step {
                         ],
                         pst: $pst,
                     ],
+                    sockets: $sockets,
                     debug: $debug,
                 ],
                 pst: [],
@@ -1840,12 +3908,29 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("int (neg)", $(${count($stack0val)})?);
+        $crate::socket_debug_default!(sockets: $sockets, "int (neg)", $(${count($stack0val)})?);
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '.',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: '.',
+            pops: [[]],
+            got: [$([$($stack0val)*])?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_pm::print_integer! {
             number: -${count($stack0val)},
-            socket: "befunge.output",
+            socket: [$sockets ".output"],
             callback: [
                 name: $crate::befunge_step,
                 pre: [
@@ -1863,6 +3948,7 @@ macro_rules! befunge_step {
                         ],
                         pst: $pst,
                     ],
+                    sockets: $sockets,
                     debug: $debug,
                 ],
                 pst: [],
@@ -1902,9 +3988,26 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("chr" $(, $($stack0sgn)? ${count($stack0val)})?);
+        $crate::socket_debug_default!(sockets: $sockets, "chr" $(, $($stack0sgn)? ${count($stack0val)})?);
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: ',',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: ',',
+            pops: [[]],
+            got: [$([$($stack0val)*])?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::code_to_char! {
             @match
             num: [[$($($stack0sgn)?)?] [$($($stack0val)*)?]],
@@ -1912,7 +4015,7 @@ macro_rules! befunge_step {
                 name: $crate::befunge_pm::print_ascii,
                 pre: [],
                 pst: [
-                    socket: "befunge.output",
+                    socket: [$sockets ".output"],
                     callback: [
                         name: $crate::befunge_step,
                         pre: [
@@ -1930,6 +4033,7 @@ macro_rules! befunge_step {
                                 ],
                                 pst: $pst,
                             ],
+                            sockets: $sockets,
                             debug: $debug,
                         ],
                         pst: [],
@@ -1965,9 +4069,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("bridge: set to true");
+        $crate::socket_debug_default!(sockets: $sockets, "bridge: set to true");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '#',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: $stack,
@@ -1983,6 +4095,140 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @back
+        stack: $stack:tt,
+        dir: [right],
+        stringmode: [false],
+        bridge: $bridge:tt,
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [left],
+            stringmode: [false],
+            bridge: $bridge,
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @back
+        stack: $stack:tt,
+        dir: [left],
+        stringmode: [false],
+        bridge: $bridge:tt,
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [right],
+            stringmode: [false],
+            bridge: $bridge,
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @back
+        stack: $stack:tt,
+        dir: [up],
+        stringmode: [false],
+        bridge: $bridge:tt,
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [down],
+            stringmode: [false],
+            bridge: $bridge,
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @back
+        stack: $stack:tt,
+        dir: [down],
+        stringmode: [false],
+        bridge: $bridge:tt,
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [up],
+            stringmode: [false],
+            bridge: $bridge,
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @stc
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        char: [$fst:tt, $snd:tt],
+        orig: $orig:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "catch: stc");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [store $fst],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // Values without a printable character are stored as they are, the same as `p` does.
+    (
+        @catch @stc
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        char: $char:tt,
+        orig: $orig:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "catch: stc");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [store $orig],
+            progstate: $progstate,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2022,15 +4268,36 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("get0");
+        $crate::socket_debug_default!(sockets: $sockets, level: warn, "get0");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: 'g',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: 'g',
+            pops: [[] []],
+            got: [[] $($stack1)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
             lookfor: [[getdbg]],
             expand: [
-                const _: &str = concat!("Y index was out of bounds! Pushed 0 to stack.");
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("Y index was out of bounds! Pushed 0 to stack.")],
+                }
             ],
         }
         $crate::befunge_step! {
@@ -2048,6 +4315,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2070,15 +4338,27 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("get1");
+        $crate::socket_debug_default!(sockets: $sockets, level: warn, "get1");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: 'g',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
             lookfor: [[getdbg]],
             expand: [
-                const _: &str = concat!("X index was out of bounds! Pushed 0 to stack.");
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("X index was out of bounds! Pushed 0 to stack.")],
+                }
             ],
         }
         $crate::befunge_step! {
@@ -2096,6 +4376,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2122,9 +4403,26 @@ macro_rules! befunge_step {
             ],
             pst: [$($pst:tt)*],
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("get2", ${count($x)}, ${count($y)});
+        $crate::socket_debug_default!(sockets: $sockets, "get2", ${count($x)}, ${count($y)});
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: 'g',
+            pre: [$($pre)*],
+            cpre: [$($cpre)*],
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: 'g',
+            pops: [[] []],
+            got: [$([$($y)*] $([$($x)*])?)?],
+            pre: [$($pre)*],
+            cpre: [$($cpre)*],
+        }
         macro_rules! sanitise_coords_for_dbg {
             (
                 x: [$$($$xdbg:tt)*],
@@ -2135,13 +4433,17 @@ macro_rules! befunge_step {
                     debug: $debug,
                     lookfor: [[getdbg]],
                     expand: [
-                        const _: &str = concat!(
-                            "Retrieving value at: (",
-                            $${count($$ydbg)},
-                            ", ",
-                            $${count($$xdbg)},
-                            ")",
-                        );
+                        $crate::dbg_tagged! {
+                            @tagged
+                            debug: $debug,
+                            msg: [concat!(
+                                "Retrieving value at: (",
+                                $${count($$ydbg)},
+                                ", ",
+                                $${count($$xdbg)},
+                                ")",
+                            )],
+                        }
                     ],
                 }
             ]
@@ -2150,19 +4452,21 @@ macro_rules! befunge_step {
             x: [$($($($x)*)?)?],
             y: [$($($y)*)?],
         }
-        macro_rules! befunge_step_get_coord_check {
-            (
-                xcheck: [$($($($x)*)?)? $$([])*],
-                ycheck: [$($($y)*)? $$([])*],
-            ) => {
-                $crate::list_split_at_length_of! {
-                    @init
-                    lenof: [$($($y)*)?],
-                    split: [$($pre)* [$($cpre)* 'g' $($cpst)*] $($pst)*],
+        $crate::bounds_check! {
+            @check
+            x: [$($($($x)*)?)?],
+            y: [$($($y)*)?],
+            max_y: [$($pre)* $($pst)*],
+            inbounds: [
+                name: $crate::list_get_at,
+                pre: [
+                    @get
+                    index: [$($($y)*)?],
+                    list: [$($pre)* [$($cpre)* 'g' $($cpst)*] $($pst)*],
                     callback: [
                         name: $crate::befunge_step,
                         pre: [
-                            @catch @get @splitrow
+                            @catch @get @row
                             stack: [$($($($stackrest)*)?)?],
                             dir: $dir,
                             stringmode: [false],
@@ -2179,34 +4483,38 @@ macro_rules! befunge_step {
                             x: [$($($($x)*)?)?],
                         ],
                         pst: [
+                            sockets: $sockets,
                             debug: $debug,
                         ],
                     ],
-                }
-            };
-            ($$($$_:tt)*) => {
-                $crate::dbg_maybe_expand! {
-                    @dbg
-                    debug: $debug,
-                    lookfor: [[getdbg]],
-                    expand: [
-                        const _: &str = concat!("Index was out of bounds! Pushed 0 to stack.");
-                    ],
-                }
-                $crate::befunge_step! {
-                    @move
-                    stack: [[[pos] []] $($($($stackrest)*)?)?],
+                ],
+                pst: [],
+            ],
+            outofbounds: [
+                name: $crate::befunge_step,
+                pre: [
+                    @catch @get @row
+                    stack: [$($($($stackrest)*)?)?],
                     dir: $dir,
                     stringmode: [false],
                     bridge: [false],
-                    progstate: $progstate,
+                    progstate: [
+                        pre: [$($pre)*],
+                        cur: [
+                            pre: [$($cpre)*],
+                            cur: ['g'],
+                            pst: [$($cpst)*],
+                        ],
+                        pst: [$($pst)*],
+                    ],
+                    x: [],
+                    oob: [],
+                ],
+                pst: [
+                    sockets: $sockets,
                     debug: $debug,
-                }
-            };
-        }
-        befunge_step_get_coord_check! {
-            xcheck: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []],
-            ycheck: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []],
+                ],
+            ],
         }
     };
     /*
@@ -2248,15 +4556,36 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("put0");
+        $crate::socket_debug_default!(sockets: $sockets, level: warn, "put0");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: 'p',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: 'p',
+            pops: [[] [] []],
+            got: [[] $($stack1 $($stack2)?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
-            lookfor: [[getdbg]],
+            lookfor: [[putdbg]],
             expand: [
-                const _: &str = concat!("Y index was out of bounds! Abandoning put attempt.");
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("Y index was out of bounds! Abandoning put attempt.")],
+                }
             ],
         }
         $crate::befunge_step! {
@@ -2274,6 +4603,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2301,15 +4631,36 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("put1");
+        $crate::socket_debug_default!(sockets: $sockets, level: warn, "put1");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: 'p',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: 'p',
+            pops: [[] [] []],
+            got: [$stack0 $([$($stack1val)*] $($stack2)?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
-            lookfor: [[getdbg]],
+            lookfor: [[putdbg]],
             expand: [
-                const _: &str = concat!("X index was out of bounds! Abandoning put attempt.");
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("X index was out of bounds! Abandoning put attempt.")],
+                }
             ],
         }
         $crate::befunge_step! {
@@ -2327,6 +4678,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2356,9 +4708,26 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("put2");
+        $crate::socket_debug_default!(sockets: $sockets, "put2");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: 'p',
+            pre: $pre,
+            cpre: $cpre,
+        }
+        $crate::dbg_underflow! {
+            @underflow
+            debug: $debug,
+            instr: 'p',
+            pops: [[] [] []],
+            got: [$($stack0 $($stack1 $([$($stack2val)*])?)?)?],
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::code_to_char_pretty! {
             @match
             num: [[$($($($($stack2sgn)?)?)?)?] [$($($($($stack2val)*)?)?)?]],
@@ -2379,11 +4748,12 @@ macro_rules! befunge_step {
                         ],
                         pst: $pst,
                     ],
-                    y: $($stack0)?,
-                    x: $($($stack1)?)?,
+                    // Anything missing from the stack is a zero
+                    yx: [$($stack0 $($stack1)?)? [[pos] []] [[pos] []]],
                 ],
                 pst: [
                     orig: [[$($($($($stack2sgn)?)?)?)?] [$($($($($stack2val)*)?)?)?]],
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -2416,11 +4786,19 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("ini");
+        $crate::socket_debug_default!(sockets: $sockets, "ini");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '&',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_pm::get_integer! {
-            socket: "befunge.input",
+            socket: [$sockets ".input"],
             callback: [
                 name: $crate::befunge_step,
                 pre: [
@@ -2440,6 +4818,7 @@ macro_rules! befunge_step {
                     ],
                 ],
                 pst: [
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -2472,11 +4851,19 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("inc");
+        $crate::socket_debug_default!(sockets: $sockets, "inc");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '~',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_pm::get_ascii! {
-            socket: "befunge.input",
+            socket: [$sockets ".input"],
             callback: [
                 name: $crate::befunge_step,
                 pre: [
@@ -2496,6 +4883,7 @@ macro_rules! befunge_step {
                     ],
                 ],
                 pst: [
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -2511,7 +4899,8 @@ macro_rules! befunge_step {
          #####      #     ####### #     # ######
 
         @ : END
-        end program execution
+        end program execution, handing the stack to the macro from `[result ..]` if there is one and
+        reporting the unknown instructions collected with `[collecterrors]`
     */
     (
         @instr
@@ -2520,31 +4909,39 @@ macro_rules! befunge_step {
         stringmode: [false],
         bridge: [false],
         progstate: [
-            pre: $pre:tt,
+            pre: [$($pre:tt)*],
             cur: [
-                pre: $cpre:tt,
+                pre: [$($cpre:tt)*],
                 cur: ['@'],
-                pst: $cpst:tt,
+                pst: [$($cpst:tt)*],
             ],
-            pst: $pst:tt,
+            pst: [$($pst:tt)*],
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("end");
+        $crate::socket_debug_default!(sockets: $sockets, level: info, "end");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '@',
+            pre: [$($pre)*],
+            cpre: [$($cpre)*],
+        }
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
             lookfor: [[closeonend]],
             expand: [
                 $crate::befunge_pm::close_ui! {
-                    socket: "befunge.output",
+                    socket: [$sockets ".output"],
                 }
                 $crate::befunge_pm::close_ui! {
-                    socket: "befunge.input",
+                    socket: [$sockets ".input"],
                 }
                 #[cfg(feature = "socket_debug_default")]
                 $crate::befunge_pm::close_ui! {
-                    socket: "befunge.debug",
+                    socket: [$sockets ".debug"],
                 }
             ],
             orelse: [
@@ -2553,12 +4950,20 @@ macro_rules! befunge_step {
                     debug: $debug,
                     lookfor: [[noflush]],
                     expand: [
-                        const _: &str = "Program terminated successfully!";
+                        $crate::dbg_tagged! {
+                            @tagged
+                            debug: $debug,
+                            msg: ["Program terminated successfully!"],
+                        }
                     ],
                     orelse: [
-                        const _: &str = "Flushing program output.";
+                        $crate::dbg_tagged! {
+                            @tagged
+                            debug: $debug,
+                            msg: ["Flushing program output."],
+                        }
                         $crate::befunge_pm::flush_output! {
-                            socket: "befunge.output",
+                            socket: [$sockets ".output"],
                         }
                     ],
                 }
@@ -2569,13 +4974,79 @@ macro_rules! befunge_step {
             debug: $debug,
             lookfor: [[poststack]],
             expand: [
-                const _: &str = "Stack at program '@':";
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: ["Stack at program '@':"],
+                }
                 $crate::dbg_print_stack! {
                     @printstack
                     stack: $stack,
+                    debug: $debug,
+                }
+            ],
+        }
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[postprog]],
+            expand: [
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: ["Program memory at program '@':"],
+                }
+                $crate::befunge_stringify! {
+                    @stringify @raw
+                    lines: [$($pre)* [$($cpre)* '@' $($cpst)*] $($pst)*],
+                    debug: $debug,
                 }
             ],
         }
+        $crate::dbg_observe! {
+            @print
+            debug: $debug,
+        }
+        $crate::befunge_step! {
+            @end @result
+            stack: $stack,
+            find: $debug,
+        }
+        $crate::befunge_error! {
+            @collected
+            debug: $debug,
+        }
+    };
+    // With `[result name]`, the stack is handed to `name!` as literals.
+    (
+        @end @result
+        stack: $stack:tt,
+        find: [],
+    ) => {};
+    (
+        @end @result
+        stack: $stack:tt,
+        find: [[result $name:path] $($findt:tt)*],
+    ) => {
+        $crate::dbg_get_stack! {
+            stack: $stack,
+            callback: [
+                name: $name,
+                pre: [],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @end @result
+        stack: $stack:tt,
+        find: [$findh:tt $($findt:tt)*],
+    ) => {
+        $crate::befunge_step! {
+            @end @result
+            stack: $stack,
+            find: [$($findt)*],
+        }
     };
     /*
           ###       #     #     # #     #   ###
@@ -2604,9 +5075,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm0");
+        $crate::socket_debug_default!(sockets: $sockets, "nm0");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '0',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] []] $($stack)*],
@@ -2622,6 +5101,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2652,9 +5132,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm1");
+        $crate::socket_debug_default!(sockets: $sockets, "nm1");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '1',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] [[]]] $($stack)*],
@@ -2670,6 +5158,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2700,9 +5189,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm2");
+        $crate::socket_debug_default!(sockets: $sockets, "nm2");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '2',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] [[] []]] $($stack)*],
@@ -2718,6 +5215,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2748,9 +5246,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm3");
+        $crate::socket_debug_default!(sockets: $sockets, "nm3");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '3',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] [[] [] []]] $($stack)*],
@@ -2766,6 +5272,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2796,9 +5303,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm4");
+        $crate::socket_debug_default!(sockets: $sockets, "nm4");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '4',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] [[] [] [] []]] $($stack)*],
@@ -2814,6 +5329,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2844,9 +5360,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm5");
+        $crate::socket_debug_default!(sockets: $sockets, "nm5");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '5',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] [[] [] [] [] []]] $($stack)*],
@@ -2862,6 +5386,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2892,9 +5417,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm6");
+        $crate::socket_debug_default!(sockets: $sockets, "nm6");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '6',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] [[] [] [] [] [] []]] $($stack)*],
@@ -2910,6 +5443,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2940,9 +5474,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm7");
+        $crate::socket_debug_default!(sockets: $sockets, "nm7");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '7',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] [[] [] [] [] [] [] []]] $($stack)*],
@@ -2958,6 +5500,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -2988,9 +5531,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm8");
+        $crate::socket_debug_default!(sockets: $sockets, "nm8");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '8',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] [[] [] [] [] [] [] [] []]] $($stack)*],
@@ -3006,6 +5557,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3036,9 +5588,17 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("nm9");
+        $crate::socket_debug_default!(sockets: $sockets, "nm9");
+        $crate::dbg_trace_instr! {
+            @trace
+            debug: $debug,
+            instr: '9',
+            pre: $pre,
+            cpre: $cpre,
+        }
         $crate::befunge_step! {
             @move
             stack: [[[pos] [[] [] [] [] [] [] [] [] []]] $($stack)*],
@@ -3054,6 +5614,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3070,10 +5631,19 @@ macro_rules! befunge_step {
     */
     (
         @instr
+        $($rest:tt)*
+    ) => {
+        $crate::befunge_step! {
+            @unknown
+            $($rest)*
+        }
+    };
+    (
+        @unknown
         stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
-        bridge: $bridge:tt,
+        bridge: [false],
         progstate: [
             pre: [$($pre:tt)*],
             cur: [
@@ -3083,16 +5653,114 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("unk");
-        $crate::befunge_error! {
-            @unknowninstr
-            instr: $unknown,
-            row: ${count($pre)},
-            col: ${count($cpre)},
-            stack: $stack,
-            dir: $dir,
+        // With `[onunknown skip]`, the unknown instruction is treated as a space, and with
+        // `[onunknown reflect]`, it turns the PC around like `r`. Otherwise it's an error.
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[onunknown skip]],
+            expand: [
+                $crate::befunge_step! {
+                    @move
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: [$($pre)*],
+                        cur: [
+                            pre: [$($cpre)*],
+                            cur: [$unknown],
+                            pst: $cpst,
+                        ],
+                        pst: $pst,
+                    ],
+                    sockets: $sockets,
+                    debug: $debug,
+                }
+            ],
+            orelse: [
+                $crate::dbg_maybe_expand! {
+                    @dbg
+                    debug: $debug,
+                    lookfor: [[onunknown reflect]],
+                    expand: [
+                        $crate::befunge_step! {
+                            @catch @turn @back
+                            stack: $stack,
+                            dir: $dir,
+                            stringmode: [false],
+                            bridge: [false],
+                            progstate: [
+                                pre: [$($pre)*],
+                                cur: [
+                                    pre: [$($cpre)*],
+                                    cur: [$unknown],
+                                    pst: $cpst,
+                                ],
+                                pst: $pst,
+                            ],
+                            sockets: $sockets,
+                            debug: $debug,
+                        }
+                    ],
+                    orelse: [
+                        $crate::socket_debug_default!(sockets: $sockets, level: warn, "unk");
+                        // With `[collecterrors]`, the unknown instruction is added to the ones
+                        // collected so far and the PC carries on past it as though it were a space,
+                        // so that every unknown instruction on the path is reported together at the
+                        // end of the program.
+                        $crate::dbg_maybe_expand! {
+                            @dbg
+                            debug: $debug,
+                            lookfor: [[collecterrors]],
+                            expand: [
+                                $crate::befunge_error! {
+                                    @collect
+                                    error: [$unknown ${count($pre)} ${count($cpre)}],
+                                    debug: $debug,
+                                    seen: [],
+                                    callback: [
+                                        name: $crate::befunge_step,
+                                        pre: [
+                                            @move
+                                            stack: $stack,
+                                            dir: $dir,
+                                            stringmode: [false],
+                                            bridge: [false],
+                                            progstate: [
+                                                pre: [$($pre)*],
+                                                cur: [
+                                                    pre: [$($cpre)*],
+                                                    cur: [$unknown],
+                                                    pst: $cpst,
+                                                ],
+                                                pst: $pst,
+                                            ],
+                                            sockets: $sockets,
+                                        ],
+                                        pst: [],
+                                    ],
+                                }
+                            ],
+                            orelse: [
+                                $crate::befunge_error! {
+                                    @unknowninstr
+                                    instr: $unknown,
+                                    row: ${count($pre)},
+                                    col: ${count($cpre)},
+                                    stack: $stack,
+                                    dir: $dir,
+                                    debug: $debug,
+                                }
+                            ],
+                        }
+                    ],
+                }
+            ],
         }
     };
     /*
@@ -3114,9 +5782,10 @@ macro_rules! befunge_step {
         bridge: [false],
         progstate: $progstate:tt,
         res: $res:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: arith");
+        $crate::socket_debug_default!(sockets: $sockets, "catch: arith");
         $crate::befunge_step! {
             @move
             stack: [$res $($stack)*],
@@ -3124,6 +5793,53 @@ macro_rules! befunge_step {
             stringmode: $stringmode,
             bridge: [false],
             progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @grt
+        stack: [$($stack:tt)*],
+        dir: $dir:tt,
+        stringmode: $stringmode:tt,
+        bridge: [false],
+        progstate: $progstate:tt,
+        ord: [gt],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "catch: grt => true");
+        $crate::befunge_step! {
+            @move
+            stack: [[[pos] [[]]] $($stack)*],
+            dir: $dir,
+            stringmode: $stringmode,
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @grt
+        stack: [$($stack:tt)*],
+        dir: $dir:tt,
+        stringmode: $stringmode:tt,
+        bridge: [false],
+        progstate: $progstate:tt,
+        ord: $ord:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "catch: grt => false");
+        $crate::befunge_step! {
+            @move
+            stack: [[[pos] []] $($stack)*],
+            dir: $dir,
+            stringmode: $stringmode,
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3135,9 +5851,10 @@ macro_rules! befunge_step {
         bridge: [false],
         progstate: $progstate:tt,
         num: $num:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: char_to_code");
+        $crate::socket_debug_default!(sockets: $sockets, "catch: char_to_code");
         $crate::befunge_step! {
             @move
             stack: [$num $($stack)*],
@@ -3145,6 +5862,7 @@ macro_rules! befunge_step {
             stringmode: $stringmode,
             bridge: [false],
             progstate: $progstate,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3161,23 +5879,193 @@ macro_rules! befunge_step {
         ? : PC_
     */
     (
-        @catch @pc_
+        @catch @pc_
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        rand: $newdir:ident,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "catch: pc_");
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [$newdir],
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @left
+        stack: $stack:tt,
+        dir: [right],
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [up],
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @left
+        stack: $stack:tt,
+        dir: [up],
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [left],
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @left
+        stack: $stack:tt,
+        dir: [left],
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [down],
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @left
+        stack: $stack:tt,
+        dir: [down],
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [right],
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @right
+        stack: $stack:tt,
+        dir: [right],
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [down],
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @right
+        stack: $stack:tt,
+        dir: [down],
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [left],
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @right
+        stack: $stack:tt,
+        dir: [left],
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [up],
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @turn @right
         stack: $stack:tt,
-        dir: $dir:tt,
+        dir: [up],
         stringmode: [false],
         bridge: [false],
         progstate: $progstate:tt,
-        rand: $newdir:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: pc_");
         $crate::befunge_step! {
             @move
             stack: $stack,
-            dir: $newdir,
+            dir: [right],
             stringmode: [false],
             bridge: [false],
             progstate: $progstate,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3196,22 +6084,22 @@ macro_rules! befunge_step {
         g : GET
     */
     (
-        @catch @get @splitrow
+        @catch @get @row
         stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
         progstate: $progstate:tt,
         x: [$($x:tt)*],
-        l: $l:tt,
-        r: [$rh:tt $($rt:tt)*],
+        item: [$row:tt],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: get0", ${count($x)});
-        $crate::list_split_at_length_of! {
-            @init
+        $crate::socket_debug_default!(sockets: $sockets, "catch: get0", ${count($x)});
+        $crate::befunge_pm::split_row! {
+            pad: ' ',
             lenof: [$($x)*],
-            split: $rh,
+            split: $row,
             callback: [
                 name: $crate::befunge_step,
                 pre: [
@@ -3223,11 +6111,49 @@ macro_rules! befunge_step {
                     progstate: $progstate,
                 ],
                 pst: [
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
         }
     };
+    // Coordinates outside of the playfield get 0
+    (
+        @catch @get @row
+        stack: [$($stack:tt)*],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        x: $x:tt,
+        oob: [],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "catch: getoob");
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[getdbg]],
+            expand: [
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("Index was out of bounds! Pushed 0 to stack.")],
+                }
+            ],
+        }
+        $crate::befunge_step! {
+            @move
+            stack: [[[pos] []] $($stack)*],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
     // Push numbers directly to the stack
     (
         @catch @get @push
@@ -3238,9 +6164,10 @@ macro_rules! befunge_step {
         progstate: $progstate:tt,
         l: $l:tt,
         r: [[[$($numsgn:tt)?] [$($numval:tt)*]] $($rt:tt)*],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: get1", $($numsgn,)? ${count($numval)});
+        $crate::socket_debug_default!(sockets: $sockets, "catch: get1", $($numsgn,)? ${count($numval)});
         macro_rules! sanitise_num_for_dbg {
             (
                 num: [[$$(pos)?] [$$($$nv:tt)*]],
@@ -3250,11 +6177,15 @@ macro_rules! befunge_step {
                     debug: $debug,
                     lookfor: [[getdbg]],
                     expand: [
-                        const _: &str = concat!(
-                            "Conversion to number successful! Pushing ",
-                            stringify!($${count($$nv)}),
-                            " to the stack.",
-                        );
+                        $crate::dbg_tagged! {
+                            @tagged
+                            debug: $debug,
+                            msg: [concat!(
+                                "Conversion to number successful! Pushing ",
+                                stringify!($${count($$nv)}),
+                                " to the stack.",
+                            )],
+                        }
                     ],
                 }
             };
@@ -3266,11 +6197,15 @@ macro_rules! befunge_step {
                     debug: $debug,
                     lookfor: [[getdbg]],
                     expand: [
-                        const _: &str = concat!(
-                            "Conversion to number successful! Pushing ",
-                            stringify!(-$${count($$nv)}),
-                            " to the stack.",
-                        );
+                        $crate::dbg_tagged! {
+                            @tagged
+                            debug: $debug,
+                            msg: [concat!(
+                                "Conversion to number successful! Pushing ",
+                                stringify!(-$${count($$nv)}),
+                                " to the stack.",
+                            )],
+                        }
                     ],
                 }
             };
@@ -3285,6 +6220,7 @@ macro_rules! befunge_step {
             stringmode: [false],
             bridge: [false],
             progstate: $progstate,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3298,15 +6234,20 @@ macro_rules! befunge_step {
         progstate: $progstate:tt,
         l: $l:tt,
         r: [$rh:tt $($rt:tt)*],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: get2", $rh);
+        $crate::socket_debug_default!(sockets: $sockets, "catch: get2", $rh);
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
             lookfor: [[getdbg]],
             expand: [
-                const _: &str = concat!("Indexing succesful! Got char: ", $rh);
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("Indexing succesful! Got char: ", $rh)],
+                }
             ],
         }
         $crate::char_to_code! {
@@ -3323,6 +6264,7 @@ macro_rules! befunge_step {
                     progstate: $progstate,
                 ],
                 pst: [
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -3336,21 +6278,26 @@ macro_rules! befunge_step {
         bridge: [false],
         progstate: $progstate:tt,
         num: [[$numsgn:tt] [$($numval:tt)*]],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: get3", ${count($numval)});
+        $crate::socket_debug_default!(sockets: $sockets, "catch: get3", ${count($numval)});
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
             lookfor: [[getdbg]],
             expand: [
-                const _: &str = concat!(
-                    "Conversion to number successful! Pushing ",
-                    stringify!($numsgn),
-                    " ",
-                    ${count($numval)},
-                    " to the stack.",
-                );
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!(
+                        "Conversion to number successful! Pushing ",
+                        stringify!($numsgn),
+                        " ",
+                        ${count($numval)},
+                        " to the stack.",
+                    )],
+                }
             ],
         }
         $crate::befunge_step! {
@@ -3360,6 +6307,7 @@ macro_rules! befunge_step {
             stringmode: [false],
             bridge: [false],
             progstate: $progstate,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3377,12 +6325,68 @@ macro_rules! befunge_step {
 
         p : PUT
     */
+    // Codes with a character are put as that character
+    (
+        @catch @put @code_to_char_pretty
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        yx: [$y:tt $x:tt $($zero:tt)*],
+        char: [$char:tt, $code:tt],
+        orig: $orig:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @catch @put @check
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            y: $y,
+            x: $x,
+            put: $char,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // Anything else (negative numbers and numbers without a character) is put as the number
     (
         @catch @put @code_to_char_pretty
         stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
+        progstate: $progstate:tt,
+        yx: [$y:tt $x:tt $($zero:tt)*],
+        char: $char:tt,
+        orig: $orig:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @catch @put @check
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            y: $y,
+            x: $x,
+            put: $orig,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @catch @put @check
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
         progstate: [
             pre: [$($pre:tt)*],
             cur: [
@@ -3394,38 +6398,44 @@ macro_rules! befunge_step {
         ],
         y: [[$($ysgn:tt)?] [$($y:tt)*]],
         x: [[$($xsgn:tt)?] [$($x:tt)*]],
-        char: [-$fst:tt],
-        orig: $orig:tt,
+        put: $put:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: put0");
+        $crate::socket_debug_default!(sockets: $sockets, "catch: put0");
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
             lookfor: [[putdbg]],
             expand: [
-                const _: &str = concat!(
-                    "Putting value at: (",
-                    ${count($y)},
-                    ", ",
-                    ${count($x)},
-                    ")",
-                );
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!(
+                        "Putting value at: (",
+                        ${count($y)},
+                        ", ",
+                        ${count($x)},
+                        ")",
+                    )],
+                }
             ],
         }
-        macro_rules! befunge_step_put_coord_check {
-            (
-                xcheck: [$($x)* $$([])*],
-                ycheck: [$($y)* $$([])*],
-            ) => {
-                $crate::list_split_at_length_of! {
-                    @init
-                    lenof: [$($y)*],
-                    split: [$($pre)* [$($cpre)* $cur $($cpst)*] $($pst)*],
+        $crate::bounds_check! {
+            @check
+            x: [$($x)*],
+            y: [$($y)*],
+            max_y: [$($pre)* $($pst)*],
+            inbounds: [
+                name: $crate::list_get_at,
+                pre: [
+                    @get
+                    index: [$($y)*],
+                    list: [$($pre)* [$($cpre)* $cur $($cpst)*] $($pst)*],
                     callback: [
                         name: $crate::befunge_step,
                         pre: [
-                            @catch @put @splitrow @place
+                            @catch @put @row
                             stack: $stack,
                             dir: $dir,
                             stringmode: [false],
@@ -3439,120 +6449,132 @@ macro_rules! befunge_step {
                                 ],
                                 pst: [$($pst)*],
                             ],
+                            y: [$($y)*],
                             x: [$($x)*],
-                            put: $orig,
+                            put: $put,
                         ],
                         pst: [
+                            sockets: $sockets,
                             debug: $debug,
                         ],
                     ],
-                }
-            };
-            ($$($$_:tt)*) => {
-                $crate::dbg_maybe_expand! {
-                    @dbg
-                    debug: $debug,
-                    lookfor: [[putdbg]],
-                    expand: [
-                        const _: &str = concat!("Index was out of bounds!");
+                ],
+                pst: [],
+            ],
+            outofbounds: [
+                name: $crate::befunge_step,
+                pre: [
+                    @catch @put @row
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: [
+                        pre: [$($pre)*],
+                        cur: [
+                            pre: [$($cpre)*],
+                            cur: [$cur],
+                            pst: [$($cpst)*],
+                        ],
+                        pst: [$($pst)*],
                     ],
-                }
-            };
-        }
-        befunge_step_put_coord_check! {
-            xcheck: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []],
-            ycheck: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []],
+                    y: [],
+                    x: [],
+                    put: $put,
+                    oob: [],
+                ],
+                pst: [
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
         }
     };
     (
-        @catch @put @code_to_char_pretty
+        @catch @put @row
         stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
-        progstate: [
-            pre: [$($pre:tt)*],
-            cur: [
-                pre: [$($cpre:tt)*],
-                cur: [$cur:tt],
-                pst: [$($cpst:tt)*],
+        progstate: $progstate:tt,
+        y: $y:tt,
+        x: $x:tt,
+        put: $put:tt,
+        item: [$row:tt],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "catch: put1");
+        $crate::befunge_pm::split_row! {
+            pad: ' ',
+            lenof: $x,
+            split: $row,
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @catch @put @col
+                    stack: $stack,
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: $progstate,
+                    y: $y,
+                    put: $put,
+                ],
+                pst: [
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
             ],
-            pst: [$($pst:tt)*],
-        ],
-        y: [[$($ysgn:tt)?] [$($y:tt)*]],
-        x: [[$($xsgn:tt)?] [$($x:tt)*]],
-        char: [$fst:tt],
-        orig: $orig:tt,
+        }
+    };
+    // Coordinates outside of the playfield leave it as it is
+    (
+        @catch @put @row
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        y: $y:tt,
+        x: $x:tt,
+        put: $put:tt,
+        oob: [],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: put0");
+        $crate::socket_debug_default!(sockets: $sockets, "catch: putoob");
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
             lookfor: [[putdbg]],
             expand: [
-                const _: &str = concat!(
-                    "Putting value at: (",
-                    ${count($y)},
-                    ", ",
-                    ${count($x)},
-                    ")",
-                );
-            ],
-        }
-        macro_rules! befunge_step_put_coord_check {
-            (
-                xcheck: [$($x)* $$([])*],
-                ycheck: [$($y)* $$([])*],
-            ) => {
-                $crate::list_split_at_length_of! {
-                    @init
-                    lenof: [$($y)*],
-                    split: [$($pre)* [$($cpre)* $cur $($cpst)*] $($pst)*],
-                    callback: [
-                        name: $crate::befunge_step,
-                        pre: [
-                            @catch @put @splitrow @place
-                            stack: $stack,
-                            dir: $dir,
-                            stringmode: [false],
-                            bridge: [false],
-                            progstate: [
-                                pre: [$($pre)*],
-                                cur: [
-                                    pre: [$($cpre)*],
-                                    cur: [$cur],
-                                    pst: [$($cpst)*],
-                                ],
-                                pst: [$($pst)*],
-                            ],
-                            x: [$($x)*],
-                            put: $orig,
-                        ],
-                        pst: [
-                            debug: $debug,
-                        ],
-                    ],
-                }
-            };
-            ($$($$_:tt)*) => {
-                $crate::dbg_maybe_expand! {
-                    @dbg
+                $crate::dbg_tagged! {
+                    @tagged
                     debug: $debug,
-                    lookfor: [[putdbg]],
-                    expand: [
-                        const _: &str = concat!("Index was out of bounds!");
-                    ],
+                    msg: [concat!("Index was out of bounds! Abandoning put attempt.")],
                 }
-            };
+            ],
         }
-        befunge_step_put_coord_check! {
-            xcheck: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []],
-            ycheck: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []],
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
         }
     };
+    // Only the row that was put to changes, so a row before or after the current one is set in
+    // place. The current row has to be split again so that the cell under the PC stays `cur`.
+    // Finding the column is left to `split_row!` (in `@catch @put @row`) rather than a second
+    // `list_set_at!`, because `[compact]` rows hold runs of spaces, so a column isn't an index into
+    // the row, and rows can be shorter than the column being put to. The rows themselves are split
+    // into `pre`, the current row, and `pst`, so `put_set_row!` picks which of those to set.
     (
-        @catch @put @code_to_char_pretty
+        @catch @put @col
         stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
@@ -3566,197 +6588,146 @@ macro_rules! befunge_step {
             ],
             pst: [$($pst:tt)*],
         ],
-        y: [[$($ysgn:tt)?] [$($y:tt)*]],
-        x: [[$($xsgn:tt)?] [$($x:tt)*]],
-        char: [$fst:tt, $snd:tt],
-        orig: $orig:tt,
+        y: [$($y:tt)*],
+        put: $put:tt,
+        l: [$($putcpre:tt)*],
+        r: [$_old:tt $($putcpst:tt)*],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: put0");
-        $crate::dbg_maybe_expand! {
-            @dbg
-            debug: $debug,
-            lookfor: [[putdbg]],
-            expand: [
-                const _: &str = concat!(
-                    "Putting value at: (",
-                    ${count($y)},
-                    ", ",
-                    ${count($x)},
-                    ")",
-                );
-            ],
-        }
-        macro_rules! befunge_step_put_coord_check {
-            (
-                xcheck: [$($x)* $$([])*],
-                ycheck: [$($y)* $$([])*],
-            ) => {
-                $crate::list_split_at_length_of! {
-                    @init
-                    lenof: [$($y)*],
-                    split: [$($pre)* [$($cpre)* $cur $($cpst)*] $($pst)*],
+        $crate::socket_debug_default!(sockets: $sockets, "catch: put2");
+        macro_rules! put_set_row {
+            (y: [$(${ignore($pre)} [])* [] $$($$pstidx:tt)*]) => {
+                $crate::list_set_at! {
+                    @set
+                    index: [$$($$pstidx)*],
+                    list: [$($pst)*],
+                    value: [[$($putcpre)* $put $($putcpst)*]],
                     callback: [
                         name: $crate::befunge_step,
                         pre: [
-                            @catch @put @splitrow @place
+                            @catch @put @newps
                             stack: $stack,
                             dir: $dir,
                             stringmode: [false],
                             bridge: [false],
-                            progstate: [
-                                pre: [$($pre)*],
-                                cur: [
-                                    pre: [$($cpre)*],
-                                    cur: [$cur],
-                                    pst: [$($cpst)*],
-                                ],
-                                pst: [$($pst)*],
-                            ],
-                            x: [$($x)*],
-                            put: $fst,
+                            pre: [$($pre)*],
+                            l: [$($cpre)*],
+                            r: [$cur $($cpst)*],
                         ],
                         pst: [
+                            sockets: $sockets,
                             debug: $debug,
                         ],
                     ],
                 }
             };
-            ($$($$_:tt)*) => {
-                $crate::dbg_maybe_expand! {
-                    @dbg
-                    debug: $debug,
-                    lookfor: [[putdbg]],
-                    expand: [
-                        const _: &str = concat!("Index was out of bounds!");
+            (y: [$(${ignore($pre)} [])*]) => {
+                $crate::befunge_pm::split_row! {
+                    lenof: [$($cpre)*],
+                    split: [$($putcpre)* $put $($putcpst)*],
+                    callback: [
+                        name: $crate::befunge_step,
+                        pre: [
+                            @catch @put @newps
+                            stack: $stack,
+                            dir: $dir,
+                            stringmode: [false],
+                            bridge: [false],
+                            pre: [$($pre)*],
+                            pst: [$($pst)*],
+                        ],
+                        pst: [
+                            sockets: $sockets,
+                            debug: $debug,
+                        ],
+                    ],
+                }
+            };
+            (y: $$_y:tt) => {
+                $crate::list_set_at! {
+                    @set
+                    index: [$($y)*],
+                    list: [$($pre)*],
+                    value: [[$($putcpre)* $put $($putcpst)*]],
+                    callback: [
+                        name: $crate::befunge_step,
+                        pre: [
+                            @catch @put @newps
+                            stack: $stack,
+                            dir: $dir,
+                            stringmode: [false],
+                            bridge: [false],
+                            pst: [$($pst)*],
+                            l: [$($cpre)*],
+                            r: [$cur $($cpst)*],
+                        ],
+                        pst: [
+                            sockets: $sockets,
+                            debug: $debug,
+                        ],
                     ],
                 }
             };
         }
-        befunge_step_put_coord_check! {
-            xcheck: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []],
-            ycheck: [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []],
+        put_set_row! {
+            y: [$($y)*]
         }
     };
     (
-        @catch @put @splitrow @place
+        @catch @put @newps
         stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
-        progstate: $progstate:tt,
-        x: $x:tt,
-        put: $put:tt,
+        pre: $pre:tt,
         l: $l:tt,
-        r: [$rh:tt $($rt:tt)*],
-        debug: $debug:tt,
-    ) => {
-        $crate::socket_debug_default!("catch: put1");
-        $crate::list_split_at_length_of! {
-            @init
-            lenof: $x,
-            split: $rh,
-            callback: [
-                name: $crate::befunge_step,
-                pre: [
-                    @catch @put @splitcol @place
-                    stack: $stack,
-                    dir: $dir,
-                    stringmode: [false],
-                    bridge: [false],
-                    progstate: $progstate,
-                    put: $put,
-                    putpre: $l,
-                    putpst: [$($rt)*],
-                ],
-                pst: [
-                    debug: $debug,
-                ],
-            ],
-        }
-    };
-    (
-        @catch @put @splitcol @place
-        stack: $stack:tt,
-        dir: $dir:tt,
-        stringmode: [false],
-        bridge: [false],
-        progstate: [
-            pre: $pre:tt,
-            cur: $cur:tt,
-            pst: $pst:tt,
-        ],
-        put: $newcur:tt,
-        putpre: [$($putpre:tt)*],
-        putpst: [$($putpst:tt)*],
-        l: [$($putcpre:tt)*],
-        r: [$_cur:tt $($putcpst:tt)*],
+        r: $r:tt,
+        list: $pst:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: put2");
-        $crate::list_split_at_length_of! {
-            @init
-            lenof: $pre,
-            split: [$($putpre)* [$($putcpre)* $newcur $($putcpst)*] $($putpst)*],
-            callback: [
-                name: $crate::befunge_step,
-                pre: [
-                    @catch @put @splitrow @newps
-                    stack: $stack,
-                    dir: $dir,
-                    stringmode: [false],
-                    bridge: [false],
-                    progstate: [
-                        pre: $pre,
-                        cur: $cur,
-                        pst: $pst,
-                    ],
-                ],
-                pst: [
-                    debug: $debug,
-                ],
-            ],
+        $crate::socket_debug_default!(sockets: $sockets, "catch: put3");
+        $crate::befunge_step! {
+            @catch @put @newps
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            pre: $pre,
+            pst: $pst,
+            l: $l,
+            r: $r,
+            sockets: $sockets,
+            debug: $debug,
         }
     };
     (
-        @catch @put @splitrow @newps
+        @catch @put @newps
         stack: $stack:tt,
         dir: $dir:tt,
         stringmode: [false],
         bridge: [false],
-        progstate: [
-            pre: $pre:tt,
-            cur: [
-                pre: $cpre:tt,
-                cur: $cur:tt,
-                pst: $cpst:tt,
-            ],
-            pst: $pst:tt,
-        ],
-        l: $newpre:tt,
-        r: [$newcur:tt $($newpst:tt)*],
+        pst: $pst:tt,
+        l: $l:tt,
+        r: $r:tt,
+        list: $pre:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: put3");
-        $crate::list_split_at_length_of! {
-            @init
-            lenof: $cpre,
-            split: $newcur,
-            callback: [
-                name: $crate::befunge_step,
-                pre: [
-                    @catch @put @newps
-                    stack: $stack,
-                    dir: $dir,
-                    stringmode: [false],
-                    bridge: [false],
-                    pre: $newpre,
-                    pst: [$($newpst)*],
-                ],
-                pst: [
-                    debug: $debug,
-                ],
-            ],
+        $crate::socket_debug_default!(sockets: $sockets, "catch: put3");
+        $crate::befunge_step! {
+            @catch @put @newps
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            pre: $pre,
+            pst: $pst,
+            l: $l,
+            r: $r,
+            sockets: $sockets,
+            debug: $debug,
         }
     };
     (
@@ -3769,18 +6740,25 @@ macro_rules! befunge_step {
         pst: [$($pst:tt)*],
         l: [$($cpre:tt)*],
         r: [$cur:tt $($cpst:tt)*],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: put4");
+        $crate::socket_debug_default!(sockets: $sockets, "catch: put4");
         $crate::dbg_maybe_expand! {
             @dbg
             debug: $debug,
             lookfor: [[putdbg]],
             expand: [
-                const _: &str = "Successfully reassembled program memory! Result:";
-                $crate::befunge_stringify! {
-                    @stringify @raw
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: ["Successfully reassembled program memory! Result:"],
+                }
+                $crate::socket_debug_stringify! {
+                    sockets: $sockets,
+                    mode: [@stringify @raw],
                     lines: [$($pre)* [$($cpre)* $cur $($cpst)*] $($pst)*],
+                    debug: $debug,
                 }
             ],
         }
@@ -3799,6 +6777,7 @@ macro_rules! befunge_step {
                 ],
                 pst: [$($pst)*],
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3821,9 +6800,10 @@ macro_rules! befunge_step {
         bridge: [false],
         progstate: $progstate:tt,
         integer: $int:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: ini");
+        $crate::socket_debug_default!(sockets: $sockets, "catch: ini");
         $crate::befunge_step! {
             @move
             stack: [$int $($stack)*],
@@ -3831,6 +6811,7 @@ macro_rules! befunge_step {
             stringmode: [false],
             bridge: [false],
             progstate: $progstate,
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3853,9 +6834,10 @@ macro_rules! befunge_step {
         bridge: [false],
         progstate: $progstate:tt,
         ascii: $ascii:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: inc0");
+        $crate::socket_debug_default!(sockets: $sockets, "catch: inc0");
         $crate::char_to_code! {
             @match
             char: $ascii,
@@ -3870,43 +6852,302 @@ macro_rules! befunge_step {
                     progstate: $progstate,
                 ],
                 pst: [
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    (
+        @catch @inc @char_to_code
+        stack: [$($stack:tt)*],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        num: $num:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::socket_debug_default!(sockets: $sockets, "catch: inc1");
+        $crate::befunge_step! {
+            @move
+            stack: [$num $($stack)*],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    /*
+        #     # ####### #     # ####### #     # ####### #     # #######
+        ##   ## #     # #     # #       ##   ## #       ##    #    #
+        # # # # #     # #     # #       # # # # #       # #   #    #
+        #  #  # #     # #     # #####   #  #  # #####   #  #  #    #
+        #     # #     #  #   #  #       #     # #       #   # #    #
+        #     # #     #   # #   #       #     # #       #    ##    #
+        #     # #######    #    ####### #     # ####### #     #    #
+
+        MOVEMENT
+    */
+    // Observe (`[profile]`, `[heatmap]`, `[breakpoints ..]`, and `[stepcount]`): the cell that was
+    // hidden from being seen again has been dealt with, so show it the next one. This comes before
+    // `k`'s rules so that every run of the instruction it repeats is counted.
+    (
+        @move
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: [[observed profile: $profile:tt heat: $heat:tt breaks: $breaks:tt steps: $steps:tt] $($debug:tt)*],
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: $stringmode,
+            bridge: $bridge,
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: [[observe profile: $profile heat: $heat breaks: $breaks steps: $steps] $($debug)*],
+        }
+    };
+    (
+        @move
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: [[kiter $kiter:tt] [observed profile: $profile:tt heat: $heat:tt breaks: $breaks:tt steps: $steps:tt] $($debug:tt)*],
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: $stringmode,
+            bridge: $bridge,
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: [[kiter $kiter] [observe profile: $profile heat: $heat breaks: $breaks steps: $steps] $($debug)*],
+        }
+    };
+    // Iterate (`k`): run the same instruction again instead of moving until the count runs out,
+    // then move on from it as usual.
+    (
+        @move
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: [[kiter [$counth:tt $($countt:tt)*]] $($debug:tt)*],
+    ) => {
+        $crate::befunge_step! {
+            @instr
+            stack: $stack,
+            dir: $dir,
+            stringmode: $stringmode,
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: [[kiter [$($countt)*]] $($debug)*],
+        }
+    };
+    (
+        @move
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: [[kiter []] $($debug:tt)*],
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: $stringmode,
+            bridge: $bridge,
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: [$($debug)*],
+        }
+    };
+    // Move right (wrap) in a compacted playfield (see `[compact]`), where the row has nothing
+    // left after the current cell but the `[sp []]` that marks it as compacted. The row is split
+    // again from its start, so that the runs of spaces the PC has gone past are put back together.
+    (
+        @move
+        stack: $stack:tt,
+        dir: [right],
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: [$($cpre:tt)*],
+                cur: [$cur:tt],
+                pst: [[sp []]],
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_pm::split_row! {
+            lenof: [],
+            split: [$($cpre)* $cur [sp []]],
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @move @compactwrap
+                    stack: $stack,
+                    stringmode: $stringmode,
+                    bridge: $bridge,
+                    pre: $pre,
+                ],
+                pst: [
+                    pst: $pst,
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
+    (
+        @move @compactwrap
+        stack: $stack:tt,
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        pre: $pre:tt,
+        l: [],
+        r: [$cur:tt $($cpst:tt)*],
+        pst: $pst:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_maybe_expand! {
+            @dbg
+            debug: $debug,
+            lookfor: [[tracemove]],
+            expand: [
+                $crate::dbg_tagged! {
+                    @tagged
                     debug: $debug,
+                    msg: [concat!("newcur: right => ", $cur)],
+                }
+            ],
+        }
+        $crate::dbg_step_delay! {
+            @delay
+            debug: $debug,
+        }
+        $crate::befunge_step! {
+            @instr
+            stack: $stack,
+            dir: [right],
+            stringmode: $stringmode,
+            bridge: $bridge,
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: [],
+                    cur: [$cur],
+                    pst: [$($cpst)*],
                 ],
+                pst: $pst,
             ],
+            sockets: $sockets,
+            debug: $debug,
         }
     };
+    // Move right onto a run of spaces in a compacted playfield. Spaces don't do anything, so the
+    // PC goes straight to the last one in the run, and then moves on from it as usual. Nothing
+    // else sees the spaces in between.
     (
-        @catch @inc @char_to_code
-        stack: [$($stack:tt)*],
-        dir: $dir:tt,
+        @move
+        stack: $stack:tt,
+        dir: [right],
         stringmode: [false],
         bridge: [false],
-        progstate: $progstate:tt,
-        num: $num:tt,
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: [$($cpre:tt)*],
+                cur: [$cur:tt],
+                pst: [[sp [[] $($run:tt)*]] $($cpt:tt)*],
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::socket_debug_default!("catch: inc1");
         $crate::befunge_step! {
             @move
-            stack: [$num $($stack)*],
-            dir: $dir,
+            stack: $stack,
+            dir: [right],
             stringmode: [false],
             bridge: [false],
-            progstate: $progstate,
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: [$($cpre)* $cur $(${ignore($run)} ' ')*],
+                    cur: [' '],
+                    pst: [$($cpt)*],
+                ],
+                pst: $pst,
+            ],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // The same in stringmode, or while jumping over cells, where each space has to be seen one at
+    // a time. The run is written out as spaces, and the PC moves onto the first of them.
+    (
+        @move
+        stack: $stack:tt,
+        dir: [right],
+        stringmode: $stringmode:tt,
+        bridge: $bridge:tt,
+        progstate: [
+            pre: $pre:tt,
+            cur: [
+                pre: $cpre:tt,
+                cur: $cur:tt,
+                pst: [[sp [$($run:tt)+]] $($cpt:tt)*],
+            ],
+            pst: $pst:tt,
+        ],
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: [right],
+            stringmode: $stringmode,
+            bridge: $bridge,
+            progstate: [
+                pre: $pre,
+                cur: [
+                    pre: $cpre,
+                    cur: $cur,
+                    pst: [$(${ignore($run)} ' ')* $($cpt)*],
+                ],
+                pst: $pst,
+            ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
-    /*
-        #     # ####### #     # ####### #     # ####### #     # #######
-        ##   ## #     # #     # #       ##   ## #       ##    #    #
-        # # # # #     # #     # #       # # # # #       # #   #    #
-        #  #  # #     # #     # #####   #  #  # #####   #  #  #    #
-        #     # #     #  #   #  #       #     # #       #   # #    #
-        #     # #     #   # #   #       #     # #       #    ##    #
-        #     # #######    #    ####### #     # ####### #     #    #
-
-        MOVEMENT
-    */
     // Move right
     (
         @move
@@ -3923,6 +7164,7 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -3930,9 +7172,17 @@ macro_rules! befunge_step {
             debug: $debug,
             lookfor: [[tracemove]],
             expand: [
-                const _: &str = concat!("newcur: right => ", $cph);
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("newcur: right => ", $cph)],
+                }
             ],
         }
+        $crate::dbg_step_delay! {
+            @delay
+            debug: $debug,
+        }
         $crate::befunge_step! {
             @instr
             stack: $stack,
@@ -3948,6 +7198,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -3967,6 +7218,7 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -3974,9 +7226,17 @@ macro_rules! befunge_step {
             debug: $debug,
             lookfor: [[tracemove]],
             expand: [
-                const _: &str = concat!("newcur: right => ", $cph);
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("newcur: right => ", $cph)],
+                }
             ],
         }
+        $crate::dbg_step_delay! {
+            @delay
+            debug: $debug,
+        }
         $crate::befunge_step! {
             @instr
             stack: $stack,
@@ -3992,6 +7252,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -4011,6 +7272,7 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::list_init_last! {
@@ -4030,6 +7292,7 @@ macro_rules! befunge_step {
                     cur: $cur,
                     cpst: $cpst,
                     pst: $pst,
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -4047,6 +7310,7 @@ macro_rules! befunge_step {
         cur: [$cur:tt],
         cpst: [$($cpst:tt)*],
         pst: $pst:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -4054,9 +7318,17 @@ macro_rules! befunge_step {
             debug: $debug,
             lookfor: [[tracemove]],
             expand: [
-                const _: &str = concat!("newcur: left => ", $last);
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("newcur: left => ", $last)],
+                }
             ],
         }
+        $crate::dbg_step_delay! {
+            @delay
+            debug: $debug,
+        }
         $crate::befunge_step! {
             @instr
             stack: $stack,
@@ -4072,6 +7344,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -4091,11 +7364,12 @@ macro_rules! befunge_step {
             ],
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::list_init_last! {
-            @init,
-            list: $cpst,
+        $crate::befunge_pm::split_row! {
+            last: true,
+            split: $cpst,
             callback: [
                 name: $crate::befunge_step,
                 pre: [
@@ -4109,6 +7383,7 @@ macro_rules! befunge_step {
                 pst: [
                     cur: $cur,
                     pst: $pst,
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -4121,10 +7396,11 @@ macro_rules! befunge_step {
         stringmode: $stringmode:tt,
         bridge: $bridge:tt,
         pre: [$($pre:tt)*],
+        l: [$($init:tt)*],
+        r: [$last:tt $($lpst:tt)*],
         cur: [$cur:tt],
-        init: [$($init:tt)+],
-        last: [$last:tt],
         pst: $pst:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -4132,9 +7408,17 @@ macro_rules! befunge_step {
             debug: $debug,
             lookfor: [[tracemove]],
             expand: [
-                const _: &str = concat!("newcur: left => ", $last);
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("newcur: left => ", $last)],
+                }
             ],
         }
+        $crate::dbg_step_delay! {
+            @delay
+            debug: $debug,
+        }
         $crate::befunge_step! {
             @instr
             stack: $stack,
@@ -4144,12 +7428,13 @@ macro_rules! befunge_step {
             progstate: [
                 pre: [$($pre)*],
                 cur: [
-                    pre: [$cur $($init)+],
+                    pre: [$cur $($init)*],
                     cur: [$last],
-                    pst: [],
+                    pst: [$($lpst)*],
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -4169,10 +7454,11 @@ macro_rules! befunge_step {
             ],
             pst: [$psth:tt $($pstt:tt)*],
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::list_split_at_length_of! {
-            @init
+        $crate::befunge_pm::split_row! {
+            pad: ' ',
             lenof: [$($cpre)*],
             split: $psth,
             callback: [
@@ -4187,6 +7473,7 @@ macro_rules! befunge_step {
                 ],
                 pst: [
                     pst: [$($pstt)*],
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -4202,6 +7489,7 @@ macro_rules! befunge_step {
         l: [$($cpre:tt)*],
         r: [$cur:tt $($cpst:tt)*],
         pst: $pst:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -4209,9 +7497,17 @@ macro_rules! befunge_step {
             debug: $debug,
             lookfor: [[tracemove]],
             expand: [
-                const _: &str = concat!("newcur: down => ", $cur);
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("newcur: down => ", $cur)],
+                }
             ],
         }
+        $crate::dbg_step_delay! {
+            @delay
+            debug: $debug,
+        }
         $crate::befunge_step! {
             @instr
             stack: $stack,
@@ -4227,6 +7523,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -4246,10 +7543,11 @@ macro_rules! befunge_step {
             ],
             pst: [],
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::list_split_at_length_of! {
-            @init
+        $crate::befunge_pm::split_row! {
+            pad: ' ',
             lenof: [$($cpre)*],
             split: $preh,
             callback: [
@@ -4263,6 +7561,7 @@ macro_rules! befunge_step {
                 ],
                 pst: [
                     pst: [$($pret)* [$($cpre)* $cur $($cpst)*]],
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -4277,6 +7576,7 @@ macro_rules! befunge_step {
         l: [$($cpre:tt)*],
         r: [$cur:tt $($cpst:tt)*],
         pst: $pst:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -4284,9 +7584,17 @@ macro_rules! befunge_step {
             debug: $debug,
             lookfor: [[tracemove]],
             expand: [
-                const _: &str = concat!("newcur: down => ", $cur);
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("newcur: down => ", $cur)],
+                }
             ],
         }
+        $crate::dbg_step_delay! {
+            @delay
+            debug: $debug,
+        }
         $crate::befunge_step! {
             @instr
             stack: $stack,
@@ -4302,6 +7610,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -4317,6 +7626,7 @@ macro_rules! befunge_step {
             cur: $cur:tt,
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::list_init_last! {
@@ -4334,6 +7644,7 @@ macro_rules! befunge_step {
                 ],
                 pst: [
                     pst: $pst,
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -4353,10 +7664,11 @@ macro_rules! befunge_step {
         init: $init:tt,
         last: [$last:tt],
         pst: [$($pst:tt)*],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::list_split_at_length_of! {
-            @init
+        $crate::befunge_pm::split_row! {
+            pad: ' ',
             lenof: [$($cpre)*],
             split: $last,
             callback: [
@@ -4371,6 +7683,7 @@ macro_rules! befunge_step {
                 ],
                 pst: [
                     pst: [[$($cpre)* $cur $($cpst)*] $($pst)*],
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -4386,6 +7699,7 @@ macro_rules! befunge_step {
         l: [$($cpre:tt)*],
         r: [$cur:tt $($cpst:tt)*],
         pst: $pst:tt,
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -4393,9 +7707,17 @@ macro_rules! befunge_step {
             debug: $debug,
             lookfor: [[tracemove]],
             expand: [
-                const _: &str = concat!("newcur: up => ", $cur);
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("newcur: up => ", $cur)],
+                }
             ],
         }
+        $crate::dbg_step_delay! {
+            @delay
+            debug: $debug,
+        }
         $crate::befunge_step! {
             @instr
             stack: $stack,
@@ -4411,6 +7733,7 @@ macro_rules! befunge_step {
                 ],
                 pst: $pst,
             ],
+            sockets: $sockets,
             debug: $debug,
         }
     };
@@ -4426,6 +7749,7 @@ macro_rules! befunge_step {
             cur: $cur:tt,
             pst: $pst:tt,
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::list_init_last! {
@@ -4442,6 +7766,7 @@ macro_rules! befunge_step {
                 ],
                 pst: [
                     cur: $cur,
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -4460,10 +7785,11 @@ macro_rules! befunge_step {
             cur: [$cur:tt],
             pst: [$($cpst:tt)*],
         ],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
-        $crate::list_split_at_length_of! {
-            @init
+        $crate::befunge_pm::split_row! {
+            pad: ' ',
             lenof: [$($cpre)*],
             split: $last,
             callback: [
@@ -4477,6 +7803,7 @@ macro_rules! befunge_step {
                     pre: [[$($cpre)* $cur $($cpst)*] $($init)*],
                 ],
                 pst: [
+                    sockets: $sockets,
                     debug: $debug,
                 ],
             ],
@@ -4491,6 +7818,7 @@ macro_rules! befunge_step {
         pre: $pre:tt,
         l: [$($l:tt)*],
         r: [$rh:tt $($rt:tt)*],
+        sockets: $sockets:tt,
         debug: $debug:tt,
     ) => {
         $crate::dbg_maybe_expand! {
@@ -4498,9 +7826,17 @@ macro_rules! befunge_step {
             debug: $debug,
             lookfor: [[tracemove]],
             expand: [
-                const _: &str = concat!("newcur: up => ", $rh);
+                $crate::dbg_tagged! {
+                    @tagged
+                    debug: $debug,
+                    msg: [concat!("newcur: up => ", $rh)],
+                }
             ],
         }
+        $crate::dbg_step_delay! {
+            @delay
+            debug: $debug,
+        }
         $crate::befunge_step! {
             @instr
             stack: $stack,
@@ -4516,7 +7852,193 @@ macro_rules! befunge_step {
                 ],
                 pst: [],
             ],
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // Jump (`j`): pop how far to go and hand it to the jump bridges. Zero (or an empty stack) is
+    // just a move.
+    (
+        @move @jump
+        stack: [$([$sgn:tt []] $($stack:tt)*)?],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: [$($($stack)*)?],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [false],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @move @jump
+        stack: [[[neg] [$jumph:tt $($jumpt:tt)*]] $($stack:tt)*],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @catch @turn @back
+            stack: [$($stack)*],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [jumpback [$($jumpt)*]],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @move @jump
+        stack: [[$sgn:tt $jump:tt] $($stack:tt)*],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: [$($stack)*],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [jump $jump],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // Iterate (`k`): pop how many times to run the next instruction and go looking for it. Anything
+    // below 1 (or an empty stack) skips it.
+    (
+        @move @iterate
+        stack: [$([$sgn:tt []] $($stack:tt)*)?],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: [$($($stack)*)?],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [iterate []],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @move @iterate
+        stack: [[[neg] $count:tt] $($stack:tt)*],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: [$($stack)*],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [iterate []],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    (
+        @move @iterate
+        stack: [[$sgn:tt $count:tt] $($stack:tt)*],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: [$($stack)*],
+            dir: $dir,
+            stringmode: [false],
+            bridge: [iterate $count],
+            progstate: $progstate,
+            sockets: $sockets,
+            debug: $debug,
+        }
+    };
+    // Fetch (`'`): push whatever's in the next cell when it's landed on.
+    (
+        @move @fch
+        stack: $stack:tt,
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::befunge_step! {
+            @move
+            stack: $stack,
+            dir: $dir,
+            stringmode: [false],
+            bridge: [fetch],
+            progstate: $progstate,
+            sockets: $sockets,
             debug: $debug,
         }
     };
+    // Store (`s`): pop the value to store (0 if the stack is empty) and turn it into a character
+    // first if it is one, then overwrite the next cell with it when it's landed on.
+    (
+        @move @stc
+        stack: [$([[$($sgn:tt)?] [$($val:tt)*]] $($stack:tt)*)?],
+        dir: $dir:tt,
+        stringmode: [false],
+        bridge: [false],
+        progstate: $progstate:tt,
+        sockets: $sockets:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::code_to_char_pretty! {
+            @match
+            num: [[$($($sgn)?)?] [$($($val)*)?]],
+            callback: [
+                name: $crate::befunge_step,
+                pre: [
+                    @catch @stc
+                    stack: [$($($stack)*)?],
+                    dir: $dir,
+                    stringmode: [false],
+                    bridge: [false],
+                    progstate: $progstate,
+                ],
+                pst: [
+                    orig: [[$($($sgn)?)?] [$($($val)*)?]],
+                    sockets: $sockets,
+                    debug: $debug,
+                ],
+            ],
+        }
+    };
 }
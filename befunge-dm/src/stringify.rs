@@ -1,5 +1,125 @@
 #[macro_export]
 /// Prints out the program memory from the Befunge interpreter.
+///
+/// # Example
+/// `@stringify @raw` outputs a `const _: &str = "...";` for each row, so
+/// `lines: [['v' ' ' '_'] ['>' '|' '@']]` expands to `const _: &str = "v _";`, then
+/// `const _: &str = ">|@";`.
+///
+/// `@stringify @raw @joined` puts every row into the same `const`, with a newline after each row
+/// but the last, so that the whole program can be copied out of `cargo expand` in one go. It can be
+/// given a `name:` for the `const` before `lines:`, which is `_` otherwise. Every character comes
+/// out as itself, `_` and `|` included:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_stringify! {
+///     @stringify @raw @joined
+///     name: PROGRAM,
+///     lines: [['v' ' ' '_'] ['>' '|' '@'] ['"' 'h' 'i' '"']],
+/// }
+///
+/// assert_eq!(PROGRAM, "v _\n>|@\n\"hi\"");
+/// ```
+///
+/// Cells that `p` has put a control character into are written out as `\xNN`, so that they can be
+/// seen and don't break up the row. Quotes and backslashes come out as themselves:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// // The cells `p` puts for 7 (bell) and 34 (`"`).
+/// macro_rules! put {
+///     (char: [$bell:tt, $code:tt],) => {
+///         befunge_dm::code_to_char_pretty! {
+///             @match
+///             num: [[pos] [
+///                 [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///                 [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []
+///             ]],
+///             callback: [
+///                 name: put,
+///                 pre: [bell: $bell,],
+///                 pst: [],
+///             ],
+///         }
+///     };
+///     (bell: $bell:tt, char: [$quote:tt, $code:tt],) => {
+///         befunge_dm::befunge_stringify! {
+///             @stringify @raw @joined
+///             name: PUT,
+///             lines: [['7' '0' '0' 'p' $bell] [$quote '\\' '@']],
+///         }
+///     };
+/// }
+///
+/// befunge_dm::code_to_char_pretty! {
+///     @match
+///     num: [[pos] [[] [] [] [] [] [] []]],
+///     callback: [
+///         name: put,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+///
+/// assert_eq!(PUT, "700p\\x07\n\"\\@");
+/// ```
+///
+/// `@stringify @tosocket` puts the rows together the same way, but sends them over the
+/// debugging socket (`befunge.debug`, or `sockets.debug` if given `sockets: "sockets",` before
+/// `lines:`) instead of outputting a `const`. It can be given `debug:` too. Built with the
+/// `headless` feature, nothing is sent:
+#[cfg_attr(feature = "headless", doc = "```")]
+#[cfg_attr(not(feature = "headless"), doc = "```ignore")]
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_stringify! {
+///     @stringify @tosocket
+///     lines: [['v' ' ' '_'] ['>' '|' '@']],
+///     debug: [[tag "dump"]],
+/// }
+/// ```
+///
+/// `@stringify @framed` does the same as `@joined`, but draws a border around the playfield, with
+/// every row padded out to the longest one. Given `pc: [x y]` as base-1 numbers, the cell the PC is
+/// on is swapped for a `#` so that the columns still line up. A PC that isn't on the playfield
+/// leaves it unmarked. It takes `name:` and `debug:` the same way as `@joined`. Breakpoints use it
+/// to show where the PC stopped:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// befunge_dm::befunge_stringify! {
+///     @stringify @framed
+///     name: MIDDLE,
+///     lines: [['v' ' ' '<'] ['>' '1' '^'] ['@']],
+///     pc: [[[pos] [[]]] [[pos] [[]]]],
+/// }
+///
+/// assert_eq!(
+///     MIDDLE,
+///     "+---+\n\
+///      |v <|\n\
+///      |>#^|\n\
+///      |@  |\n\
+///      +---+",
+/// );
+///
+/// befunge_dm::befunge_stringify! {
+///     @stringify @framed
+///     name: EDGE,
+///     lines: [['v' ' ' '<'] ['>' '1' '^'] ['@']],
+///     pc: [[[pos] []] [[pos] [[] []]]],
+/// }
+///
+/// assert_eq!(
+///     EDGE,
+///     "+---+\n\
+///      |v <|\n\
+///      |>1^|\n\
+///      |#  |\n\
+///      +---+",
+/// );
+/// ```
 macro_rules! befunge_stringify {
     (
         @stringify
@@ -9,555 +129,1984 @@ macro_rules! befunge_stringify {
             @stringify @inner
             lines: $lines,
             obuf: [],
+            tag: [],
+        }
+    };
+    (
+        @stringify
+        lines: $lines:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: $debug,
+            callback: [
+                name: $crate::befunge_stringify,
+                pre: [
+                    @stringify @tagged
+                    lines: $lines,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @stringify @tagged
+        lines: $lines:tt,
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @inner
+            lines: $lines,
+            obuf: [],
+            tag: $tag,
         }
     };
     (
         @stringify @inner
         lines: [[]],
         obuf: [$($out:tt)*],
+        tag: [$($tag:literal)?],
+    ) => {
+        const _: &str = concat!($("[", $tag, "] ",)? $($out),*);
+    };
+    (
+        @stringify @inner
+        lines: [[] $($lrest:tt)+],
+        obuf: [$($out:tt)*],
+        tag: [$($tag:literal)?],
+    ) => {
+        const _: &str = concat!($("[", $tag, "] ",)? $($out),*);
+        $crate::befunge_stringify! {
+            @stringify @inner
+            lines: [$($lrest)+],
+            obuf: [],
+            tag: [$($tag)?],
+        }
+    };
+    (
+       @stringify @inner
+       lines: [[$lhh:tt $($lht:tt)*] $($lt:tt)*],
+       obuf: $obuf:tt,
+       tag: $tag:tt,
+    ) => {
+        $crate::befunge_pm::stringify_with_callback! {
+            tokens: [$lhh],
+            callback: [
+                name: $crate::befunge_stringify,
+                pre: [
+                    @stringify @inner @makeliteral
+                    lines: [[$($lht)*] $($lt)*],
+                    obuf: $obuf,
+                    tag: $tag,
+                ]
+            ],
+        }
+    };
+    (
+        @stringify @raw
+        lines: $lines:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [],
+            tag: [],
+        }
+    };
+    (
+        @stringify @raw
+        lines: $lines:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: $debug,
+            callback: [
+                name: $crate::befunge_stringify,
+                pre: [
+                    @stringify @raw @tagged
+                    lines: $lines,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @stringify @raw @tagged
+        lines: $lines:tt,
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @joined
+        lines: $lines:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [],
+            tag: [@joined name: [_], tag: [], rows: []],
+        }
+    };
+    (
+        @stringify @raw @joined
+        name: $name:ident,
+        lines: $lines:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [],
+            tag: [@joined name: [$name], tag: [], rows: []],
+        }
+    };
+    (
+        @stringify @raw @joined
+        lines: $lines:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: $debug,
+            callback: [
+                name: $crate::befunge_stringify,
+                pre: [
+                    @stringify @raw @joined @tagged
+                    lines: $lines,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @stringify @raw @joined @tagged
+        lines: $lines:tt,
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [],
+            tag: [@joined name: [_], tag: $tag, rows: []],
+        }
+    };
+    (
+        @stringify @tosocket
+        lines: $lines:tt,
+        $(debug: $debug:tt,)?
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @tosocket
+            sockets: "befunge",
+            lines: $lines,
+            $(debug: $debug,)?
+        }
+    };
+    (
+        @stringify @tosocket
+        sockets: $sockets:literal,
+        lines: $lines:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [],
+            tag: [@joined name: [@socket $sockets], tag: [], rows: []],
+        }
+    };
+    (
+        @stringify @tosocket
+        sockets: $sockets:literal,
+        lines: $lines:tt,
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: $debug,
+            callback: [
+                name: $crate::befunge_stringify,
+                pre: [
+                    @stringify @tosocket @tagged
+                    sockets: $sockets,
+                    lines: $lines,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @stringify @tosocket @tagged
+        sockets: $sockets:literal,
+        lines: $lines:tt,
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [],
+            tag: [@joined name: [@socket $sockets], tag: $tag, rows: []],
+        }
+    };
+    (
+        @stringify @framed
+        $(name: $name:ident,)?
+        lines: $lines:tt,
+        $(pc: $pc:tt,)?
+        debug: $debug:tt,
+    ) => {
+        $crate::dbg_find_tag! {
+            @tag
+            debug: $debug,
+            callback: [
+                name: $crate::befunge_stringify,
+                pre: [
+                    @stringify @framed @tagged
+                    name: [$($name)?],
+                    lines: $lines,
+                    pc: [$($pc)?],
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @stringify @framed
+        $(name: $name:ident,)?
+        lines: $lines:tt,
+        $(pc: $pc:tt,)?
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @framed @tagged
+            name: [$($name)?],
+            lines: $lines,
+            pc: [$($pc)?],
+            tag: [],
+        }
+    };
+    (
+        @stringify @framed @tagged
+        name: [],
+        lines: $lines:tt,
+        pc: $pc:tt,
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @framed @tagged
+            name: [_],
+            lines: $lines,
+            pc: $pc,
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @framed @tagged
+        name: [$name:tt],
+        lines: $lines:tt,
+        pc: [],
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [],
+            tag: [@framed name: [$name], tag: $tag, width: [], rows: []],
+        }
+    };
+    // A negative coordinate is never on the playfield, so there's nothing to mark.
+    (
+        @stringify @framed @tagged
+        name: $name:tt,
+        lines: $lines:tt,
+        pc: [[[[neg] [$($_x:tt)+]] $_y:tt]],
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @framed @tagged
+            name: $name,
+            lines: $lines,
+            pc: [],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @framed @tagged
+        name: $name:tt,
+        lines: $lines:tt,
+        pc: [[$_x:tt [[neg] [$($_y:tt)+]]]],
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @framed @tagged
+            name: $name,
+            lines: $lines,
+            pc: [],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @framed @tagged
+        name: $name:tt,
+        lines: $lines:tt,
+        pc: [[[[$_xsign:ident] $x:tt] [[$_ysign:ident] $y:tt]]],
+        tag: $tag:tt,
+    ) => {
+        $crate::list_get_at! {
+            @get
+            index: $y,
+            list: $lines,
+            callback: [
+                name: $crate::befunge_stringify,
+                pre: [
+                    @stringify @framed @pcrow
+                    name: $name,
+                    lines: $lines,
+                    pc: [$x $y],
+                    tag: $tag,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    // The PC is on a row that isn't there, so there's nothing to mark.
+    (
+        @stringify @framed @pcrow
+        name: $name:tt,
+        lines: $lines:tt,
+        pc: $pc:tt,
+        tag: $tag:tt,
+        oob: [],
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @framed @tagged
+            name: $name,
+            lines: $lines,
+            pc: [],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @framed @pcrow
+        name: $name:tt,
+        lines: $lines:tt,
+        pc: [$x:tt $y:tt],
+        tag: $tag:tt,
+        item: [$row:tt],
+    ) => {
+        $crate::befunge_pm::split_row! {
+            pad: ' ',
+            lenof: $x,
+            split: $row,
+            callback: [
+                name: $crate::befunge_stringify,
+                pre: [
+                    @stringify @framed @pccell
+                    name: $name,
+                    lines: $lines,
+                    y: $y,
+                    tag: $tag,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @stringify @framed @pccell
+        name: $name:tt,
+        lines: $lines:tt,
+        y: $y:tt,
+        tag: $tag:tt,
+        l: [$($l:tt)*],
+        r: [$_cur:tt $($r:tt)*],
+    ) => {
+        $crate::list_set_at! {
+            @set
+            index: $y,
+            list: $lines,
+            value: [[$($l)* '#' $($r)*]],
+            callback: [
+                name: $crate::befunge_stringify,
+                pre: [
+                    @stringify @framed @marked
+                    name: $name,
+                    tag: $tag,
+                ],
+                pst: [],
+            ],
+        }
+    };
+    (
+        @stringify @framed @marked
+        name: $name:tt,
+        tag: $tag:tt,
+        list: $lines:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @framed @tagged
+            name: $name,
+            lines: $lines,
+            pc: [],
+            tag: $tag,
+        }
+    };
+    // Once every row has been stringified, each of them is padded out to the widest one and put
+    // between the sides of the frame.
+    (
+        @stringify @framed @rows
+        name: $name:tt,
+        tag: $tag:tt,
+        width: [$($w:tt)*],
+        rows: [[$($p:tt)*] $($rrest:tt)*],
+        done: [$($done:tt)*],
+    ) => {
+        macro_rules! stringify_framed_pad {
+            (width: [$(${ignore($p)} [])* $$($$pad:tt)*]) => {
+                $crate::befunge_stringify! {
+                    @stringify @framed @rows
+                    name: $name,
+                    tag: $tag,
+                    width: [$($w)*],
+                    rows: [$($rrest)*],
+                    done: [$($done)* "|" $($p)* $$($${ignore($$pad)} " ")* "|\n"],
+                }
+            };
+        }
+        stringify_framed_pad! {
+            width: [$($w)*]
+        }
+    };
+    (
+        @stringify @framed @rows
+        name: [$name:tt],
+        tag: [$($tag:literal)?],
+        width: [$($w:tt)*],
+        rows: [],
+        done: [$($done:tt)*],
+    ) => {
+        const $name: &str = concat!(
+            $("[", $tag, "]\n",)?
+            "+", $(${ignore($w)} "-",)* "+\n",
+            $($done,)*
+            "+", $(${ignore($w)} "-",)* "+",
+        );
+    };
+    (
+        @stringify @framed @next
+        lines: [],
+        obuf: [],
+        tag: [@framed name: $name:tt, tag: $tag:tt, width: $width:tt, rows: $rows:tt],
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @framed @rows
+            name: $name,
+            tag: $tag,
+            width: $width,
+            rows: $rows,
+            done: [],
+        }
+    };
+    (
+        @stringify @framed @next
+        lines: $lines:tt,
+        obuf: [],
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [],
+            tag: $tag,
+        }
+    };
+    // With `@framed`, the tag carries the rows finished so far and the width of the widest one, as
+    // a `[]` for each cell.
+    (
+        @stringify @raw @inner
+        lines: [[] $($lrest:tt)*],
+        obuf: [$($out:tt)*],
+        tag: [@framed name: $name:tt, tag: $tag:tt, width: $width:tt, rows: [$($rows:tt)*]],
+    ) => {
+        macro_rules! stringify_framed_width {
+            (width: [$(${ignore($out)} [])* $$($$more:tt)*]) => {
+                $crate::befunge_stringify! {
+                    @stringify @framed @next
+                    lines: [$($lrest)*],
+                    obuf: [],
+                    tag: [
+                        @framed
+                        name: $name,
+                        tag: $tag,
+                        width: [$(${ignore($out)} [])* $$($$more)*],
+                        rows: [$($rows)* [$($out)*]]
+                    ],
+                }
+            };
+            (width: $$_width:tt) => {
+                $crate::befunge_stringify! {
+                    @stringify @framed @next
+                    lines: [$($lrest)*],
+                    obuf: [],
+                    tag: [
+                        @framed
+                        name: $name,
+                        tag: $tag,
+                        width: [$(${ignore($out)} [])*],
+                        rows: [$($rows)* [$($out)*]]
+                    ],
+                }
+            };
+        }
+        stringify_framed_width! {
+            width: $width
+        }
+    };
+    // With `@joined`, the tag also carries the name of the `const` and the rows finished so far,
+    // which the arms for each character pass along without looking at.
+    // `@tosocket` is `@joined` with `@socket` and the socket prefix in place of the name.
+    (
+        @stringify @raw @inner
+        lines: [[]],
+        obuf: [$($out:tt)*],
+        tag: [
+            @joined
+            name: [@socket $sockets:literal],
+            tag: [$($tag:literal)?],
+            rows: [$([$($row:tt)*])*]
+        ],
+    ) => {
+        $crate::befunge_pm::socket_debug! {
+            string: [$("[", $tag, "]\n",)? $($($row,)* "\n",)* $($out),*],
+            socket: [$sockets ".debug"],
+            level: info,
+            noack: true,
+        }
+    };
+    (
+        @stringify @raw @inner
+        lines: [[]],
+        obuf: [$($out:tt)*],
+        tag: [@joined name: [$name:tt], tag: [$($tag:literal)?], rows: [$([$($row:tt)*])*]],
+    ) => {
+        const $name: &str = concat!($("[", $tag, "]\n",)? $($($row,)* "\n",)* $($out),*);
+    };
+    (
+        @stringify @raw @inner
+        lines: [[] $($lrest:tt)+],
+        obuf: $out:tt,
+        tag: [@joined name: $name:tt, tag: $tag:tt, rows: [$($rows:tt)*]],
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: [$($lrest)+],
+            obuf: [],
+            tag: [@joined name: $name, tag: $tag, rows: [$($rows)* $out]],
+        }
+    };
+    (
+        @stringify @raw @inner
+        lines: [[]],
+        obuf: [$($out:tt)*],
+        tag: [$($tag:literal)?],
+    ) => {
+        const _: &str = concat!($("[", $tag, "] ",)? $($out),*);
+    };
+    (
+        @stringify @raw @inner
+        lines: [[] $($lrest:tt)+],
+        obuf: [$($out:tt)*],
+        tag: [$($tag:literal)?],
+    ) => {
+        const _: &str = concat!($("[", $tag, "] ",)? $($out),*);
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: [$($lrest)+],
+            obuf: [],
+            tag: [$($tag)?],
+        }
+    };
+    (
+        @stringify @raw @inner
+        lines: [[$lhh:tt $($lht:tt)*] $($lt:tt)*],
+        obuf: $obuf:tt,
+        tag: $tag:tt,
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner @char
+            lines: [[$($lht)*] $($lt)*],
+            obuf: $obuf,
+            tag: $tag,
+            char: $lhh,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: ' ',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* " "],
+            tag: $tag,
+        }
+    };
+    // A run of spaces in a compacted playfield, which is a space for each `[]` in it.
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: [sp [$($n:tt)*]],
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* $(${ignore($n)} " ")*],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '+',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "+"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '-',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "-"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '*',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "*"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '/',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "/"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '%',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "%"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '!',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "!"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '`',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "`"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '>',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* ">"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '<',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "<"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '^',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "^"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'v',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "v"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '?',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "?"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '_',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "_"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '|',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "|"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '"',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "\""],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: ':',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* ":"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '\\',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "\\"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '$',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "$"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '.',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "."],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: ',',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* ","],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '#',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "#"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'g',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "g"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'p',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "p"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '&',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "&"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '~',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "~"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '@',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "@"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '[',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "["],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: ']',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "]"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'j',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "j"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'k',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "k"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '\'',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "'"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 's',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "s"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'n',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "n"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'r',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "r"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: ';',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* ";"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '0',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "0"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '1',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "1"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '2',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "2"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '3',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "3"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '4',
     ) => {
-        const _: &str = concat!($($out),*);
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "4"],
+            tag: $tag,
+        }
     };
     (
-        @stringify @inner
-        lines: [[] $($lrest:tt)+],
-        obuf: [$($out:tt)*],
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '5',
     ) => {
-        const _: &str = concat!($($out),*);
         $crate::befunge_stringify! {
-            @stringify @inner
-            lines: [$($lrest)+],
-            obuf: [],
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "5"],
+            tag: $tag,
         }
     };
     (
-       @stringify @inner
-       lines: [[$lhh:tt $($lht:tt)*] $($lt:tt)*],
-       obuf: $obuf:tt,
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '6',
     ) => {
-        $crate::befunge_pm::stringify_with_callback! {
-            tokens: [$lhh],
-            callback: [
-                name: $crate::befunge_stringify,
-                pre: [
-                    @stringify @inner @makeliteral
-                    lines: [[$($lht)*] $($lt)*],
-                    obuf: $obuf,
-                ]
-            ],
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "6"],
+            tag: $tag,
         }
     };
     (
-        @stringify @raw
+        @stringify @raw @inner @char
         lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '7',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [],
+            obuf: [$($obuf)* "7"],
+            tag: $tag,
         }
     };
     (
-        @stringify @raw @inner
-        lines: [[]],
-        obuf: [$($out:tt)*],
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '8',
     ) => {
-        const _: &str = concat!($($out),*);
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "8"],
+            tag: $tag,
+        }
     };
     (
-        @stringify @raw @inner
-        lines: [[] $($lrest:tt)+],
-        obuf: [$($out:tt)*],
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '9',
     ) => {
-        const _: &str = concat!($($out),*);
         $crate::befunge_stringify! {
             @stringify @raw @inner
-            lines: [$($lrest)+],
-            obuf: [],
+            lines: $lines,
+            obuf: [$($obuf)* "9"],
+            tag: $tag,
         }
     };
+    // The rest of printable ASCII.
     (
-        @stringify @raw @inner
-        lines: [[$lhh:tt $($lht:tt)*] $($lt:tt)*],
-        obuf: $obuf:tt,
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '(',
     ) => {
         $crate::befunge_stringify! {
-            @stringify @raw @inner @char
-            lines: [[$($lht)*] $($lt)*],
-            obuf: $obuf,
-            char: $lhh,
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "("],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '\n',
+        tag: $tag:tt,
+        char: ')',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "\n"],
+            obuf: [$($obuf)* ")"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: ' ',
+        tag: $tag:tt,
+        char: '=',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* " "],
+            obuf: [$($obuf)* "="],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '+',
+        tag: $tag:tt,
+        char: 'A',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "+"],
+            obuf: [$($obuf)* "A"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '-',
+        tag: $tag:tt,
+        char: 'B',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "-"],
+            obuf: [$($obuf)* "B"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '*',
+        tag: $tag:tt,
+        char: 'C',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "*"],
+            obuf: [$($obuf)* "C"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '/',
+        tag: $tag:tt,
+        char: 'D',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "/"],
+            obuf: [$($obuf)* "D"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '%',
+        tag: $tag:tt,
+        char: 'E',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "%"],
+            obuf: [$($obuf)* "E"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '!',
+        tag: $tag:tt,
+        char: 'F',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "!"],
+            obuf: [$($obuf)* "F"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '`',
+        tag: $tag:tt,
+        char: 'G',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "`"],
+            obuf: [$($obuf)* "G"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '>',
+        tag: $tag:tt,
+        char: 'H',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* ">"],
+            obuf: [$($obuf)* "H"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '<',
+        tag: $tag:tt,
+        char: 'I',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "<"],
+            obuf: [$($obuf)* "I"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '^',
+        tag: $tag:tt,
+        char: 'J',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "^"],
+            obuf: [$($obuf)* "J"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: 'v',
+        tag: $tag:tt,
+        char: 'K',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "v"],
+            obuf: [$($obuf)* "K"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '?',
+        tag: $tag:tt,
+        char: 'L',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "?"],
+            obuf: [$($obuf)* "L"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '|',
+        tag: $tag:tt,
+        char: 'M',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "_"],
+            obuf: [$($obuf)* "M"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '|',
+        tag: $tag:tt,
+        char: 'N',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "|"],
+            obuf: [$($obuf)* "N"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '"',
+        tag: $tag:tt,
+        char: 'O',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "\""],
+            obuf: [$($obuf)* "O"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: ':',
+        tag: $tag:tt,
+        char: 'P',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* ":"],
+            obuf: [$($obuf)* "P"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '\\',
+        tag: $tag:tt,
+        char: 'Q',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "\\"],
+            obuf: [$($obuf)* "Q"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '$',
+        tag: $tag:tt,
+        char: 'R',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "$"],
+            obuf: [$($obuf)* "R"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '.',
+        tag: $tag:tt,
+        char: 'S',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "."],
+            obuf: [$($obuf)* "S"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: ',',
+        tag: $tag:tt,
+        char: 'T',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* ","],
+            obuf: [$($obuf)* "T"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '#',
+        tag: $tag:tt,
+        char: 'U',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "#"],
+            obuf: [$($obuf)* "U"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: 'g',
+        tag: $tag:tt,
+        char: 'V',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "g"],
+            obuf: [$($obuf)* "V"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: 'p',
+        tag: $tag:tt,
+        char: 'W',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "p"],
+            obuf: [$($obuf)* "W"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '&',
+        tag: $tag:tt,
+        char: 'X',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "&"],
+            obuf: [$($obuf)* "X"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '~',
+        tag: $tag:tt,
+        char: 'Y',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "~"],
+            obuf: [$($obuf)* "Y"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '@',
+        tag: $tag:tt,
+        char: 'Z',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "@"],
+            obuf: [$($obuf)* "Z"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '0',
+        tag: $tag:tt,
+        char: 'a',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "0"],
+            obuf: [$($obuf)* "a"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '1',
+        tag: $tag:tt,
+        char: 'b',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "1"],
+            obuf: [$($obuf)* "b"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '2',
+        tag: $tag:tt,
+        char: 'c',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "2"],
+            obuf: [$($obuf)* "c"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '3',
+        tag: $tag:tt,
+        char: 'd',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "3"],
+            obuf: [$($obuf)* "d"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '4',
+        tag: $tag:tt,
+        char: 'e',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "4"],
+            obuf: [$($obuf)* "e"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '5',
+        tag: $tag:tt,
+        char: 'f',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "5"],
+            obuf: [$($obuf)* "f"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '6',
+        tag: $tag:tt,
+        char: 'h',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "6"],
+            obuf: [$($obuf)* "h"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '7',
+        tag: $tag:tt,
+        char: 'i',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "7"],
+            obuf: [$($obuf)* "i"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '8',
+        tag: $tag:tt,
+        char: 'l',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "8"],
+            obuf: [$($obuf)* "l"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
-        char: '9',
+        tag: $tag:tt,
+        char: 'm',
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
-            obuf: [$($obuf)* "9"],
+            obuf: [$($obuf)* "m"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'o',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "o"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'q',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "q"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 't',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "t"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'u',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "u"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'w',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "w"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'x',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "x"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'y',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "y"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: 'z',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "z"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '{',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "{"],
+            tag: $tag,
+        }
+    };
+    (
+        @stringify @raw @inner @char
+        lines: $lines:tt,
+        obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
+        char: '}',
+    ) => {
+        $crate::befunge_stringify! {
+            @stringify @raw @inner
+            lines: $lines,
+            obuf: [$($obuf)* "}"],
+            tag: $tag,
         }
     };
     (
         @stringify @raw @inner @char
         lines: $lines:tt,
         obuf: $obuf:tt,
+        tag: $tag:tt,
         char: $other:tt,
     ) => {
         $crate::befunge_pm::stringify_with_callback! {
             tokens: [$other],
+            escape: true,
             callback: [
                 name: $crate::befunge_stringify,
                 pre: [
                     @stringify @raw @inner @catch
                     lines: $lines,
                     obuf: $obuf,
+                    tag: $tag,
                 ],
                 pst: [],
             ],
@@ -567,12 +2116,14 @@ macro_rules! befunge_stringify {
         @stringify @raw @inner @catch
         lines: $lines:tt,
         obuf: [$($obuf:tt)*],
+        tag: $tag:tt,
         stringified: $char:tt,
     ) => {
         $crate::befunge_stringify! {
             @stringify @raw @inner
             lines: $lines,
             obuf: [$($obuf)* $char],
+            tag: $tag,
         }
     }
 }
@@ -1,5 +1,25 @@
 #[macro_export]
 /// Lookup table that converts a character to a signed magnitude base 1 number.
+///
+/// Every ASCII character has an entry, so stringmode can push any of them.
+/// `befunge-dm/tests/fixtures/printable.bfg` pushes all of printable ASCII (except for `"`, which
+/// would end stringmode, so it's pushed with `48*2+` instead) and prints it back in order with `,`:
+/// ```befunge
+#[doc = include_str!("../tests/fixtures/printable.bfg")]
+/// ```
+/// As with the other programs that do output, this needs `befunge-if` running unless the crate is
+/// built with the `headless` feature:
+#[cfg_attr(feature = "headless", doc = "```")]
+#[cfg_attr(not(feature = "headless"), doc = "```ignore")]
+/// #![recursion_limit = "8192"]
+/// #![feature(macro_metavar_expr)]
+///
+/// // Prints all of printable ASCII in order, from the space to `~`.
+/// befunge_dm::befunge! {
+///     file: "befunge-dm/tests/fixtures/printable.bfg",
+///     debug: [[noflush]],
+/// }
+/// ```
 macro_rules! char_to_code {
     (
         @match
@@ -1942,7 +1962,531 @@ macro_rules! char_to_code {
 /// that a number is provided without a corresponding character code, the macro just returns that
 /// number as a literal. If the corresponding character exists, the return is instead the character
 /// code _and_ the literal.
+///
+/// Control characters (and DEL) come back as escaped character literals, so passing the result
+/// through `stringify!` gives a readable single-line spelling rather than the raw character:
+/// ```
+/// #![feature(macro_metavar_expr)]
+///
+/// macro_rules! render {
+///     (name: $name:ident, char: [$char:tt, $code:tt],) => {
+///         const $name: &str = concat!(stringify!($char), " (", $code, ")");
+///     };
+/// }
+///
+/// befunge_dm::code_to_char_pretty! {
+///     @match
+///     num: [[pos] [[] [] [] [] [] [] [] [] [] []]],
+///     callback: [
+///         name: render,
+///         pre: [name: NEWLINE,],
+///         pst: [],
+///     ],
+/// }
+/// befunge_dm::code_to_char_pretty! {
+///     @match
+///     num: [[pos] [[] [] [] [] [] [] [] [] []]],
+///     callback: [
+///         name: render,
+///         pre: [name: TAB,],
+///         pst: [],
+///     ],
+/// }
+/// befunge_dm::code_to_char_pretty! {
+///     @match
+///     num: [[pos] [[] [] [] [] [] [] []]],
+///     callback: [
+///         name: render,
+///         pre: [name: BELL,],
+///         pst: [],
+///     ],
+/// }
+///
+/// assert_eq!(NEWLINE, r"'\n' (10)");
+/// assert_eq!(TAB, r"'\t' (9)");
+/// assert_eq!(BELL, r"'\x07' (7)");
+/// ```
 macro_rules! code_to_char_pretty {
+    (
+        @match
+        num: [[$(pos)?] []],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\0', 0],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[]]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x01', 1],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x02', 2],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x03', 3],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x04', 4],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x05', 5],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x06', 6],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x07', 7],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x08', 8],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\t', 9],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\n', 10],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x0b', 11],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x0c', 12],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\r', 13],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x0e', 14],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x0f', 15],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x10', 16],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x11', 17],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x12', 18],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x13', 19],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x14', 20],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x15', 21],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x16', 22],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x17', 23],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x18', 24],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x19', 25],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x1a', 26],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x1b', 27],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x1c', 28],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x1d', 29],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x1e', 30],
+            $($pst)*
+        }
+    };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x1f', 31],
+            $($pst)*
+        }
+    };
     (
         @match
         num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
@@ -3368,6 +3912,21 @@ macro_rules! code_to_char_pretty {
             $($pst)*
         }
     };
+    (
+        @match
+        num: [[$(pos)?] [[] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] [] []]],
+        callback: [
+            name: $name:path,
+            pre: [$($pre:tt)*],
+            pst: [$($pst:tt)*],
+        ],
+    ) => {
+        $name! {
+            $($pre)*
+            char: ['\x7f', 127],
+            $($pst)*
+        }
+    };
 
     (
         @match
@@ -0,0 +1,30 @@
+//! Runs a program with every `socket_debug_default!` in the interpreter switched on and nothing
+//! listening on the debugging socket, so that the build only completes if `debug_fallback` turns
+//! each message into a `const` instead of an error. Run with
+//! `cargo test -p befunge-dm --features socket_debug_default,debug_fallback`.
+
+#![cfg(all(
+    feature = "socket_debug_default",
+    feature = "debug_fallback",
+    not(feature = "headless"),
+))]
+#![recursion_limit = "16384"]
+#![feature(macro_metavar_expr)]
+
+macro_rules! countdown {
+    (stack: [$($n:literal)*],) => {
+        const COUNTDOWN: &[isize] = &[$($n),*];
+    };
+}
+
+befunge_dm::befunge! {
+    file: "befunge-dm/tests/fixtures/countdown.bfg",
+    sockets: "befunge-dm.fallback",
+    result: countdown,
+    debug: [[noflush] [postinit]],
+}
+
+#[test]
+fn countdown() {
+    assert_eq!(COUNTDOWN, [0]);
+}
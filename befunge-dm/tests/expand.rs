@@ -0,0 +1,263 @@
+//! Expands programs with `rustc -Zunpretty=expanded` to check the `const _: &str = "..."`s that the
+//! debugging flags output, which nothing else gets to see. The programs are built as a crate of
+//! their own under the `target` directory, the same way trybuild does it. Each test gets its own
+//! crate, but they all share a target directory so that `befunge-dm` is only built the once.
+
+use std::{fs, path::Path, process::Command};
+
+const CRATE: &str = r#"
+#![recursion_limit = "16384"]
+#![feature(macro_metavar_expr)]
+
+PROGRAMS
+
+fn main() {}
+"#;
+
+const SOURCE: &str = r#"
+befunge_dm::befunge_pm::befunge_source! {
+    source: SOURCE,
+    callback: [
+        name: befunge_dm::befunge_init,
+        pre: [@init],
+        pst: [
+            debug: [[noflush] FLAGS],
+        ],
+    ],
+}
+"#;
+
+const FILE: &str = r#"
+befunge_dm::befunge! {
+    file: FILE,
+    OPTIONS
+}
+"#;
+
+/// Runs `source` with the debugging flags `flags`.
+fn source(source: &str, flags: &str) -> String {
+    SOURCE
+        .replace("SOURCE", &format!("{source:?}"))
+        .replace("FLAGS", flags)
+}
+
+/// Runs the file `name` from `tests/fixtures` with `options` (`debug:` and everything else after
+/// `file:`).
+fn file(name: &str, options: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    FILE.replace("FILE", &format!("{path:?}"))
+        .replace("OPTIONS", options)
+}
+
+/// Expands `programs`, giving back the messages they output, in order and without the quotes.
+fn expand(programs: &str) -> Vec<String> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_tmpdir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let test = std::thread::current().name().unwrap().replace("::", "-");
+    let dir = target_tmpdir.join("expand").join(test);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    let manifest = format!(
+        "[package]\nname = \"befunge-dm-expand\"\nversion = \"0.0.0\"\nedition = \"2024\"\n\
+         publish = false\n\n[dependencies.befunge-dm]\npath = {manifest_dir:?}\n\
+         features = [\"headless\"]\n\n[workspace]\n"
+    );
+    fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+    // The same versions of everything as the workspace, so that nothing has to be downloaded.
+    let _ = fs::copy(manifest_dir.join("../Cargo.lock"), dir.join("Cargo.lock"));
+    fs::write(dir.join("src/main.rs"), CRATE.replace("PROGRAMS", programs)).unwrap();
+    let output = Command::new(env!("CARGO"))
+        .current_dir(&dir)
+        .env_remove("RUSTFLAGS")
+        .env("CARGO_TARGET_DIR", target_tmpdir.join("expand-target"))
+        .args(["rustc", "--offline", "--quiet", "--", "-Zunpretty=expanded"])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // Long messages go on the line after the `=`.
+    let mut lines = stdout.lines().map(str::trim);
+    let mut messages = Vec::new();
+    while let Some(line) = lines.next() {
+        let Some(msg) = line.strip_prefix("const _: &str =") else {
+            continue;
+        };
+        let msg = match msg.trim() {
+            "" => lines.next().unwrap(),
+            msg => msg,
+        };
+        messages.push(unescape(msg.trim_end_matches(';')));
+    }
+    messages
+}
+
+/// Expands `source` with the debugging flags `flags`, giving back the messages it outputs.
+fn messages(source: &str, flags: &str) -> Vec<String> {
+    expand(&self::source(source, flags))
+}
+
+/// Turns a string literal as the pretty printer writes it back into the string.
+fn unescape(literal: &str) -> String {
+    let mut chars = literal
+        .strip_prefix('"')
+        .unwrap()
+        .strip_suffix('"')
+        .unwrap()
+        .chars();
+    let mut string = String::new();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+        match chars.next().unwrap() {
+            'n' => string.push('\n'),
+            't' => string.push('\t'),
+            'r' => string.push('\r'),
+            '0' => string.push('\0'),
+            'x' => {
+                let code = chars.by_ref().take(2).collect::<String>();
+                string.push(u8::from_str_radix(&code, 16).unwrap() as char);
+            }
+            'u' => {
+                let code = chars.by_ref().skip(1).take_while(|&c| c != '}');
+                let code = u32::from_str_radix(&code.collect::<String>(), 16).unwrap();
+                string.push(char::from_u32(code).unwrap());
+            }
+            c => string.push(c),
+        }
+    }
+    string
+}
+
+#[test]
+fn put_out_of_bounds() {
+    // Tries to put a 1 at column 0 of row -1.
+    let abandoned = "Y index was out of bounds! Abandoning put attempt.";
+    let end = "Program terminated successfully!";
+    assert_eq!(messages("1001-p@", "[putdbg]"), [abandoned, end]);
+    // `[getdbg]` is only for `g` instructions.
+    assert_eq!(messages("1001-p@", "[getdbg]"), [end]);
+}
+
+#[test]
+fn underflow() {
+    // Each of these pops from an empty stack straight away. `|` goes down to its `@`.
+    let instrs = [
+        "+", "-", "*", "/", "%", "!", "`", ":", "\\", "$", ".", ",", "g", "p", "_", "|",
+    ];
+    let programs = |flags| {
+        let programs = instrs.iter().enumerate().map(|(i, instr)| {
+            let end = if *instr == "|" { "\n@" } else { "@" };
+            format!(
+                "mod instr{i} {{{}}}",
+                source(&format!("{instr}{end}"), flags)
+            )
+        });
+        programs.collect::<String>()
+    };
+    let end = "Program terminated successfully!";
+    let warned = instrs.iter().flat_map(|instr| {
+        // The `\` comes out escaped.
+        let instr = instr.replace('\\', "\\\\");
+        [
+            format!("Stack underflow: '{instr}' at (0, 0)"),
+            end.to_string(),
+        ]
+    });
+    assert_eq!(expand(&programs("[underflow]")), warned.collect::<Vec<_>>());
+    assert_eq!(expand(&programs("")), [end; 16]);
+}
+
+#[test]
+fn breakpoint_in_loop() {
+    // Stops on the `1` at the start of the loop, once for each time around.
+    let options = "breakpoints: [[4 0]],\ndebug: [[noflush]],";
+    let messages = expand(&file("countdown.bfg", options));
+    let dumps = messages.split(|msg| msg == "Breakpoint at (0, 4):").skip(1);
+    let tops = dumps.map(|dump| {
+        assert_eq!(dump[0], "dir: right, stringmode: false, bridge: false");
+        assert_eq!(dump[1], "Stack:");
+        assert_eq!(dump[3], "Playfield:");
+        // The `1` the PC is on is swapped for a `#`.
+        let mut rows = dump[4]
+            .lines()
+            .map(|row| row.trim_end_matches('|').trim_end());
+        let frame = format!("+{}+", "-".repeat(80));
+        assert_eq!(rows.next().unwrap(), frame);
+        assert_eq!(rows.next().unwrap(), "|25*>#-:v");
+        assert_eq!(rows.next().unwrap(), "|   ^   _@");
+        assert_eq!(rows.next_back().unwrap(), frame);
+        dump[2].clone()
+    });
+    let expected = (1..=10).rev().map(|n| format!("top: {n}"));
+    assert_eq!(tops.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+    assert_eq!(messages.last().unwrap(), "Program terminated successfully!");
+}
+
+#[test]
+fn stack_values() {
+    // Leaves 0, -3, and 72 on the stack. Only 72 has a printable character.
+    let expected = [
+        "Program terminated successfully!",
+        "Stack at program '@':",
+        "top: 72 ('H')",
+        "     -3",
+        "     0",
+    ];
+    assert_eq!(messages("003-89*@", "[poststack]"), expected);
+}
+
+/// Counts down from 10, going around the loop from the `>` to the `_` ten times.
+const COUNTDOWN: &str = "25*>1-:v\n   ^   _@";
+
+#[test]
+fn profile() {
+    let expected = [
+        "Program terminated successfully!",
+        "Instructions executed: 73",
+        "  arithmetic:   11",
+        "  numbers:      12",
+        "  stack:        10",
+        "  movement:     29",
+        "  conditionals: 10",
+        "  memory:       0",
+        "  I/O:          0",
+        "  other:        1",
+    ];
+    assert_eq!(messages(COUNTDOWN, "[profile]"), expected);
+}
+
+#[test]
+fn heatmap() {
+    // Only the numbers at the start and the `@` at the end are visited once.
+    let expected = [
+        "Program terminated successfully!",
+        "Cell visits (.:*#@ from fewest to most, 10 at most):",
+        "...@@@@@",
+        "   @@@@@.",
+    ];
+    assert_eq!(messages(COUNTDOWN, "[heatmap]"), expected);
+}
+
+#[test]
+fn stepcount() {
+    let end = "Program terminated successfully!";
+    assert_eq!(
+        messages(COUNTDOWN, "[stepcount]"),
+        [end, "Executed 100 steps"]
+    );
+    // Most of the steps in `sparse.bfg` are spent crossing the spaces that `compact: true` skips.
+    let using = format!(
+        "Using Befunge file: '{}/tests/fixtures/sparse.bfg'",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let options = "debug: [[noflush] [stepcount]],";
+    let expected = [&using, end, "Executed 320 steps"];
+    assert_eq!(expand(&file("sparse.bfg", options)), expected);
+    let options = format!("compact: true,\n{options}");
+    let expected = [&using, end, "Executed 96 steps"];
+    assert_eq!(expand(&file("sparse.bfg", &options)), expected);
+}
@@ -0,0 +1,41 @@
+//! Runs programs with every `socket_debug_default!` in the interpreter switched on, so that a
+//! message that uses a metavariable its arm never bound (or binds at a different depth) fails the
+//! build. Run with `cargo test -p befunge-dm --features socket_debug_default,headless`.
+
+#![cfg(all(feature = "socket_debug_default", feature = "headless"))]
+#![recursion_limit = "16384"]
+#![feature(macro_metavar_expr)]
+
+macro_rules! sparse {
+    (stack: [$($n:literal)*],) => {
+        const SPARSE: &[isize] = &[$($n),*];
+    };
+}
+
+befunge_dm::befunge! {
+    file: "befunge-dm/tests/fixtures/torture.bfg",
+    debug: [[noflush]],
+}
+
+befunge_dm::befunge! {
+    file: "befunge-dm/examples/io.bfg",
+    debug: [[noflush]],
+}
+
+befunge_dm::befunge! {
+    file: "befunge-dm/tests/fixtures/sparse.bfg",
+    result: sparse,
+    compact: true,
+    debug: [[noflush]],
+}
+
+// `[postinit]`, `[putdbg]`, and breakpoints send the playfield with `@stringify @tosocket`.
+befunge_dm::befunge! {
+    file: "befunge-dm/tests/fixtures/put.bfg",
+    debug: [[noflush] [postinit] [putdbg] [breakpoints [5 0]]],
+}
+
+#[test]
+fn sparse() {
+    assert_eq!(SPARSE, [0]);
+}
@@ -0,0 +1,8 @@
+//! Checks the messages of the errors the macros give on purpose. Run with `TRYBUILD=overwrite` to
+//! update the expected output after changing an error message.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,13 @@
+// With `[collecterrors]`, all three unknown instructions on the way to the `@` should be reported
+// in one error, rather than just the `X`.
+
+#![recursion_limit = "1024"]
+#![feature(macro_metavar_expr)]
+
+befunge_dm::befunge_init! {
+    @init
+    filecontents: ['>' 'X' 'Y' 'v' '\n' '@' ' ' 'Z' '<' '\n'],
+    debug: [[noflush] [collecterrors]],
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+// A program that keeps going around past the same unknown instructions should stop with them
+// reported the first time it comes back to one, rather than at the recursion limit.
+
+#![recursion_limit = "1024"]
+#![feature(macro_metavar_expr)]
+
+befunge_dm::befunge_init! {
+    @init
+    filecontents: ['>' 'Q' ' ' 'W' '<' '\n'],
+    debug: [[noflush] [collecterrors] [tag "loop"]],
+}
+
+fn main() {}
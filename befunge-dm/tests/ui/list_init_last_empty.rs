@@ -0,0 +1,20 @@
+// An empty list has no last item, so this should fail with the callback in the message rather
+// than with an error about `stringify` not being a function.
+
+#![allow(unused_macros)]
+
+macro_rules! helper {
+    (init: $init:tt, last: [$last:tt],) => {};
+}
+
+befunge_dm::list_init_last! {
+    @init
+    list: [],
+    callback: [
+        name: helper,
+        pre: [],
+        pst: [],
+    ],
+}
+
+fn main() {}
@@ -0,0 +1,236 @@
+//! Where `Debug` requests end up once an interface has received them.
+
+use crate::{Client, DebugLevel, Request, socket_name};
+use interprocess::local_socket::{Stream, prelude::*};
+use std::{
+    fs::{File, OpenOptions},
+    io::{Result as IoResult, Write},
+    path::Path,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{SyncSender, TrySendError, sync_channel},
+    },
+    thread::{self, JoinHandle},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Destination for debug messages. Messages go to every configured destination, or are printed to
+/// the terminal if there are none. Messages less important than the sink's minimum level are
+/// dropped, and anything other than [`DebugLevel::Info`] is labelled with its level.
+///
+/// # Example
+/// Appending to a file, one timestamped line per message:
+/// ```
+/// use befunge_if::{DebugLevel, debug_sink::DebugSink};
+///
+/// let path = std::env::temp_dir().join(format!("befunge-if-doctest-{}.log", std::process::id()));
+/// let _ = std::fs::remove_file(&path);
+/// let sink = DebugSink::new(Some(&path), None, DebugLevel::Info).unwrap();
+/// sink.debug(DebugLevel::Info, "first".to_string()).unwrap();
+/// sink.debug(DebugLevel::Trace, "too quiet".to_string()).unwrap();
+/// sink.debug(DebugLevel::Warn, "second one".to_string()).unwrap();
+///
+/// let contents = std::fs::read_to_string(&path).unwrap();
+/// std::fs::remove_file(&path).unwrap();
+/// let lines = contents
+///     .lines()
+///     .map(|line| {
+///         let (stamp, message) = line.strip_prefix('[').unwrap().split_once("] ").unwrap();
+///         let (secs, millis) = stamp.split_once('.').unwrap();
+///         assert!(secs.parse::<u64>().is_ok());
+///         assert!(millis.len() == 3 && millis.parse::<u16>().is_ok());
+///         message
+///     })
+///     .collect::<Vec<_>>();
+/// assert_eq!(lines, ["first", "(warn) second one"]);
+/// assert!(contents.ends_with('\n'));
+/// ```
+///
+/// Forwarding to another interface, here a mock that acknowledges everything and keeps the
+/// messages it receives:
+/// ```
+/// use befunge_if::{DebugLevel, PROTOCOL_VERSION, Request, debug_sink::DebugSink, socket_name};
+/// use interprocess::local_socket::{ListenerOptions, prelude::*};
+///
+/// let socket = format!("befunge-if.doctest.forward.{}", std::process::id());
+/// let listener = ListenerOptions::new()
+///     .name(socket_name(&socket).unwrap())
+///     .create_sync()
+///     .unwrap();
+/// let mock = std::thread::spawn(move || {
+///     let mut received = Vec::new();
+///     while received.len() < 3 {
+///         let mut conn = listener.accept().unwrap();
+///         loop {
+///             let reply = match befunge_if::ciborium::de::from_reader(&mut conn).unwrap() {
+///                 Request::Hello { .. } => Request::HelloAns {
+///                     accepted: true,
+///                     protocol_version: PROTOCOL_VERSION,
+///                 },
+///                 Request::Debug { level, msg } => {
+///                     received.push((level, msg));
+///                     Request::Ack
+///                 }
+///                 Request::CloseConnection => break,
+///                 other => panic!("unexpected request: {other:?}"),
+///             };
+///             befunge_if::ciborium::ser::into_writer(&reply, &mut conn).unwrap();
+///         }
+///     }
+///     received
+/// });
+///
+/// let sink = DebugSink::new(None, Some(&socket), DebugLevel::Trace).unwrap();
+/// sink.debug(DebugLevel::Trace, "a".to_string()).unwrap();
+/// sink.debug(DebugLevel::Info, "b".to_string()).unwrap();
+/// sink.debug(DebugLevel::Warn, "c".to_string()).unwrap();
+/// assert_eq!(sink.finish(), 0);
+/// assert_eq!(
+///     mock.join().unwrap(),
+///     [
+///         (DebugLevel::Trace, "a".to_string()),
+///         (DebugLevel::Info, "b".to_string()),
+///         (DebugLevel::Warn, "c".to_string()),
+///     ]
+/// );
+/// ```
+pub struct DebugSink {
+    file: Option<Mutex<File>>,
+    forward: Option<Forwarder>,
+    min_level: DebugLevel,
+}
+
+impl DebugSink {
+    /// Number of messages the forwarding queue holds before further messages are dropped.
+    pub const FORWARD_QUEUE_LEN: usize = 1024;
+
+    /// Creates a sink that appends to `file` and/or forwards to the interface on `forward`, keeping
+    /// only messages at least as important as `min_level`.
+    pub fn new(file: Option<&Path>, forward: Option<&str>, min_level: DebugLevel) -> IoResult<Self> {
+        let file = file
+            .map(|path| OpenOptions::new().create(true).append(true).open(path))
+            .transpose()?
+            .map(Mutex::new);
+        let forward =
+            forward.map(|socket| Forwarder::new(socket.to_string(), Self::FORWARD_QUEUE_LEN));
+        Ok(DebugSink {
+            file,
+            forward,
+            min_level,
+        })
+    }
+
+    /// Handles the contents of a single `Debug` request.
+    pub fn debug(&self, level: DebugLevel, contents: String) -> IoResult<()> {
+        if level < self.min_level {
+            return Ok(());
+        }
+        let label = match level {
+            DebugLevel::Info => String::new(),
+            other => format!("({other}) "),
+        };
+        if self.file.is_none() && self.forward.is_none() {
+            println!("DEBUG: {label}{contents}");
+            return Ok(());
+        }
+        if let Some(file) = &self.file {
+            let since_epoch = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let mut file = file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            writeln!(
+                file,
+                "[{}.{:03}] {label}{contents}",
+                since_epoch.as_secs(),
+                since_epoch.subsec_millis(),
+            )?;
+        }
+        if let Some(forward) = &self.forward {
+            forward.forward(level, contents);
+        }
+        Ok(())
+    }
+
+    /// Number of messages that couldn't be forwarded, either because the queue was full or because
+    /// the downstream interface couldn't be reached.
+    pub fn dropped(&self) -> usize {
+        self.forward
+            .as_ref()
+            .map_or(0, |forward| forward.dropped.load(Ordering::Relaxed))
+    }
+
+    /// Waits for every message still in the forwarding queue to be sent on, then gives back the
+    /// final [`dropped`](Self::dropped) count. Messages left in the queue would otherwise be lost
+    /// without being counted when the process exits. This doesn't return until the downstream
+    /// interface has dealt with all of them, or failed to.
+    pub fn finish(self) -> usize {
+        self.forward.map_or(0, Forwarder::finish)
+    }
+}
+
+/// Re-sends debug messages to another interface from a background thread, so that a slow
+/// downstream never holds up the socket the messages arrived on.
+struct Forwarder {
+    queue: SyncSender<(DebugLevel, String)>,
+    dropped: Arc<AtomicUsize>,
+    thread: JoinHandle<()>,
+}
+
+impl Forwarder {
+    fn new(socket: String, capacity: usize) -> Self {
+        let (queue, messages) = sync_channel::<(DebugLevel, String)>(capacity);
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let thread_dropped = Arc::clone(&dropped);
+        let thread = thread::spawn(move || {
+            for (level, contents) in messages {
+                if let Err(err) = send_debug(&socket, level, contents) {
+                    println!("Failed to forward debug message to '{socket}': '{err}'");
+                    thread_dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        });
+        Forwarder {
+            queue,
+            dropped,
+            thread,
+        }
+    }
+
+    fn forward(&self, level: DebugLevel, contents: String) {
+        match self.queue.try_send((level, contents)) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Closes the queue and waits for the thread to forward what's left in it.
+    fn finish(self) -> usize {
+        let Forwarder {
+            queue,
+            dropped,
+            thread,
+        } = self;
+        drop(queue);
+        // A panic on the thread has already been reported, and there's nothing left to wait for.
+        let _ = thread.join();
+        dropped.load(Ordering::Relaxed)
+    }
+}
+
+fn send_debug(
+    socket: &str,
+    level: DebugLevel,
+    contents: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut client = Client::new(Stream::connect(socket_name(socket)?)?);
+    client.handshake()?;
+    client.request_ack(Request::Debug {
+        level,
+        msg: contents,
+    })?;
+    client.close()?;
+    Ok(())
+}
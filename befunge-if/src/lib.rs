@@ -1,12 +1,168 @@
+pub mod debug_sink;
+pub mod prompt;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+use clap::ValueEnum;
+use interprocess::local_socket::{GenericFilePath, GenericNamespaced, Name, Stream, prelude::*};
 use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    io::{Read, Result as IoResult, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
 
 pub use ciborium;
 pub use serde;
 
+/// Version of the request protocol spoken by this build. Bump this whenever [`Request`] changes in a
+/// way that an older build couldn't understand.
+pub const PROTOCOL_VERSION: u32 = 4;
+
+/// Environment variable that, when set while the macros in `befunge-pm` are expanded, is put in
+/// front of every socket name they connect to. For instance `BEFUNGE_SOCKET_PREFIX=ci.` sends
+/// `befunge.output` to `ci.befunge.output`.
+pub const SOCKET_PREFIX_VAR: &str = "BEFUNGE_SOCKET_PREFIX";
+
+/// Environment variable naming the directory that socket files are put in on platforms without
+/// namespaced sockets. Defaults to `/tmp`.
+pub const SOCKET_DIR_VAR: &str = "BEFUNGE_SOCKET_DIR";
+
+/// Works out which socket to actually use for `socket`, given the value of [`SOCKET_PREFIX_VAR`]
+/// (if it's set).
+///
+/// # Example
+/// ```
+/// use befunge_if::resolve_socket;
+///
+/// assert_eq!(resolve_socket("befunge.output", None), "befunge.output");
+/// assert_eq!(resolve_socket("befunge.output", Some("ci.")), "ci.befunge.output");
+/// ```
+pub fn resolve_socket(socket: &str, prefix: Option<&str>) -> String {
+    format!("{}{socket}", prefix.unwrap_or_default())
+}
+
+/// Describes where `socket` lives: just its name where namespaced sockets are supported, and
+/// otherwise the path of the socket file in `dir` (the value of [`SOCKET_DIR_VAR`]), or `/tmp` if
+/// that isn't set.
+///
+/// # Example
+/// ```
+/// use befunge_if::socket_location;
+/// use interprocess::local_socket::{GenericNamespaced, NameType};
+///
+/// if GenericNamespaced::is_supported() {
+///     assert_eq!(socket_location("befunge.output", None), "befunge.output");
+///     assert_eq!(socket_location("befunge.output", Some("/run/bf")), "befunge.output");
+/// } else {
+///     assert_eq!(socket_location("befunge.output", None), "/tmp/befunge.output");
+///     assert_eq!(socket_location("befunge.output", Some("/run/bf")), "/run/bf/befunge.output");
+/// }
+/// ```
+pub fn socket_location(socket: &str, dir: Option<&str>) -> String {
+    if GenericNamespaced::is_supported() {
+        socket.to_string()
+    } else {
+        Path::new(dir.unwrap_or("/tmp")).join(socket).display().to_string()
+    }
+}
+
+/// Turns a socket name like `befunge.output` into a local socket name. Namespaced sockets are used
+/// where the platform supports them, and otherwise the socket is a file in the directory given by
+/// [`SOCKET_DIR_VAR`] (or `/tmp`).
+pub fn socket_name(socket: &str) -> IoResult<Name<'static>> {
+    let location = socket_location(socket, env::var(SOCKET_DIR_VAR).ok().as_deref());
+    if GenericNamespaced::is_supported() {
+        location.to_ns_name::<GenericNamespaced>()
+    } else {
+        location.to_fs_name::<GenericFilePath>()
+    }
+}
+
+/// Connects to the interface on `socket`. If nothing is listening there yet, this tries again up to
+/// `retries` more times, waiting `delay` before each new attempt, and gives back the last error if
+/// none of them succeed.
+///
+/// # Example
+/// An interface that only starts listening after a short delay:
+/// ```
+/// use befunge_if::{connect_with_retry, socket_name};
+/// use interprocess::local_socket::{ListenerOptions, prelude::*};
+/// use std::{thread, time::Duration};
+///
+/// let socket = format!("befunge-if.doctest.retry.{}", std::process::id());
+///
+/// // Nobody is listening, and we aren't willing to wait.
+/// assert!(connect_with_retry(&socket, 0, Duration::from_millis(50)).is_err());
+///
+/// let listener_socket = socket.clone();
+/// let listener = thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(300));
+///     let listener = ListenerOptions::new()
+///         .name(socket_name(&listener_socket).unwrap())
+///         .create_sync()
+///         .unwrap();
+///     listener.accept().unwrap();
+/// });
+/// assert!(connect_with_retry(&socket, 20, Duration::from_millis(50)).is_ok());
+/// listener.join().unwrap();
+/// ```
+pub fn connect_with_retry(socket: &str, retries: u32, delay: Duration) -> IoResult<Stream> {
+    let name = socket_name(socket)?;
+    let mut attempt = 0;
+    loop {
+        match Stream::connect(name.borrow()) {
+            Ok(conn) => break Ok(conn),
+            Err(_) if attempt < retries => {
+                attempt += 1;
+                thread::sleep(delay);
+            }
+            Err(err) => break Err(err),
+        }
+    }
+}
+
+/// How important a debugging message is, from least to most.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, ValueEnum,
+)]
+pub enum DebugLevel {
+    /// Step-by-step tracing of what the interpreter is doing.
+    Trace,
+    /// Everything else, like playfield dumps. Messages sent without a level are this.
+    #[default]
+    Info,
+    /// Something has probably gone wrong, like an index being out of bounds.
+    Warn,
+}
+
+impl Display for DebugLevel {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            DebugLevel::Trace => write!(f, "trace"),
+            DebugLevel::Info => write!(f, "info"),
+            DebugLevel::Warn => write!(f, "warn"),
+        }
+    }
+}
+
 /// Each of the request/message types that can be sent to/from an interface.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum Request {
     OpenConnection,
+    /// Sent first on every connection so that both ends can check they speak the same protocol.
+    Hello {
+        protocol_version: u32,
+    },
+    /// Reply to `Hello`, carrying the interface's own protocol version whether or not it accepted.
+    HelloAns {
+        accepted: bool,
+        protocol_version: u32,
+    },
     Ack,
     Nack,
     DivByZero,
@@ -17,10 +173,408 @@ pub enum Request {
     PrintAscii(u8),
     GetInteger,
     GetIntegerAns(isize),
-    GetAscii,
+    /// Asks for a single ASCII character. If `allowed` is given, the interface keeps asking until
+    /// one of those characters is entered. Leaving `allowed` out entirely is the same as `None`:
+    /// ```
+    /// use befunge_if::{Request, ciborium::{self, Value}};
+    ///
+    /// let mut bytes = Vec::new();
+    /// let bare = Value::Map(vec![(Value::Text("GetAscii".into()), Value::Map(vec![]))]);
+    /// ciborium::ser::into_writer(&bare, &mut bytes).unwrap();
+    /// let request: Request = ciborium::de::from_reader(&bytes[..]).unwrap();
+    /// assert!(matches!(request, Request::GetAscii { allowed: None }));
+    /// ```
+    GetAscii {
+        #[serde(default)]
+        allowed: Option<Vec<u8>>,
+    },
     GetAsciiAns(u8),
+    /// Asks for a whole line of ASCII input, answered with `GetStringAns` without the line ending.
+    GetString,
+    GetStringAns(String),
+    /// Prints a whole string of ASCII characters, answered with `Ack` (or `Nack` if the string isn't
+    /// ASCII).
+    PrintString(String),
     FlushOutput,
-    Debug(String),
+    /// A debugging message. Leaving `level` out entirely is the same as [`DebugLevel::Info`]:
+    /// ```
+    /// use befunge_if::{DebugLevel, Request, ciborium::{self, Value}};
+    ///
+    /// let request = Request::Debug {
+    ///     level: DebugLevel::Warn,
+    ///     msg: "Y index was out of bounds!".to_string(),
+    /// };
+    /// let mut bytes = Vec::new();
+    /// ciborium::ser::into_writer(&request, &mut bytes).unwrap();
+    /// let request: Request = ciborium::de::from_reader(&bytes[..]).unwrap();
+    /// assert!(matches!(
+    ///     request,
+    ///     Request::Debug { level: DebugLevel::Warn, msg } if msg == "Y index was out of bounds!"
+    /// ));
+    ///
+    /// let mut bytes = Vec::new();
+    /// let bare = Value::Map(vec![(
+    ///     Value::Text("Debug".into()),
+    ///     Value::Map(vec![(Value::Text("msg".into()), Value::Text("init".into()))]),
+    /// )]);
+    /// ciborium::ser::into_writer(&bare, &mut bytes).unwrap();
+    /// let request: Request = ciborium::de::from_reader(&bytes[..]).unwrap();
+    /// assert!(matches!(request, Request::Debug { level: DebugLevel::Info, msg } if msg == "init"));
+    /// ```
+    Debug {
+        #[serde(default)]
+        level: DebugLevel,
+        msg: String,
+    },
     CloseConnection,
     CloseUi,
+    /// Several requests sent as one message, answered with a single `Ack` once all of them have
+    /// been handled. Only requests that don't need an answer (printing, flushing, and debug
+    /// messages) may be batched.
+    Batch(Vec<Request>),
+}
+
+/// Ways in which [`handshake`] can fail.
+#[derive(Debug)]
+pub enum HandshakeError {
+    /// Sending `Hello` failed.
+    Send(String),
+    /// The interface hung up or sent something that couldn't be decoded. This is what interfaces
+    /// from before the handshake existed do when sent a `Hello`.
+    TooOld(String),
+    /// The interface speaks a different version of the protocol.
+    Mismatch { ours: u32, theirs: u32 },
+    /// The interface replied with something other than `HelloAns`.
+    Unexpected(Request),
+}
+
+impl Display for HandshakeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Send(err) => write!(f, "Failed to send handshake to Befunge UI.\nError: '{err}'"),
+            Self::TooOld(err) => write!(
+                f,
+                "Befunge UI did not understand the handshake; it is probably too old for this \
+                 version of befunge-pm (protocol version {PROTOCOL_VERSION}). Rebuild befunge-if.\n\
+                 Error: '{err}'"
+            ),
+            Self::Mismatch { ours, theirs } => write!(
+                f,
+                "Protocol version mismatch: this build of befunge-pm speaks version {ours}, but the \
+                 Befunge UI speaks version {theirs}. Rebuild whichever is older."
+            ),
+            Self::Unexpected(other) => {
+                write!(f, "Received unexpected reply to handshake: '{other:?}'")
+            }
+        }
+    }
+}
+
+impl Error for HandshakeError {}
+
+/// Sends `Hello` over `conn` and waits for the interface to accept it.
+///
+/// # Example
+/// Against a stub interface that speaks a different protocol version:
+/// ```
+/// use befunge_if::{HandshakeError, PROTOCOL_VERSION, Request, handshake};
+/// use std::io::{Cursor, Read, Result, Write};
+///
+/// struct Stub {
+///     reply: Cursor<Vec<u8>>,
+/// }
+///
+/// impl Read for Stub {
+///     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+///         self.reply.read(buf)
+///     }
+/// }
+///
+/// impl Write for Stub {
+///     fn write(&mut self, buf: &[u8]) -> Result<usize> {
+///         Ok(buf.len())
+///     }
+///
+///     fn flush(&mut self) -> Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut reply = Vec::new();
+/// let ans = Request::HelloAns {
+///     accepted: false,
+///     protocol_version: PROTOCOL_VERSION + 1,
+/// };
+/// befunge_if::ciborium::ser::into_writer(&ans, &mut reply).unwrap();
+/// let res = handshake(Stub { reply: Cursor::new(reply) });
+/// assert!(matches!(
+///     res,
+///     Err(HandshakeError::Mismatch { ours, theirs })
+///         if ours == PROTOCOL_VERSION && theirs == PROTOCOL_VERSION + 1
+/// ));
+///
+/// // An interface that hangs up without replying is reported as being too old.
+/// let res = handshake(Stub { reply: Cursor::new(Vec::new()) });
+/// assert!(matches!(res, Err(HandshakeError::TooOld(_))));
+/// ```
+pub fn handshake<S: Read + Write>(mut conn: S) -> Result<(), HandshakeError> {
+    let hello = Request::Hello {
+        protocol_version: PROTOCOL_VERSION,
+    };
+    ciborium::ser::into_writer(&hello, &mut conn)
+        .map_err(|err| HandshakeError::Send(err.to_string()))?;
+    conn.flush()
+        .map_err(|err| HandshakeError::Send(err.to_string()))?;
+    match ciborium::de::from_reader(&mut conn) {
+        Ok(Request::HelloAns {
+            accepted: true,
+            protocol_version: _,
+        }) => Ok(()),
+        Ok(Request::HelloAns {
+            accepted: false,
+            protocol_version,
+        }) => Err(HandshakeError::Mismatch {
+            ours: PROTOCOL_VERSION,
+            theirs: protocol_version,
+        }),
+        Ok(other) => Err(HandshakeError::Unexpected(other)),
+        Err(err) => Err(HandshakeError::TooOld(err.to_string())),
+    }
+}
+
+/// Ways in which a [`Client`] request can fail.
+#[derive(Debug)]
+pub enum ClientError {
+    /// Sending the request failed.
+    Send(String),
+    /// Reading the reply failed.
+    Receive(String),
+    /// The interface replied with something other than what was expected.
+    Unexpected(Request),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Send(err) => write!(f, "Failed to send request to Befunge UI.\nError: '{err}'"),
+            Self::Receive(err) => {
+                write!(f, "Failed to read response from Befunge UI.\nError: '{err}'")
+            }
+            Self::Unexpected(other) => write!(f, "Received unexpected request: '{other:?}'"),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+/// Prefixes a debug message with where it came from, as `file:line:column: message`. This is what
+/// `socket_debug!` sends with `locations: true`.
+///
+/// # Example
+/// Capturing what gets sent to a stub interface:
+/// ```
+/// use befunge_if::{Client, DebugLevel, Request};
+/// use std::io::{Cursor, Read, Result, Write};
+///
+/// struct Stub {
+///     reply: Cursor<Vec<u8>>,
+///     sent: Vec<u8>,
+/// }
+///
+/// impl Read for Stub {
+///     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+///         self.reply.read(buf)
+///     }
+/// }
+///
+/// impl Write for Stub {
+///     fn write(&mut self, buf: &[u8]) -> Result<usize> {
+///         self.sent.write(buf)
+///     }
+///
+///     fn flush(&mut self) -> Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut reply = Vec::new();
+/// befunge_if::ciborium::ser::into_writer(&Request::Ack, &mut reply).unwrap();
+/// let mut client = Client::new(Stub {
+///     reply: Cursor::new(reply),
+///     sent: Vec::new(),
+/// });
+/// let msg = befunge_if::located_debug("step.rs", 1234, 9, "catch: put2");
+/// client.request_ack(Request::Debug { level: DebugLevel::Info, msg }).unwrap();
+///
+/// let sent = client.into_inner().sent;
+/// let sent: Request = befunge_if::ciborium::de::from_reader(&sent[..]).unwrap();
+/// assert!(matches!(sent, Request::Debug { msg, .. } if msg == "step.rs:1234:9: catch: put2"));
+/// ```
+pub fn located_debug(file: &str, line: usize, column: usize, msg: &str) -> String {
+    format!("{file}:{line}:{column}: {msg}")
+}
+
+/// The macro side of a connection to an interface. Every request made through this is written and
+/// flushed in one go, and every reply is read as a single message.
+///
+/// # Example
+/// Against an in-memory stub interface that has already queued up its replies:
+/// ```
+/// use befunge_if::{Client, ClientError, Request};
+/// use std::io::{Cursor, Read, Result, Write};
+///
+/// struct Stub {
+///     reply: Cursor<Vec<u8>>,
+///     sent: Vec<u8>,
+/// }
+///
+/// impl Read for Stub {
+///     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+///         self.reply.read(buf)
+///     }
+/// }
+///
+/// impl Write for Stub {
+///     fn write(&mut self, buf: &[u8]) -> Result<usize> {
+///         self.sent.write(buf)
+///     }
+///
+///     fn flush(&mut self) -> Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut reply = Vec::new();
+/// for request in [Request::Ack, Request::GetIntegerAns(7), Request::Nack] {
+///     befunge_if::ciborium::ser::into_writer(&request, &mut reply).unwrap();
+/// }
+/// let mut client = Client::new(Stub {
+///     reply: Cursor::new(reply),
+///     sent: Vec::new(),
+/// });
+/// client.request_ack(Request::PrintInteger(3)).unwrap();
+/// assert!(matches!(
+///     client.request_reply(Request::GetInteger),
+///     Ok(Request::GetIntegerAns(7))
+/// ));
+/// assert!(matches!(
+///     client.request_ack(Request::FlushOutput),
+///     Err(ClientError::Unexpected(Request::Nack))
+/// ));
+/// // Nothing left to read: the interface hung up.
+/// assert!(matches!(
+///     client.request_ack(Request::FlushOutput),
+///     Err(ClientError::Receive(_))
+/// ));
+/// client.close().unwrap();
+///
+/// let mut expected = Vec::new();
+/// for request in [
+///     Request::PrintInteger(3),
+///     Request::GetInteger,
+///     Request::FlushOutput,
+///     Request::FlushOutput,
+///     Request::CloseConnection,
+/// ] {
+///     befunge_if::ciborium::ser::into_writer(&request, &mut expected).unwrap();
+/// }
+/// assert_eq!(client.into_inner().sent, expected);
+/// ```
+pub struct Client<S> {
+    conn: S,
+}
+
+impl<S: Read + Write> Client<S> {
+    pub fn new(conn: S) -> Self {
+        Client { conn }
+    }
+
+    /// Gives back the underlying connection.
+    pub fn into_inner(self) -> S {
+        self.conn
+    }
+
+    /// Performs the [`handshake`] over this connection.
+    pub fn handshake(&mut self) -> Result<(), HandshakeError> {
+        handshake(&mut self.conn)
+    }
+
+    /// Sends a request without waiting for a reply.
+    pub fn send(&mut self, request: &Request) -> Result<(), ClientError> {
+        ciborium::ser::into_writer(request, &mut self.conn)
+            .map_err(|err| ClientError::Send(err.to_string()))?;
+        self.conn
+            .flush()
+            .map_err(|err| ClientError::Send(err.to_string()))
+    }
+
+    /// Sends a request and returns whatever the interface replies with.
+    pub fn request_reply(&mut self, request: Request) -> Result<Request, ClientError> {
+        self.send(&request)?;
+        ciborium::de::from_reader(&mut self.conn).map_err(|err| ClientError::Receive(err.to_string()))
+    }
+
+    /// Sends a request and checks that the interface replies with `Ack`.
+    pub fn request_ack(&mut self, request: Request) -> Result<(), ClientError> {
+        match self.request_reply(request)? {
+            Request::Ack => Ok(()),
+            other => Err(ClientError::Unexpected(other)),
+        }
+    }
+
+    /// Sends a request that would normally be answered with `Ack`, then closes the connection
+    /// straight away instead of waiting for the answer. This saves a round trip, but nothing says
+    /// whether the interface actually got the request, so it's lost without a trace if the
+    /// interface goes down first.
+    ///
+    /// # Example
+    /// A burst of messages to an interface that never answers doesn't get stuck waiting on it:
+    /// ```
+    /// use befunge_if::{Client, DebugLevel, Request};
+    /// use std::io::{Read, Result, Write};
+    ///
+    /// struct Mute(Vec<u8>);
+    ///
+    /// impl Read for Mute {
+    ///     fn read(&mut self, _: &mut [u8]) -> Result<usize> {
+    ///         panic!("nothing should be read");
+    ///     }
+    /// }
+    ///
+    /// impl Write for Mute {
+    ///     fn write(&mut self, buf: &[u8]) -> Result<usize> {
+    ///         self.0.write(buf)
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut sent = Vec::new();
+    /// for n in 0..1000 {
+    ///     let mut client = Client::new(Mute(Vec::new()));
+    ///     let msg = format!("message {n}");
+    ///     client.notify(Request::Debug { level: DebugLevel::Trace, msg }).unwrap();
+    ///     sent.extend(client.into_inner().0);
+    /// }
+    ///
+    /// let mut sent = &sent[..];
+    /// for n in 0..1000 {
+    ///     let debug: Request = befunge_if::ciborium::de::from_reader(&mut sent).unwrap();
+    ///     assert!(matches!(debug, Request::Debug { msg, .. } if msg == format!("message {n}")));
+    ///     let close: Request = befunge_if::ciborium::de::from_reader(&mut sent).unwrap();
+    ///     assert!(matches!(close, Request::CloseConnection));
+    /// }
+    /// assert!(sent.is_empty());
+    /// ```
+    pub fn notify(&mut self, request: Request) -> Result<(), ClientError> {
+        self.send(&request)?;
+        self.close()
+    }
+
+    /// Tells the interface that this connection is finished with.
+    pub fn close(&mut self) -> Result<(), ClientError> {
+        self.send(&Request::CloseConnection)
+    }
 }
@@ -1,78 +1,309 @@
 #![feature(ascii_char)]
 
-use befunge_if::Request;
-use clap::Parser;
-use interprocess::local_socket::{
-    GenericFilePath, GenericNamespaced, Listener, ListenerOptions, Stream, prelude::*,
+use befunge_if::{
+    DebugLevel, PROTOCOL_VERSION, Request, debug_sink::DebugSink, prompt, socket_name,
+};
+use clap::{Parser, ValueEnum};
+use interprocess::local_socket::{Listener, ListenerOptions, Stream, prelude::*};
+use std::{
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult, Write, stdin, stdout},
+    path::PathBuf,
+    sync::Arc,
+    thread,
 };
-use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult, Write, stdin};
 
 #[derive(Parser)]
 struct Opts {
-    #[arg(short, long)]
-    socket: String,
+    /// Socket to serve, as `NAME[:buffered|unbuffered]`. May be given more than once to serve
+    /// several sockets from the same process. Sockets are buffered unless told otherwise.
+    #[arg(short, long, required = true, value_parser = parse_socket_spec)]
+    socket: Vec<SocketSpec>,
+    /// What to do when asked to print a byte that isn't valid ASCII.
+    #[arg(long, value_enum, default_value_t = NonAscii::Escape)]
+    non_ascii: NonAscii,
+    /// Append debug messages to this file, each with a timestamp, instead of printing them.
+    #[arg(long, value_name = "FILE")]
+    debug_to: Option<PathBuf>,
+    /// Re-send debug messages to the interface on this socket instead of printing them. Messages
+    /// are dropped rather than holding up the program if that interface falls behind.
+    #[arg(long, value_name = "SOCKET")]
+    debug_forward: Option<String>,
+    /// Drop debug messages less important than this.
+    #[arg(long, value_enum, default_value_t = DebugLevel::Trace)]
+    min_level: DebugLevel,
+}
+
+/// Policy for `PrintAscii` requests carrying a byte outside of the ASCII range.
+#[derive(Clone, Copy, ValueEnum)]
+enum NonAscii {
+    /// Print U+FFFD in place of the byte.
+    Replace,
+    /// Print the byte as a `\xNN` escape.
+    Escape,
+    /// Reply to the request with a `Nack` and print nothing.
+    Nack,
+}
+
+/// How program output received on a socket is written to the terminal.
+#[derive(Clone, Copy, ValueEnum)]
+enum Buffering {
+    /// Hold output until a newline, a flush request, a prompt for input, or the end of the
+    /// connection it came in on.
+    Buffered,
+    /// Write output to the terminal as soon as it is received.
+    Unbuffered,
+}
+
+#[derive(Clone)]
+struct SocketSpec {
+    name: String,
+    buffering: Buffering,
+}
+
+fn parse_socket_spec(spec: &str) -> Result<SocketSpec, String> {
+    let (name, buffering) = match spec.rsplit_once(':') {
+        Some((name, policy)) => (name, Buffering::from_str(policy, true)?),
+        None => (spec, Buffering::Buffered),
+    };
+    if name.is_empty() {
+        return Err(format!("Missing socket name in '{spec}'"));
+    }
+    Ok(SocketSpec {
+        name: name.to_string(),
+        buffering,
+    })
+}
+
+/// State kept for a socket across all of the connections made to it.
+struct Session {
+    buf: String,
+    /// Whether output has been written out without the line it's on being ended yet.
+    line_open: bool,
+    buffering: Buffering,
+    non_ascii: NonAscii,
+    debug: Arc<DebugSink>,
+}
+
+impl Session {
+    /// Adds program output to the buffer, writing it straight out if the socket is unbuffered.
+    fn push_output(&mut self, output: &str) -> IoResult<()> {
+        self.buf.push_str(output);
+        match self.buffering {
+            Buffering::Buffered => Ok(()),
+            Buffering::Unbuffered => self.write_out(),
+        }
+    }
+
+    /// Writes out and clears anything held in the buffer without ending the line.
+    fn write_out(&mut self) -> IoResult<()> {
+        if !self.buf.is_empty() {
+            print!("{}", self.buf);
+            self.buf.clear();
+            self.line_open = true;
+        }
+        stdout().flush()
+    }
+
+    /// Writes out and clears anything held in the buffer, then ends the line.
+    fn end_line(&mut self) {
+        println!("{}", self.buf);
+        self.buf.clear();
+        self.line_open = false;
+    }
+
+    /// Ends the line if there's any output on it, whether it's still held in the buffer or has
+    /// already been written out.
+    fn finish_line(&mut self) {
+        if self.line_open || !self.buf.is_empty() {
+            self.end_line();
+        }
+    }
+
+    /// Handles a `PrintAscii` request, returning the reply for the client.
+    fn print_ascii(&mut self, c: u8) -> IoResult<Request> {
+        if c == b'\n' {
+            self.end_line();
+            Ok(Request::Ack)
+        } else if let Some(c_ascii) = std::ascii::Char::from_u8(c) {
+            self.push_output(c_ascii.as_str())?;
+            Ok(Request::Ack)
+        } else {
+            match self.non_ascii {
+                NonAscii::Replace => {
+                    self.push_output(&char::REPLACEMENT_CHARACTER.to_string())?;
+                    Ok(Request::Ack)
+                }
+                NonAscii::Escape => {
+                    self.push_output(&format!("\\x{c:02x}"))?;
+                    Ok(Request::Ack)
+                }
+                NonAscii::Nack => {
+                    println!("Refusing to print non-ASCII byte: '{c:#04x}'");
+                    Ok(Request::Nack)
+                }
+            }
+        }
+    }
+
+    /// Handles a `PrintString` request, returning the reply for the client.
+    fn print_string(&mut self, string: &str) -> IoResult<Request> {
+        if !string.is_ascii() {
+            println!("Refusing to print non-ASCII string: '{string:?}'");
+            return Ok(Request::Nack);
+        }
+        for c in string.bytes() {
+            self.print_ascii(c)?;
+        }
+        Ok(Request::Ack)
+    }
+
+    /// Handles a `FlushOutput` request.
+    fn flush_output(&mut self) -> IoResult<()> {
+        self.finish_line();
+        stdout().flush()
+    }
+
+    /// Handles the requests contained in a `Batch` in order, returning the single reply for the
+    /// whole batch. Only requests that don't need an answer from the user may be batched. Anything
+    /// else, or a request that would itself be refused, stops the batch and gets a `Nack`.
+    fn run_batch(&mut self, requests: Vec<Request>) -> IoResult<Request> {
+        for request in requests {
+            match request {
+                Request::PrintInteger(num) => self.push_output(&format!("{num}"))?,
+                Request::PrintAscii(c) => {
+                    if let Request::Nack = self.print_ascii(c)? {
+                        return Ok(Request::Nack);
+                    }
+                }
+                Request::PrintString(string) => {
+                    if let Request::Nack = self.print_string(&string)? {
+                        return Ok(Request::Nack);
+                    }
+                }
+                Request::FlushOutput => self.flush_output()?,
+                Request::Debug { level, msg } => self.debug.debug(level, msg)?,
+                other => {
+                    println!("Received unexpected request in batch: '{other:?}'");
+                    return Ok(Request::Nack);
+                }
+            }
+        }
+        Ok(Request::Ack)
+    }
 }
 
 fn main() -> IoResult<()> {
-    let Opts { socket } = Opts::parse();
+    let Opts {
+        socket,
+        non_ascii,
+        debug_to,
+        debug_forward,
+        min_level,
+    } = Opts::parse();
+    let debug = Arc::new(DebugSink::new(
+        debug_to.as_deref(),
+        debug_forward.as_deref(),
+        min_level,
+    )?);
+    let handles = socket
+        .into_iter()
+        .map(|spec| {
+            let debug = Arc::clone(&debug);
+            thread::spawn(move || serve_socket(spec, non_ascii, debug))
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle
+            .join()
+            .map_err(|_| IoError::other("Socket thread panicked"))??;
+    }
+    // Every socket thread has finished with its handle on the sink by now.
+    let dropped = Arc::try_unwrap(debug).map_or_else(|debug| debug.dropped(), DebugSink::finish);
+    if dropped > 0 {
+        println!("Dropped {dropped} debug message(s) that couldn't be forwarded.");
+    }
+    Ok(())
+}
+
+fn serve_socket(spec: SocketSpec, non_ascii: NonAscii, debug: Arc<DebugSink>) -> IoResult<()> {
+    let SocketSpec { name: socket, buffering } = spec;
     println!("Using socket name: '{socket}'");
-    let name = if GenericNamespaced::is_supported() {
-        socket.to_ns_name::<GenericNamespaced>()?
-    } else {
-        format!("/tmp/{socket}").to_fs_name::<GenericFilePath>()?
-    };
+    let name = socket_name(&socket)?;
     println!("Created socket path: '{name:?}'");
     let mut lstn = ListenerOptions::new().name(name).create_sync()?;
     println!("Successfully connected to socket.");
-    await_open_connection(&mut lstn)
+    let mut session = Session {
+        buf: String::new(),
+        line_open: false,
+        buffering,
+        non_ascii,
+        debug,
+    };
+    await_open_connection(&mut lstn, &mut session)
 }
 
-fn await_open_connection(lstn: &mut Listener) -> IoResult<()> {
-    let mut buf = String::new();
+fn await_open_connection(lstn: &mut Listener, session: &mut Session) -> IoResult<()> {
     let res = loop {
         match lstn.accept() {
             Ok(mut conn) => {
-                let close = run_connection(&mut conn, &mut buf)?;
+                let close = run_connection(&mut conn, session)?;
+                // Each macro makes its own connection, so anything still held once it's done
+                // would otherwise only turn up when the next one gets around to a newline or a
+                // flush, if one ever does.
+                session.write_out()?;
                 if close {
                     break Ok(());
                 }
             }
             Err(err) => {
                 let msg = format!("Error while attempting to accept connections: '{err}'");
-                break Err(IoError::new(IoErrorKind::Other, msg));
+                break Err(IoError::other(msg));
             }
         }
     };
-    if !buf.is_empty() {
-        println!("{buf}");
-    }
+    session.finish_line();
     res
 }
 
-fn run_connection(mut conn: &mut Stream, buf: &mut String) -> IoResult<bool> {
+fn run_connection(mut conn: &mut Stream, session: &mut Session) -> IoResult<bool> {
     let mut expecting_ack = false;
     loop {
         match ciborium::de::from_reader(&mut conn) {
-            Ok(Request::DivByZero) => {
-                if !buf.is_empty() {
-                    print!("{buf}");
-                    buf.clear();
+            Ok(Request::Hello { protocol_version }) => {
+                let accepted = protocol_version == PROTOCOL_VERSION;
+                let reply = Request::HelloAns {
+                    accepted,
+                    protocol_version: PROTOCOL_VERSION,
+                };
+                ciborium::ser::into_writer(&reply, &mut conn).map_err(
+                    |err| {
+                        IoError::other(
+                            format!("Error sending handshake response: '{err}'"),
+                        )
+                    },
+                )?;
+                conn.flush()?;
+                if !accepted {
+                    println!(
+                        "Refused client speaking protocol version {protocol_version} (expected \
+                         {PROTOCOL_VERSION})"
+                    );
+                    return Ok(false);
                 }
-                expecting_ack = div_by_zero(&mut conn)?;
+            }
+            Ok(Request::DivByZero) => {
+                session.write_out()?;
+                expecting_ack = div_by_zero(conn)?;
             }
             Ok(Request::ModByZero) => {
-                if !buf.is_empty() {
-                    print!("{buf}");
-                    buf.clear();
-                }
-                expecting_ack = mod_by_zero(&mut conn)?;
+                session.write_out()?;
+                expecting_ack = mod_by_zero(conn)?;
             }
             Ok(Request::PrintInteger(num)) => {
-                buf.push_str(&format!("{num}"));
+                session.push_output(&format!("{num}"))?;
                 ciborium::ser::into_writer(&Request::Ack, &mut conn).map_err(
                     |err| {
-                        IoError::new(
-                            IoErrorKind::Other,
+                        IoError::other(
                             format!("Error sending ack response: '{err}'"),
                         )
                     },
@@ -80,62 +311,78 @@ fn run_connection(mut conn: &mut Stream, buf: &mut String) -> IoResult<bool> {
             }
             Ok(Request::PrintAscii(c)) => {
                 // println!("got print req: {c:?} ({:?})", c as char);
-                if c == b'\n' {
-                    println!("{buf}");
-                    buf.clear();
-                } else {
-                    let c_ascii = std::ascii::Char::from_u8(c).unwrap();
-                    buf.push(c_ascii.to_char());
-                }
-                ciborium::ser::into_writer(&Request::Ack, &mut conn).map_err(
+                let reply = session.print_ascii(c)?;
+                ciborium::ser::into_writer(&reply, &mut conn).map_err(
                     |err| {
-                        IoError::new(
-                            IoErrorKind::Other,
+                        IoError::other(
                             format!("Error sending ack response: '{err}'"),
                         )
                     },
                 )?;
             }
-            Ok(Request::GetInteger) => {
-                if !buf.is_empty() {
-                    print!("{buf}");
-                    buf.clear();
-                }
-                expecting_ack = ask_for_integer(&mut conn)?;
-            }
-            Ok(Request::GetAscii) => {
-                if !buf.is_empty() {
-                    print!("{buf}");
-                    buf.clear();
-                }
-                expecting_ack = ask_for_ascii(&mut conn)?;
+            Ok(Request::PrintString(string)) => {
+                let reply = session.print_string(&string)?;
+                ciborium::ser::into_writer(&reply, &mut conn).map_err(
+                    |err| {
+                        IoError::other(
+                            format!("Error sending ack response: '{err}'"),
+                        )
+                    },
+                )?;
             }
-            Ok(Request::FlushOutput) => {
-                // println!("received flush");
-                if !buf.is_empty() {
-                    println!("{buf}");
-                    buf.clear();
-                }
-                ciborium::ser::into_writer(&Request::Ack, &mut conn).map_err(
+            Ok(Request::Batch(requests)) => {
+                let reply = session.run_batch(requests)?;
+                ciborium::ser::into_writer(&reply, &mut conn).map_err(
                     |err| {
-                        IoError::new(
-                            IoErrorKind::Other,
+                        IoError::other(
                             format!("Error sending ack response: '{err}'"),
                         )
                     },
                 )?;
             }
-            Ok(Request::Debug(contents)) => {
-                println!("DEBUG: {contents}");
+            Ok(Request::GetInteger) => {
+                session.write_out()?;
+                expecting_ack = ask_for_integer(conn)?;
+            }
+            Ok(Request::GetAscii { allowed }) => {
+                session.write_out()?;
+                expecting_ack = ask_for_ascii(conn, allowed.as_deref())?;
+            }
+            Ok(Request::GetString) => {
+                session.write_out()?;
+                expecting_ack = ask_for_string(conn)?;
+            }
+            Ok(Request::FlushOutput) => {
+                // println!("received flush");
+                session.flush_output()?;
                 ciborium::ser::into_writer(&Request::Ack, &mut conn).map_err(
                     |err| {
-                        IoError::new(
-                            IoErrorKind::Other,
+                        IoError::other(
                             format!("Error sending ack response: '{err}'"),
                         )
                     },
                 )?;
             }
+            Ok(Request::Debug { level, msg }) => {
+                session.debug.debug(level, msg)?;
+                match ciborium::ser::into_writer(&Request::Ack, &mut conn) {
+                    Ok(()) => {}
+                    // Debug messages can be sent without waiting for the `Ack`, in which case the
+                    // client may well have hung up already.
+                    Err(ciborium::ser::Error::Io(err))
+                        if matches!(
+                            err.kind(),
+                            IoErrorKind::BrokenPipe | IoErrorKind::ConnectionReset
+                        ) =>
+                    {
+                        return Ok(false);
+                    }
+                    Err(err) => {
+                        let msg = format!("Error sending ack response: '{err}'");
+                        return Err(IoError::other(msg));
+                    }
+                }
+            }
             Ok(Request::Ack) if expecting_ack => expecting_ack = false,
             Ok(Request::CloseUi) => return Ok(true),
             Ok(Request::CloseConnection) => return Ok(false),
@@ -143,17 +390,21 @@ fn run_connection(mut conn: &mut Stream, buf: &mut String) -> IoResult<bool> {
                 println!("Received unexpected request: '{other:?}'");
                 return ciborium::ser::into_writer(&Request::Nack, &mut conn)
                     .map_err(|err| {
-                        IoError::new(
-                            IoErrorKind::Other,
+                        IoError::other(
                             format!("Error replying to client with ACK: '{err}'"),
                         )
                     })
                     .and_then(|_| conn.flush())
                     .map(|_| false);
             }
+            // The client hung up without sending `CloseConnection` (for example after being sent a
+            // `Nack`). Keep listening for the next connection instead of bringing the UI down.
+            Err(ciborium::de::Error::Io(err)) if err.kind() == IoErrorKind::UnexpectedEof => {
+                return Ok(false);
+            }
             Err(err) => {
                 let msg = format!("Error while reading from data stream: '{err}'");
-                break Err(IoError::new(IoErrorKind::Other, msg));
+                break Err(IoError::other(msg));
             }
         }
     }
@@ -179,8 +430,7 @@ fn div_by_zero(mut conn: &mut Stream) -> IoResult<bool> {
     let val = prompt_for_integer()?;
     ciborium::ser::into_writer(&Request::DivByZeroAns(val), &mut conn).map_err(
         |err| {
-            IoError::new(
-                IoErrorKind::Other,
+            IoError::other(
                 format!("Error sending back divide by zero response: '{err}'"),
             )
         },
@@ -194,8 +444,7 @@ fn mod_by_zero(mut conn: &mut Stream) -> IoResult<bool> {
     let val = prompt_for_integer()?;
     ciborium::ser::into_writer(&Request::ModByZeroAns(val), &mut conn).map_err(
         |err| {
-            IoError::new(
-                IoErrorKind::Other,
+            IoError::other(
                 format!("Error sending back modulus by zero response: '{err}'"),
             )
         },
@@ -209,8 +458,7 @@ fn ask_for_integer(mut conn: &mut Stream) -> IoResult<bool> {
     let val = prompt_for_integer()?;
     ciborium::ser::into_writer(&Request::GetIntegerAns(val), &mut conn).map_err(
         |err| {
-            IoError::new(
-                IoErrorKind::Other,
+            IoError::other(
                 format!("Error sending back integer response: '{err}'"),
             )
         },
@@ -219,65 +467,44 @@ fn ask_for_integer(mut conn: &mut Stream) -> IoResult<bool> {
     Ok(true)
 }
 
-fn prompt_for_char() -> IoResult<u8> {
+fn ask_for_ascii(mut conn: &mut Stream, allowed: Option<&[u8]>) -> IoResult<bool> {
+    println!("Please enter an ASCII character (\\x00 format or literal):");
+    let val = prompt::read_ascii(&mut stdin().lock(), &mut stdout(), allowed)?;
+    ciborium::ser::into_writer(&Request::GetAsciiAns(val), &mut conn).map_err(
+        |err| {
+            IoError::other(
+                format!("Error sending back ASCII response: '{err}'"),
+            )
+        },
+    )?;
+    conn.flush()?;
+    Ok(true)
+}
+
+fn prompt_for_line() -> IoResult<String> {
     let mut linebuf = String::new();
     loop {
         stdin().read_line(&mut linebuf)?;
-        if linebuf.starts_with("\\x")
-            && linebuf.trim().len() == 4
-            && linebuf
-                .trim()
-                .chars()
-                .skip(2)
-                .all(|c| c.is_ascii_hexdigit())
-        {
-            let &[_, _, u, l] = linebuf.as_bytes() else {
-                unreachable!();
-            };
-            let u = match u {
-                b'0'..=b'9' => u - b'0',
-                b'a'..=b'f' => u - b'a',
-                b'A'..=b'F' => u - b'A',
-                _ => unreachable!(),
-            };
-            let l = match l {
-                b'0'..=b'9' => l - b'0',
-                b'a'..=b'f' => l - b'a',
-                b'A'..=b'F' => l - b'A',
-                _ => unreachable!(),
-            };
-            let c = u * 16 + l;
-            if c.is_ascii() {
-                break Ok(c);
-            } else {
-                println!("Entered value '{c}' is not valid ASCII! Please try again:");
-                linebuf.clear();
-            }
+        let line = linebuf
+            .strip_suffix('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .unwrap_or(&linebuf);
+        if line.is_ascii() {
+            break Ok(line.to_string());
         } else {
-            match linebuf.trim().parse::<char>() {
-                Ok(c) if c.is_ascii() => break Ok(c as u8),
-                Ok(c) => {
-                    println!("Entered value '{c}' is not valid ASCII! Please try again:");
-                    linebuf.clear();
-                }
-                Err(err) => {
-                    println!("Error reading value: '{err}'");
-                    println!("Please try again:");
-                    linebuf.clear();
-                }
-            }
+            println!("Entered line '{line}' is not valid ASCII! Please try again:");
+            linebuf.clear();
         }
     }
 }
 
-fn ask_for_ascii(mut conn: &mut Stream) -> IoResult<bool> {
-    println!("Please enter an ASCII character (\\x00 format or literal):");
-    let val = prompt_for_char()?;
-    ciborium::ser::into_writer(&Request::GetAsciiAns(val), &mut conn).map_err(
+fn ask_for_string(mut conn: &mut Stream) -> IoResult<bool> {
+    println!("Please enter a line of ASCII text:");
+    let val = prompt_for_line()?;
+    ciborium::ser::into_writer(&Request::GetStringAns(val), &mut conn).map_err(
         |err| {
-            IoError::new(
-                IoErrorKind::Other,
-                format!("Error sending back ASCII response: '{err}'"),
+            IoError::other(
+                format!("Error sending back string response: '{err}'"),
             )
         },
     )?;
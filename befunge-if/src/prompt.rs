@@ -0,0 +1,100 @@
+//! Reading answers to input requests from the person running the interface.
+
+use std::io::{BufRead, Error as IoError, ErrorKind, Result as IoResult, Write};
+
+/// Reads a single ASCII character from `input`, given either literally or in `\x00` format, asking
+/// again on `output` until a valid one is entered. If `allowed` is given then only those characters
+/// are accepted, and they're listed whenever something else is entered.
+///
+/// # Example
+/// ```
+/// use befunge_if::prompt::read_ascii;
+/// use std::io::Cursor;
+///
+/// let mut output = Vec::new();
+/// let c = read_ascii(&mut Cursor::new("x\n\\x79\n"), &mut output, Some(b"yn")).unwrap();
+/// assert_eq!(c, b'y');
+/// assert_eq!(
+///     String::from_utf8(output).unwrap(),
+///     "Entered value 'x' is not allowed! Please enter one of 'y', 'n':\n",
+/// );
+///
+/// let mut output = Vec::new();
+/// let c = read_ascii(&mut Cursor::new("\\x1b\n"), &mut output, None).unwrap();
+/// assert_eq!(c, 0x1b);
+/// assert!(output.is_empty());
+///
+/// // running out of input is an error rather than an endless loop
+/// assert!(read_ascii(&mut Cursor::new("z\n"), &mut Vec::new(), Some(b"yn")).is_err());
+/// ```
+pub fn read_ascii(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    allowed: Option<&[u8]>,
+) -> IoResult<u8> {
+    let mut linebuf = String::new();
+    loop {
+        linebuf.clear();
+        if input.read_line(&mut linebuf)? == 0 {
+            break Err(IoError::new(ErrorKind::UnexpectedEof, "Input ended before a valid answer"));
+        }
+        let c = if linebuf.starts_with("\\x")
+            && linebuf.trim().len() == 4
+            && linebuf
+                .trim()
+                .chars()
+                .skip(2)
+                .all(|c| c.is_ascii_hexdigit())
+        {
+            let &[_, _, u, l, ..] = linebuf.as_bytes() else {
+                unreachable!();
+            };
+            let u = match u {
+                b'0'..=b'9' => u - b'0',
+                b'a'..=b'f' => u - b'a' + 10,
+                b'A'..=b'F' => u - b'A' + 10,
+                _ => unreachable!(),
+            };
+            let l = match l {
+                b'0'..=b'9' => l - b'0',
+                b'a'..=b'f' => l - b'a' + 10,
+                b'A'..=b'F' => l - b'A' + 10,
+                _ => unreachable!(),
+            };
+            let c = u * 16 + l;
+            if !c.is_ascii() {
+                writeln!(output, "Entered value '{c}' is not valid ASCII! Please try again:")?;
+                continue;
+            }
+            c
+        } else {
+            match linebuf.trim().parse::<char>() {
+                Ok(c) if c.is_ascii() => c as u8,
+                Ok(c) => {
+                    writeln!(output, "Entered value '{c}' is not valid ASCII! Please try again:")?;
+                    continue;
+                }
+                Err(err) => {
+                    writeln!(output, "Error reading value: '{err}'")?;
+                    writeln!(output, "Please try again:")?;
+                    continue;
+                }
+            }
+        };
+        match allowed {
+            Some(allowed) if !allowed.contains(&c) => {
+                let list = allowed
+                    .iter()
+                    .map(|&c| format!("'{}'", c.escape_ascii()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(
+                    output,
+                    "Entered value '{}' is not allowed! Please enter one of {list}:",
+                    c.escape_ascii(),
+                )?;
+            }
+            _ => break Ok(c),
+        }
+    }
+}
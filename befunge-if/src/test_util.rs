@@ -0,0 +1,181 @@
+//! A stand-in for the interface program that runs inside of a test, so that the macros in
+//! `befunge-pm` can be tested without anybody sitting at a `befunge-if` to answer them. Only built
+//! with the `test-util` feature.
+
+use crate::{PROTOCOL_VERSION, Request, socket_name};
+use interprocess::local_socket::{Listener, ListenerOptions, Stream, prelude::*};
+use std::{
+    collections::VecDeque,
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult, Write},
+    process,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+};
+
+/// What every socket served by a [`MockInterface`] in this process starts with. Setting
+/// [`SOCKET_PREFIX_VAR`](crate::SOCKET_PREFIX_VAR) to this while the macros are expanded sends
+/// `socket: "name"` to the mock started with [`MockInterface::start`]`("name", ..)`.
+pub fn prefix() -> String {
+    format!("befunge-if.mock.{}.", process::id())
+}
+
+/// An interface listening on its own socket in a background thread. Questions (`DivByZero`,
+/// `ModByZero`, `GetInteger`, `GetAscii`, and `GetString`) are answered with the replies it was
+/// started with, in order, and with `Nack` once those run out. Everything else is answered with
+/// `Ack`, the same as `befunge-if` does. Every request received other than the handshake is kept
+/// until [`finish`](MockInterface::finish) is called.
+///
+/// # Example
+/// ```
+/// use befunge_if::{Client, Request, connect_with_retry, test_util::MockInterface};
+/// use std::time::Duration;
+///
+/// let mock = MockInterface::start("doctest", vec![Request::GetIntegerAns(5)]).unwrap();
+///
+/// let conn = connect_with_retry(mock.socket(), 0, Duration::ZERO).unwrap();
+/// let mut client = Client::new(conn);
+/// client.handshake().unwrap();
+/// assert_eq!(
+///     client.request_reply(Request::GetInteger).unwrap(),
+///     Request::GetIntegerAns(5),
+/// );
+/// client.request_ack(Request::PrintInteger(5)).unwrap();
+/// // Out of answers.
+/// assert_eq!(client.request_reply(Request::GetInteger).unwrap(), Request::Nack);
+/// client.close().unwrap();
+///
+/// assert_eq!(
+///     mock.finish().unwrap(),
+///     [
+///         Request::GetInteger,
+///         Request::PrintInteger(5),
+///         Request::GetInteger,
+///         Request::CloseConnection,
+///     ],
+/// );
+/// ```
+pub struct MockInterface {
+    socket: String,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<IoResult<Vec<Request>>>>,
+}
+
+impl MockInterface {
+    /// Starts listening on `name` with [`prefix`] in front of it.
+    pub fn start(name: &str, replies: Vec<Request>) -> IoResult<Self> {
+        let socket = format!("{}{name}", prefix());
+        let listener = ListenerOptions::new()
+            .name(socket_name(&socket)?)
+            .create_sync()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let thread = thread::spawn(move || serve(listener, replies.into(), &thread_stop));
+        Ok(MockInterface {
+            socket,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// The full name of the socket being listened on.
+    pub fn socket(&self) -> &str {
+        &self.socket
+    }
+
+    /// Stops listening and gives back every request received, in the order they arrived.
+    pub fn finish(mut self) -> IoResult<Vec<Request>> {
+        self.shut_down()
+    }
+
+    fn shut_down(&mut self) -> IoResult<Vec<Request>> {
+        let Some(thread) = self.thread.take() else {
+            return Ok(Vec::new());
+        };
+        self.stop.store(true, Ordering::SeqCst);
+        // Wakes the listener up so that it sees it's been told to stop.
+        let _ = Stream::connect(socket_name(&self.socket)?);
+        thread
+            .join()
+            .map_err(|_| IoError::other("Mock interface thread panicked"))?
+    }
+}
+
+impl Drop for MockInterface {
+    fn drop(&mut self) {
+        let _ = self.shut_down();
+    }
+}
+
+fn serve(
+    listener: Listener,
+    mut replies: VecDeque<Request>,
+    stop: &AtomicBool,
+) -> IoResult<Vec<Request>> {
+    let mut received = Vec::new();
+    loop {
+        let mut conn = listener.accept()?;
+        if stop.load(Ordering::SeqCst) {
+            break Ok(received);
+        }
+        serve_connection(&mut conn, &mut replies, &mut received)?;
+    }
+}
+
+fn serve_connection(
+    mut conn: &mut Stream,
+    replies: &mut VecDeque<Request>,
+    received: &mut Vec<Request>,
+) -> IoResult<()> {
+    loop {
+        let request = match ciborium::de::from_reader(&mut conn) {
+            Ok(request) => request,
+            Err(ciborium::de::Error::Io(err)) if err.kind() == IoErrorKind::UnexpectedEof => {
+                return Ok(());
+            }
+            Err(err) => {
+                return Err(IoError::other(format!("Error reading request: '{err}'")));
+            }
+        };
+        let reply = match request {
+            Request::Hello { protocol_version } => Request::HelloAns {
+                accepted: protocol_version == PROTOCOL_VERSION,
+                protocol_version: PROTOCOL_VERSION,
+            },
+            Request::CloseConnection | Request::CloseUi => {
+                received.push(request);
+                return Ok(());
+            }
+            Request::DivByZero
+            | Request::ModByZero
+            | Request::GetInteger
+            | Request::GetAscii { .. }
+            | Request::GetString => {
+                received.push(request);
+                replies.pop_front().unwrap_or(Request::Nack)
+            }
+            other => {
+                received.push(other);
+                Request::Ack
+            }
+        };
+        let sent = match ciborium::ser::into_writer(&reply, &mut conn) {
+            Ok(()) => conn.flush(),
+            Err(ciborium::ser::Error::Io(err)) => Err(err),
+            Err(err) => Err(IoError::other(format!("Error sending reply: '{err}'"))),
+        };
+        match sent {
+            Ok(()) => {}
+            // Requests sent with `Client::notify` don't wait around for their `Ack`, but whatever
+            // else they sent before hanging up can still be read.
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    IoErrorKind::BrokenPipe | IoErrorKind::ConnectionReset
+                ) => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Runs the `befunge-if` binary and talks to it the way the macros in `befunge-pm` do, checking
+//! what the person running it would see.
+
+use befunge_if::{
+    Client, DebugLevel, PROTOCOL_VERSION, Request, connect_with_retry, socket_name,
+};
+use interprocess::local_socket::{ListenerOptions, prelude::*};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    process::{Child, ChildStdout, Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+/// Sends `request` over a connection of its own, like every macro does.
+fn send(socket: &str, request: Request) {
+    let conn = connect_with_retry(socket, 50, Duration::from_millis(100)).unwrap();
+    let mut client = Client::new(conn);
+    client.handshake().unwrap();
+    client.request_ack(request).unwrap();
+    client.close().unwrap();
+}
+
+/// Kills the interface if the test fails before it's closed.
+struct Interface(Child);
+
+impl Drop for Interface {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Starts the interface with `args`, and waits until it's listening on all `sockets` of them.
+fn start(args: &[&str], sockets: usize) -> (Interface, BufReader<ChildStdout>) {
+    let mut child = Interface(
+        Command::new(env!("CARGO_BIN_EXE_befunge-if"))
+            .args(args)
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap(),
+    );
+    let mut stdout = BufReader::new(child.0.stdout.take().unwrap());
+    let mut line = String::new();
+    for _ in 0..sockets {
+        while line != "Successfully connected to socket.\n" {
+            line.clear();
+            assert_ne!(stdout.read_line(&mut line).unwrap(), 0);
+        }
+        line.clear();
+    }
+    (child, stdout)
+}
+
+/// Tells the interface to stop serving `socket`.
+fn close_ui(socket: &str) {
+    let conn = connect_with_retry(socket, 0, Duration::ZERO).unwrap();
+    let mut client = Client::new(conn);
+    client.handshake().unwrap();
+    client.send(&Request::CloseUi).unwrap();
+}
+
+#[test]
+fn output_without_newline_is_shown_before_close() {
+    let socket = format!("befunge-if.test.{}.output", std::process::id());
+    let (mut child, mut stdout) = start(&["--socket", &socket], 1);
+
+    // `"ba",,@` with `[noflush]`, so nothing ends the line.
+    send(&socket, Request::PrintAscii(b'a'));
+    send(&socket, Request::PrintAscii(b'b'));
+
+    let (tx, rx) = mpsc::channel();
+    let reader = thread::spawn(move || {
+        let mut byte = [0];
+        while stdout.read(&mut byte).unwrap() == 1 {
+            tx.send(byte[0]).unwrap();
+        }
+    });
+    let shown = [(); 2].map(|_| rx.recv_timeout(Duration::from_secs(10)).unwrap());
+    assert_eq!(&shown, b"ab");
+    assert!(child.0.try_wait().unwrap().is_none());
+
+    close_ui(&socket);
+    assert!(child.0.wait().unwrap().success());
+    reader.join().unwrap();
+    // The line is ended once the interface closes.
+    assert_eq!(rx.iter().collect::<Vec<_>>(), b"\n");
+}
+
+#[test]
+fn non_ascii_byte_follows_policy() {
+    // `PrintAscii(0xff)` written out by hand, since the macros never send it: a map of one entry
+    // from the 10-character variant name to the byte.
+    const PRINT_FF: &[u8] = b"\xa1\x6aPrintAscii\x18\xff";
+    for (policy, reply, shown) in [
+        ("replace", Request::Ack, "\u{fffd}!\n"),
+        ("escape", Request::Ack, "\\xff!\n"),
+        ("nack", Request::Nack, "Refusing to print non-ASCII byte: '0xff'\n!\n"),
+    ] {
+        let socket = format!("befunge-if.test.{}.non-ascii-{policy}", std::process::id());
+        let (mut child, mut stdout) = start(&["--socket", &socket, "--non-ascii", policy], 1);
+
+        let mut conn = connect_with_retry(&socket, 50, Duration::from_millis(100)).unwrap();
+        conn.write_all(PRINT_FF).unwrap();
+        let got: Request = befunge_if::ciborium::de::from_reader(&mut conn).unwrap();
+        assert_eq!(got, reply, "--non-ascii {policy}");
+        // The same connection is still served afterwards.
+        let mut client = Client::new(conn);
+        client.request_ack(Request::PrintAscii(b'!')).unwrap();
+        client.close().unwrap();
+
+        close_ui(&socket);
+        assert!(child.0.wait().unwrap().success());
+        let mut rest = String::new();
+        stdout.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, shown, "--non-ascii {policy}");
+    }
+}
+
+#[test]
+fn buffering_is_per_socket() {
+    let pid = std::process::id();
+    let buffered = format!("befunge-if.test.{pid}.buffered");
+    let unbuffered = format!("befunge-if.test.{pid}.unbuffered");
+    let (mut child, mut stdout) = start(
+        &[
+            "--socket",
+            &format!("{buffered}:buffered"),
+            "--socket",
+            &format!("{unbuffered}:unbuffered"),
+        ],
+        2,
+    );
+    let (tx, rx) = mpsc::channel();
+    let reader = thread::spawn(move || {
+        let mut byte = [0];
+        while stdout.read(&mut byte).unwrap() == 1 {
+            tx.send(byte[0]).unwrap();
+        }
+    });
+
+    // The same traffic on each: an `x` on a connection that's kept open until it's been checked
+    // whether the `x` has been shown yet.
+    let shown_before_close = |socket: &str| {
+        let conn = connect_with_retry(socket, 50, Duration::from_millis(100)).unwrap();
+        let mut client = Client::new(conn);
+        client.handshake().unwrap();
+        client.request_ack(Request::PrintAscii(b'x')).unwrap();
+        let before = rx.recv_timeout(Duration::from_millis(500)).ok();
+        client.close().unwrap();
+        let after = match before {
+            Some(_) => None,
+            None => rx.recv_timeout(Duration::from_secs(10)).ok(),
+        };
+        assert_eq!(before.or(after), Some(b'x'), "{socket}");
+        before.is_some()
+    };
+    // Held until the connection ends.
+    assert!(!shown_before_close(&buffered));
+    // Written as soon as it's received.
+    assert!(shown_before_close(&unbuffered));
+
+    close_ui(&buffered);
+    close_ui(&unbuffered);
+    assert!(child.0.wait().unwrap().success());
+    reader.join().unwrap();
+}
+
+#[test]
+fn queued_debug_messages_are_forwarded_before_exit() {
+    let pid = std::process::id();
+    let socket = format!("befunge-if.test.{pid}.forwarding");
+    let downstream = format!("befunge-if.test.{pid}.downstream");
+    // A downstream interface slow enough that most of the messages are still queued when the
+    // interface is closed.
+    let listener = ListenerOptions::new()
+        .name(socket_name(&downstream).unwrap())
+        .create_sync()
+        .unwrap();
+    let mock = thread::spawn(move || {
+        let mut received = Vec::new();
+        for mut conn in listener.incoming().map(Result::unwrap) {
+            loop {
+                let reply = match befunge_if::ciborium::de::from_reader(&mut conn).unwrap() {
+                    Request::Hello { .. } => Request::HelloAns {
+                        accepted: true,
+                        protocol_version: PROTOCOL_VERSION,
+                    },
+                    Request::Debug { msg, .. } => {
+                        thread::sleep(Duration::from_millis(20));
+                        received.push(msg);
+                        Request::Ack
+                    }
+                    Request::CloseConnection => break,
+                    Request::CloseUi => return received,
+                    other => panic!("unexpected request: {other:?}"),
+                };
+                befunge_if::ciborium::ser::into_writer(&reply, &mut conn).unwrap();
+            }
+        }
+        unreachable!()
+    });
+    let (mut child, mut stdout) = start(&["--socket", &socket, "--debug-forward", &downstream], 1);
+
+    let sent = (0..20).map(|n| format!("message {n}")).collect::<Vec<_>>();
+    for msg in &sent {
+        let level = DebugLevel::Info;
+        send(&socket, Request::Debug { level, msg: msg.clone() });
+    }
+    close_ui(&socket);
+    assert!(child.0.wait().unwrap().success());
+    close_ui(&downstream);
+    assert_eq!(mock.join().unwrap(), sent);
+    let mut rest = String::new();
+    stdout.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "");
+}
+
+#[test]
+fn burst_of_debug_messages_without_ack() {
+    let socket = format!("befunge-if.test.{}.noack", std::process::id());
+    let (mut child, mut stdout) = start(&["--socket", &socket], 1);
+
+    // Each on a connection of its own, closed without waiting for the `Ack`, like
+    // `socket_debug!` does with `noack: true`.
+    for n in 0..1000 {
+        let conn = connect_with_retry(&socket, 50, Duration::from_millis(100)).unwrap();
+        let mut client = Client::new(conn);
+        client.handshake().unwrap();
+        let msg = format!("message {n}");
+        client.notify(Request::Debug { level: DebugLevel::Info, msg }).unwrap();
+    }
+    // A request that does wait still gets its own `Ack`, rather than one left over from the burst.
+    let conn = connect_with_retry(&socket, 50, Duration::from_millis(100)).unwrap();
+    let mut client = Client::new(conn);
+    client.handshake().unwrap();
+    let msg = "acked".to_string();
+    client.request_ack(Request::Debug { level: DebugLevel::Info, msg }).unwrap();
+    client.request_ack(Request::PrintAscii(b'!')).unwrap();
+    client.close().unwrap();
+
+    close_ui(&socket);
+    assert!(child.0.wait().unwrap().success());
+    let mut rest = String::new();
+    stdout.read_to_string(&mut rest).unwrap();
+    let expected = (0..1000)
+        .map(|n| format!("DEBUG: message {n}\n"))
+        .chain(["DEBUG: acked\n!\n".to_string()])
+        .collect::<String>();
+    assert_eq!(rest, expected);
+}
@@ -0,0 +1,28 @@
+use crate::callback::Callback;
+use proc_macro2::Span;
+use syn::{
+    Token,
+    parse::{Parse, ParseStream},
+};
+
+pub struct Base1FromLiteral {
+    pub value: isize,
+    pub span: Span,
+    pub callback: Callback,
+}
+
+impl Parse for Base1FromLiteral {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<crate::kw::value>()?;
+        input.parse::<Token![:]>()?;
+        let (value, span) = crate::print::parse_spanned_integer(input)?;
+        input.parse::<Token![,]>()?;
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(Base1FromLiteral {
+            value,
+            span,
+            callback,
+        })
+    }
+}
@@ -1,9 +1,11 @@
-use proc_macro2::Group;
+use proc_macro2::{Delimiter, Group, TokenStream as TokenStream2};
 use syn::{
     Path as SynPath, Token, bracketed,
     parse::{Parse, ParseStream},
 };
 
+/// A callback given as `callback: [name: path, pre: [..], pst: [..]]`. The fields can be given in
+/// any order, and `pre` and `pst` are empty if they're left out.
 pub struct Callback {
     pub name: SynPath,
     pub pre: Group,
@@ -12,19 +14,61 @@ pub struct Callback {
 
 impl Parse for Callback {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        input.parse::<crate::kw::name>()?;
-        input.parse::<Token![:]>()?;
-        let name = input.parse()?;
-        input.parse::<Token![,]>()?;
-        input.parse::<crate::kw::pre>()?;
-        input.parse::<Token![:]>()?;
-        let pre = input.parse()?;
-        input.parse::<Token![,]>()?;
-        input.parse::<crate::kw::pst>()?;
-        input.parse::<Token![:]>()?;
-        let pst = input.parse()?;
-        crate::maybe_trailing_comma(input)?;
-        Ok(Callback { name, pre, pst })
+        let mut name = None;
+        let mut pre = None;
+        let mut pst = None;
+        let mut duplicates = Vec::new();
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(crate::kw::name) {
+                let kw = input.parse::<crate::kw::name>()?;
+                input.parse::<Token![:]>()?;
+                if name.replace(input.parse::<SynPath>()?).is_some() {
+                    duplicates.push(("name", kw.span));
+                }
+            } else if lookahead.peek(crate::kw::pre) {
+                let kw = input.parse::<crate::kw::pre>()?;
+                input.parse::<Token![:]>()?;
+                if pre.replace(input.parse::<Group>()?).is_some() {
+                    duplicates.push(("pre", kw.span));
+                }
+            } else if lookahead.peek(crate::kw::pst) {
+                let kw = input.parse::<crate::kw::pst>()?;
+                input.parse::<Token![:]>()?;
+                if pst.replace(input.parse::<Group>()?).is_some() {
+                    duplicates.push(("pst", kw.span));
+                }
+            } else {
+                return Err(lookahead.error());
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        let missing = name
+            .is_none()
+            .then(|| syn::Error::new(input.span(), "this callback is missing `name`"));
+        let error = duplicates
+            .into_iter()
+            .map(|(field, span)| {
+                let msg = format!("`{field}` is given more than once in this callback");
+                syn::Error::new(span, msg)
+            })
+            .chain(missing)
+            .reduce(|mut error, other| {
+                error.combine(other);
+                error
+            });
+        if let Some(error) = error {
+            return Err(error);
+        }
+        let empty = || Group::new(Delimiter::Bracket, TokenStream2::new());
+        Ok(Callback {
+            name: name.expect("a missing name is reported above"),
+            pre: pre.unwrap_or_else(empty),
+            pst: pst.unwrap_or_else(empty),
+        })
     }
 }
 
@@ -35,3 +79,132 @@ pub fn parse_callback(input: ParseStream) -> syn::Result<Callback> {
     bracketed!(callback in input);
     callback.parse()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Callback, parse_callback};
+    use quote::ToTokens;
+    use syn::parse::Parser;
+
+    fn parse(fields: &str) -> syn::Result<Callback> {
+        syn::parse_str(fields)
+    }
+
+    fn check(fields: &[&str], trailing_comma: bool) {
+        let mut joined = fields.join(", ");
+        if trailing_comma {
+            joined.push(',');
+        }
+        let callback = parse(&joined).unwrap_or_else(|err| panic!("`{joined}`: {err}"));
+        let has = |field: &str| fields.iter().any(|given| given.starts_with(field));
+        assert_eq!(
+            callback.name.to_token_stream().to_string(),
+            "a :: b",
+            "{joined}"
+        );
+        let pre = if has("pre") { "[@ pre x ,]" } else { "[]" };
+        let pst = if has("pst") { "[y : 1 ,]" } else { "[]" };
+        assert_eq!(callback.pre.to_string(), pre, "{joined}");
+        assert_eq!(callback.pst.to_string(), pst, "{joined}");
+    }
+
+    fn permutations<'a>(fields: &[&'a str]) -> Vec<Vec<&'a str>> {
+        if fields.is_empty() {
+            return vec![Vec::new()];
+        }
+        let mut all = Vec::new();
+        for (i, first) in fields.iter().enumerate() {
+            let mut rest = fields.to_vec();
+            rest.remove(i);
+            for mut permutation in permutations(&rest) {
+                permutation.insert(0, first);
+                all.push(permutation);
+            }
+        }
+        all
+    }
+
+    #[test]
+    fn every_order() {
+        let name = "name: a::b";
+        let pre = "pre: [@pre x,]";
+        let pst = "pst: [y: 1,]";
+        let field_sets = [
+            vec![name],
+            vec![name, pre],
+            vec![name, pst],
+            vec![name, pre, pst],
+        ];
+        for fields in field_sets {
+            for permutation in permutations(&fields) {
+                check(&permutation, false);
+                check(&permutation, true);
+            }
+        }
+    }
+
+    #[test]
+    fn through_parse_callback() {
+        let callback = parse_callback
+            .parse_str("callback: [pst: [z,], name: c]")
+            .unwrap();
+        assert_eq!(callback.name.to_token_stream().to_string(), "c");
+        assert_eq!(callback.pre.to_string(), "[]");
+        assert_eq!(callback.pst.to_string(), "[z ,]");
+    }
+
+    #[test]
+    fn missing_name() {
+        for fields in ["", "pre: [],", "pst: [], pre: []"] {
+            let err = parse(fields).err().expect(fields);
+            assert_eq!(err.to_string(), "this callback is missing `name`");
+        }
+    }
+
+    #[test]
+    fn duplicates() {
+        let err = parse("name: a, pre: [], pst: [], pre: [], name: b")
+            .err()
+            .unwrap();
+        let messages = err
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "`pre` is given more than once in this callback",
+                "`name` is given more than once in this callback",
+            ],
+        );
+    }
+
+    #[test]
+    fn duplicate_and_missing() {
+        let err = parse("pst: [], pst: []").err().unwrap();
+        let messages = err
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "`pst` is given more than once in this callback",
+                "this callback is missing `name`",
+            ],
+        );
+    }
+
+    #[test]
+    fn rejects_everything_else() {
+        for fields in [
+            "name: a pre: []",
+            "nme: a",
+            "name a",
+            "name: a,, pre: []",
+            "pre: x, name: a",
+        ] {
+            assert!(parse(fields).is_err(), "{fields} parsed");
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::callback::Callback;
+use syn::{
+    LitBool, LitInt, Token,
+    parse::{Parse, ParseStream},
+};
+
+pub struct GetTime {
+    pub epoch: bool,
+    pub callback: Callback,
+}
+
+impl Parse for GetTime {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut epoch = false;
+        if input.peek(crate::kw::epoch) {
+            input.parse::<crate::kw::epoch>()?;
+            input.parse::<Token![:]>()?;
+            epoch = input.parse::<LitBool>()?.value;
+            input.parse::<Token![,]>()?;
+        }
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(GetTime { epoch, callback })
+    }
+}
+
+pub struct Sleep {
+    pub millis: u64,
+    pub callback: Option<Callback>,
+}
+
+impl Parse for Sleep {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<crate::kw::millis>()?;
+        input.parse::<Token![:]>()?;
+        let millis = input.parse::<LitInt>()?.base10_parse()?;
+        let mut callback = None;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.peek(crate::kw::callback) {
+                callback = Some(crate::callback::parse_callback(input)?);
+            }
+        }
+        crate::maybe_trailing_comma(input)?;
+        Ok(Sleep { millis, callback })
+    }
+}
@@ -0,0 +1,329 @@
+use crate::callback::Callback;
+use proc_macro2::{
+    Delimiter, Group, Ident, Span, TokenStream as TokenStream2, TokenTree as TokenTree2,
+};
+use quote::quote;
+use syn::{
+    LitBool, Token, bracketed,
+    parse::{Parse, ParseStream},
+};
+
+/// Runs of spaces shorter than this are left as they are, since crossing them one cell at a time
+/// costs no more than crossing them in one go.
+const MIN_RUN: usize = 2;
+
+/// A cell of a row, or a run of spaces stored as `[sp [[] ..]]` with a `[]` for each space. A run
+/// with no spaces in it, `[sp []]`, goes at the end of every row of a compacted playfield to mark it
+/// as one.
+#[derive(Clone, Debug)]
+enum Item {
+    Cell(TokenTree2),
+    Run(usize),
+}
+
+fn parse_item(tt: TokenTree2) -> Item {
+    if let TokenTree2::Group(group) = &tt
+        && group.delimiter() == Delimiter::Bracket
+        && let mut inner = group.stream().into_iter()
+        && let (Some(TokenTree2::Ident(ident)), Some(TokenTree2::Group(count)), None) =
+            (inner.next(), inner.next(), inner.next())
+        && ident == "sp"
+        && count.delimiter() == Delimiter::Bracket
+    {
+        return Item::Run(count.stream().into_iter().count());
+    }
+    Item::Cell(tt)
+}
+
+fn is_space(tt: &TokenTree2) -> bool {
+    matches!(tt, TokenTree2::Literal(lit) if lit.to_string() == "' '")
+}
+
+fn space() -> TokenTree2 {
+    TokenTree2::Literal(proc_macro2::Literal::character(' '))
+}
+
+fn run(len: usize) -> TokenTree2 {
+    let units = std::iter::repeat_n(quote!([]), len);
+    let sp = Ident::new("sp", Span::call_site());
+    TokenTree2::Group(Group::new(Delimiter::Bracket, quote!(#sp [#(#units)*])))
+}
+
+/// Turns a row into its cells, with every run of spaces written out, and whether it had the marker
+/// at the end of it.
+fn expand(row: &Group) -> (Vec<TokenTree2>, bool) {
+    let mut items: Vec<Item> = row.stream().into_iter().map(parse_item).collect();
+    let compact = matches!(items.last(), Some(Item::Run(0)));
+    if compact {
+        items.pop();
+    }
+    let cells = items
+        .into_iter()
+        .flat_map(|item| match item {
+            Item::Cell(tt) => vec![tt],
+            Item::Run(len) => vec![space(); len],
+        })
+        .collect();
+    (cells, compact)
+}
+
+/// Puts the spaces counted so far on the end of `out`, as a run if there are enough of them.
+fn flush(out: &mut Vec<TokenTree2>, spaces: &mut usize) {
+    if *spaces >= MIN_RUN {
+        out.push(run(*spaces));
+    } else {
+        out.extend(std::iter::repeat_n(space(), *spaces));
+    }
+    *spaces = 0;
+}
+
+/// Turns every run of at least [`MIN_RUN`] spaces in `cells` into a run, and puts the marker at the
+/// end.
+fn compact(cells: &[TokenTree2]) -> Vec<TokenTree2> {
+    let mut out = Vec::new();
+    let mut spaces = 0;
+    for tt in cells {
+        if is_space(tt) {
+            spaces += 1;
+        } else {
+            flush(&mut out, &mut spaces);
+            out.push(tt.clone());
+        }
+    }
+    flush(&mut out, &mut spaces);
+    out.push(run(0));
+    out
+}
+
+fn bracket(tts: Vec<TokenTree2>) -> TokenStream2 {
+    let tts = tts.into_iter();
+    quote!([#(#tts)*])
+}
+
+/// Where to split a row for [`SplitRow`]: after as many cells as there are token trees in a list,
+/// or before the last cell.
+pub enum At {
+    LengthOf(usize),
+    Last,
+}
+
+pub struct SplitRow {
+    pub pad: Option<TokenTree2>,
+    pub at: At,
+    pub split: Group,
+    pub callback: Callback,
+}
+
+impl Parse for SplitRow {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut pad = None;
+        if input.peek(crate::kw::pad) {
+            input.parse::<crate::kw::pad>()?;
+            input.parse::<Token![:]>()?;
+            pad = Some(input.parse()?);
+            input.parse::<Token![,]>()?;
+        }
+        let at = if input.peek(crate::kw::last) {
+            input.parse::<crate::kw::last>()?;
+            input.parse::<Token![:]>()?;
+            let last = input.parse::<LitBool>()?;
+            if !last.value {
+                return Err(syn::Error::new(last.span, "`last` can only be `true`"));
+            }
+            At::Last
+        } else {
+            input.parse::<crate::kw::lenof>()?;
+            input.parse::<Token![:]>()?;
+            let lenof;
+            bracketed!(lenof in input);
+            At::LengthOf(lenof.parse::<TokenStream2>()?.into_iter().count())
+        };
+        input.parse::<Token![,]>()?;
+        input.parse::<crate::kw::split>()?;
+        input.parse::<Token![:]>()?;
+        let split = input.parse::<Group>()?;
+        input.parse::<Token![,]>()?;
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(SplitRow {
+            pad,
+            at,
+            split,
+            callback,
+        })
+    }
+}
+
+/// Splits a row the same way as `list_split_at_length_of!` does, except that runs of spaces count
+/// for as many cells as they have spaces. The left side always has every run written out, and the
+/// right side always starts with a cell. In a compacted row, the rest of the right side is
+/// compacted again and still ends with the marker. Gives back the left and right sides, or an
+/// error message if the row is too short to split without `pad`.
+pub fn split_row(
+    pad: Option<&TokenTree2>,
+    at: &At,
+    row: &Group,
+) -> Result<(TokenStream2, TokenStream2), &'static str> {
+    let (mut cells, compacted) = expand(row);
+    let mut right = match *at {
+        At::LengthOf(len) => match pad {
+            Some(pad) if cells.len() <= len => {
+                cells.resize(len, pad.clone());
+                vec![pad.clone()]
+            }
+            None if cells.len() < len => return Err("Row is too short to split"),
+            _ => cells.split_off(len),
+        },
+        At::Last => match cells.pop() {
+            Some(last) => vec![last],
+            None => return Err("Row is empty, so it has no last cell"),
+        },
+    };
+    if compacted {
+        let tail = if right.is_empty() {
+            compact(&[])
+        } else {
+            compact(&right.split_off(1))
+        };
+        right.extend(tail);
+    }
+    Ok((bracket(cells), bracket(right)))
+}
+
+pub struct CompactRows {
+    pub rows: Vec<Group>,
+    pub callback: Callback,
+}
+
+impl Parse for CompactRows {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<crate::kw::rows>()?;
+        input.parse::<Token![:]>()?;
+        let list;
+        bracketed!(list in input);
+        let mut rows = Vec::new();
+        while !list.is_empty() {
+            rows.push(list.parse()?);
+        }
+        input.parse::<Token![,]>()?;
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(CompactRows { rows, callback })
+    }
+}
+
+/// Compacts every row, putting the marker at the end of each one.
+pub fn compact_rows(rows: &[Group]) -> Vec<TokenStream2> {
+    rows.iter()
+        .map(|row| bracket(compact(&expand(row).0)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{At, compact_rows, split_row};
+    use proc_macro2::{Group, TokenTree as TokenTree2};
+
+    fn row(src: &str) -> Group {
+        match syn::parse_str::<TokenTree2>(src).unwrap() {
+            TokenTree2::Group(group) => group,
+            other => panic!("`{other}` isn't a row"),
+        }
+    }
+
+    fn split(pad: Option<&str>, at: At, src: &str) -> (String, String) {
+        let pad = pad.map(|pad| syn::parse_str::<TokenTree2>(pad).unwrap());
+        let (l, r) = split_row(pad.as_ref(), &at, &row(src)).unwrap();
+        (l.to_string(), r.to_string())
+    }
+
+    #[test]
+    fn compacts_runs_of_spaces() {
+        let rows = compact_rows(&[row("['>' ' ' ' ' ' ' 'v' ' ' '@' ' ' ' ']"), row("[]")]);
+        let rows: Vec<String> = rows.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rows,
+            [
+                "['>' [sp [[] [] []]] 'v' ' ' '@' [sp [[] []]] [sp []]]",
+                "[[sp []]]",
+            ],
+        );
+    }
+
+    #[test]
+    fn splits_plain_rows_like_lists() {
+        assert_eq!(
+            split(Some("' '"), At::LengthOf(2), "['a' 'b' 'c']"),
+            ("['a' 'b']".into(), "['c']".into()),
+        );
+        assert_eq!(
+            split(Some("' '"), At::LengthOf(3), "['a' 'b' 'c']"),
+            ("['a' 'b' 'c']".into(), "[' ']".into()),
+        );
+        assert_eq!(
+            split(Some("oob"), At::LengthOf(4), "['a']"),
+            ("['a' oob oob oob]".into(), "[oob]".into()),
+        );
+        assert_eq!(
+            split(None, At::LengthOf(0), "['a' 'b']"),
+            ("[]".into(), "['a' 'b']".into()),
+        );
+        assert_eq!(
+            split(None, At::Last, "['a' 'b' 'c']"),
+            ("['a' 'b']".into(), "['c']".into()),
+        );
+    }
+
+    #[test]
+    fn splits_inside_runs() {
+        assert_eq!(
+            split(
+                Some("' '"),
+                At::LengthOf(2),
+                "['a' [sp [[] [] [] []]] 'b' [sp []]]"
+            ),
+            ("['a' ' ']".into(), "[' ' [sp [[] []]] 'b' [sp []]]".into()),
+        );
+        assert_eq!(
+            split(
+                Some("' '"),
+                At::LengthOf(4),
+                "['a' [sp [[] [] [] []]] 'b' [sp []]]"
+            ),
+            ("['a' ' ' ' ' ' ']".into(), "[' ' 'b' [sp []]]".into()),
+        );
+        assert_eq!(
+            split(Some("' '"), At::LengthOf(7), "['a' [sp [[] []]] [sp []]]"),
+            (
+                "['a' ' ' ' ' ' ' ' ' ' ' ' ']".into(),
+                "[' ' [sp []]]".into()
+            ),
+        );
+        assert_eq!(
+            split(None, At::Last, "['a' 'b' [sp [[] [] []]] [sp []]]"),
+            ("['a' 'b' ' ' ' ']".into(), "[' ' [sp []]]".into()),
+        );
+    }
+
+    #[test]
+    fn compacts_what_was_walked_over_again() {
+        assert_eq!(
+            split(
+                None,
+                At::LengthOf(0),
+                "['a' ' ' ' ' ' ' 'b' ' ' ' ' [sp []]]"
+            ),
+            (
+                "[]".into(),
+                "['a' [sp [[] [] []]] 'b' [sp [[] []]] [sp []]]".into()
+            ),
+        );
+    }
+
+    #[test]
+    fn refuses_rows_that_are_too_short() {
+        let short = row("['a']");
+        assert!(split_row(None, &At::LengthOf(2), &short).is_err());
+        assert!(split_row(None, &At::Last, &row("[]")).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! Stand-ins for the parts of `proc_macro` that are only available on nightly. With the `nightly`
+//! feature, errors are emitted as proper diagnostics and environment variables are tracked so that
+//! changing them causes macros to be expanded again. Without it, errors are turned into
+//! `compile_error!`s with the same text, and environment variables are read without tracking.
+
+use proc_macro::{Span, TokenStream};
+use std::env::VarError;
+
+/// An error to report at a span, with an optional line of help.
+pub struct Diagnostic {
+    span: Span,
+    msg: String,
+    help: Option<String>,
+}
+
+pub fn error(span: Span, msg: impl Into<String>) -> Diagnostic {
+    Diagnostic {
+        span,
+        msg: msg.into(),
+        help: None,
+    }
+}
+
+impl Diagnostic {
+    pub fn help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Reports the error, giving the tokens that the macro should expand to.
+    #[cfg(feature = "nightly")]
+    pub fn emit(self) -> TokenStream {
+        let mut diagnostic = self.span.error(self.msg);
+        if let Some(help) = self.help {
+            diagnostic = diagnostic.help(help);
+        }
+        diagnostic.emit();
+        TokenStream::new()
+    }
+
+    /// Reports the error, giving the tokens that the macro should expand to.
+    #[cfg(not(feature = "nightly"))]
+    pub fn emit(self) -> TokenStream {
+        let msg = match self.help {
+            Some(help) => format!("{}\n\nhelp: {help}", self.msg),
+            None => self.msg,
+        };
+        syn::Error::new(self.span.into(), msg)
+            .to_compile_error()
+            .into()
+    }
+}
+
+/// Reads an environment variable at expansion time.
+pub fn env_var(name: &str) -> Result<String, VarError> {
+    #[cfg(feature = "nightly")]
+    return proc_macro::tracked::env_var(name);
+    #[cfg(not(feature = "nightly"))]
+    return std::env::var(name);
+}
+
+/// The same place as `span`, but as if it had been written there directly rather than come out of
+/// a macro. Errors at a span that's deep in the interpreter's expansions are otherwise reported
+/// with every one of those expansions attached, which is too much for cargo to read back.
+pub fn unexpanded(span: Span) -> Span {
+    #[cfg(feature = "nightly")]
+    return span.resolved_at(span.source());
+    #[cfg(not(feature = "nightly"))]
+    return span;
+}
@@ -1,20 +1,263 @@
-use interprocess::local_socket::Stream;
-use proc_macro2::Group;
-use syn::{parse::Parse, Token};
+use crate::interface::{Conn, SocketTarget};
+use befunge_if::DebugLevel;
+use proc_macro2::{Delimiter, Group, Punct, Span, TokenStream as TokenStream2, TokenTree as TokenTree2};
+use quote::ToTokens;
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+use syn::{Error as SynError, Ident, Lit, LitBool, parse::Parse, Token};
+
+/// Whether this was built with the `debug_fallback` feature, in which case a message that can't be
+/// sent because nothing is listening on the socket is output as a `const _: &str` instead of
+/// failing the build.
+pub const FALLBACK: bool = cfg!(feature = "debug_fallback");
+
+/// The sockets that couldn't be connected to so far, with `debug_fallback`. Later messages for them
+/// go straight to the fallback instead of waiting out the retries again.
+static UNREACHABLE: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Environment variable that, set to `N`, only sends every `N`th debug message.
+pub const SAMPLE_VAR: &str = "BEFUNGE_DEBUG_SAMPLE";
+/// Environment variable holding a comma-separated list of prefixes. When it's set, only messages
+/// whose `category:` starts with one of them are sent.
+pub const CATEGORY_VAR: &str = "BEFUNGE_DEBUG_CATEGORY";
+
+/// How many messages have got past [`CATEGORY_VAR`] so far, for [`SAMPLE_VAR`].
+static SAMPLED: AtomicUsize = AtomicUsize::new(0);
 
 pub struct Debug {
     pub tokens: Group,
-    pub conn: Stream,
+    /// The message already put together from `string: [...]`, if that was given instead of
+    /// `tokens: [...]`.
+    pub string: Option<String>,
+    pub conn: Option<Conn>,
+    /// Whether the socket couldn't be connected to and the message should be output as a
+    /// `const _: &str` instead, with `debug_fallback`.
+    pub fallback: bool,
+    pub level: DebugLevel,
+    pub locations: bool,
+    pub noack: bool,
 }
 
 impl Parse for Debug {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        input.parse::<crate::kw::tokens>()?;
+        let string = if input.peek(crate::kw::string) {
+            input.parse::<crate::kw::string>()?;
+            true
+        } else {
+            input.parse::<crate::kw::tokens>()?;
+            false
+        };
         input.parse::<Token![:]>()?;
-        let tokens = input.parse()?;
+        let tokens = input.parse::<Group>()?;
+        if let Some((dollar, name)) = unbound_metavar(tokens.stream()) {
+            let msg = format!("`${name}` was never bound by the macro sending this message");
+            return Err(SynError::new(dollar.span(), msg));
+        }
+        let string = if string {
+            Some(concat_literals(tokens.stream())?)
+        } else {
+            None
+        };
         input.parse::<Token![,]>()?;
-        let conn = crate::interface::parse_socket(input)?;
+        let target = crate::interface::parse_socket_target(input)?;
+        let mut level = DebugLevel::Info;
+        if input.peek(Token![,]) && input.peek2(crate::kw::level) {
+            input.parse::<Token![,]>()?;
+            input.parse::<crate::kw::level>()?;
+            input.parse::<Token![:]>()?;
+            let ident = input.parse::<Ident>()?;
+            level = match ident.to_string().as_str() {
+                "trace" => DebugLevel::Trace,
+                "info" => DebugLevel::Info,
+                "warn" => DebugLevel::Warn,
+                _ => {
+                    let msg = "Expected one of `trace`, `info`, or `warn`";
+                    return Err(SynError::new(ident.span(), msg));
+                }
+            };
+        }
+        let mut locations = false;
+        if input.peek(Token![,]) && input.peek2(crate::kw::locations) {
+            input.parse::<Token![,]>()?;
+            input.parse::<crate::kw::locations>()?;
+            input.parse::<Token![:]>()?;
+            locations = input.parse::<LitBool>()?.value;
+        }
+        let mut noack = false;
+        if input.peek(Token![,]) && input.peek2(crate::kw::noack) {
+            input.parse::<Token![,]>()?;
+            input.parse::<crate::kw::noack>()?;
+            input.parse::<Token![:]>()?;
+            noack = input.parse::<LitBool>()?.value;
+        }
+        let mut category = None;
+        if input.peek(Token![,]) && input.peek2(crate::kw::category) {
+            input.parse::<Token![,]>()?;
+            input.parse::<crate::kw::category>()?;
+            input.parse::<Token![:]>()?;
+            category = Some(concat_literals(input.parse::<Lit>()?.into_token_stream())?);
+        }
         crate::maybe_trailing_comma(input)?;
-        Ok(Debug { tokens, conn })
+        let wanted = wanted(category.as_deref()).map_err(|msg| SynError::new(Span::call_site(), msg))?;
+        let (conn, fallback) = if wanted {
+            connect_or_fallback(target)?
+        } else {
+            (None, false)
+        };
+        Ok(Debug {
+            tokens,
+            string,
+            conn,
+            fallback,
+            level,
+            locations,
+            noack,
+        })
+    }
+}
+
+/// Whether a message in `category` should be sent at all, going by [`CATEGORY_VAR`] and then
+/// [`SAMPLE_VAR`]. Only messages that get past the categories count towards the sample.
+fn wanted(category: Option<&str>) -> Result<bool, String> {
+    if let Ok(filter) = crate::compat::env_var(CATEGORY_VAR)
+        && !in_categories(&filter, category)
+    {
+        return Ok(false);
+    }
+    let every = match crate::compat::env_var(SAMPLE_VAR) {
+        Ok(value) => value.trim().parse::<NonZeroUsize>().map_err(|_| {
+            format!("{SAMPLE_VAR} must be a positive integer, not '{value}'")
+        })?,
+        Err(_) => return Ok(true),
+    };
+    Ok(SAMPLED.fetch_add(1, Ordering::Relaxed) % every == 0)
+}
+
+/// Whether `category` starts with any of the comma-separated prefixes in `filter`. A filter with
+/// no prefixes in it lets everything through, and a message without a category only gets through
+/// that.
+fn in_categories(filter: &str, category: Option<&str>) -> bool {
+    let mut prefixes = filter
+        .split(',')
+        .map(str::trim)
+        .filter(|prefix| !prefix.is_empty())
+        .peekable();
+    if prefixes.peek().is_none() {
+        return true;
+    }
+    category.is_some_and(|category| prefixes.any(|prefix| category.starts_with(prefix)))
+}
+
+/// Connects to the socket, unless it's already known to be unreachable. With `debug_fallback`, a
+/// failed connection gives `(None, true)` instead of an error.
+fn connect_or_fallback(target: SocketTarget) -> syn::Result<(Option<Conn>, bool)> {
+    if !FALLBACK {
+        return Ok((target.connect()?, false));
+    }
+    let mut unreachable = UNREACHABLE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if unreachable.contains(&target.socket) {
+        return Ok((None, true));
+    }
+    let socket = target.socket.clone();
+    match target.connect() {
+        Ok(conn) => Ok((conn, false)),
+        Err(_) => {
+            unreachable.push(socket);
+            Ok((None, true))
+        }
+    }
+}
+
+/// Puts a list of literals together into a single string, the same way `concat!` would. The
+/// literals may be separated by commas.
+pub fn concat_literals(tokens: TokenStream2) -> syn::Result<String> {
+    let mut out = String::new();
+    for tt in tokens {
+        if matches!(&tt, TokenTree2::Punct(punct) if punct.as_char() == ',') {
+            continue;
+        }
+        match syn::parse2::<Lit>(tt.clone().into()) {
+            Ok(Lit::Str(lit)) => out.push_str(&lit.value()),
+            Ok(Lit::Char(lit)) => out.push(lit.value()),
+            Ok(Lit::Int(lit)) => out.push_str(lit.base10_digits()),
+            Ok(Lit::Bool(lit)) => out.push_str(if lit.value { "true" } else { "false" }),
+            _ => {
+                let msg = "Expected a string, char, integer, or bool literal";
+                return Err(SynError::new(tt.span(), msg));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// The span of the first token, looking inside of the invisible groups that a `macro_rules!` puts
+/// around metavariables like `$category:literal`, so that it's where the token was written rather
+/// than where it was passed along.
+pub fn first_span(tokens: TokenStream2) -> Option<Span> {
+    match tokens.into_iter().next()? {
+        TokenTree2::Group(group) if group.delimiter() == Delimiter::None => {
+            first_span(group.stream()).or(Some(group.span()))
+        }
+        token => Some(token.span()),
+    }
+}
+
+/// Finds a `$` followed by an identifier, which is what a `macro_rules!` arm leaves behind when it
+/// uses a metavariable that it never bound.
+fn unbound_metavar(tokens: TokenStream2) -> Option<(Punct, Ident)> {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(tt) = tokens.next() {
+        match tt {
+            TokenTree2::Punct(punct) if punct.as_char() == '$' => {
+                if let Some(TokenTree2::Ident(name)) = tokens.peek() {
+                    return Some((punct, name.clone()));
+                }
+            }
+            TokenTree2::Group(group) => {
+                if let Some(found) = unbound_metavar(group.stream()) {
+                    return Some(found);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{concat_literals, in_categories, unbound_metavar};
+    use quote::quote;
+
+    #[test]
+    fn finds_unbound_metavars() {
+        assert!(unbound_metavar(quote!("pop", 3)).is_none());
+        assert!(unbound_metavar(quote!("catch: put2", '$')).is_none());
+        let (_, name) = unbound_metavar(quote!("pop", $stack0sgn 3)).unwrap();
+        assert_eq!(name, "stack0sgn");
+        let (_, name) = unbound_metavar(quote!("get2", [[] [$x]])).unwrap();
+        assert_eq!(name, "x");
+    }
+
+    #[test]
+    fn concatenates_literals() {
+        let msg = concat_literals(quote!("[tag]\n" "v " '@' "\n", 12 true)).unwrap();
+        assert_eq!(msg, "[tag]\nv @\n12true");
+        assert!(concat_literals(quote!("row" x)).is_err());
+    }
+
+    #[test]
+    fn filters_categories() {
+        assert!(in_categories("catch,jump", Some("catch: put2")));
+        assert!(in_categories(" catch , jump ", Some("jump: landed")));
+        assert!(!in_categories("catch,jump", Some("stringmode: char")));
+        assert!(!in_categories("catch", None));
+        assert!(in_categories("", Some("stringmode: char")));
+        assert!(in_categories(",", None));
     }
 }
@@ -0,0 +1,29 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{
+    Token, bracketed,
+    parse::{Parse, ParseStream},
+};
+
+pub struct ErrorAt {
+    pub span: Span,
+    pub msg: String,
+}
+
+impl Parse for ErrorAt {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<crate::kw::at>()?;
+        input.parse::<Token![:]>()?;
+        let at;
+        bracketed!(at in input);
+        let span = crate::debug::first_span(at.parse::<TokenStream2>()?)
+            .unwrap_or_else(Span::call_site);
+        input.parse::<Token![,]>()?;
+        input.parse::<crate::kw::string>()?;
+        input.parse::<Token![:]>()?;
+        let string;
+        bracketed!(string in input);
+        let msg = crate::debug::concat_literals(string.parse()?)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(ErrorAt { span, msg })
+    }
+}
@@ -0,0 +1,156 @@
+use crate::callback::Callback;
+use proc_macro::Span;
+use std::path::{Path, PathBuf};
+use syn::{
+    LitBool, LitChar, Token, bracketed,
+    parse::{Parse, ParseStream},
+};
+
+pub struct FileGet {
+    pub path: CharList,
+    pub unsafe_paths: bool,
+    pub callback: Callback,
+}
+
+impl Parse for FileGet {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<crate::kw::path>()?;
+        input.parse::<Token![:]>()?;
+        let path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let unsafe_paths = parse_unsafe_paths(input)?;
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(FileGet {
+            path,
+            unsafe_paths,
+            callback,
+        })
+    }
+}
+
+pub struct FilePut {
+    pub path: CharList,
+    pub contents: CharList,
+    pub unsafe_paths: bool,
+    pub callback: Callback,
+}
+
+impl Parse for FilePut {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<crate::kw::path>()?;
+        input.parse::<Token![:]>()?;
+        let path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        input.parse::<crate::kw::contents>()?;
+        input.parse::<Token![:]>()?;
+        let contents = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let unsafe_paths = parse_unsafe_paths(input)?;
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(FilePut {
+            path,
+            contents,
+            unsafe_paths,
+            callback,
+        })
+    }
+}
+
+fn parse_unsafe_paths(input: ParseStream) -> syn::Result<bool> {
+    let mut unsafe_paths = false;
+    if input.peek(crate::kw::unsafe_paths) {
+        input.parse::<crate::kw::unsafe_paths>()?;
+        input.parse::<Token![:]>()?;
+        unsafe_paths = input.parse::<LitBool>()?.value;
+        input.parse::<Token![,]>()?;
+    }
+    Ok(unsafe_paths)
+}
+
+/// A bracketed list of character literals, e.g. `['a' 'b' 'c']`, as made from the stack by the
+/// interpreter.
+pub struct CharList {
+    pub value: String,
+    pub span: Span,
+}
+
+impl Parse for CharList {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let chars;
+        let span = bracketed!(chars in input).span.join().unwrap();
+        let mut value = String::new();
+        while !chars.is_empty() {
+            value.push(chars.parse::<LitChar>()?.value());
+        }
+        Ok(CharList { value, span })
+    }
+}
+
+/// The directory files are confined to unless `unsafe_paths: true` is given: the directory of the
+/// crate being built, or the current directory if that isn't known.
+fn crate_dir() -> Result<PathBuf, String> {
+    crate::compat::env_var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::current_dir())
+        .and_then(|dir| dir.canonicalize())
+        .map_err(|err| format!("Couldn't find the crate directory: {err}"))
+}
+
+/// Refuses paths outside of [`crate_dir`] unless `unsafe_paths` is set. `path` should already be
+/// canonical, so that there's no `..` left in it to sneak out with.
+fn confine(path: &Path, unsafe_paths: bool) -> Result<(), String> {
+    if unsafe_paths {
+        return Ok(());
+    }
+    let dir = crate_dir()?;
+    if !path.starts_with(&dir) {
+        Err(format!(
+            "'{}' is outside of the crate directory '{}'; give `unsafe_paths: true` to allow it",
+            path.display(),
+            dir.display(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Finds the file to read for `file_get!` the same way [`befunge_input!`](crate::befunge_input)
+/// does.
+pub fn resolve_get(path: &str, unsafe_paths: bool) -> Result<PathBuf, String> {
+    let path = crate::input::expand_path(path)?;
+    let candidates = crate::input::candidate_paths(Path::new(&path));
+    let Some(found) = candidates.iter().find(|path| path.exists()) else {
+        let tried = candidates
+            .iter()
+            .map(|path| format!("'{}'", path.display()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(format!("File '{path}' does not exist (tried {tried})"));
+    };
+    let found = found
+        .canonicalize()
+        .map_err(|err| format!("Couldn't resolve '{}': {err}", found.display()))?;
+    confine(&found, unsafe_paths)?;
+    Ok(found)
+}
+
+/// Works out where `file_put!` should write. The file doesn't have to exist yet, but the directory
+/// it's in does. Relative paths are relative to the directory of the crate being built.
+pub fn resolve_put(path: &str, unsafe_paths: bool) -> Result<PathBuf, String> {
+    let path = PathBuf::from(crate::input::expand_path(path)?);
+    let path = crate::input::candidate_paths(&path)
+        .into_iter()
+        .next()
+        .unwrap_or(path);
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return Err(format!("'{}' isn't a path to a file", path.display()));
+    };
+    let parent = parent
+        .canonicalize()
+        .map_err(|err| format!("Couldn't resolve '{}': {err}", parent.display()))?;
+    let path = parent.join(name);
+    confine(&path, unsafe_paths)?;
+    Ok(path)
+}
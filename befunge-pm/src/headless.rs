@@ -0,0 +1,37 @@
+
+/// Whether this was built with the `headless` feature, in which case the macros that would talk to
+/// an interface don't connect to one at all. Output is thrown away and input is answered with
+/// [`integer`] and [`ascii`].
+pub const ENABLED: bool = cfg!(feature = "headless");
+
+/// Environment variable holding the answer to give `get_integer!` when headless.
+pub const INTEGER_VAR: &str = "BEFUNGE_HEADLESS_INTEGER";
+/// Environment variable holding the answer to give `get_ascii!` when headless.
+pub const ASCII_VAR: &str = "BEFUNGE_HEADLESS_ASCII";
+
+/// The answer to give `get_integer!` when headless: the value of [`INTEGER_VAR`], or 0.
+pub fn integer() -> Result<isize, String> {
+    match crate::compat::env_var(INTEGER_VAR) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| format!("{INTEGER_VAR} must be an integer, not '{value}'")),
+        Err(_) => Ok(0),
+    }
+}
+
+/// The answer to give `get_ascii!` when headless: the value of [`ASCII_VAR`], or a newline. If that
+/// isn't one of the `allowed` characters, the first of those is given instead.
+pub fn ascii(allowed: Option<&[u8]>) -> Result<u8, String> {
+    let ascii = match crate::compat::env_var(ASCII_VAR) {
+        Ok(value) => match value.as_bytes() {
+            &[ascii] if ascii.is_ascii() => ascii,
+            _ => return Err(format!("{ASCII_VAR} must be a single ASCII character, not '{value}'")),
+        },
+        Err(_) => b'\n',
+    };
+    match allowed {
+        Some(allowed) if !allowed.contains(&ascii) => Ok(allowed[0]),
+        _ => Ok(ascii),
+    }
+}
@@ -0,0 +1,64 @@
+use crate::callback::Callback;
+use syn::{
+    LitInt, Token, bracketed,
+    parse::{Parse, ParseStream},
+};
+
+/// The characters used for each bucket of visits, from fewest to most.
+const BUCKETS: [char; 5] = ['.', ':', '*', '#', '@'];
+
+pub struct Heatmap {
+    pub visits: Vec<(usize, usize)>,
+    pub callback: Callback,
+}
+
+impl Parse for Heatmap {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<crate::kw::visits>()?;
+        input.parse::<Token![:]>()?;
+        let list;
+        bracketed!(list in input);
+        let mut visits = Vec::new();
+        while !list.is_empty() {
+            let visit;
+            bracketed!(visit in list);
+            let row = visit.parse::<LitInt>()?.base10_parse()?;
+            let col = visit.parse::<LitInt>()?.base10_parse()?;
+            visits.push((row, col));
+        }
+        input.parse::<Token![,]>()?;
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(Heatmap { visits, callback })
+    }
+}
+
+/// Counts the visits to each cell and gives back the most visits to any one cell, along with a
+/// line of characters for each row up to the last one visited. Each line stops at the last cell
+/// visited in it, and unvisited cells are spaces. There's always at least one line, even if it's
+/// empty.
+pub fn render(visits: &[(usize, usize)]) -> (usize, Vec<Vec<char>>) {
+    let mut counts: Vec<Vec<usize>> = vec![Vec::new()];
+    for &(row, col) in visits {
+        if counts.len() <= row {
+            counts.resize_with(row + 1, Vec::new);
+        }
+        if counts[row].len() <= col {
+            counts[row].resize(col + 1, 0);
+        }
+        counts[row][col] += 1;
+    }
+    let most = counts.iter().flatten().copied().max().unwrap_or(0);
+    let lines = counts
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&count| match count {
+                    0 => ' ',
+                    _ => BUCKETS[(count * BUCKETS.len()).div_ceil(most) - 1],
+                })
+                .collect()
+        })
+        .collect();
+    (most, lines)
+}
@@ -1,4 +1,5 @@
-use crate::callback::Callback;
+use crate::{callback::Callback, program::ProgramOptions};
+use std::path::{Path, PathBuf};
 use syn::{
     LitStr, Token,
     parse::{Parse, ParseStream},
@@ -6,6 +7,7 @@ use syn::{
 
 pub struct BefungeInput {
     pub file: LitStr,
+    pub options: ProgramOptions,
     pub callback: Callback,
 }
 
@@ -15,8 +17,54 @@ impl Parse for BefungeInput {
         input.parse::<Token![:]>()?;
         let file = input.parse()?;
         input.parse::<Token![,]>()?;
+        let options = crate::program::parse_options(input)?;
         let callback = crate::callback::parse_callback(input)?;
         crate::maybe_trailing_comma(input)?;
-        Ok(BefungeInput { file, callback })
+        Ok(BefungeInput {
+            file,
+            options,
+            callback,
+        })
     }
 }
+
+/// Expands a leading `~` to the home directory and `${NAME}` to the value of the environment
+/// variable `NAME`.
+pub fn expand_path(path: &str) -> Result<String, String> {
+    let mut expanded = String::new();
+    let mut rest = path;
+    if rest == "~" || rest.starts_with("~/") {
+        expanded.push_str(&env_var("HOME")?);
+        rest = &rest[1..];
+    }
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let Some(len) = rest[start + 2..].find('}') else {
+            return Err(format!("Unterminated '${{' in file path '{path}'"));
+        };
+        expanded.push_str(&env_var(&rest[start + 2..start + 2 + len])?);
+        rest = &rest[start + 3 + len..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+fn env_var(name: &str) -> Result<String, String> {
+    crate::compat::env_var(name).map_err(|_| format!("Environment variable '{name}' is not set"))
+}
+
+/// Lists the places a file could be. Relative paths are tried against the directory of the crate
+/// being built first, then the current directory.
+pub fn candidate_paths(path: &Path) -> Vec<PathBuf> {
+    if path.is_absolute() {
+        return vec![path.to_path_buf()];
+    }
+    let mut candidates = crate::compat::env_var("CARGO_MANIFEST_DIR")
+        .ok()
+        .map(|dir| Path::new(&dir).join(path))
+        .into_iter()
+        .chain(std::env::current_dir().ok().map(|pwd| pwd.join(path)))
+        .collect::<Vec<_>>();
+    candidates.dedup();
+    candidates
+}
@@ -1,12 +1,14 @@
 use crate::callback::Callback;
-use interprocess::local_socket::{GenericFilePath, GenericNamespaced, Stream, prelude::*};
+use crate::compat::{self, Diagnostic};
+use befunge_if::{Client, ClientError};
+use interprocess::local_socket::Stream;
 use proc_macro2::{Delimiter, Group, TokenStream as TokenStream2, TokenTree as TokenTree2};
 use quote::quote;
-use std::iter::repeat_n;
-use syn::{Error as SynError, LitStr, Token, parse::{Parse, ParseStream}};
+use std::{iter::repeat_n, time::Duration};
+use syn::{Error as SynError, LitInt, LitStr, Token, bracketed, parse::{Parse, ParseStream}};
 
 pub struct InterfaceConn {
-    pub conn: Stream,
+    pub conn: Option<Conn>,
     pub callback: Callback,
 }
 
@@ -20,32 +22,209 @@ impl Parse for InterfaceConn {
     }
 }
 
-pub fn parse_socket(input: ParseStream) -> syn::Result<Stream> {
+pub struct GetAscii {
+    pub conn: Option<Conn>,
+    pub allowed: Option<Vec<u8>>,
+    pub callback: Callback,
+}
+
+impl Parse for GetAscii {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let conn = parse_socket(input)?;
+        input.parse::<Token![,]>()?;
+        let mut allowed = None;
+        if input.peek(crate::kw::allowed) {
+            input.parse::<crate::kw::allowed>()?;
+            input.parse::<Token![:]>()?;
+            let chars: LitStr = input.parse()?;
+            let value = chars.value();
+            if value.is_empty() || !value.is_ascii() {
+                return Err(SynError::new(
+                    chars.span(),
+                    "`allowed` must be a non-empty string of ASCII characters",
+                ));
+            }
+            allowed = Some(value.into_bytes());
+            input.parse::<Token![,]>()?;
+        }
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(GetAscii { conn, allowed, callback })
+    }
+}
+
+/// How many more times to try connecting to a socket that nothing is listening on yet, unless
+/// overridden with `retries: N`.
+const DEFAULT_RETRIES: u32 = 10;
+/// How long to wait between connection attempts, unless overridden with `retry_delay_ms: N`.
+const DEFAULT_RETRY_DELAY_MS: u64 = 200;
+
+/// A connection to an interface, along with where its `socket:` argument was written, so that
+/// anything that goes wrong with it can be reported there rather than wherever the outermost macro
+/// happened to be invoked.
+pub struct Conn {
+    pub client: Client<Stream>,
+    pub span: proc_macro::Span,
+    /// The socket's name and, if it's different, where it actually is.
+    pub describe: String,
+}
+
+impl Conn {
+    /// Reports a failed request at the `socket:` argument.
+    pub fn error(&self, err: ClientError) -> Diagnostic {
+        compat::error(self.span, format!("{err} (on socket {})", self.describe))
+    }
+}
+
+/// Parses `socket: "name"`, optionally followed by `, retries: N` and/or `, retry_delay_ms: N`,
+/// then connects to that socket and performs the handshake. The name may also be given as a
+/// bracketed list of string literals to be concatenated, e.g. `socket: ["myprefix" ".output"]`.
+/// `BEFUNGE_SOCKET_PREFIX` and `BEFUNGE_SOCKET_DIR` are applied on top of the given name.
+///
+/// Built with the `headless` feature, the socket is parsed but never connected to, and this gives
+/// `None`.
+pub fn parse_socket(input: ParseStream) -> syn::Result<Option<Conn>> {
+    parse_socket_target(input)?.connect()
+}
+
+/// A socket that's been parsed by [`parse_socket_target`] but not connected to yet.
+pub struct SocketTarget {
+    /// The socket's name, with `BEFUNGE_SOCKET_PREFIX` applied.
+    pub socket: String,
+    span: proc_macro2::Span,
+    describe: String,
+    retries: u32,
+    retry_delay_ms: u64,
+}
+
+/// Parses the same things as [`parse_socket`], without connecting to the socket.
+pub fn parse_socket_target(input: ParseStream) -> syn::Result<SocketTarget> {
     input.parse::<crate::kw::socket>()?;
     input.parse::<Token![:]>()?;
-    let socket: LitStr = input.parse()?;
-    let socket = socket.value();
-    let name = if GenericNamespaced::is_supported() {
-        socket
-            .to_ns_name::<GenericNamespaced>()
-            .map_err(|e| SynError::new(input.span(), format!("{e}")))?
+    let (socket, span) = if input.peek(syn::token::Bracket) {
+        let content;
+        let span = bracketed!(content in input).span.join();
+        let mut socket = String::new();
+        while !content.is_empty() {
+            socket.push_str(&content.parse::<LitStr>()?.value());
+        }
+        (socket, span)
     } else {
-        format!("/tmp/{socket}")
-            .to_fs_name::<GenericFilePath>()
-            .map_err(|e| SynError::new(input.span(), format!("{e}")))?
+        let socket = input.parse::<LitStr>()?;
+        (socket.value(), socket.span())
     };
-    let conn =
-        Stream::connect(name).map_err(|e| SynError::new(input.span(), format!("{e}")))?;
-    Ok(conn)
+    let socket = befunge_if::resolve_socket(
+        &socket,
+        crate::compat::env_var(befunge_if::SOCKET_PREFIX_VAR).ok().as_deref(),
+    );
+    // Only `befunge_if::socket_name` reads this, but it's fetched here so that changing it causes
+    // the macros to be expanded again.
+    let socket_dir = crate::compat::env_var(befunge_if::SOCKET_DIR_VAR).ok();
+    let location = befunge_if::socket_location(&socket, socket_dir.as_deref());
+    let describe = if location == socket {
+        format!("'{socket}'")
+    } else {
+        format!("'{socket}' (at '{location}')")
+    };
+    let mut retries = DEFAULT_RETRIES;
+    if input.peek(Token![,]) && input.peek2(crate::kw::retries) {
+        input.parse::<Token![,]>()?;
+        input.parse::<crate::kw::retries>()?;
+        input.parse::<Token![:]>()?;
+        retries = input.parse::<LitInt>()?.base10_parse()?;
+    }
+    let mut retry_delay_ms = DEFAULT_RETRY_DELAY_MS;
+    if input.peek(Token![,]) && input.peek2(crate::kw::retry_delay_ms) {
+        input.parse::<Token![,]>()?;
+        input.parse::<crate::kw::retry_delay_ms>()?;
+        input.parse::<Token![:]>()?;
+        retry_delay_ms = input.parse::<LitInt>()?.base10_parse()?;
+    }
+    Ok(SocketTarget {
+        socket,
+        span,
+        describe,
+        retries,
+        retry_delay_ms,
+    })
+}
+
+impl SocketTarget {
+    /// Connects to the socket and performs the handshake. Built with the `headless` feature, this
+    /// gives `None` without connecting.
+    pub fn connect(self) -> syn::Result<Option<Conn>> {
+        let SocketTarget {
+            socket,
+            span,
+            describe,
+            retries,
+            retry_delay_ms,
+        } = self;
+        if crate::headless::ENABLED {
+            return Ok(None);
+        }
+        let conn = befunge_if::connect_with_retry(
+            &socket,
+            retries,
+            Duration::from_millis(retry_delay_ms),
+        )
+        .map_err(|e| {
+            let msg = format!(
+                "Failed to connect to Befunge UI on socket {describe} after {} attempt(s).\n\
+                 Error: '{e}'\n\
+                 Is it running? Start it with `cargo run --bin befunge-if -- --socket {socket}`",
+                retries + 1,
+            );
+            SynError::new(span, msg)
+        })?;
+        let mut client = Client::new(conn);
+        client
+            .handshake()
+            .map_err(|e| SynError::new(span, format!("{e} (on socket {describe})")))?;
+        Ok(Some(Conn {
+            client,
+            span: span.unwrap(),
+            describe,
+        }))
+    }
 }
 
 fn empty_group() -> TokenTree2 {
     TokenTree2::Group(Group::new(Delimiter::Bracket, TokenStream2::new()))
 }
 
+/// Environment variable that overrides [`DEFAULT_MAX_MAGNITUDE`].
+pub const MAX_MAGNITUDE_VAR: &str = "BEFUNGE_MAX_MAGNITUDE";
+/// The largest magnitude of an answer from the interface that will be turned into a base 1 number,
+/// unless overridden with [`MAX_MAGNITUDE_VAR`]. Every unit of magnitude is another token, so much
+/// bigger answers make the compiler grind to a halt.
+pub const DEFAULT_MAX_MAGNITUDE: usize = 4096;
+
+/// The largest magnitude of an answer from the interface that will be turned into a base 1 number.
+pub fn max_magnitude() -> Result<usize, String> {
+    match crate::compat::env_var(MAX_MAGNITUDE_VAR) {
+        Ok(value) => value.trim().parse().map_err(|_| {
+            format!("{MAX_MAGNITUDE_VAR} must be a non-negative integer, not '{value}'")
+        }),
+        Err(_) => Ok(DEFAULT_MAX_MAGNITUDE),
+    }
+}
+
+/// Turns an answer from the interface into a base 1 number, unless its magnitude is more than
+/// `max`.
+pub fn answer_to_base1(num: isize, max: usize) -> Result<TokenStream2, String> {
+    if num.unsigned_abs() > max {
+        return Err(format!(
+            "{num} is too large to be represented as a base 1 number; the largest magnitude \
+             allowed is {max} (set {MAX_MAGNITUDE_VAR} to change this)"
+        ));
+    }
+    Ok(isize_to_base1(num))
+}
+
 pub fn isize_to_base1(num: isize) -> TokenStream2 {
-    let groups = TokenStream2::from_iter(repeat_n(empty_group(), num.abs() as usize));
-    let stream = if num.is_negative() {
+    let groups = TokenStream2::from_iter(repeat_n(empty_group(), num.unsigned_abs()));
+    if num.is_negative() {
         quote! {
             [[neg] [#groups]]
         }
@@ -53,12 +232,11 @@ pub fn isize_to_base1(num: isize) -> TokenStream2 {
         quote! {
             [[pos] [#groups]]
         }
-    };
-    TokenStream2::from(stream)
+    }
 }
 
 pub struct CloseUi {
-    pub conn: Stream,
+    pub conn: Option<Conn>,
 }
 
 impl Parse for CloseUi {
@@ -68,3 +246,38 @@ impl Parse for CloseUi {
         Ok(CloseUi { conn })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{DEFAULT_MAX_MAGNITUDE, answer_to_base1};
+
+    fn base1(num: isize, max: usize) -> Result<String, String> {
+        answer_to_base1(num, max).map(|tokens| tokens.to_string())
+    }
+
+    #[test]
+    fn within_limit() {
+        assert_eq!(base1(0, 3).unwrap(), "[[pos] []]");
+        assert_eq!(base1(3, 3).unwrap(), "[[pos] [[] [] []]]");
+        assert_eq!(base1(-3, 3).unwrap(), "[[neg] [[] [] []]]");
+        assert_eq!(base1(0, 0).unwrap(), "[[pos] []]");
+    }
+
+    #[test]
+    fn over_limit() {
+        assert!(base1(4, 3).is_err());
+        assert!(base1(-4, 3).is_err());
+        assert!(base1(1, 0).is_err());
+        let max = DEFAULT_MAX_MAGNITUDE as isize;
+        assert!(base1(max, DEFAULT_MAX_MAGNITUDE).is_ok());
+        assert!(base1(-max - 1, DEFAULT_MAX_MAGNITUDE).is_err());
+    }
+
+    #[test]
+    fn extremes() {
+        let err = base1(isize::MIN, DEFAULT_MAX_MAGNITUDE).unwrap_err();
+        assert!(err.starts_with("-9223372036854775808 is too large"), "{err}");
+        assert!(base1(isize::MAX, DEFAULT_MAX_MAGNITUDE).is_err());
+        assert!(base1(isize::MIN, isize::MAX as usize).is_err());
+    }
+}
@@ -1,27 +1,49 @@
-#![feature(proc_macro_def_site, proc_macro_diagnostic)]
+#![cfg_attr(
+    feature = "nightly",
+    feature(proc_macro_diagnostic, proc_macro_span, proc_macro_tracked_env)
+)]
 
 extern crate proc_macro;
 
+mod base1;
 mod callback;
+mod clock;
+mod compact;
+mod compat;
 mod debug;
+mod error_at;
+mod file_io;
+mod headless;
+mod heatmap;
 mod input;
 mod interface;
 mod print;
+mod program;
 mod random_token;
+mod source;
 mod stringify_callback;
 
-use befunge_if::Request;
+use befunge_if::{ClientError, Request};
+use base1::Base1FromLiteral;
 use callback::Callback;
+use clock::{GetTime, Sleep};
+use compact::{CompactRows, SplitRow};
 use debug::Debug;
+use error_at::ErrorAt;
+use heatmap::Heatmap;
+use file_io::{FileGet, FilePut};
 use input::BefungeInput;
-use interface::{CloseUi, InterfaceConn, isize_to_base1};
-use print::{PrintAscii, PrintInteger};
+use interface::{CloseUi, GetAscii, InterfaceConn, isize_to_base1};
+use print::{PrintAscii, PrintInteger, PrintString};
 use proc_macro::{Span, TokenStream};
-use proc_macro2::{Group, Literal, TokenStream as TokenStream2, TokenTree as TokenTree2};
+use proc_macro2::{Literal, TokenStream as TokenStream2, TokenTree as TokenTree2};
 use quote::quote;
-use rand::{SeedableRng, rngs::StdRng, seq::IndexedRandom};
 use random_token::ChooseRandom;
-use std::{io::Write, path::PathBuf};
+use source::BefungeSource;
+use std::{
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use stringify_callback::StringifyCallback;
 use syn::{
     Error as SynError, Token,
@@ -40,33 +62,76 @@ fn maybe_trailing_comma(input: ParseStream) -> syn::Result<()> {
     }
 }
 
-macro_rules! do_or_err {
-    ($msg:literal, $do:expr$(,)?) => {
-        if let Err(err) = $do {
-            let msg = format!(concat!($msg, "\nError: {}"), err);
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+/// Makes a [`befunge_if::Client`] request on a [`Conn`](interface::Conn), emitting the error at
+/// its `socket:` argument and returning early on failure.
+macro_rules! client_or_err {
+    ($conn:ident.$method:ident($($args:tt)*)$(,)?) => {
+        match $conn.client.$method($($args)*) {
+            Ok(val) => val,
+            Err(err) => return $conn.error(err).emit(),
         }
     };
 }
 
 mod kw {
+    syn::custom_keyword!(allow_oversize);
+    syn::custom_keyword!(allowed);
     syn::custom_keyword!(ascii);
+    syn::custom_keyword!(at);
     syn::custom_keyword!(callback);
+    syn::custom_keyword!(category);
     syn::custom_keyword!(choices);
+    syn::custom_keyword!(contents);
+    syn::custom_keyword!(epoch);
+    syn::custom_keyword!(escape);
     syn::custom_keyword!(file);
+    syn::custom_keyword!(last);
+    syn::custom_keyword!(lenof);
+    syn::custom_keyword!(level);
+    syn::custom_keyword!(locations);
+    syn::custom_keyword!(millis);
     syn::custom_keyword!(name);
     syn::custom_keyword!(neg);
+    syn::custom_keyword!(noack);
     syn::custom_keyword!(number);
+    syn::custom_keyword!(pad);
+    syn::custom_keyword!(path);
     syn::custom_keyword!(pos);
     syn::custom_keyword!(pre);
     syn::custom_keyword!(pst);
+    syn::custom_keyword!(raw);
+    syn::custom_keyword!(retries);
+    syn::custom_keyword!(retry_delay_ms);
+    syn::custom_keyword!(rows);
+    syn::custom_keyword!(seed);
     syn::custom_keyword!(socket);
+    syn::custom_keyword!(source);
+    syn::custom_keyword!(split);
+    syn::custom_keyword!(string);
+    syn::custom_keyword!(tabs);
     syn::custom_keyword!(tokens);
+    syn::custom_keyword!(trim);
+    syn::custom_keyword!(unsafe_paths);
+    syn::custom_keyword!(unwrap);
+    syn::custom_keyword!(value);
+    syn::custom_keyword!(visits);
 }
 
 #[proc_macro]
 /// Reads in an input file and makes a callback with a stream of character literals as the result.
+/// Windows (`\r\n`) and old Mac (`\r`) line endings are turned into `\n`. If `trim: true` is given
+/// between the file and the callback, trailing spaces are also removed from every line.
+///
+/// Tabs are rejected unless `tabs: N` is given (after `trim:`, if that's given too), in which case
+/// each one is expanded to spaces up to the next column that's a multiple of `N`, just as most
+/// editors show them.
+///
+/// Programs with lines longer than 80 characters or more than 25 lines are rejected, unless
+/// `allow_oversize: true` is given (after `trim:` and `tabs:`).
+///
+/// A leading `~` in the path is replaced with the home directory and `${NAME}` with the value of
+/// the environment variable `NAME`. Relative paths are looked up in the directory of the crate
+/// being built (`CARGO_MANIFEST_DIR`) first, then in the current directory.
 /// 
 /// The callback format is:
 /// ```ignore
@@ -77,26 +142,28 @@ mod kw {
 /// }
 /// ```
 pub fn befunge_input(input: TokenStream) -> TokenStream {
-    let BefungeInput { file, callback } = parse_macro_input!(input as BefungeInput);
-    let file_string = file.value();
-    let file_path = PathBuf::from(&file_string);
-    if !file_path.exists() {
-        let msg = file_path
-            .is_relative()
-            .then_some(())
-            .and(std::env::current_dir().ok())
-            .map(|pwd| {
-                format!(
-                    "File '{}' does not exist ({}/{0})",
-                    file_path.display(),
-                    pwd.display()
-                )
-            })
-            .unwrap_or_else(|| format!("File '{}' does not exist", file_path.display()));
-        file.span().unwrap().error(msg).emit();
-        return TokenStream::new();
-    }
-    let contents = match std::fs::read_to_string(&file_path) {
+    let BefungeInput {
+        file,
+        options,
+        callback,
+    } = parse_macro_input!(input as BefungeInput);
+    let file_string = match input::expand_path(&file.value()) {
+        Ok(file_string) => file_string,
+        Err(msg) => {
+            return compat::error(file.span().unwrap(), msg).emit();
+        }
+    };
+    let candidates = input::candidate_paths(Path::new(&file_string));
+    let Some(file_path) = candidates.iter().find(|path| path.exists()) else {
+        let tried = candidates
+            .iter()
+            .map(|path| format!("'{}'", path.display()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let msg = format!("File '{file_string}' does not exist (tried {tried})");
+        return compat::error(file.span().unwrap(), msg).emit();
+    };
+    let contents = match std::fs::read_to_string(file_path) {
         Ok(contents) => contents,
         Err(err) => {
             let msg = file_path
@@ -104,27 +171,49 @@ pub fn befunge_input(input: TokenStream) -> TokenStream {
                 .ok()
                 .map(|canon| format!("Error reading file contents: {err} ({})", canon.display()))
                 .unwrap_or_else(|| format!("Error reading file contents: {err}"));
-            file.span().unwrap().error(&msg).emit();
-            return TokenStream::new();
+            return compat::error(file.span().unwrap(), &msg).emit();
         }
     };
-    let contents_ts = TokenStream2::from_iter(contents.chars().map(|c| {
-        if c.is_ascii() {
-            TokenTree2::Literal(Literal::character(c))
-        } else {
-            let path = file_path
-                .canonicalize()
-                .ok()
-                .map(|canon| canon.display().to_string())
-                .unwrap_or_else(|| file_path.display().to_string());
-            let msg = format!("File {path} contains non-ASCII character: {c:?}");
-            file.span().unwrap().error(&msg).emit();
-            return TokenTree2::Group(Group::new(
-                proc_macro2::Delimiter::None,
-                TokenStream2::new(),
-            ));
-        }
-    }));
+    let origin = format!("File {}", display_path(file_path));
+    match program::prepare(&contents, &origin, &options, file.span().unwrap()) {
+        Ok(contents) => filecontents_callback(&contents, callback),
+        Err(diagnostic) => {
+            diagnostic.emit()
+        }
+    }
+}
+
+#[proc_macro]
+/// Like [`befunge_input!`], but reads the program from a string instead of a file. Takes the same
+/// options between the source and the callback, and makes the same callback:
+/// ```ignore
+/// name! {
+///     pre
+///     filecontents: ['a' 'b' 'c' ...],
+///     pst
+/// }
+/// ```
+pub fn befunge_source(input: TokenStream) -> TokenStream {
+    let BefungeSource {
+        source,
+        options,
+        callback,
+    } = parse_macro_input!(input as BefungeSource);
+    match program::prepare(&source.value(), "Source", &options, source.span().unwrap()) {
+        Ok(contents) => filecontents_callback(&contents, callback),
+        Err(diagnostic) => {
+            diagnostic.emit()
+        }
+    }
+}
+
+/// Makes a callback with a program's source as a stream of character literals.
+fn filecontents_callback(contents: &str, callback: Callback) -> TokenStream {
+    let contents_ts = TokenStream2::from_iter(
+        contents
+            .chars()
+            .map(|c| TokenTree2::Literal(Literal::character(c))),
+    );
     let Callback { name, pre, pst } = callback;
     let pre_inner = pre.stream();
     let pst_inner = pst.stream();
@@ -138,9 +227,182 @@ pub fn befunge_input(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Shows the canonical form of a path if there is one.
+fn display_path(path: &Path) -> String {
+    path.canonicalize()
+        .ok()
+        .map(|canon| canon.display().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[proc_macro]
+/// Reads a file whose path is given as a list of character literals, as would be made from the
+/// stack by Befunge-98's `i` instruction, and makes a callback with its contents as a list of
+/// character literals. The path is looked up the same way as for [`befunge_input!`], and the file
+/// has to be ASCII. Files outside of the directory of the crate being built are refused unless
+/// `unsafe_paths: true` is given between the path and the callback.
+/// ```
+/// macro_rules! manifest {
+///     (contents: [$($c:literal)*],) => {
+///         [$($c),*]
+///     };
+/// }
+///
+/// let manifest: String = befunge_pm::file_get! {
+///     path: ['C' 'a' 'r' 'g' 'o' '.' 't' 'o' 'm' 'l'],
+///     callback: [
+///         name: manifest,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+/// .into_iter()
+/// .collect();
+/// assert!(manifest.starts_with("[package]\nname = \"befunge-pm\""));
+/// ```
+/// ```compile_fail
+/// macro_rules! manifest {
+///     (contents: [$($c:literal)*],) => {};
+/// }
+///
+/// // The workspace's manifest is outside of this crate's directory.
+/// befunge_pm::file_get! {
+///     path: ['.' '.' '/' 'C' 'a' 'r' 'g' 'o' '.' 't' 'o' 'm' 'l'],
+///     callback: [
+///         name: manifest,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+/// ```
+///
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     contents: ['a' 'b' 'c' ...],
+///     pst
+/// }
+/// ```
+pub fn file_get(input: TokenStream) -> TokenStream {
+    let FileGet {
+        path,
+        unsafe_paths,
+        callback,
+    } = parse_macro_input!(input as FileGet);
+    let file_path = match file_io::resolve_get(&path.value, unsafe_paths) {
+        Ok(file_path) => file_path,
+        Err(msg) => {
+            return compat::error(path.span, msg).emit();
+        }
+    };
+    let contents = match std::fs::read_to_string(&file_path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            let msg = format!("Error reading file contents: {err} ({})", file_path.display());
+            return compat::error(path.span, msg).emit();
+        }
+    };
+    let origin = format!("File {}", file_path.display());
+    if let Err(diagnostic) = program::check_ascii(&contents, &origin, path.span) {
+        return diagnostic.emit();
+    }
+    let res = TokenStream2::from_iter(
+        contents
+            .chars()
+            .map(|c| TokenTree2::Literal(Literal::character(c))),
+    );
+    let Callback { name, pre, pst } = callback;
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            contents: [#res],
+            #pst_inner
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+#[proc_macro]
+/// Writes a list of character literals to a file whose path is also given as a list of character
+/// literals, as would be made from the stack by Befunge-98's `o` instruction. The file is created
+/// if it doesn't exist and replaced if it does, and the contents have to be ASCII. Relative paths
+/// are relative to the directory of the crate being built, and files outside of it are refused
+/// unless `unsafe_paths: true` is given between the contents and the callback.
+///
+/// `befunge-pm/tests/file_io.rs` writes a file under the system's temporary directory this way
+/// and reads it back in with [`file_get!`]. Without `unsafe_paths: true`, writing outside of the
+/// crate's directory fails the build:
+/// ```compile_fail
+/// macro_rules! written {
+///     () => {};
+/// }
+///
+/// befunge_pm::file_put! {
+///     path: ['.' '.' '/' 'i' 'o' '.' 't' 'x' 't'],
+///     contents: ['>' '@' '\n'],
+///     callback: [
+///         name: written,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+/// ```
+///
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     pst
+/// }
+/// ```
+pub fn file_put(input: TokenStream) -> TokenStream {
+    let FilePut {
+        path,
+        contents,
+        unsafe_paths,
+        callback,
+    } = parse_macro_input!(input as FilePut);
+    let file_path = match file_io::resolve_put(&path.value, unsafe_paths) {
+        Ok(file_path) => file_path,
+        Err(msg) => {
+            return compat::error(path.span, msg).emit();
+        }
+    };
+    if let Err(diagnostic) = program::check_ascii(&contents.value, "The contents", contents.span) {
+        return diagnostic.emit();
+    }
+    if let Err(err) = std::fs::write(&file_path, &contents.value) {
+        let msg = format!("Error writing file: {err} ({})", file_path.display());
+        return compat::error(path.span, msg).emit();
+    }
+    let Callback { name, pre, pst } = callback;
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            #pst_inner
+        }
+    };
+    TokenStream::from(expanded)
+}
+
 #[proc_macro]
 /// Similar to [`stringify`], but capable of making a callback with the result.
 /// 
+/// Like [`stringify`], spacing between the tokens is decided by the compiler rather than kept as it
+/// was written. Giving `raw: true` after `tokens` makes the callback get exactly what was written
+/// at the call site instead, where the compiler still has that source text. When it doesn't, e.g.
+/// for several tokens passed along by another macro, the result is the same as without `raw`.
+///
+/// Giving `escape: true` after that turns a lone char literal into the character it stands for,
+/// rather than the literal with its quotes, with control characters written out as `\xNN`. The
+/// result is a string literal with the quotes and backslashes in it escaped, so it can go straight
+/// into `concat!`.
+/// 
 /// The callback format is:
 /// ```ignore
 /// name! {
@@ -149,9 +411,68 @@ pub fn befunge_input(input: TokenStream) -> TokenStream {
 ///     pst
 /// }
 /// ```
+/// 
+/// ```
+/// # use befunge_pm::stringify_with_callback;
+/// macro_rules! check {
+///     (stringified: $s:literal, expected: $e:literal,) => {
+///         assert_eq!($s, $e);
+///     };
+/// }
+/// stringify_with_callback! {
+///     tokens: [a { b } ; '_'],
+///     raw: true,
+///     callback: [name: check, pre: [], pst: [expected: "a { b } ; '_'",]],
+/// }
+/// stringify_with_callback! {
+///     tokens: [{ ;;'_' }],
+///     raw: true,
+///     callback: [name: check, pre: [], pst: [expected: "{ ;;'_' }",]],
+/// }
+/// stringify_with_callback! {
+///     tokens: [x  ,   y],
+///     raw: true,
+///     callback: [name: check, pre: [], pst: [expected: "x  ,   y",]],
+/// }
+/// // A single token passed along by a `macro_rules!` macro keeps its spacing too.
+/// macro_rules! relay {
+///     ($one:tt) => {
+///         stringify_with_callback! {
+///             tokens: [$one],
+///             raw: true,
+///             callback: [name: check, pre: [], pst: [expected: "{ ; '_'}",]],
+///         }
+///     };
+/// }
+/// relay!({ ; '_'});
+/// // Escaped char literals give the character itself.
+/// stringify_with_callback! {
+///     tokens: ['\x07'],
+///     escape: true,
+///     callback: [name: check, pre: [], pst: [expected: r"\x07",]],
+/// }
+/// stringify_with_callback! {
+///     tokens: ['\"'],
+///     escape: true,
+///     callback: [name: check, pre: [], pst: [expected: "\"",]],
+/// }
+/// stringify_with_callback! {
+///     tokens: ['\\'],
+///     escape: true,
+///     callback: [name: check, pre: [], pst: [expected: "\\",]],
+/// }
+/// ```
 pub fn stringify_with_callback(ts: TokenStream) -> TokenStream {
-    let StringifyCallback { tokens, callback } = parse_macro_input!(ts as StringifyCallback);
-    let tokens_string = tokens.stream().to_string();
+    let StringifyCallback {
+        tokens,
+        raw,
+        escape,
+        callback,
+    } = parse_macro_input!(ts as StringifyCallback);
+    let tokens_string = escape
+        .then(|| stringify_callback::escape_char(&tokens))
+        .flatten()
+        .unwrap_or_else(|| stringify_callback::stringify(&tokens, raw));
     let Callback { name, pre, pst } = callback;
     let pre_inner = pre.stream();
     let pst_inner = pst.stream();
@@ -165,8 +486,242 @@ pub fn stringify_with_callback(ts: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+#[proc_macro]
+/// Makes a callback with an integer literal as a base 1 number in the representation used by the
+/// interpreter, which saves counting brackets when writing one out by hand. The same limit on
+/// magnitude as for [`div_by_zero!`] applies.
+/// ```
+/// macro_rules! check {
+///     (num: [[neg] [[] [] [] [] [] [] [] [] [] [] [] []]],) => {};
+///     (num: [[pos] []],) => {};
+/// }
+///
+/// befunge_pm::base1_from_literal! {
+///     value: -12,
+///     callback: [name: check],
+/// }
+/// befunge_pm::base1_from_literal! {
+///     value: 0,
+///     callback: [name: check],
+/// }
+/// ```
+///
+/// Anything but an integer literal is refused:
+/// ```compile_fail
+/// macro_rules! check {
+///     (num: $num:tt,) => {};
+/// }
+///
+/// befunge_pm::base1_from_literal! {
+///     value: 1.5,
+///     callback: [name: check],
+/// }
+/// ```
+///
+/// As are numbers that are too large:
+/// ```compile_fail
+/// macro_rules! check {
+///     (num: $num:tt,) => {};
+/// }
+///
+/// befunge_pm::base1_from_literal! {
+///     value: 1000000,
+///     callback: [name: check],
+/// }
+/// ```
+///
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     num: [[pos] [...]],
+///     pst
+/// }
+/// ```
+pub fn base1_from_literal(input: TokenStream) -> TokenStream {
+    let Base1FromLiteral {
+        value,
+        span,
+        callback,
+    } = parse_macro_input!(input as Base1FromLiteral);
+    let num = interface::max_magnitude().and_then(|max| interface::answer_to_base1(value, max));
+    let num = match num {
+        Ok(num) => num,
+        Err(msg) => {
+            return compat::error(span.unwrap(), msg).emit();
+        }
+    };
+    let Callback { name, pre, pst } = callback;
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            num: #num,
+            #pst_inner
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+#[proc_macro]
+/// Turns a list of the cells the PC has visited, given as `[row col]` pairs of integer literals,
+/// into a heatmap of the playfield for the `[heatmap]` debugging flag. Each cell becomes one of
+/// `.:*#@` depending on how many times it was visited compared to the most visited cell, with `@`
+/// for the most visited ones, or a space if it was never visited. Rows stop at their last visited
+/// cell, and there are only as many rows as it takes to reach the last visited one.
+/// ```
+/// macro_rules! check {
+///     (most: 4, lines: [['@' ' ' ':'] [] [' ' '*']],) => {};
+/// }
+///
+/// befunge_pm::heatmap! {
+///     visits: [[0 0] [0 0] [0 0] [0 0] [0 2] [2 1] [2 1]],
+///     callback: [name: check],
+/// }
+/// ```
+///
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     most: 4,
+///     lines: [['@' ' ' '.'] ...],
+///     pst
+/// }
+/// ```
+pub fn heatmap(input: TokenStream) -> TokenStream {
+    let Heatmap { visits, callback } = parse_macro_input!(input as Heatmap);
+    let (most, lines) = heatmap::render(&visits);
+    let most = Literal::usize_unsuffixed(most);
+    let lines = lines.iter().map(|line| quote!([#(#line)*]));
+    let Callback { name, pre, pst } = callback;
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            most: #most,
+            lines: [#(#lines)*],
+            #pst_inner
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+#[proc_macro]
+/// Compacts the rows of a playfield for the `compact:` option. Every run of two or more spaces in a
+/// row becomes `[sp [[] ...]]`, with a `[]` for each space, and every row gets a `[sp []]` put at
+/// the end of it to mark it as compacted.
+/// ```
+/// macro_rules! check {
+///     (rows: [['>' [sp [[] [] []]] 'v' ' ' '@' [sp []]] [[sp []]]],) => {};
+/// }
+///
+/// befunge_pm::compact_rows! {
+///     rows: [['>' ' ' ' ' ' ' 'v' ' ' '@'] []],
+///     callback: [name: check],
+/// }
+/// ```
+///
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     rows: [[...] ...],
+///     pst
+/// }
+/// ```
+pub fn compact_rows(input: TokenStream) -> TokenStream {
+    let CompactRows { rows, callback } = parse_macro_input!(input as CompactRows);
+    let rows = compact::compact_rows(&rows);
+    let Callback { name, pre, pst } = callback;
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            rows: [#(#rows)*],
+            #pst_inner
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+#[proc_macro]
+/// Splits a row of the playfield, which may have been compacted by [`compact_rows!`]. With
+/// `lenof: [...]`, this works like `list_split_at_length_of!`, counting each run of spaces as the
+/// spaces in it, and padding the row with `pad` if it's too short. With `last: true`, the row is
+/// split before its last cell instead. Every run on the left side is written out, and the right
+/// side always starts with a cell. The rest of the right side of a compacted row is compacted
+/// again, and keeps the `[sp []]` at the end.
+/// ```
+/// macro_rules! check {
+///     (l: ['a' ' '], r: [' ' [sp [[] []]] 'b' [sp []]],) => {};
+///     (l: ['a' ' ' ' ' ' ' ' ' ' ' 'b'], r: [' ' [sp []]],) => {};
+///     (l: ['a'], r: ['b'],) => {};
+/// }
+///
+/// befunge_pm::split_row! {
+///     pad: ' ',
+///     lenof: [[] []],
+///     split: ['a' [sp [[] [] [] []]] 'b' [sp []]],
+///     callback: [name: check],
+/// }
+///
+/// befunge_pm::split_row! {
+///     pad: ' ',
+///     lenof: [[] [] [] [] [] [] []],
+///     split: ['a' [sp [[] [] [] [] []]] 'b' [sp []]],
+///     callback: [name: check],
+/// }
+///
+/// befunge_pm::split_row! {
+///     last: true,
+///     split: ['a' 'b'],
+///     callback: [name: check],
+/// }
+/// ```
+///
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     l: [...],
+///     r: [...],
+///     pst
+/// }
+/// ```
+pub fn split_row(input: TokenStream) -> TokenStream {
+    let SplitRow {
+        pad,
+        at,
+        split,
+        callback,
+    } = parse_macro_input!(input as SplitRow);
+    let (l, r) = match compact::split_row(pad.as_ref(), &at, &split) {
+        Ok(sides) => sides,
+        Err(msg) => return compat::error(split.span().unwrap(), msg).emit(),
+    };
+    let Callback { name, pre, pst } = callback;
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            l: #l,
+            r: #r,
+            #pst_inner
+        }
+    };
+    TokenStream::from(expanded)
+}
+
 #[proc_macro]
 /// Called by the interpreter when division by 0 occurs. Prompts the input interface for a response.
+/// Built with the `headless` feature, the response is always 0. Responses with a magnitude over
+/// 4096 (or `BEFUNGE_MAX_MAGNITUDE`, if that's set) are refused, since base 1 numbers that big
+/// would take the compiler forever.
 /// 
 /// The callback format is:
 /// ```ignore
@@ -177,31 +732,25 @@ pub fn stringify_with_callback(ts: TokenStream) -> TokenStream {
 /// }
 /// ```
 pub fn div_by_zero(input: TokenStream) -> TokenStream {
-    let InterfaceConn { mut conn, callback } = parse_macro_input!(input as InterfaceConn);
-    do_or_err!(
-        "Failed to request divide by zero resolution from Befunge UI.",
-        befunge_if::ciborium::ser::into_writer(&Request::DivByZero, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    let ans = match befunge_if::ciborium::de::from_reader(&mut conn) {
-        Ok(Request::DivByZeroAns(ans)) => ans,
-        Ok(other) => {
-            let msg = format!("Received unexpected request: '{other:?}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+    let InterfaceConn { conn, callback } = parse_macro_input!(input as InterfaceConn);
+    let ans = match conn {
+        Some(mut conn) => {
+            let ans = match client_or_err!(conn.request_reply(Request::DivByZero)) {
+                Request::DivByZeroAns(ans) => ans,
+                other => return conn.error(ClientError::Unexpected(other)).emit(),
+            };
+            client_or_err!(conn.close());
+            ans
         }
-        Err(err) => {
-            let msg = format!("Failed to deserialise message.\nError: '{err}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+        None => 0,
+    };
+    let res = interface::max_magnitude().and_then(|max| interface::answer_to_base1(ans, max));
+    let res = match res {
+        Ok(res) => res,
+        Err(msg) => {
+            return compat::error(Span::call_site(), msg).emit();
         }
     };
-    do_or_err!(
-        "Failed to write close connection.",
-        befunge_if::ciborium::ser::into_writer(&Request::CloseConnection, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    let res = isize_to_base1(ans);
     let Callback { name, pre, pst } = callback;
     let pre_inner = pre.stream();
     let pst_inner = pst.stream();
@@ -217,6 +766,9 @@ pub fn div_by_zero(input: TokenStream) -> TokenStream {
 
 #[proc_macro]
 /// Called by the interpreter when modulus by 0 occurs. Prompts the input interface for a response.
+/// Built with the `headless` feature, the response is always 0. Responses with a magnitude over
+/// 4096 (or `BEFUNGE_MAX_MAGNITUDE`, if that's set) are refused, since base 1 numbers that big
+/// would take the compiler forever.
 /// 
 /// The callback format is:
 /// ```ignore
@@ -227,31 +779,25 @@ pub fn div_by_zero(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 pub fn mod_by_zero(input: TokenStream) -> TokenStream {
-    let InterfaceConn { mut conn, callback } = parse_macro_input!(input as InterfaceConn);
-    do_or_err!(
-        "Failed to request modulus by zero resolution from Befunge UI.",
-        befunge_if::ciborium::ser::into_writer(&Request::ModByZero, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    let ans = match befunge_if::ciborium::de::from_reader(&mut conn) {
-        Ok(Request::ModByZeroAns(ans)) => ans,
-        Ok(other) => {
-            let msg = format!("Received unexpected request: '{other:?}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+    let InterfaceConn { conn, callback } = parse_macro_input!(input as InterfaceConn);
+    let ans = match conn {
+        Some(mut conn) => {
+            let ans = match client_or_err!(conn.request_reply(Request::ModByZero)) {
+                Request::ModByZeroAns(ans) => ans,
+                other => return conn.error(ClientError::Unexpected(other)).emit(),
+            };
+            client_or_err!(conn.close());
+            ans
         }
-        Err(err) => {
-            let msg = format!("Failed to deserialise message.\nError: '{err}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+        None => 0,
+    };
+    let res = interface::max_magnitude().and_then(|max| interface::answer_to_base1(ans, max));
+    let res = match res {
+        Ok(res) => res,
+        Err(msg) => {
+            return compat::error(Span::call_site(), msg).emit();
         }
     };
-    do_or_err!(
-        "Failed to write close connection.",
-        befunge_if::ciborium::ser::into_writer(&Request::CloseConnection, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    let res = isize_to_base1(ans);
     let Callback { name, pre, pst } = callback;
     let pre_inner = pre.stream();
     let pst_inner = pst.stream();
@@ -265,8 +811,308 @@ pub fn mod_by_zero(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+#[proc_macro]
+/// Reads the clock when it's expanded and makes a callback with the time of day (UTC), for things
+/// like Befunge-98's `y` instruction or timing how long a program takes to run.
+/// ```
+/// macro_rules! time {
+///     (
+///         seconds: [[pos] [$($s:tt)*]],
+///         minutes: [[pos] [$($m:tt)*]],
+///         hours: [[pos] [$($h:tt)*]],
+///     ) => {
+///         [
+///             <[&str]>::len(&[$(stringify!($s)),*]),
+///             <[&str]>::len(&[$(stringify!($m)),*]),
+///             <[&str]>::len(&[$(stringify!($h)),*]),
+///         ]
+///     };
+/// }
+///
+/// let [seconds, minutes, hours] = befunge_pm::get_time! {
+///     callback: [
+///         name: time,
+///         pre: [],
+///         pst: [],
+///     ],
+/// };
+/// assert!(seconds < 60 && minutes < 60 && hours < 24);
+/// ```
+///
+/// With `epoch: true` before the callback, the number of seconds since the Unix epoch is given
+/// instead. That's far too big to be worth turning into a base 1 number, so it's given as an
+/// integer literal.
+/// ```
+/// macro_rules! epoch {
+///     (epoch: $secs:literal,) => {
+///         $secs
+///     };
+/// }
+///
+/// let built: u64 = befunge_pm::get_time! {
+///     epoch: true,
+///     callback: [
+///         name: epoch,
+///         pre: [],
+///         pst: [],
+///     ],
+/// };
+/// let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap();
+/// assert!(built <= now.as_secs());
+/// ```
+///
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     seconds: [[sgn] [mag]],
+///     minutes: [[sgn] [mag]],
+///     hours: [[sgn] [mag]],
+///     pst
+/// }
+/// ```
+/// or with `epoch: true`:
+/// ```ignore
+/// name! {
+///     pre
+///     epoch: 1234567890,
+///     pst
+/// }
+/// ```
+pub fn get_time(input: TokenStream) -> TokenStream {
+    let GetTime { epoch, callback } = parse_macro_input!(input as GetTime);
+    let secs = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs(),
+        Err(err) => {
+            let msg = format!("The clock is before the Unix epoch: {err}");
+            return compat::error(Span::call_site(), msg).emit();
+        }
+    };
+    let fields = if epoch {
+        let secs = Literal::u64_unsuffixed(secs);
+        quote! {
+            epoch: #secs,
+        }
+    } else {
+        let seconds = isize_to_base1((secs % 60) as isize);
+        let minutes = isize_to_base1((secs / 60 % 60) as isize);
+        let hours = isize_to_base1((secs / 3600 % 24) as isize);
+        quote! {
+            seconds: #seconds,
+            minutes: #minutes,
+            hours: #hours,
+        }
+    };
+    let Callback { name, pre, pst } = callback;
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            #fields
+            #pst_inner
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+#[proc_macro]
+/// Sleeps for `millis: N` milliseconds when it's expanded, then expands to nothing, or makes a
+/// callback if one is given after `millis:`. This slows down the build rather than the built
+/// program, so it's only good for pacing programs run by the interpreter, e.g. so that an animation
+/// drawn over the output socket can be watched.
+/// ```
+/// macro_rules! after {
+///     () => {
+///         "done"
+///     };
+/// }
+///
+/// befunge_pm::sleep! { millis: 1 }
+///
+/// let after = befunge_pm::sleep! {
+///     millis: 1,
+///     callback: [
+///         name: after,
+///         pre: [],
+///         pst: [],
+///     ],
+/// };
+/// assert_eq!(after, "done");
+/// ```
+///
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     pst
+/// }
+/// ```
+pub fn sleep(input: TokenStream) -> TokenStream {
+    let Sleep { millis, callback } = parse_macro_input!(input as Sleep);
+    std::thread::sleep(Duration::from_millis(millis));
+    let Some(Callback { name, pre, pst }) = callback else {
+        return TokenStream::new();
+    };
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            #pst_inner
+        }
+    };
+    TokenStream::from(expanded)
+}
+
 #[proc_macro]
 /// Expands to a random token from its input.
+///
+/// Normally a different token is picked every build. Giving `seed: N` after the choices, or
+/// setting the `BEFUNGE_SEED` environment variable to a number, makes the picks the same from one
+/// build to the next instead. Every seeded `choose_random!` in a crate still picks independently of
+/// the others, since the seed is mixed with how many of them have been expanded so far. That means
+/// adding or removing one can change what the ones expanded after it pick.
+/// Seeded picks are made with ChaCha12 and no other help from `rand`, so they don't change when
+/// any dependency is upgraded either.
+/// ```standalone_crate
+/// macro_rules! pick {
+///     (rand: $rand:tt,) => {
+///         $rand
+///     };
+/// }
+///
+/// macro_rules! seeded {
+///     () => {
+///         befunge_pm::choose_random! {
+///             choices: [0 1 2 3 4 5 6 7 8 9],
+///             seed: 12345,
+///             callback: [
+///                 name: pick,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+/// }
+///
+/// // The same every build.
+/// assert_eq!([seeded!(), seeded!(), seeded!(), seeded!()], [5, 7, 5, 0]);
+/// ```
+///
+/// Choices can also be given weights, as `[weight => choice]`, in which case each is picked with a
+/// chance of its weight out of the total. Here `left` is three times as likely as `right`:
+/// ```standalone_crate
+/// macro_rules! pick {
+///     (rand: [$rand:literal],) => {
+///         $rand
+///     };
+/// }
+///
+/// macro_rules! weighted {
+///     () => {
+///         befunge_pm::choose_random! {
+///             choices: [[3 => ["left"]] [1 => ["right"]]],
+///             seed: 12345,
+///             callback: [
+///                 name: pick,
+///                 pre: [],
+///                 pst: [],
+///             ],
+///         }
+///     };
+/// }
+///
+/// macro_rules! ten {
+///     ($($inner:tt)*) => {
+///         [
+///             $($inner)*, $($inner)*, $($inner)*, $($inner)*, $($inner)*,
+///             $($inner)*, $($inner)*, $($inner)*, $($inner)*, $($inner)*,
+///         ]
+///     };
+/// }
+///
+/// let picks: [[[&str; 10]; 10]; 10] = ten!(ten!(ten!(weighted!())));
+/// let lefts = picks.as_flattened().as_flattened().iter().filter(|&&pick| pick == "left").count();
+/// // 750 on average, give or take 14 or so.
+/// assert!((700..=800).contains(&lefts), "{lefts}");
+/// ```
+/// ```compile_fail
+/// macro_rules! pick {
+///     (rand: $rand:tt,) => {};
+/// }
+///
+/// befunge_pm::choose_random! {
+///     choices: [[0 => [left]] [0 => [right]]],
+///     callback: [
+///         name: pick,
+///         pre: [],
+///         pst: [],
+///     ],
+/// }
+/// ```
+///
+/// With `unwrap: true` after the choices, every choice has to be in brackets, which are taken off
+/// of the one that's picked. This way a choice can be several tokens without the callback having
+/// to unwrap it:
+/// ```
+/// macro_rules! single {
+///     (rand: $rand:ident,) => {
+///         stringify!($rand)
+///     };
+/// }
+///
+/// macro_rules! multi {
+///     (rand: $name:literal $value:literal,) => {
+///         ($name, $value)
+///     };
+/// }
+///
+/// let single = befunge_pm::choose_random! {
+///     choices: [[up] [down]],
+///     unwrap: true,
+///     callback: [name: single],
+/// };
+/// assert!(["up", "down"].contains(&single));
+///
+/// let multi = befunge_pm::choose_random! {
+///     choices: [["one" 1] ["two" 2]],
+///     unwrap: true,
+///     callback: [name: multi],
+/// };
+/// assert!([("one", 1), ("two", 2)].contains(&multi));
+///
+/// let weighted = befunge_pm::choose_random! {
+///     choices: [[1 => ["one" 1]] [0 => ["two" 2]]],
+///     unwrap: true,
+///     callback: [name: multi],
+/// };
+/// assert_eq!(weighted, ("one", 1));
+///
+/// // Without `unwrap`, the brackets are kept.
+/// macro_rules! wrapped {
+///     (rand: [$($rand:tt)*],) => {
+///         stringify!($($rand)*)
+///     };
+/// }
+///
+/// let wrapped = befunge_pm::choose_random! {
+///     choices: [[a b] [a b]],
+///     callback: [name: wrapped],
+/// };
+/// assert_eq!(wrapped, "a b");
+/// ```
+/// ```compile_fail
+/// macro_rules! pick {
+///     (rand: $($rand:tt)*) => {};
+/// }
+///
+/// befunge_pm::choose_random! {
+///     choices: [[left] right],
+///     unwrap: true,
+///     callback: [name: pick],
+/// }
+/// ```
 /// 
 /// The callback format is:
 /// ```ignore
@@ -276,11 +1122,31 @@ pub fn mod_by_zero(input: TokenStream) -> TokenStream {
 ///     pst
 /// }
 /// ```
+///
+/// or, with `unwrap: true`, the tokens of the choice in place of `tt`.
 pub fn choose_random(input: TokenStream) -> TokenStream {
-    let ChooseRandom { choices, callback } = parse_macro_input!(input as ChooseRandom);
-    let mut rng = StdRng::from_os_rng();
-    let choices = choices.into_iter().collect::<Vec<_>>();
-    let choice = TokenStream2::from(choices.choose(&mut rng).unwrap().clone());
+    let ChooseRandom {
+        choices,
+        unwrap,
+        seed,
+        callback,
+    } = parse_macro_input!(input as ChooseRandom);
+    let mut rng = match random_token::make_rng(seed) {
+        Ok(rng) => rng,
+        Err(msg) => {
+            return compat::error(Span::call_site(), msg).emit();
+        }
+    };
+    let choice = random_token::choose(&choices, &mut rng);
+    let choice = if unwrap {
+        choice.map(random_token::unwrap_choice)
+    } else {
+        choice.map(TokenStream2::from)
+    };
+    let Some(choice) = choice else {
+        let msg = "There's nothing to choose from (no choices, or all of their weights are 0)";
+        return compat::error(Span::call_site(), msg).emit();
+    };
     let Callback { name, pre, pst } = callback;
     let pre_inner = pre.stream();
     let pst_inner = pst.stream();
@@ -295,7 +1161,8 @@ pub fn choose_random(input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro]
-/// Prints out an integer over the socket described by the input.
+/// Prints out an integer over the socket described by the input. Built with the `headless` feature,
+/// the integer is thrown away instead.
 /// 
 /// The callback format is:
 /// ```ignore
@@ -307,32 +1174,12 @@ pub fn choose_random(input: TokenStream) -> TokenStream {
 pub fn print_integer(input: TokenStream) -> TokenStream {
     let PrintInteger {
         number,
-        mut conn,
+        conn,
         callback,
     } = parse_macro_input!(input as PrintInteger);
-    do_or_err!(
-        "Failed to send integer to Befunge UI",
-        befunge_if::ciborium::ser::into_writer(&Request::PrintInteger(number), &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    match befunge_if::ciborium::de::from_reader(&mut conn) {
-        Ok(Request::Ack) => {
-            do_or_err!(
-                "Failed to send close connection to Befunge UI",
-                befunge_if::ciborium::ser::into_writer(&Request::CloseConnection, &mut conn),
-            );
-            do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-        }
-        Ok(other) => {
-            let msg = format!("Received unexpected request: '{other:?}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
-        }
-        Err(err) => {
-            let msg = format!("Failed to read response from Befunge UI.\nError: '{err}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
-        }
+    if let Some(mut conn) = conn {
+        client_or_err!(conn.request_ack(Request::PrintInteger(number)));
+        client_or_err!(conn.close());
     }
     let Callback { name, pre, pst } = callback;
     let pre_inner = pre.stream();
@@ -347,7 +1194,8 @@ pub fn print_integer(input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro]
-/// Prints out an ASCII character over the socket described by the input.
+/// Prints out an ASCII character over the socket described by the input. Built with the `headless`
+/// feature, the character is thrown away instead.
 /// 
 /// The callback format is:
 /// ```ignore
@@ -359,32 +1207,51 @@ pub fn print_integer(input: TokenStream) -> TokenStream {
 pub fn print_ascii(input: TokenStream) -> TokenStream {
     let PrintAscii {
         ascii,
-        mut conn,
+        conn,
         callback,
     } = parse_macro_input!(input as PrintAscii);
-    do_or_err!(
-        "Failed to send integer to Befunge UI",
-        befunge_if::ciborium::ser::into_writer(&Request::PrintAscii(ascii as u8), &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    match befunge_if::ciborium::de::from_reader(&mut conn) {
-        Ok(Request::Ack) => {
-            do_or_err!(
-                "Failed to send close connection to Befunge UI",
-                befunge_if::ciborium::ser::into_writer(&Request::CloseConnection, &mut conn),
-            );
-            do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-        }
-        Ok(other) => {
-            let msg = format!("Received unexpected request: '{other:?}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
-        }
-        Err(err) => {
-            let msg = format!("Failed to read response from Befunge UI.\nError: '{err}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+    if let Some(mut conn) = conn {
+        client_or_err!(conn.request_ack(Request::PrintAscii(ascii as u8)));
+        client_or_err!(conn.close());
+    }
+    let Callback { name, pre, pst } = callback;
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            #pst_inner
         }
+    };
+    TokenStream::from(expanded)
+}
+
+#[proc_macro]
+/// Prints out a string of ASCII characters over the socket described by the input. The whole string
+/// is sent as a single request, so this takes one round trip to the interface no matter how long the
+/// string is. Built with the `headless` feature, the string is thrown away instead.
+/// 
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     pst
+/// }
+/// ```
+pub fn print_string(input: TokenStream) -> TokenStream {
+    let PrintString {
+        string,
+        conn,
+        callback,
+    } = parse_macro_input!(input as PrintString);
+    let value = string.value();
+    if let Some(c) = value.chars().find(|c| !c.is_ascii()) {
+        let msg = format!("String contains non-ASCII character: {c:?}");
+        return compat::error(string.span().unwrap(), &msg).emit();
+    }
+    if let Some(mut conn) = conn {
+        client_or_err!(conn.request_ack(Request::PrintString(value)));
+        client_or_err!(conn.close());
     }
     let Callback { name, pre, pst } = callback;
     let pre_inner = pre.stream();
@@ -399,50 +1266,33 @@ pub fn print_ascii(input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro]
-/// Requests the specified socket to flush its output buffer.
+/// Requests the specified socket to flush its output buffer. Does nothing when built with the
+/// `headless` feature.
 pub fn flush_output(input: TokenStream) -> TokenStream {
-    let CloseUi { mut conn } = parse_macro_input!(input as CloseUi);
-    do_or_err!(
-        "Failed to send output flush request",
-        befunge_if::ciborium::ser::into_writer(&Request::FlushOutput, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    match befunge_if::ciborium::de::from_reader(&mut conn) {
-        Ok(Request::Ack) => {
-            do_or_err!(
-                "Failed to send close connection to Befunge UI",
-                befunge_if::ciborium::ser::into_writer(&Request::CloseConnection, &mut conn),
-            );
-            do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-        }
-        Ok(other) => {
-            let msg = format!("Received unexpected request: '{other:?}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
-        }
-        Err(err) => {
-            let msg = format!("Failed to read response from Befunge UI.\nError: '{err}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
-        }
+    let CloseUi { conn } = parse_macro_input!(input as CloseUi);
+    if let Some(mut conn) = conn {
+        client_or_err!(conn.request_ack(Request::FlushOutput));
+        client_or_err!(conn.close());
     }
     TokenStream::new()
 }
 
 #[proc_macro]
 /// Sends a request for the interface program on the other side of the specified socket to exit.
+/// Does nothing when built with the `headless` feature.
 pub fn close_ui(input: TokenStream) -> TokenStream {
-    let CloseUi { mut conn } = parse_macro_input!(input as CloseUi);
-    do_or_err!(
-        "Failed to send close UI request",
-        befunge_if::ciborium::ser::into_writer(&Request::CloseUi, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
+    let CloseUi { conn } = parse_macro_input!(input as CloseUi);
+    if let Some(mut conn) = conn {
+        client_or_err!(conn.send(&Request::CloseUi));
+    }
     TokenStream::new()
 }
 
 #[proc_macro]
-/// Sends a request for a single digit integer input over the specified socket.
+/// Sends a request for a single digit integer input over the specified socket. Built with the
+/// `headless` feature, the answer is the value of `BEFUNGE_HEADLESS_INTEGER` instead, or 0 if that
+/// isn't set. As with [`div_by_zero!`], answers with a magnitude over 4096 (or
+/// `BEFUNGE_MAX_MAGNITUDE`) are refused.
 /// 
 /// The callback format is:
 /// ```ignore
@@ -453,31 +1303,30 @@ pub fn close_ui(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 pub fn get_integer(input: TokenStream) -> TokenStream {
-    let InterfaceConn { mut conn, callback } = parse_macro_input!(input as InterfaceConn);
-    do_or_err!(
-        "Failed to request integer from Befunge UI.",
-        befunge_if::ciborium::ser::into_writer(&Request::GetInteger, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    let ans = match befunge_if::ciborium::de::from_reader(&mut conn) {
-        Ok(Request::GetIntegerAns(ans)) => ans,
-        Ok(other) => {
-            let msg = format!("Received unexpected request: '{other:?}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+    let InterfaceConn { conn, callback } = parse_macro_input!(input as InterfaceConn);
+    let ans = match conn {
+        Some(mut conn) => {
+            let ans = match client_or_err!(conn.request_reply(Request::GetInteger)) {
+                Request::GetIntegerAns(ans) => ans,
+                other => return conn.error(ClientError::Unexpected(other)).emit(),
+            };
+            client_or_err!(conn.close());
+            ans
         }
-        Err(err) => {
-            let msg = format!("Failed to deserialise message.\nError: '{err}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+        None => match headless::integer() {
+            Ok(ans) => ans,
+            Err(msg) => {
+                return compat::error(Span::call_site(), msg).emit();
+            }
+        },
+    };
+    let res = interface::max_magnitude().and_then(|max| interface::answer_to_base1(ans, max));
+    let res = match res {
+        Ok(res) => res,
+        Err(msg) => {
+            return compat::error(Span::call_site(), msg).emit();
         }
     };
-    do_or_err!(
-        "Failed to write close connection.",
-        befunge_if::ciborium::ser::into_writer(&Request::CloseConnection, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    let res = isize_to_base1(ans);
     let Callback { name, pre, pst } = callback;
     let pre_inner = pre.stream();
     let pst_inner = pst.stream();
@@ -492,7 +1341,11 @@ pub fn get_integer(input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro]
-/// Sends a request for a single ASCII character input over the specified socket.
+/// Sends a request for a single ASCII character input over the specified socket. An optional
+/// `allowed: "..."` between the socket and the callback restricts the answer to the characters in
+/// that string; the interface asks again until one of them is entered. Built with the `headless`
+/// feature, the answer is the value of `BEFUNGE_HEADLESS_ASCII` instead, or a newline if that isn't
+/// set (or the first allowed character, if it isn't one of those).
 /// 
 /// The callback format is:
 /// ```ignore
@@ -503,30 +1356,23 @@ pub fn get_integer(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 pub fn get_ascii(input: TokenStream) -> TokenStream {
-    let InterfaceConn { mut conn, callback } = parse_macro_input!(input as InterfaceConn);
-    do_or_err!(
-        "Failed to request character from Befunge UI.",
-        befunge_if::ciborium::ser::into_writer(&Request::GetAscii, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
-    let ans = match befunge_if::ciborium::de::from_reader(&mut conn) {
-        Ok(Request::GetAsciiAns(ans)) => ans,
-        Ok(other) => {
-            let msg = format!("Received unexpected request: '{other:?}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
-        }
-        Err(err) => {
-            let msg = format!("Failed to deserialise message.\nError: '{err}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+    let GetAscii { conn, allowed, callback } = parse_macro_input!(input as GetAscii);
+    let ans = match conn {
+        Some(mut conn) => {
+            let ans = match client_or_err!(conn.request_reply(Request::GetAscii { allowed })) {
+                Request::GetAsciiAns(ans) => ans,
+                other => return conn.error(ClientError::Unexpected(other)).emit(),
+            };
+            client_or_err!(conn.close());
+            ans
         }
+        None => match headless::ascii(allowed.as_deref()) {
+            Ok(ans) => ans,
+            Err(msg) => {
+                return compat::error(Span::call_site(), msg).emit();
+            }
+        },
     };
-    do_or_err!(
-        "Failed to write close connection.",
-        befunge_if::ciborium::ser::into_writer(&Request::CloseConnection, &mut conn),
-    );
-    do_or_err!("Failed to flush buffer to Befunge UI", conn.flush());
     let res = TokenTree2::Literal(Literal::character(ans as char));
     let Callback { name, pre, pst } = callback;
     let pre_inner = pre.stream();
@@ -542,30 +1388,131 @@ pub fn get_ascii(input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro]
-/// Converts the input tokens to a string and sends them to the specified socket.
-pub fn socket_debug(input: TokenStream) -> TokenStream {
-    let Debug { tokens, mut conn } = parse_macro_input!(input as Debug);
-    let tokens = tokens.to_string();
-    do_or_err!(
-        "Failed to send debug request to Befunge UI.",
-        befunge_if::ciborium::ser::into_writer(&Request::Debug(tokens), &mut conn),
-    );
-    match befunge_if::ciborium::de::from_reader(&mut conn) {
-        Ok(Request::Ack) => (),
-        Ok(other) => {
-            let msg = format!("Received unexpected request: '{other:?}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+/// Sends a request for a line of ASCII input over the specified socket. The line ending is not
+/// included in the result. Built with the `headless` feature, the line is always empty.
+/// 
+/// The callback format is:
+/// ```ignore
+/// name! {
+///     pre
+///     string: ['a' 'b' 'c' ...],
+///     pst
+/// }
+/// ```
+pub fn get_string(input: TokenStream) -> TokenStream {
+    let InterfaceConn { conn, callback } = parse_macro_input!(input as InterfaceConn);
+    let ans = match conn {
+        Some(mut conn) => {
+            let ans = match client_or_err!(conn.request_reply(Request::GetString)) {
+                Request::GetStringAns(ans) => ans,
+                other => return conn.error(ClientError::Unexpected(other)).emit(),
+            };
+            client_or_err!(conn.close());
+            ans
         }
-        Err(err) => {
-            let msg = format!("Failed to deserialise message.\nError: '{err}'");
-            Span::call_site().error(&msg).emit();
-            return TokenStream::new();
+        None => String::new(),
+    };
+    if let Some(c) = ans.chars().find(|c| !c.is_ascii()) {
+        let msg = format!("Befunge UI sent string containing non-ASCII character: {c:?}");
+        return compat::error(Span::call_site(), &msg).emit();
+    }
+    let res = TokenStream2::from_iter(ans.chars().map(|c| TokenTree2::Literal(Literal::character(c))));
+    let Callback { name, pre, pst } = callback;
+    let pre_inner = pre.stream();
+    let pst_inner = pst.stream();
+    let expanded = quote! {
+        #name! {
+            #pre_inner
+            string: [#res],
+            #pst_inner
         }
     };
-    do_or_err!(
-        "Failed to write close connection.",
-        befunge_if::ciborium::ser::into_writer(&Request::CloseConnection, &mut conn),
-    );
+    TokenStream::from(expanded)
+}
+
+#[proc_macro]
+/// Converts the input tokens to a string and sends them to the specified socket, as a message of
+/// `level: trace`, `level: info` (the default), or `level: warn` if given after the socket. With
+/// `locations: true` after that, the message is prefixed with where the tokens were written,
+/// e.g. `step.rs:1234:9: catch: put2`. With `noack: true` (after `locations:`, if that's given
+/// too), the message is sent without waiting for the interface to acknowledge it; see
+/// [`befunge_if::Client::notify`]. Built with the `headless` feature, nothing is sent.
+///
+/// `string: [...]` can be given in place of `tokens: [...]` to send a message that's already been
+/// put together, like the rows from `befunge_dm::befunge_stringify!`. It takes string, char,
+/// integer, and bool literals and puts their values together like `concat!` does, so that the
+/// message isn't mangled by the tokens being turned back into source code.
+///
+/// Built with the `debug_fallback` feature, a message that can't be sent because nothing is
+/// listening on the socket is output as a `const _: &str = "..."` instead of failing the build, so
+/// that it can still be read with `cargo expand`. Once a socket has failed to connect, later
+/// messages for it don't try again.
+///
+/// With `category: "..."` last, the message can be filtered out by setting `BEFUNGE_DEBUG_CATEGORY`
+/// while building to a comma-separated list of prefixes: only messages whose category starts with
+/// one of them are sent, and messages without a category aren't sent at all. Setting
+/// `BEFUNGE_DEBUG_SAMPLE=N` only sends every `N`th message that gets past the categories, starting
+/// with the first. Messages that aren't sent expand to nothing without connecting to the socket,
+/// even with `debug_fallback`.
+///
+/// The count behind `BEFUNGE_DEBUG_SAMPLE` is kept for the whole compiler process, in the order the
+/// compiler gets around to expanding the messages. For the interpreter, that's the order the
+/// program runs in, so the messages that are sent still arrive in order and are evenly spread out,
+/// but which ones are sent depends on every other message sent while building the same crate
+/// before them. A program that's changed even slightly can be sampled differently, and since
+/// `rust-analyzer` keeps its process around, it carries on counting from where the last expansion
+/// stopped.
+///
+/// A `$` followed by a name anywhere in the tokens fails the build, headless or not, since that's
+/// what a `macro_rules!` arm leaves behind when it uses a metavariable it never bound.
+pub fn socket_debug(input: TokenStream) -> TokenStream {
+    let Debug {
+        tokens,
+        string,
+        conn,
+        fallback,
+        level,
+        locations,
+        noack,
+    } = parse_macro_input!(input as Debug);
+    if conn.is_none() && !fallback {
+        return TokenStream::new();
+    }
+    let mut msg = string.unwrap_or_else(|| tokens.to_string());
+    if locations {
+        let span = debug::first_span(tokens.stream()).map_or_else(Span::call_site, |span| span.unwrap());
+        let file = span.file();
+        let file = Path::new(&file)
+            .file_name()
+            .map_or(file.clone(), |name| name.to_string_lossy().into_owned());
+        msg = befunge_if::located_debug(&file, span.line(), span.column(), &msg);
+    }
+    let Some(mut conn) = conn else {
+        return TokenStream::from(quote! {
+            const _: &str = #msg;
+        });
+    };
+    if noack {
+        client_or_err!(conn.notify(Request::Debug { level, msg }));
+    } else {
+        client_or_err!(conn.request_ack(Request::Debug { level, msg }));
+        client_or_err!(conn.close());
+    }
     TokenStream::new()
 }
+
+#[proc_macro]
+/// Fails the build with a message put together from `string: [...]` the same way as for
+/// [`socket_debug!`], pointing at the first token in `at: [...]` instead of where this was expanded.
+/// The interpreter uses this to point at a cell of the program when it finds a problem deep into
+/// running it, rather than at the outermost macro with a backtrace hundreds of expansions long.
+/// ```compile_fail
+/// befunge_pm::error_at! {
+///     at: ['X'],
+///     string: ["Unknown instruction `", 'X', "` at location (", 0, ", ", 1, ")"],
+/// }
+/// ```
+pub fn error_at(input: TokenStream) -> TokenStream {
+    let ErrorAt { span, msg } = parse_macro_input!(input as ErrorAt);
+    compat::error(compat::unexpanded(span.unwrap()), msg).emit()
+}
@@ -1,10 +1,10 @@
-use crate::callback::Callback;
-use interprocess::local_socket::Stream;
-use syn::{LitChar, LitInt, Token, parse::{Parse, ParseStream}};
+use crate::{callback::Callback, interface::Conn};
+use proc_macro2::Span;
+use syn::{Lit, LitChar, LitStr, Token, parse::{Parse, ParseStream}};
 
 pub struct PrintInteger {
     pub number: isize,
-    pub conn: Stream,
+    pub conn: Option<Conn>,
     pub callback: Callback,
 }
 
@@ -12,8 +12,7 @@ impl Parse for PrintInteger {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         input.parse::<crate::kw::number>()?;
         input.parse::<Token![:]>()?;
-        let number: LitInt = input.parse()?;
-        let number: isize = number.base10_parse()?;
+        let number = parse_integer(input)?;
         input.parse::<Token![,]>()?;
         let conn = crate::interface::parse_socket(input)?;
         input.parse::<Token![,]>()?;
@@ -27,9 +26,52 @@ impl Parse for PrintInteger {
     }
 }
 
+/// Parses an integer given either as a plain literal or as one produced by `${count(...)}`, with
+/// an optional leading `-`. Literals passed along through a `macro_rules!` fragment show up
+/// wrapped in an invisible group, which syn's cursor looks through for us.
+pub fn parse_integer(input: ParseStream) -> syn::Result<isize> {
+    parse_spanned_integer(input).map(|(number, _)| number)
+}
+
+/// Like [`parse_integer`], but also gives the span of the integer, including its `-`.
+pub fn parse_spanned_integer(input: ParseStream) -> syn::Result<(isize, Span)> {
+    const EXPECTED: &str =
+        "expected an integer literal or `${count(...)}`, optionally preceded by `-`";
+    let minus = input.parse::<Option<Token![-]>>()?;
+    let lit = input.step(|cursor| match cursor.literal() {
+        Some((lit, rest)) => match Lit::new(lit) {
+            Lit::Int(lit) => Ok((lit, rest)),
+            lit => Err(syn::Error::new(lit.span(), EXPECTED)),
+        },
+        None => Err(cursor.error(EXPECTED)),
+    })?;
+    if !lit.suffix().is_empty() {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("`{lit}` has a suffix; {EXPECTED}"),
+        ));
+    }
+    let digits = lit.base10_digits();
+    let number = if minus.is_some() {
+        format!("-{digits}")
+    } else {
+        digits.to_string()
+    };
+    let span = minus.map_or(lit.span(), |minus| {
+        minus.span.join(lit.span()).unwrap_or(lit.span())
+    });
+    match number.parse::<isize>() {
+        Ok(parsed) => Ok((parsed, span)),
+        Err(_) => Err(syn::Error::new(
+            span,
+            format!("`{number}` doesn't fit in an `isize`"),
+        )),
+    }
+}
+
 pub struct PrintAscii {
     pub ascii: char,
-    pub conn: Stream,
+    pub conn: Option<Conn>,
     pub callback: Callback,
 }
 
@@ -51,3 +93,79 @@ impl Parse for PrintAscii {
         })
     }
 }
+
+pub struct PrintString {
+    pub string: LitStr,
+    pub conn: Option<Conn>,
+    pub callback: Callback,
+}
+
+impl Parse for PrintString {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<crate::kw::string>()?;
+        input.parse::<Token![:]>()?;
+        let string: LitStr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let conn = crate::interface::parse_socket(input)?;
+        input.parse::<Token![,]>()?;
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(PrintString {
+            string,
+            conn,
+            callback,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_integer;
+    use proc_macro2::{Delimiter, Group, Literal, Punct, Spacing, TokenStream, TokenTree};
+    use syn::parse::Parser;
+
+    fn parse(tokens: TokenStream) -> syn::Result<isize> {
+        parse_integer.parse2(tokens)
+    }
+
+    /// What `-${count($x)}` looks like once it's been passed along through a fragment: a `-`
+    /// followed by an invisible group holding the literal.
+    fn negated_count(count: usize) -> TokenStream {
+        let literal = TokenTree::Literal(Literal::usize_unsuffixed(count));
+        [
+            TokenTree::Punct(Punct::new('-', Spacing::Alone)),
+            TokenTree::Group(Group::new(Delimiter::None, literal.into())),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(parse("0".parse().unwrap()).unwrap(), 0);
+        assert_eq!(parse("-0".parse().unwrap()).unwrap(), 0);
+        assert_eq!(parse("42".parse().unwrap()).unwrap(), 42);
+        assert_eq!(parse("- 42".parse().unwrap()).unwrap(), -42);
+        assert_eq!(
+            parse("-9223372036854775808".parse().unwrap()).unwrap(),
+            isize::MIN,
+        );
+    }
+
+    #[test]
+    fn counts() {
+        assert_eq!(parse(negated_count(0)).unwrap(), 0);
+        assert_eq!(parse(negated_count(7)).unwrap(), -7);
+        let literal = TokenTree::Literal(Literal::usize_unsuffixed(7));
+        let group = TokenTree::Group(Group::new(Delimiter::None, literal.into()));
+        assert_eq!(parse(group.into()).unwrap(), 7);
+    }
+
+    #[test]
+    fn rejects_everything_else() {
+        for tokens in ["x", "1.5", "'a'", "--1", "42usize", "-"] {
+            assert!(parse(tokens.parse().unwrap()).is_err(), "{tokens} parsed");
+        }
+        assert!(parse("9223372036854775808".parse().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::compat::{self, Diagnostic};
+use proc_macro::Span;
+use syn::{LitBool, LitInt, Token, parse::ParseStream};
+
+/// Width of the Befunge-93 playfield.
+const MAX_COLS: usize = 80;
+/// Height of the Befunge-93 playfield.
+const MAX_ROWS: usize = 25;
+
+/// Options shared by everything that reads in a program's source, given between the source and
+/// the callback.
+pub struct ProgramOptions {
+    pub trim: bool,
+    pub tabs: Option<usize>,
+    pub allow_oversize: bool,
+}
+
+pub fn parse_options(input: ParseStream) -> syn::Result<ProgramOptions> {
+    let mut trim = false;
+    if input.peek(crate::kw::trim) {
+        input.parse::<crate::kw::trim>()?;
+        input.parse::<Token![:]>()?;
+        trim = input.parse::<LitBool>()?.value;
+        input.parse::<Token![,]>()?;
+    }
+    let mut tabs = None;
+    if input.peek(crate::kw::tabs) {
+        input.parse::<crate::kw::tabs>()?;
+        input.parse::<Token![:]>()?;
+        let width = input.parse::<LitInt>()?;
+        match width.base10_parse()? {
+            0 => return Err(syn::Error::new(width.span(), "Tab width must be at least 1")),
+            width => tabs = Some(width),
+        }
+        input.parse::<Token![,]>()?;
+    }
+    let mut allow_oversize = false;
+    if input.peek(crate::kw::allow_oversize) {
+        input.parse::<crate::kw::allow_oversize>()?;
+        input.parse::<Token![:]>()?;
+        allow_oversize = input.parse::<LitBool>()?.value;
+        input.parse::<Token![,]>()?;
+    }
+    Ok(ProgramOptions {
+        trim,
+        tabs,
+        allow_oversize,
+    })
+}
+
+/// Normalises the line endings of a program and checks that it'll fit in the playfield. `origin`
+/// says where the program came from in error messages, e.g. `File /path/to/program.bfg`.
+pub fn prepare(
+    contents: &str,
+    origin: &str,
+    options: &ProgramOptions,
+    span: Span,
+) -> Result<String, Diagnostic> {
+    let mut contents = contents.replace("\r\n", "\n").replace('\r', "\n");
+    if let Some(width) = options.tabs {
+        contents = contents
+            .split('\n')
+            .map(|line| expand_tabs(line, width))
+            .collect::<Vec<_>>()
+            .join("\n");
+    } else if let Some((row, line)) = contents
+        .split('\n')
+        .enumerate()
+        .find(|(_, line)| line.contains('\t'))
+    {
+        let (col, _) = line.chars().enumerate().find(|&(_, c)| c == '\t').unwrap();
+        // Keep any tabs before the one being pointed at so that the caret lines up with it however
+        // wide the terminal shows them.
+        let pad = line
+            .chars()
+            .take(col)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let msg = format!(
+            "{origin} contains a tab at line {}, column {}; give `tabs: N` to expand tabs to every \
+             Nth column:\n{line}\n{pad}^",
+            row + 1,
+            col + 1,
+        );
+        return Err(compat::error(span, msg));
+    }
+    if options.trim {
+        contents = contents
+            .split('\n')
+            .map(|line| line.trim_end_matches(' '))
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+    check_ascii(&contents, origin, span)?;
+    if !options.allow_oversize {
+        let lines = contents
+            .strip_suffix('\n')
+            .unwrap_or(&contents)
+            .split('\n')
+            .collect::<Vec<_>>();
+        let too_wide = lines
+            .iter()
+            .enumerate()
+            .find(|(_, line)| line.len() > MAX_COLS);
+        let msg = if let Some((row, line)) = too_wide {
+            Some(format!(
+                "{origin} has a line {} characters long at line {}, but programs can be at most \
+                 {MAX_COLS} columns wide",
+                line.len(),
+                row + 1,
+            ))
+        } else if lines.len() > MAX_ROWS {
+            Some(format!(
+                "{origin} has {} lines, but programs can be at most {MAX_ROWS} rows tall",
+                lines.len(),
+            ))
+        } else {
+            None
+        };
+        if let Some(msg) = msg {
+            return Err(compat::error(span, msg)
+                .help("give `allow_oversize: true` to skip this check"));
+        }
+    }
+    Ok(contents)
+}
+
+/// Checks that every character in `contents` is ASCII, pointing at the first one that isn't.
+pub fn check_ascii(contents: &str, origin: &str, span: Span) -> Result<(), Diagnostic> {
+    let mut non_ascii = contents
+        .split('\n')
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| !c.is_ascii())
+                .map(move |(col, c)| (row, col, c, line))
+        });
+    if let Some((row, col, c, line)) = non_ascii.next() {
+        let more = match non_ascii.count() {
+            0 => String::new(),
+            n => format!(" (and {n} more)"),
+        };
+        let msg = format!(
+            "{origin} contains non-ASCII character {c:?} at line {}, column {}{more}:\n{line}\n{}^",
+            row + 1,
+            col + 1,
+            " ".repeat(col),
+        );
+        return Err(compat::error(span, msg));
+    }
+    Ok(())
+}
+
+/// Replaces every tab in a line with spaces up to the next column that's a multiple of `width`.
+fn expand_tabs(line: &str, width: usize) -> String {
+    let mut expanded = String::new();
+    let mut col = 0;
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = width - col % width;
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+            col += spaces;
+        } else {
+            expanded.push(c);
+            col += 1;
+        }
+    }
+    expanded
+}
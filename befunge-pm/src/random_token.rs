@@ -1,12 +1,90 @@
 use crate::callback::Callback;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Delimiter, Group, TokenStream as TokenStream2, TokenTree as TokenTree2};
+use rand_chacha::{
+    ChaCha12Rng,
+    rand_core::{RngCore, SeedableRng},
+};
+use std::sync::atomic::{AtomicU64, Ordering};
 use syn::{
-    Token, bracketed,
+    Error as SynError, LitBool, LitInt, Token, bracketed,
     parse::{Parse, ParseStream},
 };
 
+/// Environment variable that seeds `choose_random!` when no `seed:` is given.
+pub const SEED_VAR: &str = "BEFUNGE_SEED";
+
+/// Number of seeded `choose_random!`s expanded so far in this crate, so that each of them picks
+/// independently of the others even though they share a seed.
+static SEEDED_INVOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The tokens to choose between, either all equally likely or each with its own weight.
+pub enum Choices {
+    Unweighted(Vec<TokenTree2>),
+    Weighted(Vec<(u32, TokenTree2)>),
+}
+
+/// A single `[weight => choice]`.
+struct WeightedChoice {
+    weight: u32,
+    choice: TokenTree2,
+}
+
+impl Parse for WeightedChoice {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let weight = input.parse::<LitInt>()?.base10_parse()?;
+        input.parse::<Token![=>]>()?;
+        let choice = input.parse()?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(WeightedChoice { weight, choice })
+    }
+}
+
+/// Checks whether a choice looks like `[weight => ...]`.
+fn is_weighted(choice: &Group) -> bool {
+    let mut tokens = choice.stream().into_iter();
+    matches!(tokens.next(), Some(TokenTree2::Literal(_)))
+        && matches!(tokens.next(), Some(TokenTree2::Punct(punct)) if punct.as_char() == '=')
+}
+
+impl Parse for Choices {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let choices = input.parse::<TokenStream2>()?.into_iter().collect::<Vec<_>>();
+        if !matches!(choices.first(), Some(TokenTree2::Group(first)) if is_weighted(first)) {
+            return Ok(Choices::Unweighted(choices));
+        }
+        choices
+            .into_iter()
+            .map(|choice| match choice {
+                TokenTree2::Group(group) if is_weighted(&group) => {
+                    let WeightedChoice { weight, choice } = syn::parse2(group.stream())?;
+                    Ok((weight, choice))
+                }
+                other => Err(SynError::new(
+                    other.span(),
+                    "Expected `[weight => choice]`, since the first choice has a weight",
+                )),
+            })
+            .collect::<syn::Result<_>>()
+            .map(Choices::Weighted)
+    }
+}
+
+fn is_bracketed(choice: &TokenTree2) -> bool {
+    matches!(choice, TokenTree2::Group(group) if group.delimiter() == Delimiter::Bracket)
+}
+
+/// Takes the brackets off of a choice given with `unwrap: true`.
+pub fn unwrap_choice(choice: TokenTree2) -> TokenStream2 {
+    match choice {
+        TokenTree2::Group(group) if group.delimiter() == Delimiter::Bracket => group.stream(),
+        other => other.into(),
+    }
+}
+
 pub struct ChooseRandom {
-    pub choices: TokenStream2,
+    pub choices: Choices,
+    pub unwrap: bool,
+    pub seed: Option<u64>,
     pub callback: Callback,
 }
 
@@ -16,10 +94,127 @@ impl Parse for ChooseRandom {
         input.parse::<Token![:]>()?;
         let choices;
         bracketed!(choices in input);
-        let choices = choices.parse()?;
+        let choices = choices.parse::<Choices>()?;
         input.parse::<Token![,]>()?;
+        let mut unwrap = false;
+        if input.peek(crate::kw::unwrap) {
+            input.parse::<crate::kw::unwrap>()?;
+            input.parse::<Token![:]>()?;
+            unwrap = input.parse::<LitBool>()?.value;
+            input.parse::<Token![,]>()?;
+        }
+        if unwrap {
+            let unbracketed = match &choices {
+                Choices::Unweighted(choices) => choices.iter().find(|choice| !is_bracketed(choice)),
+                Choices::Weighted(choices) => choices
+                    .iter()
+                    .map(|(_, choice)| choice)
+                    .find(|choice| !is_bracketed(choice)),
+            };
+            if let Some(choice) = unbracketed {
+                return Err(SynError::new(
+                    choice.span(),
+                    "Expected `[...]`, since every choice is unwrapped with `unwrap: true`",
+                ));
+            }
+        }
+        let mut seed = None;
+        if input.peek(crate::kw::seed) {
+            input.parse::<crate::kw::seed>()?;
+            input.parse::<Token![:]>()?;
+            seed = Some(input.parse::<LitInt>()?.base10_parse()?);
+            input.parse::<Token![,]>()?;
+        }
         let callback = crate::callback::parse_callback(input)?;
         crate::maybe_trailing_comma(input)?;
-        Ok(ChooseRandom { choices, callback })
+        Ok(ChooseRandom {
+            choices,
+            unwrap,
+            seed,
+            callback,
+        })
+    }
+}
+
+/// Makes the generator for a `choose_random!`. The seed is taken from `seed:` if it was given,
+/// then from [`SEED_VAR`], and otherwise the OS is asked for randomness instead.
+///
+/// This is ChaCha12 seeded with the bytes of the seed directly, rather than `rand`'s `StdRng` or
+/// `seed_from_u64`, either of which may give different numbers in a later version of `rand`.
+pub fn make_rng(seed: Option<u64>) -> Result<ChaCha12Rng, String> {
+    let seed = match seed {
+        Some(seed) => seed,
+        None => match crate::compat::env_var(SEED_VAR) {
+            Ok(var) => var
+                .trim()
+                .parse()
+                .map_err(|_| format!("{SEED_VAR} should be an integer, but is '{var}'"))?,
+            Err(_) => return Ok(ChaCha12Rng::from_os_rng()),
+        },
+    };
+    let invocation = SEEDED_INVOCATIONS.fetch_add(1, Ordering::Relaxed);
+    let seed = seed.wrapping_add(invocation.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    let mut bytes = [0; 32];
+    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+    Ok(ChaCha12Rng::from_seed(bytes))
+}
+
+/// Picks one of the choices, or nothing if there aren't any or all of their weights are 0. This is
+/// done here rather than with `rand`'s `choose`, which is also free to pick differently from one
+/// version to the next, so that a seeded program always takes the same path.
+pub fn choose(choices: &Choices, rng: &mut impl RngCore) -> Option<TokenTree2> {
+    match choices {
+        Choices::Unweighted(choices) => {
+            let index = below(rng, choices.len() as u64);
+            choices.get(index as usize).cloned()
+        }
+        Choices::Weighted(choices) => {
+            let total = choices.iter().map(|&(weight, _)| u64::from(weight)).sum();
+            let mut pick = below(rng, total);
+            for (weight, choice) in choices {
+                if pick < u64::from(*weight) {
+                    return Some(choice.clone());
+                }
+                pick -= u64::from(*weight);
+            }
+            None
+        }
+    }
+}
+
+/// A number below `bound`, or 0 if `bound` is 0. Scaling rather than taking the remainder keeps
+/// the numbers about as evenly spread as a `u64` allows.
+fn below(rng: &mut impl RngCore, bound: u64) -> u64 {
+    ((u128::from(rng.next_u64()) * u128::from(bound)) >> 64) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Choices, SEEDED_INVOCATIONS, choose, make_rng};
+    use std::sync::atomic::Ordering;
+
+    /// The picks that `count` seeded `choose_random!`s make in a build, starting from a fresh count
+    /// the way a new compiler process would.
+    fn build(seed: u64, count: usize, choices: &str) -> Vec<String> {
+        SEEDED_INVOCATIONS.store(0, Ordering::Relaxed);
+        let choices = syn::parse_str::<Choices>(choices).unwrap();
+        (0..count)
+            .map(|_| {
+                let mut rng = make_rng(Some(seed)).unwrap();
+                choose(&choices, &mut rng).unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn seeded_builds_agree() {
+        for choices in ["0 1 2 3 4 5 6 7 8 9", "[3 => left] [0 => up] [1 => right]"] {
+            let first = build(12345, 32, choices);
+            assert_eq!(build(12345, 32, choices), first, "{choices}");
+            assert_ne!(build(54321, 32, choices), first, "{choices}");
+            // Each of them picks independently, even with the same seed.
+            assert!(first.iter().any(|pick| *pick != first[0]), "{choices}");
+            assert!(!first.iter().any(|pick| pick == "up"), "{choices}");
+        }
     }
 }
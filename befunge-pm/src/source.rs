@@ -0,0 +1,28 @@
+use crate::{callback::Callback, program::ProgramOptions};
+use syn::{
+    LitStr, Token,
+    parse::{Parse, ParseStream},
+};
+
+pub struct BefungeSource {
+    pub source: LitStr,
+    pub options: ProgramOptions,
+    pub callback: Callback,
+}
+
+impl Parse for BefungeSource {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<crate::kw::source>()?;
+        input.parse::<Token![:]>()?;
+        let source = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let options = crate::program::parse_options(input)?;
+        let callback = crate::callback::parse_callback(input)?;
+        crate::maybe_trailing_comma(input)?;
+        Ok(BefungeSource {
+            source,
+            options,
+            callback,
+        })
+    }
+}
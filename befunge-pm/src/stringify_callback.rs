@@ -1,12 +1,14 @@
 use crate::callback::Callback;
-use proc_macro2::Group;
+use proc_macro2::{Delimiter, Group, TokenStream as TokenStream2};
 use syn::{
-    Token,
+    LitBool, LitChar, Token,
     parse::{Parse, ParseStream},
 };
 
 pub struct StringifyCallback {
     pub tokens: Group,
+    pub raw: bool,
+    pub escape: bool,
     pub callback: Callback,
 }
 
@@ -16,8 +18,82 @@ impl Parse for StringifyCallback {
         input.parse::<Token![:]>()?;
         let tokens = input.parse()?;
         input.parse::<Token![,]>()?;
+        let mut raw = false;
+        if input.peek(crate::kw::raw) {
+            input.parse::<crate::kw::raw>()?;
+            input.parse::<Token![:]>()?;
+            raw = input.parse::<LitBool>()?.value;
+            input.parse::<Token![,]>()?;
+        }
+        let mut escape = false;
+        if input.peek(crate::kw::escape) {
+            input.parse::<crate::kw::escape>()?;
+            input.parse::<Token![:]>()?;
+            escape = input.parse::<LitBool>()?.value;
+            input.parse::<Token![,]>()?;
+        }
         let callback = crate::callback::parse_callback(input)?;
         crate::maybe_trailing_comma(input)?;
-        Ok(StringifyCallback { tokens, callback })
+        Ok(StringifyCallback {
+            tokens,
+            raw,
+            escape,
+            callback,
+        })
+    }
+}
+
+/// Stringifies the contents of `tokens`. With `raw`, this is the text exactly as it was written
+/// where that can be recovered, and otherwise the same as without it.
+pub fn stringify(tokens: &Group, raw: bool) -> String {
+    let stringified = tokens.stream().to_string();
+    if raw {
+        source_text(tokens, &stringified).unwrap_or(stringified)
+    } else {
+        stringified
+    }
+}
+
+/// Gives the character a lone char literal in `tokens` stands for, with control characters written
+/// out as `\xNN` so that they can be seen. Anything else gives `None`.
+pub fn escape_char(tokens: &Group) -> Option<String> {
+    let mut trees = tokens.stream().into_iter();
+    let (Some(tree), None) = (trees.next(), trees.next()) else {
+        return None;
+    };
+    let c = syn::parse2::<LitChar>(tree.into()).ok()?.value();
+    if c.is_control() {
+        Some(format!("\\x{:02x}", c as u32))
+    } else {
+        Some(c.to_string())
+    }
+}
+
+/// Looks up what was written between the delimiters of `tokens`. If the group was made by a
+/// `macro_rules!` macro (e.g. `[$x]`), its span points at the macro's definition rather than at the
+/// call site, so the text is only trusted if it parses back into the same tokens. A lone token is
+/// tried on its own after that, since its span still points at where it was written.
+fn source_text(tokens: &Group, stringified: &str) -> Option<String> {
+    let matches = |text: &str| {
+        text.parse::<TokenStream2>()
+            .is_ok_and(|reparsed| reparsed.to_string() == stringified)
+    };
+    let from_group = tokens.span().source_text().and_then(|text| {
+        if tokens.delimiter() == Delimiter::None {
+            Some(text)
+        } else {
+            let mut chars = text.chars();
+            chars.next()?;
+            chars.next_back()?;
+            Some(chars.as_str().to_string())
+        }
+    });
+    if let Some(text) = from_group.filter(|text| matches(text)) {
+        return Some(text);
+    }
+    let mut trees = tokens.stream().into_iter();
+    match (trees.next(), trees.next()) {
+        (Some(tree), None) => tree.span().source_text().filter(|text| matches(text)),
+        _ => None,
     }
 }
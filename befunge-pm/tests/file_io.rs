@@ -0,0 +1,63 @@
+//! Writes a file with `file_put!` and reads it back in with `file_get!`, somewhere under the
+//! system's temporary directory. The macros need the path spelled out as character literals, so the
+//! program that does this is put together here and then built and run with trybuild.
+
+use std::{fs, path::Path};
+
+const PROGRAM: &str = r#"
+macro_rules! written {
+    () => {
+        befunge_pm::file_get! {
+            path: [PATH],
+            unsafe_paths: true,
+            callback: [
+                name: read_back,
+                pre: [],
+                pst: [],
+            ],
+        }
+    };
+}
+
+macro_rules! read_back {
+    (contents: [$($c:literal)*],) => {
+        [$($c),*]
+    };
+}
+
+fn main() {
+    let contents: String = befunge_pm::file_put! {
+        path: [PATH],
+        contents: ['>' '@' '\n'],
+        unsafe_paths: true,
+        callback: [
+            name: written,
+            pre: [],
+            pst: [],
+        ],
+    }
+    .into_iter()
+    .collect();
+    assert_eq!(contents, ">@\n");
+}
+"#;
+
+/// Spells out `path` as a list of character literals.
+fn chars(path: &Path) -> String {
+    let chars = path.to_str().unwrap().chars().map(|c| format!("{c:?}"));
+    chars.collect::<Vec<_>>().join(" ")
+}
+
+#[test]
+fn put_then_get() {
+    let dir = std::env::temp_dir().join(format!("befunge-pm-file-io-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("written.txt");
+    let program = dir.join("put_then_get.rs");
+    fs::write(&program, PROGRAM.replace("PATH", &chars(&file))).unwrap();
+
+    trybuild::TestCases::new().pass(&program);
+
+    assert_eq!(fs::read_to_string(&file).unwrap(), ">@\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,94 @@
+//! Expands each of the macros that talk to an interface against a mock one, checking both what
+//! they expand to (`tests/io/expand.stderr`) and what they send. Run with `TRYBUILD=overwrite` to
+//! update the expected expansions.
+
+#![cfg(not(feature = "headless"))]
+
+use befunge_if::{DebugLevel, Request, SOCKET_PREFIX_VAR, test_util::MockInterface};
+
+#[test]
+fn io() {
+    // SAFETY: nothing else in this test binary reads or writes the environment.
+    unsafe { std::env::set_var(SOCKET_PREFIX_VAR, befunge_if::test_util::prefix()) };
+    let mocks = [
+        ("get_integer", vec![Request::GetIntegerAns(5)]),
+        ("div_by_zero", vec![Request::DivByZeroAns(-2)]),
+        ("mod_by_zero", vec![Request::ModByZeroAns(0)]),
+        ("get_ascii", vec![Request::GetAsciiAns(b'y')]),
+        ("get_string", vec![Request::GetStringAns("hi".to_string())]),
+        ("print_integer", Vec::new()),
+        ("print_ascii", Vec::new()),
+        ("print_string", Vec::new()),
+        ("flush_output", Vec::new()),
+        ("socket_debug", Vec::new()),
+        ("close_ui", Vec::new()),
+    ]
+    .map(|(name, replies)| (name, MockInterface::start(name, replies).unwrap()));
+
+    trybuild::TestCases::new().compile_fail("tests/io/expand.rs");
+
+    let received = mocks.map(|(name, mock)| (name, mock.finish().unwrap()));
+    let debug = |level, msg: &str| Request::Debug {
+        level,
+        msg: msg.to_string(),
+    };
+    assert_eq!(
+        received,
+        [
+            (
+                "get_integer",
+                vec![Request::GetInteger, Request::CloseConnection]
+            ),
+            (
+                "div_by_zero",
+                vec![Request::DivByZero, Request::CloseConnection]
+            ),
+            (
+                "mod_by_zero",
+                vec![Request::ModByZero, Request::CloseConnection]
+            ),
+            (
+                "get_ascii",
+                vec![
+                    Request::GetAscii {
+                        allowed: Some(b"yn".to_vec()),
+                    },
+                    Request::CloseConnection,
+                ],
+            ),
+            (
+                "get_string",
+                vec![Request::GetString, Request::CloseConnection]
+            ),
+            (
+                "print_integer",
+                vec![Request::PrintInteger(-3), Request::CloseConnection],
+            ),
+            (
+                "print_ascii",
+                vec![Request::PrintAscii(b'x'), Request::CloseConnection],
+            ),
+            (
+                "print_string",
+                vec![
+                    Request::PrintString("hello".to_string()),
+                    Request::CloseConnection,
+                ],
+            ),
+            (
+                "flush_output",
+                vec![Request::FlushOutput, Request::CloseConnection]
+            ),
+            (
+                "socket_debug",
+                vec![
+                    debug(DebugLevel::Warn, "[hello]"),
+                    Request::CloseConnection,
+                    debug(DebugLevel::Info, "[goodbye]"),
+                    Request::CloseConnection,
+                ],
+            ),
+            ("close_ui", vec![Request::CloseUi]),
+        ],
+    );
+}
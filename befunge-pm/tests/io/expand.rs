@@ -0,0 +1,87 @@
+// Expanded by `tests/io.rs` with a mock interface listening on each of these sockets. Every
+// callback turns what it was given into an error, so that `expand.stderr` shows what each macro
+// expanded to.
+
+macro_rules! show {
+    ($($expanded:tt)*) => {
+        compile_error!(stringify!($($expanded)*));
+    };
+}
+
+befunge_pm::get_integer! {
+    socket: "get_integer",
+    retries: 0,
+    callback: [name: show, pre: [get_integer]],
+}
+
+befunge_pm::div_by_zero! {
+    socket: "div_by_zero",
+    retries: 0,
+    callback: [name: show, pre: [div_by_zero]],
+}
+
+befunge_pm::mod_by_zero! {
+    socket: "mod_by_zero",
+    retries: 0,
+    callback: [name: show, pre: [mod_by_zero]],
+}
+
+befunge_pm::get_ascii! {
+    socket: "get_ascii",
+    retries: 0,
+    allowed: "yn",
+    callback: [name: show, pre: [get_ascii]],
+}
+
+befunge_pm::get_string! {
+    socket: "get_string",
+    retries: 0,
+    callback: [name: show, pre: [get_string]],
+}
+
+befunge_pm::print_integer! {
+    number: -3,
+    socket: "print_integer",
+    retries: 0,
+    callback: [name: show, pre: [print_integer]],
+}
+
+befunge_pm::print_ascii! {
+    ascii: 'x',
+    socket: "print_ascii",
+    retries: 0,
+    callback: [name: show, pre: [print_ascii]],
+}
+
+befunge_pm::print_string! {
+    string: "hello",
+    socket: "print_string",
+    retries: 0,
+    callback: [name: show, pre: [print_string]],
+}
+
+befunge_pm::flush_output! {
+    socket: "flush_output",
+    retries: 0,
+}
+
+befunge_pm::socket_debug! {
+    tokens: [hello],
+    socket: "socket_debug",
+    retries: 0,
+    level: warn,
+}
+
+befunge_pm::socket_debug! {
+    tokens: [goodbye],
+    socket: "socket_debug",
+    retries: 0,
+    noack: true,
+}
+
+befunge_pm::close_ui! {
+    socket: "close_ui",
+    retries: 0,
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+//! Sends a hundred debug messages, along with a hundred more in a category that's filtered out,
+//! with `BEFUNGE_DEBUG_SAMPLE=10`, checking that only a tenth of them arrive. Run with
+//! `TRYBUILD=overwrite` to update `tests/sample/send.stderr`.
+
+#![cfg(not(feature = "headless"))]
+
+use befunge_if::{DebugLevel, Request, SOCKET_PREFIX_VAR, test_util::MockInterface};
+
+#[test]
+fn sample() {
+    // SAFETY: nothing else in this test binary reads or writes the environment.
+    unsafe {
+        std::env::set_var(SOCKET_PREFIX_VAR, befunge_if::test_util::prefix());
+        std::env::set_var("BEFUNGE_DEBUG_SAMPLE", "10");
+        std::env::set_var("BEFUNGE_DEBUG_CATEGORY", "kept");
+    }
+    let mock = MockInterface::start("sample", Vec::new()).unwrap();
+
+    trybuild::TestCases::new().compile_fail("tests/sample/send.rs");
+
+    let received = mock.finish().unwrap();
+    let debug = |n: usize| {
+        [
+            Request::Debug {
+                level: DebugLevel::Info,
+                msg: format!("kept {n:02}"),
+            },
+            Request::CloseConnection,
+        ]
+    };
+    assert_eq!(received, (0..100).step_by(10).flat_map(debug).collect::<Vec<_>>());
+}
@@ -0,0 +1,31 @@
+// Expanded by `tests/sample.rs` with `BEFUNGE_DEBUG_SAMPLE=10` and `BEFUNGE_DEBUG_CATEGORY=kept`,
+// and a mock interface listening on `sample`. Ends with an error so that nothing is built and run
+// afterwards, which would expand everything a second time.
+
+macro_rules! send {
+    ($([$tens:literal])*; $units:tt) => {
+        $(send! { @units $tens $units })*
+    };
+    (@units $tens:literal [$($units:literal)*]) => {
+        $(
+            befunge_pm::socket_debug! {
+                string: ["kept " $tens $units],
+                socket: "sample",
+                retries: 0,
+                category: "kept",
+            }
+            befunge_pm::socket_debug! {
+                string: ["dropped " $tens $units],
+                socket: "sample",
+                retries: 0,
+                category: "dropped",
+            }
+        )*
+    };
+}
+
+send!([0] [1] [2] [3] [4] [5] [6] [7] [8] [9]; [0 1 2 3 4 5 6 7 8 9]);
+
+compile_error!("sent");
+
+fn main() {}
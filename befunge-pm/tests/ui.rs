@@ -0,0 +1,10 @@
+//! Checks where errors from the macros point. Run with `TRYBUILD=overwrite` to update the expected
+//! output after changing an error message.
+
+#![cfg(not(feature = "headless"))]
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
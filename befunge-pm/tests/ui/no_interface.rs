@@ -0,0 +1,19 @@
+// Nothing is listening on this socket, so the error should point at its name.
+
+#![allow(unused_macros)]
+
+macro_rules! integer {
+    (integer: $integer:tt,) => {};
+}
+
+befunge_pm::get_integer! {
+    socket: "befunge-pm-ui.no-interface",
+    retries: 0,
+    callback: [
+        name: integer,
+        pre: [],
+        pst: [],
+    ],
+}
+
+fn main() {}
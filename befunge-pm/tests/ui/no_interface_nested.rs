@@ -0,0 +1,38 @@
+// The socket's name is passed through a couple of `macro_rules!` before it gets to the macro that
+// connects to it, as it would be in the interpreter. The error should still point at the name
+// rather than at the outermost invocation.
+
+#![allow(unused_macros)]
+
+macro_rules! relay {
+    (socket: $socket:literal,) => {
+        printer! {
+            socket: $socket,
+        }
+    };
+}
+
+macro_rules! printer {
+    (socket: $socket:literal,) => {
+        befunge_pm::print_ascii! {
+            ascii: 'a',
+            socket: $socket,
+            retries: 0,
+            callback: [
+                name: done,
+                pre: [],
+                pst: [],
+            ],
+        }
+    };
+}
+
+macro_rules! done {
+    () => {};
+}
+
+relay! {
+    socket: "befunge-pm-ui.no-interface-nested",
+}
+
+fn main() {}